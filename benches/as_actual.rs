@@ -0,0 +1,35 @@
+#[allow(dead_code, unused_imports, clippy::all)]
+#[path = "../tests/generated_fbs/foo.rs"]
+mod foo;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use flatbuffers::FlatBufferBuilder;
+use flatbuffers_owned::{flatbuffers_owned, CachedRoot};
+use foo::{Foo, FooArgs};
+
+flatbuffers_owned!(Foo);
+
+fn get_foo_bytes() -> Box<[u8]> {
+    let mut builder = FlatBufferBuilder::new();
+    let b = builder.create_string("Hello, world!");
+
+    let offset = Foo::create(&mut builder, &FooArgs {
+        a: 42,
+        b: Some(b),
+    });
+
+    builder.finish(offset, None);
+
+    builder.finished_data().into()
+}
+
+fn as_actual(c: &mut Criterion) {
+    let owned_foo = OwnedFoo::new(get_foo_bytes()).unwrap();
+    let cached_foo = CachedRoot::new(owned_foo.clone());
+
+    c.bench_function("as_actual", |b| b.iter(|| black_box(&owned_foo).as_actual().a()));
+    c.bench_function("as_actual_cached_root", |b| b.iter(|| black_box(&cached_foo).as_actual().a()));
+}
+
+criterion_group!(benches, as_actual);
+criterion_main!(benches);
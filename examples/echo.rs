@@ -0,0 +1,76 @@
+//! An echo server and client exchanging size-prefixed FlatBuffers over TCP with the `FlatBufferCodec`.
+//!
+//! The server verifies every incoming `Record` once while decoding it, and answers with a `Record` carrying the same id. \
+//! The client sends a few records and reads the echoed ones back as zero-copy wrappers over the received `Bytes`.
+//!
+//! Run with `cargo run --example echo --features tokio-util`.
+
+#[allow(dead_code, unused_imports, clippy::all)]
+#[path = "../tests/generated_fbs/foo.rs"]
+mod foo;
+#[allow(dead_code, unused_imports, clippy::all)]
+#[path = "../tests/generated_fbs/record.rs"]
+mod record;
+
+use std::error::Error;
+use futures::{SinkExt, StreamExt};
+use foo::{Foo, FooArgs};
+use record::{Record, RecordArgs};
+use flatbuffers_owned::flatbuffers_owned;
+use flatbuffers_owned::size_prefixed::FlatBufferCodec;
+use tokio::net::{TcpListener, TcpStream};
+use tokio_util::codec::Framed;
+
+flatbuffers_owned!(Record);
+
+fn build_record(id: u64, text: &str) -> OwnedRecord {
+    OwnedRecord::build(|builder| {
+        let b = builder.create_string(text);
+        let nested = Foo::create(builder, &FooArgs { a: 0, b: Some(b) });
+
+        Record::create(builder, &RecordArgs { id, foo: Some(nested) })
+    }).expect("The record is a valid FlatBuffer")
+}
+
+async fn serve(listener: TcpListener) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let (stream, _) = listener.accept().await?;
+    let mut framed = Framed::new(stream, FlatBufferCodec::<Record<'static>>::new());
+
+    while let Some(request) = framed.next().await {
+        let request = request?;
+        let request = request.as_actual_at();
+        let text = request.foo().and_then(|nested| nested.b()).unwrap_or_default();
+
+        framed.send(&build_record(request.id(), &format!("echo: {}", text))).await?;
+    }
+
+    Ok(())
+}
+
+#[tokio::main(flavor = "current_thread")]
+pub async fn main() -> Result<(), Box<dyn Error + Send + Sync>> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let address = listener.local_addr()?;
+    let server = tokio::spawn(serve(listener));
+
+    let stream = TcpStream::connect(address).await?;
+    let mut framed = Framed::new(stream, FlatBufferCodec::<Record<'static>>::new());
+
+    for (id, text) in [(1, "Hello"), (2, "world")] {
+        framed.send(&build_record(id, text)).await?;
+
+        let reply = match framed.next().await {
+            Some(reply) => reply?,
+            None => return Err("The server closed the connection".into()),
+        };
+        let reply = reply.as_actual_at();
+
+        println!("Received {} {:?}", reply.id(), reply.foo().and_then(|nested| nested.b()));
+    }
+
+    // Closing the connection ends the server loop.
+    drop(framed);
+    server.await??;
+
+    Ok(())
+}
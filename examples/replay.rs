@@ -0,0 +1,50 @@
+//! Records messages into a flatlog and replays them, by iterating the log and by record number.
+//!
+//! The writer appends every `Record` as a size-prefixed frame and indexes its offset. \
+//! The reader memory-maps the log and its index and returns zero-copy wrappers over the mapping.
+//!
+//! Run with `cargo run --example replay --features mmap`.
+
+#[allow(dead_code, unused_imports, clippy::all)]
+#[path = "../tests/generated_fbs/foo.rs"]
+mod foo;
+#[allow(dead_code, unused_imports, clippy::all)]
+#[path = "../tests/generated_fbs/record.rs"]
+mod record;
+
+use std::error::Error;
+use std::fs;
+use record::{Record, RecordArgs};
+use flatbuffers_owned::flatbuffers_owned;
+use flatbuffers_owned::flatlog::{index_path, FlatLogReader, FlatLogWriter};
+
+flatbuffers_owned!(Record);
+
+pub fn main() -> Result<(), Box<dyn Error + Send + Sync>> {
+    let path = std::env::temp_dir().join(format!("flatbuffers-owned-replay-{}.log", std::process::id()));
+
+    let mut log = FlatLogWriter::<Record<'static>>::open(&path)?;
+    for id in 1..=5 {
+        let record = OwnedRecord::build(|builder| Record::create(builder, &RecordArgs { id: id * 10, foo: None }))?;
+        log.append(&record)?;
+    }
+    log.flush()?;
+    drop(log);
+
+    // Safety: The log is not modified while it is mapped.
+    let reader = unsafe { FlatLogReader::<Record<'static>>::open(&path)? };
+
+    for record in &reader {
+        println!("Replayed {}", record?.as_actual_at().id());
+    }
+
+    if let Some(record) = reader.get(2) {
+        println!("Record 2 is {}", record?.as_actual_at().id());
+    }
+
+    drop(reader);
+    fs::remove_file(index_path(&path))?;
+    fs::remove_file(&path)?;
+
+    Ok(())
+}
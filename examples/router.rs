@@ -0,0 +1,73 @@
+//! Routes incoming buffers to their handlers by file identifier.
+//!
+//! `Bar` is registered at link time with the `register` macro option,
+//! `Foo` is registered at runtime, like a plugin would do.
+//!
+//! Run with `cargo run --example router --features registry`.
+
+#[allow(dead_code, unused_imports, clippy::all)]
+#[path = "../tests/generated_fbs/foo.rs"]
+mod foo;
+#[allow(dead_code, unused_imports, clippy::all, mismatched_lifetime_syntaxes)]
+#[path = "../tests/generated_fbs/bar.rs"]
+mod bar;
+
+use flatbuffers::FlatBufferBuilder;
+use foo::{Foo, FooArgs};
+use bar::{Bar, BarArgs, BAR_IDENTIFIER, finish_bar_buffer};
+use flatbuffers_owned::{flatbuffers_owned, DecodeError};
+use flatbuffers_owned::registry::{self, DynamicResolver, StaticResolver};
+
+flatbuffers_owned!(Foo, Bar { register: BAR_IDENTIFIER });
+
+fn foo_bytes(a: u32) -> Box<[u8]> {
+    let mut builder = FlatBufferBuilder::new();
+    let offset = Foo::create(&mut builder, &FooArgs { a, b: None });
+    builder.finish(offset, Some("FOO_"));
+
+    builder.finished_data().into()
+}
+
+fn bar_bytes(id: u64, name: &str) -> Box<[u8]> {
+    let mut builder = FlatBufferBuilder::new();
+    let name = builder.create_string(name);
+    let bar = Bar::create(&mut builder, &BarArgs { id, name: Some(name) });
+    finish_bar_buffer(&mut builder, bar);
+
+    builder.finished_data().into()
+}
+
+fn route(resolver: &impl registry::TypeResolver, data: Box<[u8]>) -> Result<String, DecodeError> {
+    let decoded = registry::decode_with(resolver, data)?;
+
+    if let Some(message) = decoded.downcast_ref::<OwnedFoo>() {
+        return Ok(format!("Foo {{ a: {} }}", message.as_actual().a()));
+    }
+
+    if let Some(bar) = decoded.downcast_ref::<OwnedBar>() {
+        let bar = bar.as_actual();
+        return Ok(format!("Bar {{ id: {}, name: {:?} }}", bar.id(), bar.name()));
+    }
+
+    Err(DecodeError::UnknownIdentifier)
+}
+
+pub fn main() {
+    let plugins = DynamicResolver::new();
+    plugins.register("FOO_", registry::decoder::<OwnedFoo>());
+
+    let resolver = (StaticResolver, &plugins);
+
+    let incoming = vec![
+        foo_bytes(42),
+        bar_bytes(7, "bar"),
+        Box::from(&b"not a flatbuffer"[..]),
+    ];
+
+    for data in incoming {
+        match route(&resolver, data) {
+            Ok(message) => println!("Routed {}", message),
+            Err(e) => println!("Dropped buffer: {}", e),
+        }
+    }
+}
@@ -0,0 +1,199 @@
+//! An actix-web extractor and responder for FlatBuffers.
+//!
+//! [FlatBuffer] extracts a wrapper from the request payload and verifies it.
+//! The payload is read up to the limit of the [FlatBufferConfig] registered as app data, [DEFAULT_LIMIT] otherwise.
+//! Larger payloads are rejected with `413 Payload Too Large`, FlatBuffers failing the verification with `400 Bad Request`. \
+//! Returned from a handler, it responds with the bytes of the wrapper and the [CONTENT_TYPE].
+//!
+//! Requires the `actix-web` feature.
+//!
+//! # Example
+//! ```
+//! use flatbuffers_owned::actix::{FlatBuffer, FlatBufferConfig};
+//!
+//! async fn echo(FlatBuffer(message): FlatBuffer<OwnedMessage>) -> FlatBuffer<OwnedMessage> {
+//!     println!("{}", message.as_actual().text());
+//!     FlatBuffer(message)
+//! }
+//!
+//! let app = App::new()
+//!     .app_data(FlatBufferConfig::default().limit(64 * 1024))
+//!     .route("/echo", web::post().to(echo));
+//! ```
+
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::future::poll_fn;
+use std::ops::Deref;
+use std::pin::Pin;
+use actix_web::body::BoxBody;
+use actix_web::dev::Payload;
+use actix_web::error::PayloadError;
+use actix_web::http::header::CONTENT_LENGTH;
+use actix_web::http::StatusCode;
+use actix_web::{web, FromRequest, HttpRequest, HttpResponse, Responder, ResponseError};
+use bytes::{Bytes, BytesMut};
+use futures_core::future::LocalBoxFuture;
+use futures_core::Stream;
+use crate::{RelaxedFlatBufferTrait, VerifyError};
+
+/// The content type of [FlatBuffer] responses.
+pub const CONTENT_TYPE: &str = "application/x-flatbuffers";
+
+/// The payload limit of the [FlatBuffer] extractor without a [FlatBufferConfig], 2 MiB.
+pub const DEFAULT_LIMIT: usize = 2 * 1024 * 1024;
+
+/// Extracts the wrapper `F` from the request payload, or responds with it.
+///
+/// Extracting requires the buffer of the wrapper to be constructible from a `Vec<u8>`, e.g. `Box<[u8]>`, `Arc<[u8]>` or `Bytes`.
+/// Responding requires it to convert into `Bytes`, e.g. `Box<[u8]>`, `Vec<u8>` or `Bytes`, so the bytes are not copied.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FlatBuffer<F>(pub F);
+
+impl<F> Deref for FlatBuffer<F> {
+    type Target = F;
+
+    fn deref(&self) -> &F {
+        &self.0
+    }
+}
+
+impl<F> From<F> for FlatBuffer<F> {
+    fn from(flatbuffer: F) -> Self {
+        FlatBuffer(flatbuffer)
+    }
+}
+
+/// The configuration of the [FlatBuffer] extractor, registered with `App::app_data()` or `Resource::app_data()`,
+/// either as it is or wrapped in `web::Data`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FlatBufferConfig {
+    limit: usize,
+}
+
+impl FlatBufferConfig {
+    /// Sets the maximum size of the payload in bytes.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    fn from_req(req: &HttpRequest) -> Self {
+        req.app_data::<Self>()
+            .or_else(|| req.app_data::<web::Data<Self>>().map(|config| config.get_ref()))
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+impl Default for FlatBufferConfig {
+    fn default() -> Self {
+        Self { limit: DEFAULT_LIMIT }
+    }
+}
+
+impl<F> FromRequest for FlatBuffer<F>
+    where F: RelaxedFlatBufferTrait + 'static,
+          F::Buffer: From<Vec<u8>>
+{
+    type Error = FlatBufferPayloadError;
+    type Future = LocalBoxFuture<'static, Result<Self, FlatBufferPayloadError>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let limit = FlatBufferConfig::from_req(req).limit;
+
+        // Rejects announced oversized payloads before reading them.
+        let content_length = req.headers().get(CONTENT_LENGTH)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse::<usize>().ok());
+
+        let mut payload = payload.take();
+
+        Box::pin(async move {
+            if content_length.is_some_and(|len| len > limit) {
+                return Err(FlatBufferPayloadError::Overflow { limit });
+            }
+
+            let mut body = BytesMut::with_capacity(content_length.unwrap_or_default());
+
+            while let Some(chunk) = poll_fn(|cx| Pin::new(&mut payload).poll_next(cx)).await {
+                let chunk = chunk?;
+
+                if body.len().saturating_add(chunk.len()) > limit {
+                    return Err(FlatBufferPayloadError::Overflow { limit });
+                }
+
+                body.extend_from_slice(&chunk);
+            }
+
+            Ok(FlatBuffer(F::new(F::Buffer::from(Vec::from(body)))?))
+        })
+    }
+}
+
+impl<F> Responder for FlatBuffer<F>
+    where F: RelaxedFlatBufferTrait,
+          F::Buffer: Into<Bytes>
+{
+    type Body = BoxBody;
+
+    fn respond_to(self, _req: &HttpRequest) -> HttpResponse {
+        let body: Bytes = self.0.into_inner().into();
+
+        HttpResponse::Ok().content_type(CONTENT_TYPE).body(body)
+    }
+}
+
+/// The error of the [FlatBuffer] extractor.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum FlatBufferPayloadError {
+    /// The payload exceeds the limit of `limit` bytes. Responds with `413 Payload Too Large`.
+    Overflow { limit: usize },
+    /// The payload could not be read.
+    Payload(PayloadError),
+    /// The payload failed the verification. Responds with `400 Bad Request`.
+    InvalidFlatbuffer(VerifyError),
+}
+
+impl Display for FlatBufferPayloadError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FlatBufferPayloadError::Overflow { limit } => write!(f, "Payload exceeds the limit of {} bytes", limit),
+            FlatBufferPayloadError::Payload(e) => Display::fmt(e, f),
+            FlatBufferPayloadError::InvalidFlatbuffer(e) => write!(f, "Invalid FlatBuffer: {}", e),
+        }
+    }
+}
+
+impl Error for FlatBufferPayloadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            FlatBufferPayloadError::Overflow { .. } => None,
+            FlatBufferPayloadError::Payload(e) => Some(e),
+            FlatBufferPayloadError::InvalidFlatbuffer(e) => Some(e),
+        }
+    }
+}
+
+impl From<PayloadError> for FlatBufferPayloadError {
+    fn from(e: PayloadError) -> Self {
+        FlatBufferPayloadError::Payload(e)
+    }
+}
+
+impl From<VerifyError> for FlatBufferPayloadError {
+    fn from(e: VerifyError) -> Self {
+        FlatBufferPayloadError::InvalidFlatbuffer(e)
+    }
+}
+
+impl ResponseError for FlatBufferPayloadError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            FlatBufferPayloadError::Overflow { .. } => StatusCode::PAYLOAD_TOO_LARGE,
+            FlatBufferPayloadError::Payload(e) => e.status_code(),
+            FlatBufferPayloadError::InvalidFlatbuffer(_) => StatusCode::BAD_REQUEST,
+        }
+    }
+}
@@ -0,0 +1,40 @@
+//! Wrappers over buffers of custom allocators.
+//!
+//! Requires the `allocator-api2` feature.
+
+use allocator_api2::alloc::Allocator;
+use allocator_api2::vec::Vec;
+use alloc::boxed::Box;
+use flatbuffers::Verifiable;
+use crate::buffer::BoxIn;
+use crate::{OwnedFlatBuffer, RelaxedFlatBufferTrait, RelaxedFollowTrait, VerifierPolicy, VerifyError};
+
+fn copy_in<A: Allocator>(bytes: &[u8], alloc: A) -> BoxIn<A> {
+    let mut buffer = Vec::with_capacity_in(bytes.len(), alloc);
+    buffer.extend_from_slice(bytes);
+
+    buffer.into_boxed_slice()
+}
+
+impl<T: RelaxedFollowTrait + Verifiable, P: VerifierPolicy> OwnedFlatBuffer<T, Box<[u8]>, P> {
+    /// Copies the bytes into a buffer of the passed allocator and verifies it according to the policy `P`.
+    ///
+    /// # Example
+    /// ```
+    /// let message: OwnedInMessage<&Bump> = OwnedMessage::new_in(message_bytes, &bump)?;
+    /// ```
+    pub fn new_in<A: Allocator>(bytes: &[u8], alloc: A) -> Result<OwnedFlatBuffer<T, BoxIn<A>, P>, VerifyError> {
+        <OwnedFlatBuffer<T, BoxIn<A>, P> as RelaxedFlatBufferTrait>::new(copy_in(bytes, alloc))
+    }
+}
+
+impl<T: RelaxedFollowTrait + Verifiable, B: AsRef<[u8]>, P: VerifierPolicy> OwnedFlatBuffer<T, B, P> {
+    /// Copies the bytes into a buffer of the passed allocator. The copied bytes are not verified again.
+    pub fn to_owned_in<A: Allocator>(&self, alloc: A) -> OwnedFlatBuffer<T, BoxIn<A>, P> {
+        let buffer = copy_in(self.buffer.as_ref(), alloc);
+
+        // Safety:
+        // The bytes are copied from this verified wrapper.
+        unsafe { <OwnedFlatBuffer<T, BoxIn<A>, P> as RelaxedFlatBufferTrait>::new_unchecked(buffer) }
+    }
+}
@@ -0,0 +1,235 @@
+//! Arena allocation for batches of FlatBuffers.
+//!
+//! Batch jobs decoding millions of small messages spend much of their time allocating and freeing a buffer per message. \
+//! The [FlatBufferArena] copies incoming bytes into large chunks instead and hands out [ArenaSlice]s borrowing from them.
+//! Freeing a whole batch is then one [reset()](FlatBufferArena::reset), which keeps the chunks for the next batch.
+//!
+//! Every slice starts at an address aligned to the [ALIGNMENT](crate::buffer::ALIGNMENT) of FlatBuffers.
+//!
+//! Requires the `arena` feature, which only depends on `alloc`.
+//!
+//! # Example
+//! ```
+//! use flatbuffers_owned::arena::FlatBufferArena;
+//!
+//! let mut arena = FlatBufferArena::new();
+//!
+//! for batch in batches {
+//!     let messages = batch.iter()
+//!         .map(|bytes| OwnedMessage::new_in_arena(&arena, bytes))
+//!         .collect::<Result<Vec<_>, _>>()?;
+//!
+//!     // ... process messages
+//!
+//!     drop(messages);
+//!     arena.reset();
+//! }
+//! ```
+
+use core::cell::{Cell, RefCell};
+use core::fmt::{Debug, Formatter};
+use core::ops::Deref;
+use alloc::{boxed::Box, vec, vec::Vec};
+use flatbuffers::Verifiable;
+use stable_deref_trait::StableDeref;
+use crate::buffer::StableBuffer;
+use crate::buffer::ALIGNMENT;
+use crate::{OwnedFlatBuffer, RelaxedFlatBufferTrait, RelaxedFollowTrait, VerifierPolicy, VerifyError};
+
+/// The default size of the chunks, 64 KiB.
+pub const DEFAULT_CHUNK_SIZE: usize = 64 * 1024;
+
+/// A chunk allocated as words, so that it starts at an aligned address.
+///
+/// Only raw pointers to the chunk are kept, since the arena writes to it while slices of it are borrowed.
+struct Chunk {
+    words: *mut [u64],
+    size: usize,
+}
+
+impl Chunk {
+    fn new(size: usize) -> Self {
+        let words = vec![0u64; size.div_ceil(ALIGNMENT)].into_boxed_slice();
+
+        let size = words.len().saturating_mul(ALIGNMENT);
+
+        Self { words: Box::into_raw(words), size }
+    }
+
+    fn size(&self) -> usize {
+        self.size
+    }
+
+    fn ptr(&self) -> *mut u8 {
+        self.words as *mut u64 as *mut u8
+    }
+}
+
+impl Drop for Chunk {
+    fn drop(&mut self) {
+        // Safety:
+        // Allocated by `Box::into_raw()`, no slices can exist anymore since the arena is dropped or mutably borrowed.
+        unsafe { drop(Box::from_raw(self.words)) }
+    }
+}
+
+/// Copies bytes into large chunks and hands out [ArenaSlice]s of them.
+///
+/// Allocating only needs a shared reference, while [reset()](FlatBufferArena::reset) needs a mutable one.
+/// The borrow checker therefore guarantees that no slice outlives a reset. \
+/// The arena is `Send`, but not `Sync`.
+pub struct FlatBufferArena {
+    chunk_size: usize,
+    chunks: RefCell<Vec<Chunk>>,
+    oversized: RefCell<Vec<Chunk>>,
+    current: Cell<usize>,
+    used: Cell<usize>,
+}
+
+// Safety:
+// The chunks are owned by the arena and only accessed through it, or through slices borrowing it.
+unsafe impl Send for FlatBufferArena {}
+
+impl FlatBufferArena {
+    /// Creates an arena allocating chunks of the [DEFAULT_CHUNK_SIZE].
+    pub fn new() -> Self {
+        Self::with_chunk_size(DEFAULT_CHUNK_SIZE)
+    }
+
+    /// Creates an arena allocating chunks of the passed size. \
+    /// Bytes larger than a chunk are copied into a dedicated allocation.
+    pub fn with_chunk_size(chunk_size: usize) -> Self {
+        Self {
+            chunk_size,
+            chunks: RefCell::new(Vec::new()),
+            oversized: RefCell::new(Vec::new()),
+            current: Cell::new(0),
+            used: Cell::new(0),
+        }
+    }
+
+    /// Copies the bytes into the arena.
+    pub fn alloc(&self, bytes: &[u8]) -> ArenaSlice<'_> {
+        let len = bytes.len();
+
+        if len > self.chunk_size {
+            let chunk = Chunk::new(len);
+            let ptr = chunk.ptr();
+            self.oversized.borrow_mut().push(chunk);
+
+            // Safety:
+            // The dedicated chunk is large enough and not referenced by any other slice.
+            return unsafe { Self::copy_to(ptr, bytes) };
+        }
+
+        let mut chunks = self.chunks.borrow_mut();
+        let mut current = self.current.get();
+        // The used bytes never exceed the chunk size, the start is rounded up to the next aligned offset.
+        let mut start = self.used.get().div_ceil(ALIGNMENT).saturating_mul(ALIGNMENT);
+
+        let fits = chunks.get(current).is_some_and(|chunk| start.saturating_add(len) <= chunk.size());
+        if !fits {
+            if !chunks.is_empty() {
+                current = current.saturating_add(1);
+            }
+            start = 0;
+        }
+
+        // The chunks after the current one are kept from before the last reset.
+        let ptr = match chunks.get(current) {
+            Some(chunk) => chunk.ptr(),
+            None => {
+                let chunk = Chunk::new(self.chunk_size);
+                let ptr = chunk.ptr();
+                chunks.push(chunk);
+                ptr
+            }
+        };
+
+        self.current.set(current);
+        self.used.set(start.saturating_add(len));
+
+        // Safety:
+        // The range `start..start + len` lies within the chunk and has not been handed out since the last reset.
+        unsafe { Self::copy_to(ptr.add(start), bytes) }
+    }
+
+    /// Frees all slices at once and keeps the chunks for reuse. \
+    /// Dedicated allocations of oversized bytes are released.
+    pub fn reset(&mut self) {
+        self.oversized.get_mut().clear();
+        self.current.set(0);
+        self.used.set(0);
+    }
+
+    /// Returns the number of bytes allocated by the arena, including unused parts of the chunks.
+    pub fn allocated_bytes(&self) -> usize {
+        let chunks = self.chunks.borrow();
+        let oversized = self.oversized.borrow();
+
+        chunks.iter().chain(oversized.iter())
+            .fold(0, |sum, chunk| sum.saturating_add(chunk.size()))
+    }
+
+    unsafe fn copy_to<'a>(ptr: *mut u8, bytes: &[u8]) -> ArenaSlice<'a> {
+        core::ptr::copy_nonoverlapping(bytes.as_ptr(), ptr, bytes.len());
+
+        ArenaSlice { bytes: core::slice::from_raw_parts(ptr, bytes.len()) }
+    }
+}
+
+impl Default for FlatBufferArena {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Debug for FlatBufferArena {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("FlatBufferArena")
+            .field("chunk_size", &self.chunk_size)
+            .field("allocated_bytes", &self.allocated_bytes())
+            .finish()
+    }
+}
+
+/// Bytes copied into a [FlatBufferArena], borrowing from it until the next [reset()](FlatBufferArena::reset).
+///
+/// The slice is `Copy`, so wrappers of it are `Copy` as well.
+#[derive(Clone, Copy)]
+pub struct ArenaSlice<'a> {
+    bytes: &'a [u8],
+}
+
+impl Deref for ArenaSlice<'_> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.bytes
+    }
+}
+
+impl AsRef<[u8]> for ArenaSlice<'_> {
+    fn as_ref(&self) -> &[u8] {
+        self.bytes
+    }
+}
+
+// The bytes live in a chunk of the arena, which does not move with the slice.
+unsafe impl StableDeref for ArenaSlice<'_> {}
+unsafe impl StableBuffer for ArenaSlice<'_> {}
+
+impl Debug for ArenaSlice<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("ArenaSlice").field(&self.bytes).finish()
+    }
+}
+
+impl<T: RelaxedFollowTrait + Verifiable, P: VerifierPolicy> OwnedFlatBuffer<T, Box<[u8]>, P> {
+    /// Copies the bytes into the arena and verifies them according to the policy `P`.
+    ///
+    /// The bytes stay in the arena until its next reset, even if the verification fails.
+    pub fn new_in_arena<'a>(arena: &'a FlatBufferArena, bytes: &[u8]) -> Result<OwnedFlatBuffer<T, ArenaSlice<'a>, P>, VerifyError> {
+        <OwnedFlatBuffer<T, ArenaSlice<'a>, P> as RelaxedFlatBufferTrait>::new(arena.alloc(bytes))
+    }
+}
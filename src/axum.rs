@@ -0,0 +1,129 @@
+//! An axum extractor and response for FlatBuffers.
+//!
+//! [FlatBuffer] extracts a wrapper from the request body and verifies it.
+//! The body is read with the limit configured by axum's `DefaultBodyLimit`, 2 MB by default,
+//! larger bodies are rejected with `413 Payload Too Large`, FlatBuffers failing the verification with `400 Bad Request`. \
+//! Returned from a handler, it responds with the bytes of the wrapper and the [CONTENT_TYPE].
+//!
+//! Requires the `axum` feature.
+//!
+//! # Example
+//! ```
+//! use flatbuffers_owned::axum::FlatBuffer;
+//!
+//! async fn echo(FlatBuffer(message): FlatBuffer<OwnedMessage>) -> FlatBuffer<OwnedMessage> {
+//!     println!("{}", message.as_actual().text());
+//!     FlatBuffer(message)
+//! }
+//!
+//! let app = Router::new().route("/echo", post(echo));
+//! ```
+
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::ops::Deref;
+use axum_core::extract::rejection::BytesRejection;
+use axum_core::extract::{FromRequest, Request};
+use axum_core::response::{IntoResponse, Response};
+use bytes::Bytes;
+use http::header::CONTENT_TYPE as CONTENT_TYPE_HEADER;
+use http::{HeaderValue, StatusCode};
+use crate::{RelaxedFlatBufferTrait, VerifyError};
+
+/// The content type of [FlatBuffer] responses.
+pub const CONTENT_TYPE: &str = "application/x-flatbuffers";
+
+/// Extracts the wrapper `F` from the request body, or responds with it.
+///
+/// Extracting requires the buffer of the wrapper to be constructible from a `Vec<u8>`, e.g. `Box<[u8]>`, `Arc<[u8]>` or `Bytes`.
+/// Responding requires it to convert into `Bytes`, e.g. `Box<[u8]>`, `Vec<u8>` or `Bytes`, so the bytes are not copied.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FlatBuffer<F>(pub F);
+
+impl<F> Deref for FlatBuffer<F> {
+    type Target = F;
+
+    fn deref(&self) -> &F {
+        &self.0
+    }
+}
+
+impl<F> From<F> for FlatBuffer<F> {
+    fn from(flatbuffer: F) -> Self {
+        FlatBuffer(flatbuffer)
+    }
+}
+
+impl<S, F> FromRequest<S> for FlatBuffer<F>
+    where S: Send + Sync,
+          F: RelaxedFlatBufferTrait,
+          F::Buffer: From<Vec<u8>>
+{
+    type Rejection = FlatBufferRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, FlatBufferRejection> {
+        let body = Bytes::from_request(req, state).await?;
+
+        Ok(FlatBuffer(F::new(F::Buffer::from(Vec::from(body)))?))
+    }
+}
+
+impl<F> IntoResponse for FlatBuffer<F>
+    where F: RelaxedFlatBufferTrait,
+          F::Buffer: Into<Bytes>
+{
+    fn into_response(self) -> Response {
+        let body: Bytes = self.0.into_inner().into();
+
+        ([(CONTENT_TYPE_HEADER, HeaderValue::from_static(CONTENT_TYPE))], body).into_response()
+    }
+}
+
+/// The rejection of the [FlatBuffer] extractor.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum FlatBufferRejection {
+    /// The body could not be read or exceeded the body limit.
+    Body(BytesRejection),
+    /// The body failed the verification. Responds with `400 Bad Request`.
+    InvalidFlatbuffer(VerifyError),
+}
+
+impl Display for FlatBufferRejection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FlatBufferRejection::Body(e) => Display::fmt(e, f),
+            FlatBufferRejection::InvalidFlatbuffer(e) => Display::fmt(e, f),
+        }
+    }
+}
+
+impl Error for FlatBufferRejection {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            FlatBufferRejection::Body(e) => Some(e),
+            FlatBufferRejection::InvalidFlatbuffer(e) => Some(e),
+        }
+    }
+}
+
+impl From<BytesRejection> for FlatBufferRejection {
+    fn from(e: BytesRejection) -> Self {
+        FlatBufferRejection::Body(e)
+    }
+}
+
+impl From<VerifyError> for FlatBufferRejection {
+    fn from(e: VerifyError) -> Self {
+        FlatBufferRejection::InvalidFlatbuffer(e)
+    }
+}
+
+impl IntoResponse for FlatBufferRejection {
+    fn into_response(self) -> Response {
+        match self {
+            FlatBufferRejection::Body(e) => e.into_response(),
+            FlatBufferRejection::InvalidFlatbuffer(e) => (StatusCode::BAD_REQUEST, format!("Invalid FlatBuffer: {}", e)).into_response(),
+        }
+    }
+}
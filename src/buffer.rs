@@ -0,0 +1,285 @@
+//! Buffer types to wrap FlatBuffers in.
+//!
+//! # Example
+//! ```
+//! use std::sync::Arc;
+//! use flatbuffers_owned::buffer::SlicedBuffer;
+//!
+//! let batch: Arc<[u8]> = receive_batch().into();
+//!
+//! let messages = frame_ranges(&batch)
+//!     .map(|range| SlicedBuffer::new(batch.clone(), range).ok_or(FrameError))
+//!     .map(|sliced| Ok(RelaxedMessage::new(sliced?)?))
+//!     .collect::<Result<Vec<_>, Error>>()?;
+//! ```
+
+use core::ops::Range;
+#[cfg(feature = "alloc")]
+use core::fmt::{Debug, Formatter};
+#[cfg(feature = "alloc")]
+use core::ops::Deref;
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, rc::Rc, sync::Arc, vec, vec::Vec};
+#[cfg(feature = "alloc")]
+use stable_deref_trait::StableDeref;
+
+/// The number of bytes an [InlineBuffer] stores inline before spilling to the heap.
+#[cfg(feature = "inline")]
+pub const INLINE_CAPACITY: usize = 64;
+
+/// A buffer storing up to [INLINE_CAPACITY] bytes inline, and larger ones on the heap. \
+/// The buffer of the `Inline{FLATBUFFER_NAME}` aliases.
+///
+/// Unlike the heap allocated buffers, the bytes of an inline buffer move with it.
+/// Its wrappers can therefore not be used with the [Cached](crate::Cached) wrapper.
+#[cfg(feature = "inline")]
+pub type InlineBuffer = smallvec::SmallVec<[u8; INLINE_CAPACITY]>;
+
+/// A heap allocated buffer overwriting its bytes with zeros when it is dropped, e.g. for FlatBuffers carrying credentials. \
+/// The buffer of the `Secret{FLATBUFFER_NAME}` aliases.
+#[cfg(feature = "zeroize")]
+pub type SecretBuffer = zeroize::Zeroizing<Box<[u8]>>;
+
+/// A heap allocated buffer of the custom allocator `A`. \
+/// The buffer of the `OwnedIn{FLATBUFFER_NAME}<A>` aliases.
+#[cfg(feature = "allocator-api2")]
+pub type BoxIn<A> = allocator_api2::boxed::Box<[u8], A>;
+
+/// The alignment of the largest FlatBuffer scalars, `u64`, `i64` and `f64`.
+///
+/// FlatBuffers are built so that their scalars are aligned relative to the start of the buffer.
+/// They are therefore only aligned in memory if the buffer starts at an address that is a multiple of this alignment.
+pub const ALIGNMENT: usize = core::mem::align_of::<u64>();
+
+/// Returns `true` if the bytes start at an address aligned to the [ALIGNMENT] of FlatBuffers.
+///
+/// Buffers sliced out of the middle of a network frame are often unaligned.
+pub fn is_aligned(bytes: &[u8]) -> bool {
+    bytes.as_ptr() as usize % ALIGNMENT == 0
+}
+
+/// A buffer whose bytes, as returned by `as_ref()`, stay at the same address when the buffer is moved. \
+/// Wrappers over these buffers implement the [StableRelaxedFlatBufferTrait](crate::StableRelaxedFlatBufferTrait), so they can be used with the [Cached](crate::Cached) wrapper.
+///
+/// Implemented for `&[u8]`, `Box<[u8]>`, `Vec<u8>`, `Arc<[u8]>`, `Rc<[u8]>` and the heap backed buffers of this crate. \
+/// Unlike [StableDeref](crate::StableDeref), this trait makes its guarantee about `as_ref()`, which is what the wrappers read their bytes through.
+///
+/// # Safety
+/// `as_ref()` must return the same bytes at the same address for the whole lifetime of the buffer, even if the buffer is moved,
+/// and the bytes must not be modified until the buffer is dropped.
+pub unsafe trait StableBuffer: AsRef<[u8]> {}
+
+unsafe impl StableBuffer for &[u8] {}
+
+#[cfg(feature = "alloc")]
+unsafe impl StableBuffer for Box<[u8]> {}
+
+#[cfg(feature = "alloc")]
+unsafe impl StableBuffer for Vec<u8> {}
+
+#[cfg(feature = "alloc")]
+unsafe impl StableBuffer for Arc<[u8]> {}
+
+#[cfg(feature = "alloc")]
+unsafe impl StableBuffer for Rc<[u8]> {}
+
+/// Pairs a buffer with a byte range of it, e.g. one message carved out of a received batch.
+///
+/// Combined with a shared buffer like `Arc<[u8]>`, many wrappers can be created from one allocation without copying the bytes.
+#[derive(Clone, Debug)]
+pub struct SlicedBuffer<B: AsRef<[u8]>> {
+    buffer: B,
+    range: Range<usize>,
+}
+
+impl<B: AsRef<[u8]>> SlicedBuffer<B> {
+    /// Returns `None` if the range is out of the bounds of the buffer.
+    pub fn new(buffer: B, range: Range<usize>) -> Option<Self> {
+        buffer.as_ref().get(range.clone())?;
+
+        Some(Self { buffer, range })
+    }
+
+    /// Returns the byte range within the buffer.
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+
+    /// Returns a reference to the whole buffer.
+    pub fn buffer(&self) -> &B {
+        &self.buffer
+    }
+
+    /// Consumes the slice and returns the whole buffer.
+    pub fn into_inner(self) -> B {
+        self.buffer
+    }
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for SlicedBuffer<B> {
+    fn as_ref(&self) -> &[u8] {
+        // The range was checked on construction, the buffer is expected to be immutable.
+        self.buffer.as_ref().get(self.range.clone()).unwrap_or_default()
+    }
+}
+
+/// A heap allocated copy of bytes, starting at an address aligned to the [ALIGNMENT] of FlatBuffers.
+#[cfg(feature = "alloc")]
+#[derive(Clone)]
+pub struct AlignedBuffer {
+    words: Box<[u64]>,
+    len: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl AlignedBuffer {
+    /// Copies the bytes into a new aligned allocation.
+    pub fn copy_from_slice(bytes: &[u8]) -> Self {
+        let mut words = vec![0u64; bytes.len().div_ceil(ALIGNMENT)].into_boxed_slice();
+
+        // The words are fully initialized and cover at least `bytes.len()` bytes.
+        unsafe { core::ptr::copy_nonoverlapping(bytes.as_ptr(), words.as_mut_ptr() as *mut u8, bytes.len()) };
+
+        Self { words, len: bytes.len() }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Deref for AlignedBuffer {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        // The words cover at least `len` initialized bytes.
+        unsafe { core::slice::from_raw_parts(self.words.as_ptr() as *const u8, self.len) }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl AsRef<[u8]> for AlignedBuffer {
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+// The bytes live in the boxed allocation, which does not move with the buffer.
+#[cfg(feature = "alloc")]
+unsafe impl StableDeref for AlignedBuffer {}
+#[cfg(feature = "alloc")]
+unsafe impl StableBuffer for AlignedBuffer {}
+
+#[cfg(feature = "alloc")]
+impl Debug for AlignedBuffer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("AlignedBuffer").field(&&**self).finish()
+    }
+}
+
+/// The internal buffer of a finished `FlatBufferBuilder`, taken over without copying it.
+///
+/// The builder writes the FlatBuffer back to front, so the bytes start at the `head` of the buffer. \
+/// Created by the [FinishOwnedTrait](crate::FinishOwnedTrait).
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug)]
+pub struct BuilderBuffer {
+    buffer: Vec<u8>,
+    head: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl BuilderBuffer {
+    /// Returns `None` if the head is out of the bounds of the buffer.
+    pub fn new(buffer: Vec<u8>, head: usize) -> Option<Self> {
+        if head > buffer.len() {
+            return None;
+        }
+
+        Some(Self { buffer, head })
+    }
+
+    pub(crate) fn from_collapsed(buffer: Vec<u8>, head: usize) -> Self {
+        // The builder keeps its head within its buffer.
+        let head = head.min(buffer.len());
+
+        Self { buffer, head }
+    }
+
+    /// Returns the offset the FlatBuffer starts at within the whole buffer.
+    pub fn head(&self) -> usize {
+        self.head
+    }
+
+    /// Consumes the buffer and returns the whole internal buffer of the builder,
+    /// e.g. for reusing it with `FlatBufferBuilder::from_vec()`.
+    pub fn into_inner(self) -> Vec<u8> {
+        self.buffer
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Deref for BuilderBuffer {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        // The head was checked on construction.
+        self.buffer.get(self.head..).unwrap_or_default()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl AsRef<[u8]> for BuilderBuffer {
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+// The bytes live in the heap allocation of the `Vec`, which does not move with the buffer.
+#[cfg(feature = "alloc")]
+unsafe impl StableDeref for BuilderBuffer {}
+#[cfg(feature = "alloc")]
+unsafe impl StableBuffer for BuilderBuffer {}
+
+/// Moves the finished bytes to the front of the internal buffer, without reallocating it.
+#[cfg(feature = "alloc")]
+impl From<BuilderBuffer> for Vec<u8> {
+    fn from(mut buffer: BuilderBuffer) -> Self {
+        buffer.buffer.drain(..buffer.head);
+        buffer.buffer
+    }
+}
+
+/// The buffer of a wrapper created by [new_realigned()](crate::OwnedFlatBuffer::new_realigned).
+///
+/// Holds the passed buffer if it was already aligned, or an aligned copy of its bytes otherwise.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug)]
+pub enum Realigned<B: AsRef<[u8]>> {
+    Aligned(B),
+    Copied(AlignedBuffer),
+}
+
+#[cfg(feature = "alloc")]
+impl<B: AsRef<[u8]>> Realigned<B> {
+    /// Copies the bytes into an [AlignedBuffer] only if they are not already aligned.
+    pub fn new(buffer: B) -> Self {
+        if is_aligned(buffer.as_ref()) {
+            Realigned::Aligned(buffer)
+        } else {
+            Realigned::Copied(AlignedBuffer::copy_from_slice(buffer.as_ref()))
+        }
+    }
+
+    /// Returns `true` if the bytes had to be copied.
+    pub fn is_copied(&self) -> bool {
+        matches!(self, Realigned::Copied(_))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<B: AsRef<[u8]>> AsRef<[u8]> for Realigned<B> {
+    fn as_ref(&self) -> &[u8] {
+        match self {
+            Realigned::Aligned(buffer) => buffer.as_ref(),
+            Realigned::Copied(buffer) => buffer,
+        }
+    }
+}
@@ -0,0 +1,38 @@
+//! Wrappers over `bytes` buffers for network receive pipelines.
+//!
+//! Requires the `bytes` feature.
+
+use alloc::boxed::Box;
+use bytes::{Bytes, BytesMut};
+use flatbuffers::Verifiable;
+use crate::{OwnedFlatBuffer, RelaxedFlatBufferTrait, RelaxedFollowTrait, VerifierPolicy, VerifyError};
+
+impl<T: RelaxedFollowTrait + Verifiable, P: VerifierPolicy> OwnedFlatBuffer<T, Box<[u8]>, P> {
+    /// Freezes a frame split off a receive buffer and verifies it according to the policy `P`, without copying the bytes.
+    ///
+    /// The returned wrapper is cheap to clone and can be sent to other threads and tasks.
+    ///
+    /// # Example
+    /// ```
+    /// let mut buffer = BytesMut::with_capacity(64 * 1024);
+    ///
+    /// loop {
+    ///     socket.read_buf(&mut buffer).await?;
+    ///
+    ///     while let Some(frame_len) = next_frame_len(&buffer) {
+    ///         let message = OwnedMessage::from_bytes_mut(buffer.split_to(frame_len))?;
+    ///         // ...
+    ///     }
+    /// }
+    /// ```
+    pub fn from_bytes_mut(frame: BytesMut) -> Result<OwnedFlatBuffer<T, Bytes, P>, VerifyError> {
+        <OwnedFlatBuffer<T, Bytes, P> as RelaxedFlatBufferTrait>::new(frame.freeze())
+    }
+}
+
+impl<T: RelaxedFollowTrait + Verifiable, P: VerifierPolicy> OwnedFlatBuffer<T, BytesMut, P> {
+    /// Freezes the buffer into a `Bytes`, which is cheap to clone. The bytes are neither copied nor verified again.
+    pub fn freeze(self) -> OwnedFlatBuffer<T, Bytes, P> {
+        self.map_buffer(BytesMut::freeze)
+    }
+}
@@ -0,0 +1,154 @@
+//! Verification cache for shared buffers.
+//!
+//! Shared buffers like `Arc<[u8]>` or `bytes::Bytes` are often wrapped multiple times, e.g. once by a router and once by every consumer.
+//! The [VerificationCache] remembers recently verified buffers, so re-wrapping them skips the verifier.
+//!
+//! # Invalidation rules
+//! A cache entry is keyed by the address and length of the buffer, the wrapper type including its [VerifierPolicy](crate::VerifierPolicy) and the cache generation. \
+//! To make sure that an address can not be reused by different bytes while its entry exists,
+//! each entry keeps a clone of the verified buffer alive until it is evicted or the cache is [cleared](VerificationCache::clear).
+//!
+//! This means:
+//! - Only buffers whose clones share the same memory (`Arc<[u8]>`, `Rc<[u8]>`, `Bytes`, `&'static [u8]`) can ever hit the cache. \
+//!   Cloning a `Box<[u8]>` or `Vec<u8>` allocates, so these are verified every time.
+//! - The buffer must be immutable while it is shared, which all of the above guarantee.
+//! - The cache retains up to `capacity` buffers. Call [clear()](VerificationCache::clear) to release them early.
+//!
+//! # Example
+//! ```
+//! use flatbuffers_owned::cache::VerificationCache;
+//!
+//! let cache = VerificationCache::new(1024);
+//!
+//! let message: RelaxedMessage<Arc<[u8]>> = cache.new_verified(shared_bytes.clone())?;
+//! let again: RelaxedMessage<Arc<[u8]>> = cache.new_verified(shared_bytes)?; // skips the verifier
+//! ```
+
+use std::any::{Any, TypeId};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use crate::{RelaxedFlatBufferTrait, VerifyError};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct Key {
+    address: usize,
+    len: usize,
+    generation: u64,
+    wrapper: TypeId,
+}
+
+#[derive(Default)]
+struct Entries {
+    generation: u64,
+    buffers: HashMap<Key, Box<dyn Any + Send + Sync>>,
+    order: VecDeque<Key>,
+}
+
+/// Remembers recently verified shared buffers to skip redundant verification.
+///
+/// The cache is `Send` and `Sync` and can be shared between threads and async tasks.
+/// The lock is only held for the lookup and insertion, never while verifying. \
+/// See the [module documentation](self) for the invalidation rules.
+pub struct VerificationCache {
+    capacity: usize,
+    entries: Mutex<Entries>,
+}
+
+impl VerificationCache {
+    /// Creates a cache remembering up to `capacity` verified buffers.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Mutex::new(Entries::default()),
+        }
+    }
+
+    /// Initializes the wrapper like [RelaxedFlatBufferTrait::new()], but skips the verification if the same buffer has recently been verified by the same wrapper type. \
+    /// Wrappers with different verifier policies never share an entry, as their policies may reject different buffers.
+    pub fn new_verified<T, TBuffer>(&self, data: TBuffer) -> Result<T, VerifyError>
+        where T: RelaxedFlatBufferTrait<Buffer = TBuffer> + 'static,
+              TBuffer: AsRef<[u8]> + Clone + Send + Sync + 'static
+    {
+        let bytes = data.as_ref();
+        // The generation is filled in by the lookup.
+        let mut key = Key {
+            address: bytes.as_ptr() as usize,
+            len: bytes.len(),
+            generation: 0,
+            wrapper: TypeId::of::<T>(),
+        };
+
+        if self.contains(&mut key) {
+            // Safety:
+            // The entry keeps a clone of a verified buffer alive at this address, so these are the same, immutable bytes.
+            return Ok(unsafe { T::new_unchecked(data) });
+        }
+
+        T::verify(bytes)?;
+
+        // The entry only pins the address if the clone shares the memory of the verified buffer.
+        let clone = data.clone();
+        if clone.as_ref().as_ptr() as usize == key.address && clone.as_ref().len() == key.len {
+            self.insert(key, clone);
+        }
+
+        // Safety:
+        // Verified above.
+        Ok(unsafe { T::new_unchecked(data) })
+    }
+
+    /// Forgets all verified buffers and releases the retained clones.
+    pub fn clear(&self) {
+        let mut entries = self.lock();
+
+        entries.generation = entries.generation.wrapping_add(1);
+        entries.buffers.clear();
+        entries.order.clear();
+    }
+
+    /// Returns the number of remembered buffers.
+    pub fn len(&self) -> usize {
+        self.lock().buffers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn contains(&self, key: &mut Key) -> bool {
+        let entries = self.lock();
+        key.generation = entries.generation;
+
+        entries.buffers.contains_key(key)
+    }
+
+    fn insert<TBuffer: Send + Sync + 'static>(&self, key: Key, buffer: TBuffer) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let mut entries = self.lock();
+
+        // The cache has been cleared while verifying.
+        if entries.generation != key.generation {
+            return;
+        }
+
+        if entries.buffers.insert(key, Box::new(buffer)).is_some() {
+            return;
+        }
+
+        entries.order.push_back(key);
+        while entries.order.len() > self.capacity {
+            if let Some(evicted) = entries.order.pop_front() {
+                entries.buffers.remove(&evicted);
+            }
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Entries> {
+        // The entries stay consistent even if a panic poisoned the lock.
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
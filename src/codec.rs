@@ -0,0 +1,131 @@
+//! A tokio-util codec for size-prefixed FlatBuffers.
+//!
+//! Requires the `tokio-util` feature.
+
+use std::convert::TryFrom;
+use std::fmt::{Debug, Formatter};
+use std::io;
+use std::marker::PhantomData;
+use bytes::{Bytes, BytesMut};
+use flatbuffers::Verifiable;
+use tokio_util::codec::{Decoder, Encoder};
+use crate::size_prefixed::{frame_header, Framing, StreamError, DEFAULT_MAX_MESSAGE_SIZE, PREFIX_LEN};
+use crate::{DefaultPolicy, EmbeddedFlatBuffer, RelaxedFlatBufferTrait, RelaxedFollowTrait, VerifierPolicy};
+
+/// Decodes and encodes size-prefixed FlatBuffers `T` for tokio-util's `Framed`, `FramedRead` and `FramedWrite`.
+///
+/// Decoded frames are split off the read buffer and frozen, so every message is a zero-copy wrapper over `Bytes`,
+/// verified according to the policy `P`. \
+/// Encoded frames are written exactly like by the [SizePrefixedWriter](crate::size_prefixed::SizePrefixedWriter),
+/// from any wrapper of the FlatBuffer `T`.
+///
+/// `FramedRead` ends the stream after the first error, including a message failing the checksum or the verification.
+///
+/// # Example
+/// ```
+/// use flatbuffers_owned::size_prefixed::FlatBufferCodec;
+///
+/// let mut framed = Framed::new(stream, FlatBufferCodec::<Message<'static>>::new());
+///
+/// while let Some(message) = framed.next().await {
+///     let message = message?;
+///     framed.send(&reply_to(message.as_actual_at())).await?;
+/// }
+/// ```
+pub struct FlatBufferCodec<T: RelaxedFollowTrait + Verifiable, P: VerifierPolicy = DefaultPolicy> {
+    max_message_size: usize,
+    framing: Framing,
+    flatbuffer: PhantomData<fn() -> (T, P)>,
+}
+
+impl<T: RelaxedFollowTrait + Verifiable, P: VerifierPolicy> FlatBufferCodec<T, P> {
+    /// Creates a codec accepting messages of up to [DEFAULT_MAX_MESSAGE_SIZE] bytes.
+    pub fn new() -> Self {
+        Self::with_max_message_size(DEFAULT_MAX_MESSAGE_SIZE)
+    }
+
+    /// Creates a codec accepting messages of up to `max_message_size` bytes, excluding the size prefix.
+    pub fn with_max_message_size(max_message_size: usize) -> Self {
+        Self { max_message_size, framing: Framing::Plain, flatbuffer: PhantomData }
+    }
+
+    /// Sets the layout of the frames, [Framing::Plain] by default.
+    pub fn with_framing(mut self, framing: Framing) -> Self {
+        self.framing = framing;
+        self
+    }
+
+    /// Returns the maximum size of a message, excluding the size prefix.
+    pub fn max_message_size(&self) -> usize {
+        self.max_message_size
+    }
+}
+
+impl<T: RelaxedFollowTrait + Verifiable, P: VerifierPolicy> Default for FlatBufferCodec<T, P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: RelaxedFollowTrait + Verifiable, P: VerifierPolicy> Clone for FlatBufferCodec<T, P> {
+    fn clone(&self) -> Self {
+        Self::with_max_message_size(self.max_message_size).with_framing(self.framing)
+    }
+}
+
+impl<T: RelaxedFollowTrait + Verifiable, P: VerifierPolicy> Debug for FlatBufferCodec<T, P> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FlatBufferCodec")
+            .field("max_message_size", &self.max_message_size)
+            .field("framing", &self.framing)
+            .finish()
+    }
+}
+
+impl<T: RelaxedFollowTrait + Verifiable, P: VerifierPolicy> Decoder for FlatBufferCodec<T, P> {
+    type Item = EmbeddedFlatBuffer<T, Bytes, P>;
+    type Error = StreamError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, StreamError> {
+        let prefix = match src.get(..PREFIX_LEN).and_then(|prefix| <[u8; PREFIX_LEN]>::try_from(prefix).ok()) {
+            Some(prefix) => prefix,
+            None => return Ok(None),
+        };
+
+        let len = u32::from_le_bytes(prefix) as usize;
+        if len > self.max_message_size {
+            return Err(StreamError::TooLarge { len, max: self.max_message_size });
+        }
+
+        let frame_len = len.saturating_add(PREFIX_LEN);
+        if src.len() < frame_len {
+            // Makes room for the rest of the frame, so it is read with as few calls as possible.
+            src.reserve(frame_len.saturating_sub(src.len()));
+            return Ok(None);
+        }
+
+        let frame = src.split_to(frame_len).freeze();
+        let loc = self.framing.check(&frame).ok_or(StreamError::ChecksumMismatch)?;
+
+        Ok(Some(EmbeddedFlatBuffer::new_at(frame, loc)?))
+    }
+}
+
+impl<'a, T: RelaxedFollowTrait + Verifiable, P: VerifierPolicy, F: RelaxedFlatBufferTrait<FlatBuffer = T>> Encoder<&'a F> for FlatBufferCodec<T, P> {
+    type Error = StreamError;
+
+    fn encode(&mut self, flatbuffer: &'a F, dst: &mut BytesMut) -> Result<(), StreamError> {
+        let data: &[u8] = flatbuffer;
+
+        let (header, header_len) = match frame_header(data, self.framing) {
+            Some(header) => header,
+            None => return Err(io::Error::new(io::ErrorKind::InvalidInput, "FlatBuffer too short or too large for a size-prefixed frame").into()),
+        };
+
+        dst.reserve(header_len.saturating_add(data.len()));
+        dst.extend_from_slice(header.get(..header_len).unwrap_or_default());
+        dst.extend_from_slice(data);
+
+        Ok(())
+    }
+}
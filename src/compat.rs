@@ -0,0 +1,62 @@
+//! Compatibility with the trait signatures of flatbuffers-owned 0.1 and 0.2.
+//!
+//! Up to 0.2, the buffer type was a generic parameter of the [RelaxedFlatBufferTrait](crate::RelaxedFlatBufferTrait). \
+//! The deprecated [RelaxedFlatBufferTrait] of this module keeps that shape and is implemented for every wrapper,
+//! so generic code written against 0.2 keeps compiling while it is migrated.
+//!
+//! # Example
+//! ```
+//! #[allow(deprecated)]
+//! use flatbuffers_owned::compat::RelaxedFlatBufferTrait;
+//!
+//! fn store_fbs<TBuffer>(flatbuffers: &[impl RelaxedFlatBufferTrait<TBuffer>]) {
+//!     // ...
+//! }
+//! ```
+#![allow(deprecated)]
+
+use core::ops::Deref;
+use flatbuffers::{Follow, InvalidFlatbuffer, Verifiable};
+use crate::RelaxedFollowTrait;
+
+/// The 0.2 shape of the [RelaxedFlatBufferTrait](crate::RelaxedFlatBufferTrait), taking the buffer type as a generic parameter.
+///
+/// # Safety
+/// Implemented for all implementors of the current [RelaxedFlatBufferTrait](crate::RelaxedFlatBufferTrait), whose safety requirements apply.
+#[deprecated(since = "0.3.0", note = "use `flatbuffers_owned::RelaxedFlatBufferTrait`, which names the buffer as the associated `Buffer` type")]
+pub unsafe trait RelaxedFlatBufferTrait<TBuffer>
+    where Self: Deref<Target = [u8]> + Sized
+{
+    type FlatBuffer: RelaxedFollowTrait + Verifiable;
+
+    fn as_actual(&self) -> <<<Self as RelaxedFlatBufferTrait<TBuffer>>::FlatBuffer as RelaxedFollowTrait>::Inner<'_> as Follow<'_>>::Inner;
+
+    fn verify(data: &[u8]) -> Result<(), InvalidFlatbuffer>;
+
+    fn new(data: TBuffer) -> Result<Self, InvalidFlatbuffer>;
+
+    /// # Safety
+    /// The caller must ensure that the data has already been verified as this FlatBuffer type and that it remains immutable.
+    unsafe fn new_unchecked(data: TBuffer) -> Self;
+}
+
+unsafe impl<T: crate::RelaxedFlatBufferTrait> RelaxedFlatBufferTrait<T::Buffer> for T {
+    type FlatBuffer = T::FlatBuffer;
+
+    #[inline(always)]
+    fn as_actual(&self) -> <<<Self as RelaxedFlatBufferTrait<T::Buffer>>::FlatBuffer as RelaxedFollowTrait>::Inner<'_> as Follow<'_>>::Inner {
+        <T as crate::RelaxedFlatBufferTrait>::as_actual(self)
+    }
+
+    fn verify(data: &[u8]) -> Result<(), InvalidFlatbuffer> {
+        <T as crate::RelaxedFlatBufferTrait>::verify(data).map_err(|e| e.error)
+    }
+
+    fn new(data: T::Buffer) -> Result<Self, InvalidFlatbuffer> {
+        <T as crate::RelaxedFlatBufferTrait>::new(data).map_err(|e| e.error)
+    }
+
+    unsafe fn new_unchecked(data: T::Buffer) -> Self {
+        <T as crate::RelaxedFlatBufferTrait>::new_unchecked(data)
+    }
+}
@@ -0,0 +1,130 @@
+//! Compressed FlatBuffers for storage and transport.
+//!
+//! The padding and offsets of FlatBuffers compress very well.
+//! With the `zstd` feature, [to_zstd()](crate::OwnedFlatBuffer::to_zstd) compresses a wrapper into a zstd frame
+//! and [from_zstd()](crate::OwnedFlatBuffer::from_zstd) decompresses and verifies it again.
+//! With the `lz4` feature, [to_lz4()](crate::OwnedFlatBuffer::to_lz4) and [from_lz4()](crate::OwnedFlatBuffer::from_lz4)
+//! do the same with an LZ4 block prefixed with its uncompressed size, as written by `lz4_flex::compress_prepend_size()`.
+//!
+//! Decompressing stops at a maximum size, so a small malicious input cannot allocate an arbitrary amount of memory.
+//!
+//! # Example
+//! ```
+//! let compressed = message.to_zstd(3)?;
+//!
+//! let message = OwnedMessage::from_zstd(&compressed)?;
+//! ```
+
+use alloc::boxed::Box;
+use core::fmt::{Display, Formatter};
+#[cfg(feature = "std")]
+use std::error::Error;
+use flatbuffers::Verifiable;
+use crate::{OwnedFlatBuffer, RelaxedFlatBufferTrait, RelaxedFollowTrait, VerifierPolicy, VerifyError};
+
+/// The default maximum size of a decompressed FlatBuffer, 16 MiB.
+pub const DEFAULT_MAX_DECOMPRESSED_SIZE: usize = 16 * 1024 * 1024;
+
+/// The error returned when decompressing a FlatBuffer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DecompressError {
+    /// The compressed data is corrupt or not in the expected format.
+    Corrupt,
+    /// The decompressed data exceeds the maximum size of `max` bytes.
+    TooLarge { max: usize },
+    /// The decompressed data failed the verification.
+    InvalidFlatbuffer(VerifyError),
+}
+
+impl Display for DecompressError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            DecompressError::Corrupt => write!(f, "Corrupt compressed data"),
+            DecompressError::TooLarge { max } => write!(f, "Decompressed data exceeds the maximum of {} bytes", max),
+            DecompressError::InvalidFlatbuffer(e) => Display::fmt(e, f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for DecompressError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            DecompressError::Corrupt => None,
+            DecompressError::TooLarge { .. } => None,
+            DecompressError::InvalidFlatbuffer(e) => Some(e),
+        }
+    }
+}
+
+impl From<VerifyError> for DecompressError {
+    fn from(e: VerifyError) -> Self {
+        DecompressError::InvalidFlatbuffer(e)
+    }
+}
+
+#[cfg(feature = "zstd")]
+impl<T: RelaxedFollowTrait + Verifiable, P: VerifierPolicy> OwnedFlatBuffer<T, Box<[u8]>, P> {
+    /// Decompresses a zstd frame of up to [DEFAULT_MAX_DECOMPRESSED_SIZE] bytes and verifies it according to the policy `P`.
+    pub fn from_zstd(compressed: &[u8]) -> Result<Self, DecompressError> {
+        Self::from_zstd_with_limit(compressed, DEFAULT_MAX_DECOMPRESSED_SIZE)
+    }
+
+    /// Decompresses a zstd frame of up to `max_size` bytes and verifies it according to the policy `P`.
+    pub fn from_zstd_with_limit(compressed: &[u8], max_size: usize) -> Result<Self, DecompressError> {
+        use std::io::Read;
+
+        let decoder = zstd::stream::read::Decoder::new(compressed).map_err(|_| DecompressError::Corrupt)?;
+
+        // Reads one byte beyond the limit to tell an exactly fitting FlatBuffer from a larger one.
+        let mut decompressed = Vec::new();
+        decoder.take((max_size as u64).saturating_add(1))
+            .read_to_end(&mut decompressed)
+            .map_err(|_| DecompressError::Corrupt)?;
+
+        if decompressed.len() > max_size {
+            return Err(DecompressError::TooLarge { max: max_size });
+        }
+
+        Ok(<Self as RelaxedFlatBufferTrait>::new(decompressed.into_boxed_slice())?)
+    }
+}
+
+#[cfg(feature = "zstd")]
+impl<T: RelaxedFollowTrait + Verifiable, B: AsRef<[u8]>, P: VerifierPolicy> OwnedFlatBuffer<T, B, P> {
+    /// Compresses the FlatBuffer into a zstd frame with the passed compression level, e.g. `3`, the default of zstd.
+    pub fn to_zstd(&self, level: i32) -> std::io::Result<Vec<u8>> {
+        zstd::bulk::compress(self.buffer.as_ref(), level)
+    }
+}
+
+#[cfg(feature = "lz4")]
+impl<T: RelaxedFollowTrait + Verifiable, P: VerifierPolicy> OwnedFlatBuffer<T, Box<[u8]>, P> {
+    /// Decompresses a size-prefixed LZ4 block of up to [DEFAULT_MAX_DECOMPRESSED_SIZE] bytes and verifies it according to the policy `P`.
+    pub fn from_lz4(compressed: &[u8]) -> Result<Self, DecompressError> {
+        Self::from_lz4_with_limit(compressed, DEFAULT_MAX_DECOMPRESSED_SIZE)
+    }
+
+    /// Decompresses a size-prefixed LZ4 block of up to `max_size` bytes and verifies it according to the policy `P`. \
+    /// The size prefix is checked before any memory is allocated.
+    pub fn from_lz4_with_limit(compressed: &[u8], max_size: usize) -> Result<Self, DecompressError> {
+        let (size, block) = lz4_flex::block::uncompressed_size(compressed).map_err(|_| DecompressError::Corrupt)?;
+
+        if size > max_size {
+            return Err(DecompressError::TooLarge { max: max_size });
+        }
+
+        let decompressed = lz4_flex::block::decompress(block, size).map_err(|_| DecompressError::Corrupt)?;
+
+        Ok(<Self as RelaxedFlatBufferTrait>::new(decompressed.into_boxed_slice())?)
+    }
+}
+
+#[cfg(feature = "lz4")]
+impl<T: RelaxedFollowTrait + Verifiable, B: AsRef<[u8]>, P: VerifierPolicy> OwnedFlatBuffer<T, B, P> {
+    /// Compresses the FlatBuffer into an LZ4 block prefixed with its uncompressed size.
+    pub fn to_lz4(&self) -> alloc::vec::Vec<u8> {
+        lz4_flex::block::compress_prepend_size(self.buffer.as_ref())
+    }
+}
@@ -0,0 +1,63 @@
+//! diesel `ToSql` and `FromSql` implementations, mapping wrappers to `Binary` columns of every diesel backend.
+//!
+//! Wrappers are usable as `Binary` query arguments, in `Insertable` structs and as fields of `Queryable` structs. \
+//! They bind without copying the bytes. `Owned{FLATBUFFER_NAME}` wrappers are read from binary columns and verified before they are returned,
+//! a failed verification is reported as the deserialization error of the column.
+//!
+//! Requires the `diesel` feature.
+//!
+//! # Example
+//! ```
+//! #[derive(Queryable, Insertable)]
+//! #[diesel(table_name = messages)]
+//! struct StoredMessage {
+//!     id: i32,
+//!     message: OwnedMessage,
+//! }
+//!
+//! diesel::insert_into(messages::table).values(&stored_message).execute(&mut connection)?;
+//!
+//! let message: OwnedMessage = messages::table.find(id).select(messages::message).first(&mut connection)?;
+//! ```
+
+use core::fmt::Debug;
+use ::diesel::backend::Backend;
+use ::diesel::deserialize::{self, FromSql, FromSqlRow};
+use ::diesel::expression::AsExpression;
+use ::diesel::serialize::{self, Output, ToSql};
+use ::diesel::sql_types::Binary;
+use flatbuffers::Verifiable;
+use crate::{OwnedFlatBuffer, RelaxedFlatBufferTrait, RelaxedFollowTrait, VerifierPolicy};
+
+/// Binds the bytes without copying them.
+impl<T, B, P, DB> ToSql<Binary, DB> for OwnedFlatBuffer<T, B, P>
+    where T: RelaxedFollowTrait + Verifiable,
+          B: AsRef<[u8]> + Debug,
+          P: VerifierPolicy,
+          DB: Backend,
+          [u8]: ToSql<Binary, DB>
+{
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, DB>) -> serialize::Result {
+        <[u8] as ToSql<Binary, DB>>::to_sql(self.as_ref(), out)
+    }
+}
+
+/// Copies the column and verifies it according to the policy `P`.
+impl<T, P, DB> FromSql<Binary, DB> for OwnedFlatBuffer<T, Box<[u8]>, P>
+    where T: RelaxedFollowTrait + Verifiable,
+          P: VerifierPolicy,
+          DB: Backend,
+          Vec<u8>: FromSql<Binary, DB>
+{
+    fn from_sql(bytes: DB::RawValue<'_>) -> deserialize::Result<Self> {
+        let data = <Vec<u8> as FromSql<Binary, DB>>::from_sql(bytes)?;
+
+        Ok(<Self as RelaxedFlatBufferTrait>::new(data.into_boxed_slice())?)
+    }
+}
+
+// Implements AsExpression<Binary> and Queryable<Binary, _> for the wrapper in its field, like deriving them on the wrapper itself.
+#[derive(AsExpression, FromSqlRow)]
+#[diesel(foreign_derive, sql_type = Binary)]
+#[allow(dead_code)]
+struct OwnedFlatBufferProxy<T: RelaxedFollowTrait + Verifiable, B: AsRef<[u8]>, P: VerifierPolicy>(OwnedFlatBuffer<T, B, P>);
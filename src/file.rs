@@ -0,0 +1,113 @@
+//! Loading FlatBuffers from files and writing them back atomically.
+//!
+//! Requires the `std` feature.
+
+use std::convert::TryFrom;
+use std::ffi::OsString;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use flatbuffers::Verifiable;
+use crate::{FileError, OwnedFlatBuffer, RelaxedFlatBufferTrait, RelaxedFollowTrait, VerifierPolicy};
+
+impl<T: RelaxedFollowTrait + Verifiable, P: VerifierPolicy> OwnedFlatBuffer<T, Box<[u8]>, P> {
+    /// Reads the whole file into memory and verifies it according to the policy `P`.
+    ///
+    /// # Example
+    /// ```
+    /// let config = OwnedConfig::from_file("config.bin")?;
+    /// ```
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, FileError> {
+        Self::from_file_with_limit(path, usize::MAX)
+    }
+
+    /// Like [from_file()](OwnedFlatBuffer::from_file), but fails with [FileError::TooLarge] for files larger than `max_size` bytes. \
+    /// The size is checked before the file is read, and the read stops at the limit in case the file grows meanwhile.
+    pub fn from_file_with_limit(path: impl AsRef<Path>, max_size: usize) -> Result<Self, FileError> {
+        let file = File::open(path)?;
+
+        let len = file.metadata()?.len();
+        if len > max_size as u64 {
+            return Err(FileError::TooLarge { len, max: max_size });
+        }
+
+        // The reported size is only a hint, e.g. for sparse files, so a failed reservation is returned instead of aborting.
+        let mut data = Vec::new();
+        data.try_reserve_exact(usize::try_from(len).unwrap_or(max_size))
+            .map_err(|_| io::Error::new(io::ErrorKind::OutOfMemory, "Not enough memory to read the file"))?;
+
+        // Reads one byte beyond the limit to notice a file that grew after its size was checked.
+        file.take((max_size as u64).saturating_add(1)).read_to_end(&mut data)?;
+
+        if data.len() > max_size {
+            return Err(FileError::TooLarge { len: data.len() as u64, max: max_size });
+        }
+
+        Ok(<Self as RelaxedFlatBufferTrait>::new(data.into_boxed_slice())?)
+    }
+}
+
+impl<T: RelaxedFollowTrait + Verifiable, B: AsRef<[u8]>, P: VerifierPolicy> OwnedFlatBuffer<T, B, P> {
+    /// Writes the FlatBuffer to the file atomically, replacing an existing file.
+    ///
+    /// The bytes are written to a temporary file next to the target, synced to disk and then renamed over the target.
+    /// After a crash the file therefore contains either the previous or the new FlatBuffer, never a partially written one. \
+    /// On Unix, the directory is synced as well, so the rename itself survives a crash.
+    ///
+    /// # Example
+    /// ```
+    /// state.write_to_file("state.bin")?;
+    ///
+    /// let state = OwnedState::from_file("state.bin")?;
+    /// ```
+    pub fn write_to_file(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        let temp_path = temp_path(path)?;
+
+        if let Err(e) = write_synced(&temp_path, self.buffer.as_ref()).and_then(|_| fs::rename(&temp_path, path)) {
+            let _ = fs::remove_file(&temp_path);
+            return Err(e);
+        }
+
+        sync_parent(path)
+    }
+}
+
+/// Distinguishes the temporary files of concurrent writes within the process.
+static TEMP_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Returns the path of the temporary file, a hidden file next to the target, so the rename does not cross file systems. \
+/// The process id and a per-call counter keep concurrent writes to the same target from sharing a temporary file.
+fn temp_path(path: &Path) -> io::Result<PathBuf> {
+    let file_name = match path.file_name() {
+        Some(file_name) => file_name,
+        None => return Err(io::Error::new(io::ErrorKind::InvalidInput, "Path does not name a file")),
+    };
+
+    let mut temp_name = OsString::from(".");
+    temp_name.push(file_name);
+    temp_name.push(format!(".{}.{}.tmp", std::process::id(), TEMP_COUNTER.fetch_add(1, Ordering::Relaxed)));
+
+    Ok(path.with_file_name(temp_name))
+}
+
+fn write_synced(path: &Path, data: &[u8]) -> io::Result<()> {
+    // Fails instead of sharing a temporary file left behind by a crashed process that had the same id.
+    let mut file = OpenOptions::new().write(true).create_new(true).open(path)?;
+    file.write_all(data)?;
+    file.sync_all()
+}
+
+#[cfg(unix)]
+fn sync_parent(path: &Path) -> io::Result<()> {
+    match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => File::open(parent)?.sync_all(),
+        _ => File::open(".")?.sync_all(),
+    }
+}
+
+#[cfg(not(unix))]
+fn sync_parent(_path: &Path) -> io::Result<()> {
+    Ok(())
+}
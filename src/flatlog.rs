@@ -0,0 +1,410 @@
+//! An append-only log of size-prefixed FlatBuffers with a sidecar index.
+//!
+//! The [FlatLogWriter] appends every message as a size-prefixed frame to the log file,
+//! exactly like the [SizePrefixedWriter](crate::size_prefixed::SizePrefixedWriter), so the log can also be read
+//! with the [SizePrefixedReader](crate::size_prefixed::SizePrefixedReader). \
+//! For every message, the offset of its frame is appended to the index file next to the log, see [index_path()],
+//! as a little-endian `u64`. The record number of a message is the position of its offset in the index.
+//!
+//! The log is always written before the index. After a crash, [open()](FlatLogWriter::open) drops index entries
+//! of incompletely written frames and truncates the log behind the last indexed frame, so both files agree again.
+//!
+//! With the `mmap` feature, the [FlatLogReader] memory-maps the log and its index and returns zero-copy wrappers
+//! by record number or by iterating the log.
+//!
+//! Requires the `std` feature.
+//!
+//! # Example
+//! ```
+//! use flatbuffers_owned::flatlog::{FlatLogReader, FlatLogWriter, SyncPolicy};
+//!
+//! let mut log = FlatLogWriter::<Event<'static>>::with_sync_policy("events.log", SyncPolicy::EveryMessage)?;
+//! let record = log.append(&event)?;
+//!
+//! let reader = unsafe { FlatLogReader::<Event<'static>>::open("events.log")? };
+//! let event = reader.get(record).unwrap()?;
+//! println!("{}", event.as_actual_at().kind());
+//! ```
+
+#[cfg(feature = "mmap")]
+use std::convert::TryFrom;
+use std::ffi::OsString;
+use std::fmt::{Debug, Formatter};
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufWriter, Read, Seek, SeekFrom, Write};
+#[cfg(feature = "mmap")]
+use std::iter::FusedIterator;
+use std::marker::PhantomData;
+#[cfg(feature = "mmap")]
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+#[cfg(feature = "mmap")]
+use std::sync::{Arc, OnceLock};
+use flatbuffers::Verifiable;
+#[cfg(feature = "mmap")]
+use memmap2::Mmap;
+#[cfg(feature = "mmap")]
+use crate::size_prefixed::{next_frame, FrameError};
+use crate::size_prefixed::{frame_header, Framing, PREFIX_LEN};
+use crate::{RelaxedFlatBufferTrait, RelaxedFollowTrait};
+#[cfg(feature = "mmap")]
+use crate::{DefaultPolicy, EmbeddedFlatBuffer, FileError, VerifierPolicy, VerifyError};
+
+/// The length of an index entry in bytes.
+pub const INDEX_ENTRY_LEN: usize = 8;
+
+/// Returns the path of the index file of the log at `path`, the path with `.idx` appended.
+pub fn index_path(path: impl AsRef<Path>) -> PathBuf {
+    let mut index_path = OsString::from(path.as_ref().as_os_str());
+    index_path.push(".idx");
+
+    PathBuf::from(index_path)
+}
+
+/// When a [FlatLogWriter] syncs the log and the index to disk.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SyncPolicy {
+    /// Only syncs on [sync()](FlatLogWriter::sync).
+    #[default]
+    Manual,
+    /// Syncs after every message, so every appended message is durable once [append()](FlatLogWriter::append) returns.
+    EveryMessage,
+    /// Syncs after every `n` messages.
+    EveryMessages(usize),
+}
+
+/// Appends FlatBuffers `T` to a log file and their offsets to its index file.
+///
+/// Appended messages are buffered. [flush()](FlatLogWriter::flush) hands them to the operating system,
+/// [sync()](FlatLogWriter::sync) additionally waits until they are stored on disk.
+/// Dropping the writer flushes, but does not sync.
+pub struct FlatLogWriter<T: RelaxedFollowTrait + Verifiable> {
+    // The log is declared first, so it is also flushed first when the writer is dropped.
+    log: BufWriter<File>,
+    index: BufWriter<File>,
+    log_len: u64,
+    records: u64,
+    sync_policy: SyncPolicy,
+    unsynced: usize,
+    flatbuffer: PhantomData<fn() -> T>,
+}
+
+impl<T: RelaxedFollowTrait + Verifiable> FlatLogWriter<T> {
+    /// Opens or creates the log at `path` and its index with the [SyncPolicy::Manual].
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::with_sync_policy(path, SyncPolicy::Manual)
+    }
+
+    /// Opens or creates the log at `path` and its index, recovering both from an interrupted append.
+    pub fn with_sync_policy(path: impl AsRef<Path>, sync_policy: SyncPolicy) -> io::Result<Self> {
+        let path = path.as_ref();
+        let mut log = OpenOptions::new().read(true).write(true).create(true).truncate(false).open(path)?;
+        let mut index = OpenOptions::new().read(true).write(true).create(true).truncate(false).open(index_path(path))?;
+
+        let (log_len, records) = recover(&mut log, &mut index)?;
+
+        Ok(Self {
+            log: BufWriter::new(log),
+            index: BufWriter::new(index),
+            log_len,
+            records,
+            sync_policy,
+            unsynced: 0,
+            flatbuffer: PhantomData,
+        })
+    }
+
+    /// Appends the FlatBuffer of the wrapper and returns its record number.
+    pub fn append<F: RelaxedFlatBufferTrait<FlatBuffer = T>>(&mut self, flatbuffer: &F) -> io::Result<u64> {
+        let data: &[u8] = flatbuffer;
+
+        let (header, header_len) = match frame_header(data, Framing::Plain) {
+            Some(header) => header,
+            None => return Err(io::Error::new(io::ErrorKind::InvalidInput, "FlatBuffer too short or too large for a size-prefixed frame")),
+        };
+
+        let offset = self.log_len;
+        self.log.write_all(header.get(..header_len).unwrap_or_default())?;
+        self.log.write_all(data)?;
+        self.index.write_all(&offset.to_le_bytes())?;
+
+        self.log_len = offset.saturating_add(header_len as u64).saturating_add(data.len() as u64);
+        let record = self.records;
+        self.records = record.saturating_add(1);
+        self.unsynced = self.unsynced.saturating_add(1);
+
+        match self.sync_policy {
+            SyncPolicy::Manual => {},
+            SyncPolicy::EveryMessage => self.sync()?,
+            SyncPolicy::EveryMessages(n) if self.unsynced >= n => self.sync()?,
+            SyncPolicy::EveryMessages(_) => {},
+        }
+
+        Ok(record)
+    }
+
+    /// Hands the buffered messages to the operating system, making them visible to readers of the files.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.log.flush()?;
+        self.index.flush()
+    }
+
+    /// Flushes the buffered messages and waits until the log and the index are stored on disk.
+    pub fn sync(&mut self) -> io::Result<()> {
+        self.log.flush()?;
+        self.log.get_ref().sync_data()?;
+        self.index.flush()?;
+        self.index.get_ref().sync_data()?;
+
+        self.unsynced = 0;
+        Ok(())
+    }
+
+    /// Returns the number of records in the log.
+    pub fn len(&self) -> u64 {
+        self.records
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records == 0
+    }
+
+    pub fn sync_policy(&self) -> SyncPolicy {
+        self.sync_policy
+    }
+}
+
+impl<T: RelaxedFollowTrait + Verifiable> Debug for FlatLogWriter<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FlatLogWriter")
+            .field("log_len", &self.log_len)
+            .field("records", &self.records)
+            .field("sync_policy", &self.sync_policy)
+            .finish()
+    }
+}
+
+/// Truncates the log and the index to their last complete record and returns the length of the log and the number of records.
+///
+/// Both files are positioned at their end afterwards.
+fn recover(log: &mut File, index: &mut File) -> io::Result<(u64, u64)> {
+    let available = log.metadata()?.len();
+    let mut records = index.metadata()?.len().checked_div(INDEX_ENTRY_LEN as u64).unwrap_or_default();
+    let mut log_len = 0;
+
+    while let Some(last) = records.checked_sub(1) {
+        let mut offset = [0; INDEX_ENTRY_LEN];
+        index.seek(SeekFrom::Start(last.saturating_mul(INDEX_ENTRY_LEN as u64)))?;
+        index.read_exact(&mut offset)?;
+        let offset = u64::from_le_bytes(offset);
+
+        if let Some(end) = frame_end(log, offset, available)? {
+            log_len = end;
+            break;
+        }
+
+        records = last;
+    }
+
+    log.set_len(log_len)?;
+    index.set_len(records.saturating_mul(INDEX_ENTRY_LEN as u64))?;
+    log.seek(SeekFrom::End(0))?;
+    index.seek(SeekFrom::End(0))?;
+
+    Ok((log_len, records))
+}
+
+/// Returns the end of the frame at `offset`, or `None` if the frame is not completely contained in the log.
+fn frame_end(log: &mut File, offset: u64, available: u64) -> io::Result<Option<u64>> {
+    match offset.checked_add(PREFIX_LEN as u64) {
+        Some(end) if end <= available => {},
+        _ => return Ok(None),
+    }
+
+    let mut prefix = [0; PREFIX_LEN];
+    log.seek(SeekFrom::Start(offset))?;
+    log.read_exact(&mut prefix)?;
+
+    let end = offset.saturating_add(PREFIX_LEN as u64).saturating_add(u64::from(u32::from_le_bytes(prefix)));
+
+    Ok(if end <= available { Some(end) } else { None })
+}
+
+/// The buffer of a [LogRecord], the frame of the record within the shared memory-mapped log.
+#[cfg(feature = "mmap")]
+#[derive(Clone)]
+pub struct LogSlice {
+    log: Arc<Mmap>,
+    range: Range<usize>,
+}
+
+#[cfg(feature = "mmap")]
+impl LogSlice {
+    /// Returns the byte range of the frame within the log.
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+}
+
+#[cfg(feature = "mmap")]
+impl AsRef<[u8]> for LogSlice {
+    fn as_ref(&self) -> &[u8] {
+        // The range was checked against the log when the slice was created.
+        self.log.get(self.range.clone()).unwrap_or_default()
+    }
+}
+
+#[cfg(feature = "mmap")]
+impl Debug for LogSlice {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LogSlice").field("range", &self.range).finish()
+    }
+}
+
+/// A record of a [FlatLogReader], borrowing its frame from the memory-mapped log. \
+/// The FlatBuffer is rooted behind the size prefix, like every frame of a size-prefixed stream.
+#[cfg(feature = "mmap")]
+pub type LogRecord<T, P = DefaultPolicy> = EmbeddedFlatBuffer<T, LogSlice, P>;
+
+/// Reads the FlatBuffers `T` of a log written by the [FlatLogWriter], verifying them according to the policy `P`.
+///
+/// The log and the index are memory-mapped once on [open()](FlatLogReader::open),
+/// so the reader only sees the records appended before that.
+/// Records are verified lazily on their first access, the result is cached per record,
+/// so accessing a record again skips the verifier. \
+/// The reader is `Send` and `Sync`, and the returned records keep the mapped log alive on their own.
+#[cfg(feature = "mmap")]
+pub struct FlatLogReader<T: RelaxedFollowTrait + Verifiable, P: VerifierPolicy = DefaultPolicy> {
+    log: Arc<Mmap>,
+    index: Mmap,
+    verified: Box<[OnceLock<Result<(), VerifyError>>]>,
+    flatbuffer: PhantomData<fn() -> (T, P)>,
+}
+
+#[cfg(feature = "mmap")]
+impl<T: RelaxedFollowTrait + Verifiable, P: VerifierPolicy> FlatLogReader<T, P> {
+    /// Memory-maps the log at `path` and its index read-only.
+    ///
+    /// # Safety
+    /// The files must not be truncated or modified while they are mapped, e.g. by a [FlatLogWriter] recovering the log
+    /// in another process. Appending to them is fine.
+    pub unsafe fn open(path: impl AsRef<Path>) -> Result<Self, FileError> {
+        let path = path.as_ref();
+        let log = Mmap::map(&File::open(path)?)?;
+        let index = Mmap::map(&File::open(index_path(path))?)?;
+
+        let records = index.len().checked_div(INDEX_ENTRY_LEN).unwrap_or_default();
+
+        Ok(Self {
+            log: Arc::new(log),
+            index,
+            verified: (0..records).map(|_| OnceLock::new()).collect(),
+            flatbuffer: PhantomData,
+        })
+    }
+
+    /// Returns the record with the number `record`, or `None` if the log holds no such record.
+    ///
+    /// Fails with [FrameError::Truncated] if the indexed frame is not completely contained in the log.
+    pub fn get(&self, record: u64) -> Option<Result<LogRecord<T, P>, FrameError>> {
+        let record = usize::try_from(record).ok()?;
+        let verified = self.verified.get(record)?;
+
+        let start = record.saturating_mul(INDEX_ENTRY_LEN);
+        let offset = self.index.get(start..start.saturating_add(INDEX_ENTRY_LEN))
+            .and_then(|entry| <[u8; INDEX_ENTRY_LEN]>::try_from(entry).ok())
+            .map(u64::from_le_bytes)?;
+        let offset = usize::try_from(offset).unwrap_or(usize::MAX);
+
+        let range = match next_frame(&self.log, offset) {
+            Some(Ok(range)) => range,
+            Some(Err(e)) => return Some(Err(e)),
+            None => return Some(Err(FrameError::Truncated { offset, len: PREFIX_LEN, available: 0 })),
+        };
+
+        let frame = LogSlice { log: self.log.clone(), range };
+
+        if !P::VERIFY {
+            return Some(EmbeddedFlatBuffer::new_at(frame, PREFIX_LEN).map_err(FrameError::from));
+        }
+
+        if let Err(e) = verified.get_or_init(|| EmbeddedFlatBuffer::<T, LogSlice, P>::verify_at(frame.as_ref(), PREFIX_LEN)) {
+            return Some(Err(e.clone().into()));
+        }
+
+        // Safety:
+        // The frame was verified as the FlatBuffer `T` rooted behind its prefix, either now or on an earlier access.
+        // The mapped log is immutable as required by open().
+        Some(Ok(unsafe { EmbeddedFlatBuffer::new_at_unchecked(frame, PREFIX_LEN) }))
+    }
+
+    /// Iterates all records in the order they were appended.
+    pub fn iter(&self) -> FlatLogIter<'_, T, P> {
+        FlatLogIter { reader: self, record: 0 }
+    }
+
+    /// Returns the number of records in the log.
+    pub fn len(&self) -> u64 {
+        self.verified.len() as u64
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.verified.is_empty()
+    }
+}
+
+#[cfg(feature = "mmap")]
+impl<'a, T: RelaxedFollowTrait + Verifiable, P: VerifierPolicy> IntoIterator for &'a FlatLogReader<T, P> {
+    type Item = Result<LogRecord<T, P>, FrameError>;
+    type IntoIter = FlatLogIter<'a, T, P>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(feature = "mmap")]
+impl<T: RelaxedFollowTrait + Verifiable, P: VerifierPolicy> Debug for FlatLogReader<T, P> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FlatLogReader")
+            .field("log_len", &self.log.len())
+            .field("records", &self.verified.len())
+            .finish()
+    }
+}
+
+/// Iterates the records of a [FlatLogReader].
+///
+/// A record failing the verification is yielded as an error, the iterator continues with the next record.
+///
+/// Created by [iter()](FlatLogReader::iter).
+#[cfg(feature = "mmap")]
+pub struct FlatLogIter<'a, T: RelaxedFollowTrait + Verifiable, P: VerifierPolicy = DefaultPolicy> {
+    reader: &'a FlatLogReader<T, P>,
+    record: u64,
+}
+
+#[cfg(feature = "mmap")]
+impl<T: RelaxedFollowTrait + Verifiable, P: VerifierPolicy> Iterator for FlatLogIter<'_, T, P> {
+    type Item = Result<LogRecord<T, P>, FrameError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.reader.get(self.record)?;
+        self.record = self.record.saturating_add(1);
+
+        Some(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = usize::try_from(self.reader.len().saturating_sub(self.record)).unwrap_or(usize::MAX);
+
+        (remaining, Some(remaining))
+    }
+}
+
+#[cfg(feature = "mmap")]
+impl<T: RelaxedFollowTrait + Verifiable, P: VerifierPolicy> ExactSizeIterator for FlatLogIter<'_, T, P> {}
+
+#[cfg(feature = "mmap")]
+impl<T: RelaxedFollowTrait + Verifiable, P: VerifierPolicy> FusedIterator for FlatLogIter<'_, T, P> {}
@@ -0,0 +1,161 @@
+//! Owned FlexBuffers, the schema-less variant of FlatBuffers.
+//!
+//! A `flexbuffers::Reader` borrows the bytes it reads from, so it cannot be stored or moved along with them. \
+//! [OwnedFlexBuffer] holds the bytes in any buffer `B` instead, validates the root once, and hands out readers borrowing from itself.
+//!
+//! Requires the `flexbuffers` feature.
+//!
+//! # Example
+//! ```
+//! use flatbuffers_owned::flexbuffers::OwnedFlexBuffer;
+//!
+//! let mut builder = flexbuffers::Builder::default();
+//! let mut map = builder.start_map();
+//! map.push("hp", 100);
+//! map.end_map();
+//!
+//! let monster = OwnedFlexBuffer::from_builder(&builder)?;
+//! assert_eq!(monster.as_reader().as_map().idx("hp").as_u64(), 100);
+//! ```
+
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt::{Debug, Formatter};
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+use ::flexbuffers::{Builder, DeserializationError, Reader, ReaderError, SerializationError};
+use ::serde::{Deserialize, Serialize};
+
+/// The wrapper around the raw bytes of a FlexBuffer with a valid root, held in any buffer `B` that can convert to a byte slice reference.
+///
+/// Like for [OwnedFlatBuffer](crate::OwnedFlatBuffer), `PartialEq`, `Eq`, `PartialOrd`, `Ord` and `Hash` are implemented on the underlying byte slice.
+#[repr(transparent)]
+pub struct OwnedFlexBuffer<B: AsRef<[u8]>> {
+    buffer: B,
+}
+
+impl<B: AsRef<[u8]>> OwnedFlexBuffer<B> {
+    /// Validates the root of the FlexBuffer and wraps it.
+    ///
+    /// FlexBuffers have no verifier, the readers bounds-check every access instead and return errors or defaults for malformed data.
+    pub fn new(data: B) -> Result<Self, ReaderError> {
+        Reader::get_root(data.as_ref())?;
+
+        Ok(Self { buffer: data })
+    }
+
+    /// Returns a reader of the root, borrowing from the wrapped buffer.
+    #[inline]
+    pub fn as_reader(&self) -> Reader<&[u8]> {
+        // The root was validated by new(), the fallback to a null reader is never taken.
+        Reader::get_root(self.buffer.as_ref()).unwrap_or_default()
+    }
+
+    /// Deserializes the root into a value, which may borrow strings and blobs from the wrapped buffer.
+    pub fn deserialize<'de, T: Deserialize<'de>>(&'de self) -> Result<T, DeserializationError> {
+        T::deserialize(self.as_reader())
+    }
+
+    /// Returns a reference to the buffer.
+    #[inline]
+    pub fn buffer(&self) -> &B {
+        &self.buffer
+    }
+
+    /// Consumes the wrapper and returns the buffer.
+    #[inline]
+    pub fn into_inner(self) -> B {
+        self.buffer
+    }
+
+    /// Copies the bytes into a fresh `Box<[u8]>`, e.g. for keeping a FlexBuffer borrowed from a network frame.
+    pub fn to_owned(&self) -> OwnedFlexBuffer<Box<[u8]>> {
+        OwnedFlexBuffer { buffer: self.buffer.as_ref().into() }
+    }
+}
+
+impl OwnedFlexBuffer<Box<[u8]>> {
+    /// Copies the data of the builder.
+    ///
+    /// Fails if nothing was pushed to the builder yet.
+    pub fn from_builder(builder: &Builder) -> Result<Self, ReaderError> {
+        Self::new(builder.view().into())
+    }
+
+    /// Serializes the value into a new owned FlexBuffer.
+    pub fn from_serialize<T: Serialize>(value: T) -> Result<Self, SerializationError> {
+        let data = ::flexbuffers::to_vec(value)?;
+
+        Ok(Self { buffer: data.into_boxed_slice() })
+    }
+}
+
+/// Validates the root of the `Vec<u8>` and boxes it without copying the bytes.
+impl TryFrom<Vec<u8>> for OwnedFlexBuffer<Box<[u8]>> {
+    type Error = ReaderError;
+
+    fn try_from(data: Vec<u8>) -> Result<Self, ReaderError> {
+        Self::new(data.into_boxed_slice())
+    }
+}
+
+impl<B: AsRef<[u8]> + Clone> Clone for OwnedFlexBuffer<B> {
+    fn clone(&self) -> Self {
+        Self { buffer: self.buffer.clone() }
+    }
+}
+
+impl<B: AsRef<[u8]> + Copy> Copy for OwnedFlexBuffer<B> {}
+
+impl<B: AsRef<[u8]> + Debug> Debug for OwnedFlexBuffer<B> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("OwnedFlexBuffer").field(&self.buffer).finish()
+    }
+}
+
+impl<B: AsRef<[u8]>> PartialEq for OwnedFlexBuffer<B> {
+    fn eq(&self, other: &Self) -> bool {
+        self.buffer.as_ref() == other.buffer.as_ref()
+    }
+}
+
+impl<B: AsRef<[u8]>> Eq for OwnedFlexBuffer<B> {}
+
+impl<B: AsRef<[u8]>> PartialOrd for OwnedFlexBuffer<B> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<B: AsRef<[u8]>> Ord for OwnedFlexBuffer<B> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.buffer.as_ref().cmp(other.buffer.as_ref())
+    }
+}
+
+impl<B: AsRef<[u8]>> Hash for OwnedFlexBuffer<B> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        Hash::hash(self.buffer.as_ref(), state)
+    }
+}
+
+impl<B: AsRef<[u8]>> AsRef<[u8]> for OwnedFlexBuffer<B> {
+    fn as_ref(&self) -> &[u8] {
+        self.buffer.as_ref()
+    }
+}
+
+impl<B: AsRef<[u8]>> Borrow<[u8]> for OwnedFlexBuffer<B> {
+    fn borrow(&self) -> &[u8] {
+        self.buffer.as_ref()
+    }
+}
+
+impl<B: AsRef<[u8]>> Deref for OwnedFlexBuffer<B> {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        self.buffer.as_ref()
+    }
+}
@@ -0,0 +1,111 @@
+//! Conversions between FlatBuffers and `http_body` bodies.
+//!
+//! For HTTP code that does not use the axum or actix-web integrations, e.g. plain hyper services, reqwest middleware or lambda runtimes.
+//! [from_body()] collects any `http_body::Body` up to a size limit and verifies it as a wrapper,
+//! [into_body()] and [to_body()] turn a wrapper into a `Full<Bytes>` body.
+//!
+//! Requires the `http` feature.
+//!
+//! # Example
+//! ```
+//! use flatbuffers_owned::http::{from_body, into_body};
+//!
+//! async fn echo(req: Request<Incoming>) -> Result<Response<Full<Bytes>>, Error> {
+//!     let message: OwnedMessage = from_body(req.into_body(), 64 * 1024).await?;
+//!
+//!     Ok(Response::new(into_body(message)))
+//! }
+//! ```
+
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::pin::pin;
+use bytes::{Buf, BufMut, Bytes};
+use http_body::Body;
+use http_body_util::{BodyExt, Full};
+use crate::{RelaxedFlatBufferTrait, VerifyError};
+
+/// Collects the body up to `limit` bytes and verifies it as the wrapper `F`.
+///
+/// Fails with [BodyError::TooLarge] as soon as the body announces or exceeds the limit. \
+/// The buffer of the wrapper has to be constructible from a `Vec<u8>`, e.g. `Box<[u8]>`, `Arc<[u8]>` or `Bytes`.
+pub async fn from_body<F, B>(body: B, limit: usize) -> Result<F, BodyError<B::Error>>
+    where F: RelaxedFlatBufferTrait,
+          F::Buffer: From<Vec<u8>>,
+          B: Body
+{
+    if body.size_hint().lower() > limit as u64 {
+        return Err(BodyError::TooLarge { limit });
+    }
+
+    let mut body = pin!(body);
+    let mut data = Vec::new();
+
+    while let Some(frame) = body.frame().await {
+        let chunk = match frame.map_err(BodyError::Body)?.into_data() {
+            Ok(chunk) => chunk,
+            // Trailers carry no payload.
+            Err(_) => continue,
+        };
+
+        if data.len().saturating_add(chunk.remaining()) > limit {
+            return Err(BodyError::TooLarge { limit });
+        }
+
+        data.put(chunk);
+    }
+
+    Ok(F::new(F::Buffer::from(data))?)
+}
+
+/// Turns the wrapper into a body without copying its bytes. \
+/// The buffer of the wrapper has to convert into `Bytes`, e.g. `Box<[u8]>`, `Vec<u8>` or `Bytes`.
+pub fn into_body<F>(flatbuffer: F) -> Full<Bytes>
+    where F: RelaxedFlatBufferTrait,
+          F::Buffer: Into<Bytes>
+{
+    Full::new(flatbuffer.into_inner().into())
+}
+
+/// Copies the bytes of any wrapper into a body.
+pub fn to_body<F: RelaxedFlatBufferTrait>(flatbuffer: &F) -> Full<Bytes> {
+    Full::new(Bytes::copy_from_slice(flatbuffer))
+}
+
+/// The error returned by [from_body()].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum BodyError<E> {
+    /// The body could not be read.
+    Body(E),
+    /// The body exceeds the limit of `limit` bytes.
+    TooLarge { limit: usize },
+    /// The body failed the verification.
+    InvalidFlatbuffer(VerifyError),
+}
+
+impl<E: Display> Display for BodyError<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BodyError::Body(e) => Display::fmt(e, f),
+            BodyError::TooLarge { limit } => write!(f, "Body exceeds the limit of {} bytes", limit),
+            BodyError::InvalidFlatbuffer(e) => Display::fmt(e, f),
+        }
+    }
+}
+
+impl<E: Error + 'static> Error for BodyError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            BodyError::Body(e) => Some(e),
+            BodyError::TooLarge { .. } => None,
+            BodyError::InvalidFlatbuffer(e) => Some(e),
+        }
+    }
+}
+
+impl<E> From<VerifyError> for BodyError<E> {
+    fn from(e: VerifyError) -> Self {
+        BodyError::InvalidFlatbuffer(e)
+    }
+}
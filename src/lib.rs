@@ -9,13 +9,62 @@
 //!
 //! ```toml
 //! [dependencies]
-//! flatbuffers-owned = "0.2"
+//! flatbuffers-owned = "0.3"
+//! ```
+//!
+//! ## Features
+//! All features are strictly additive, every combination of them compiles on its own.
+//! - `std` (default): Implements `std::error::Error` for the error types and adds [from_file()](OwnedFlatBuffer::from_file) and the atomic [write_to_file()](OwnedFlatBuffer::write_to_file), as well as the append-only [message log](flatlog). Required by all features below.
+//! - `alloc`: The `Owned{FLATBUFFER_NAME}` aliases and all other `Box<[u8]>` and `Arc<[u8]>` based constructors. Enabled by `std`.
+//! - `cache` (default): The [VerificationCache](cache::VerificationCache) skipping re-verification of already verified buffers.
+//! - `snapshot` (default): The [SnapshotStream](snapshot::SnapshotStream) for snapshot + incremental update feeds.
+//! - `registry`: The global [decode registry](registry) filled by the `register` macro option. With `reflection`, also a [schema registry](registry::SchemaRegistry) keyed by file identifier.
+//! - `mmap`: Memory-mapped wrappers, created by [from_file_mmap()](OwnedFlatBuffer::from_file_mmap), and the [FlatLogReader](flatlog::FlatLogReader).
+//! - `shm`: [Shared-memory segment](shm) buffers for cross-process use. Unix only.
+//! - `pool`: The [BufferPool](pool::BufferPool) and [BuilderPool](pool::BuilderPool) recycling buffer and builder allocations.
+//! - `arena`: The [FlatBufferArena](arena::FlatBufferArena) for batches of FlatBuffers freed together. Only requires `alloc`.
+//! - `inline`: The `Inline{FLATBUFFER_NAME}` aliases, storing small FlatBuffers inline in an [InlineBuffer](buffer::InlineBuffer). Only requires `alloc`.
+//! - `zeroize`: The `Secret{FLATBUFFER_NAME}` aliases, zeroizing their [SecretBuffer](buffer::SecretBuffer) on drop. Only requires `alloc`.
+//! - `bytes`: Zero-copy wrappers over frames split off a `BytesMut`, created by [from_bytes_mut()](OwnedFlatBuffer::from_bytes_mut). Only requires `alloc`.
+//! - `allocator-api2`: The `OwnedIn{FLATBUFFER_NAME}<A>` aliases, holding a [BoxIn](buffer::BoxIn) of a custom allocator. Only requires `alloc`.
+//! - `tokio`: The async [size-prefixed stream](size_prefixed) reader and writer for tokio's `AsyncRead` and `AsyncWrite`.
+//! - `tokio-util`: The [FlatBufferCodec](size_prefixed::FlatBufferCodec) for `Framed` transports of tokio-util.
+//! - `futures`: [Verifying adapters](stream) for `futures` streams of FlatBuffers, e.g. WebSocket messages.
+//! - `crc32`: The [checksummed framing](size_prefixed::Framing::Crc32) of size-prefixed streams. Does not require `std`.
+//! - `zstd`: [Compressed](compression) FlatBuffers with zstd, created by [from_zstd()](OwnedFlatBuffer::from_zstd).
+//! - `lz4`: [Compressed](compression) FlatBuffers with LZ4, created by [from_lz4()](OwnedFlatBuffer::from_lz4). Only requires `alloc`.
+//! - `axum`: The [FlatBuffer](axum::FlatBuffer) extractor and response for axum handlers.
+//! - `actix-web`: The [FlatBuffer](actix::FlatBuffer) extractor and responder for actix-web handlers.
+//! - `http`: [Conversions](http) between FlatBuffers and `http_body` bodies, e.g. for hyper.
+//! - `tungstenite`: [Conversions](websocket) between FlatBuffers and tungstenite WebSocket messages.
+//! - `serde`: [Structured](serde) serialization of FlatBuffers through their object API.
+//! - `reflection`: Runtime [schemas](reflection) loaded from binary `.bfbs` files, [dynamic field access](reflection::dynamic) by name and [compatibility checks](reflection::compatibility) between schema versions. Only requires `alloc`.
+//! - `json`: [JSON import](reflection::json) of FlatBuffers through a runtime schema.
+//! - `flexbuffers`: The [OwnedFlexBuffer](flexbuffers::OwnedFlexBuffer) wrapper for schema-less FlexBuffers.
+//! - `rkyv`: [rkyv interop](rkyv), embedding owned FlatBuffers in rkyv-archived structures.
+//! - `rusqlite`: rusqlite `ToSql` and `FromSql` implementations, storing wrappers in `BLOB` columns.
+//! - `diesel`: diesel `ToSql` and `FromSql` implementations, mapping wrappers to `Binary` columns.
+//! - `sqlx`: sqlx `Type`, `Encode` and `Decode` implementations, binding wrappers in queries and decoding them from `BYTEA` and `BLOB` columns.
+//! - `redis`: redis `ToRedisArgs` and `FromRedisValue` implementations, storing wrappers as Redis strings.
+//! - `full`: Enables all of the above.
+//!
+//! For a minimal build, disable the default features:
+//! ```toml
+//! [dependencies]
+//! flatbuffers-owned = { version = "0.3", default-features = false }
+//! ```
+//!
+//! Without the `std` feature, the crate is `no_std`. Enable the `alloc` feature for the `Owned{FLATBUFFER_NAME}` aliases on targets with an allocator:
+//! ```toml
+//! [dependencies]
+//! flatbuffers-owned = { version = "0.3", default-features = false, features = ["alloc"] }
+//! flatbuffers = { version = "23.5.26", default-features = false }
 //! ```
 //!
 //! ## Quickstart
 //! Use the `flatbuffers_owned!` macro on your FlatBuffers to generate the wrapper structs.
 //!
-//! This generates a `RelaxedMessage` wrapper-struct and a `OwnedMessage` type alias for the `Message` FlatBuffer:
+//! This generates the `RelaxedMessage` and `OwnedMessage` type aliases of the generic wrapper struct for the `Message` FlatBuffer:
 //! ```rust
 //! use flatbuffers_owned::*;
 //!
@@ -30,6 +79,16 @@
 //! let owned_message = OwnedMessage::new(message_bytes).unwrap();
 //! ```
 //!
+//! Producers usually end up with a `Vec<u8>`, which can be wrapped as is, or converted into the `Owned{FLATBUFFER_NAME}` form without copying:
+//! ```rust
+//! let message_bytes: Vec<u8> = serialize_message();
+//!
+//! let relaxed_message: RelaxedMessage<Vec<u8>> = RelaxedMessage::new(message_bytes)?;
+//! let owned_message: OwnedMessage = relaxed_message.into_boxed();
+//!
+//! let owned_message = OwnedMessage::try_from(owned_message.into_vec())?;
+//! ```
+//!
 //! Access the actual FlatBuffer:
 //! ```rust
 //! let message: Message = owned_message.as_actual();
@@ -58,14 +117,30 @@
 //! }
 //! ```
 //!
+//! The returned [VerifyError] names the root type, the buffer length and the constructor used,
+//! and chains the `InvalidFlatbuffer` of the verifier as its `source()`.
+//!
+//! ## Panics
+//! No function of this crate panics on untrusted input. \
+//! All constructors taking raw bytes (`new()`, `decode()`, `VerificationCache::new_verified()`) return an error instead,
+//! and the crate is linted against `unwrap()`, `expect()`, `panic!()`, unchecked indexing and overflowing arithmetic.
+//!
+//! The only panicking paths are the builder-based constructors (`create()`, `build()`, `pack()`),
+//! as the `FlatBufferBuilder` itself panics when a buffer grows beyond 2 GiB or a required field is missing.
+//! These never receive bytes from the outside. \
+//! `from_builder()` and `from_builder_owned()` additionally panic in debug builds if the builder is not finished,
+//! use `try_from_builder()` and `try_from_builder_owned()` to verify the written data instead.
+//!
 //! ## Approach
 //! ### The wrapper struct
-//! The `Relaxed{FLATBUFFER_NAME}` wrapper struct is a Newtype which can wrap any struct that can convert to a byte slice reference. (```where TBuffer: AsRef<[u8]>```) \
+//! The generic `OwnedFlatBuffer<T, B>` wrapper struct is a Newtype which can wrap any struct that can convert to a byte slice reference. (```where B: AsRef<[u8]>```) \
 //! This struct can be used with buffers that fully own its memory, or only hold a shared-reference.
+//! The struct is `#[repr(transparent)]`, so it has the exact same memory layout as the wrapped buffer.
 //!
-//! The `Owned{FLATBUFFER_NAME}` type alias generated along the wrapper struct just predefines the `TBuffer` generic. \
+//! The `Relaxed{FLATBUFFER_NAME}` and `Owned{FLATBUFFER_NAME}` type aliases generated by the macro just predefine its generics. \
 //! For our `Message` example FlatBuffer, the generated type alias code would be the following:
 //! ```rust
+//! pub type RelaxedMessage<TBuffer, P = DefaultPolicy> = OwnedFlatBuffer<Message<'static>, TBuffer, P>;
 //! pub type OwnedMessage = RelaxedMessage<Box<[u8]>>;
 //! ```
 //!
@@ -78,11 +153,420 @@
 //!
 //! I will also be happy for any general tips or suggestions given that this is my first (published) library ever. :)
 
-use std::ops::Deref;
-use flatbuffers::{Follow, ForwardsUOffset, InvalidFlatbuffer, Verifiable, Verifier, VerifierOptions};
+#![cfg_attr(not(feature = "std"), no_std)]
+#![deny(
+    clippy::panic,
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::indexing_slicing,
+    clippy::arithmetic_side_effects,
+    clippy::unreachable,
+    clippy::todo,
+    clippy::unimplemented
+)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "alloc")]
+use alloc::{borrow::Cow, boxed::Box, rc::Rc, sync::Arc, vec::Vec};
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+#[cfg(feature = "alloc")]
+use core::convert::TryFrom;
+use core::fmt::{Debug, Display, Formatter};
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::ops::Deref;
+#[cfg(feature = "std")]
+use std::error::Error;
+use flatbuffers::{FlatBufferBuilder, Follow, ForwardsUOffset, InvalidFlatbuffer, Verifiable, VerifierOptions, WIPOffset};
+
+#[cfg(feature = "actix-web")]
+pub mod actix;
+#[cfg(feature = "arena")]
+pub mod arena;
+#[cfg(feature = "allocator-api2")]
+mod allocator;
+#[cfg(feature = "axum")]
+pub mod axum;
+pub mod buffer;
+#[cfg(feature = "bytes")]
+mod bytes_mut;
+#[cfg(feature = "cache")]
+pub mod cache;
+#[cfg(feature = "tokio-util")]
+mod codec;
+#[cfg(any(feature = "zstd", feature = "lz4"))]
+pub mod compression;
+pub mod compat;
+#[cfg(feature = "diesel")]
+mod diesel;
+#[cfg(feature = "std")]
+mod file;
+#[cfg(feature = "flexbuffers")]
+pub mod flexbuffers;
+#[cfg(feature = "std")]
+pub mod flatlog;
+#[cfg(feature = "http")]
+pub mod http;
+#[cfg(feature = "mmap")]
+mod mmap;
+#[cfg(feature = "pool")]
+pub mod pool;
+#[cfg(all(feature = "shm", unix))]
+pub mod shm;
+#[cfg(feature = "reflection")]
+pub mod reflection;
+#[cfg(feature = "registry")]
+pub mod registry;
+#[cfg(feature = "rkyv")]
+pub mod rkyv;
+#[cfg(feature = "redis")]
+mod redis;
+#[cfg(feature = "rusqlite")]
+mod rusqlite;
+#[cfg(feature = "zeroize")]
+mod secret;
+#[cfg(feature = "serde")]
+pub mod serde;
+pub mod size_prefixed;
+#[cfg(feature = "snapshot")]
+pub mod snapshot;
+#[cfg(feature = "sqlx")]
+mod sqlx;
+#[cfg(feature = "futures")]
+pub mod stream;
+#[cfg(feature = "tokio")]
+mod tokio_io;
+#[cfg(feature = "tungstenite")]
+pub mod websocket;
 
 #[doc(hidden)]
 pub use paste::paste;
+pub use stable_deref_trait::StableDeref;
+
+#[doc(hidden)]
+pub mod __private {
+    use flatbuffers::{Follow, ForwardsUOffset, Push, Verifiable, Verifier, VerifierOptions};
+    use crate::{Constructor, VerifyError};
+
+    #[cfg(feature = "alloc")]
+    pub use alloc::boxed::Box;
+    #[cfg(feature = "registry")]
+    pub use inventory;
+
+    #[diagnostic::on_unimplemented(
+        message = "`{Self}` cannot be used with `flatbuffers_owned!`: it does not implement `flatbuffers::Follow`",
+        label = "not a flatc generated FlatBuffer table",
+        note = "`flatbuffers_owned!` expects the name of a flatc generated table, e.g. `Message` for `table Message {{ ... }}`"
+    )]
+    pub trait AssertFollow {}
+    impl<T: Follow<'static>> AssertFollow for T {}
+
+    #[diagnostic::on_unimplemented(
+        message = "`{Self}` cannot be used with `flatbuffers_owned!`: it does not implement `flatbuffers::Verifiable`",
+        label = "not a verifiable FlatBuffer table",
+        note = "`flatbuffers_owned!` expects the name of a flatc generated table, e.g. `Message` for `table Message {{ ... }}`"
+    )]
+    pub trait AssertVerifiable {}
+    impl<T: Verifiable> AssertVerifiable for T {}
+
+    #[diagnostic::on_unimplemented(
+        message = "`{T}` cannot be used with `flatbuffers_owned!`: it is a flatc generated enum or struct, not a table",
+        label = "not a flatc generated FlatBuffer table",
+        note = "flatc generated structs are passed with the `struct` prefix, e.g. `flatbuffers_owned!(struct Vec3)`, enums can not be the root of a FlatBuffer"
+    )]
+    pub trait AssertTableKind<T> {}
+    impl<T> AssertTableKind<T> for TableKind {}
+
+    /// The kind of flatc generated tables, which, unlike enums and structs, do not implement `flatbuffers::Push`.
+    pub struct TableKind;
+    /// The kind of flatc generated enums and structs.
+    pub struct PushKind;
+
+    /// Resolves the kind of `T` through its `kind()` method. \
+    /// The inherent method only applies to types implementing `flatbuffers::Push`, all other types fall back to the method of [ProbeTable].
+    pub struct Probe<T>(pub PhantomData<T>);
+
+    impl<T: Push> Probe<T> {
+        #[inline(always)]
+        pub fn kind(&self) -> PushKind {
+            PushKind
+        }
+    }
+
+    pub trait ProbeTable {
+        #[inline(always)]
+        fn kind(&self) -> TableKind {
+            TableKind
+        }
+    }
+
+    impl<T> ProbeTable for Probe<T> {}
+
+    #[inline(always)]
+    pub fn assert_table<T: AssertFollow + AssertVerifiable, K: AssertTableKind<T>>(_kind: K) {}
+
+    #[diagnostic::on_unimplemented(
+        message = "`{Self}` cannot be used with `flatbuffers_owned!(struct ...)`: it is not a flatc generated struct",
+        label = "not a flatc generated FlatBuffer struct",
+        note = "`flatbuffers_owned!(struct ...)` expects the name of a flatc generated struct, e.g. `Vec3` for `struct Vec3 {{ ... }}`"
+    )]
+    pub trait AssertStruct {}
+    impl<T: for<'a> Follow<'a, Inner = &'a T> + Verifiable + 'static> AssertStruct for T {}
+
+    #[inline(always)]
+    pub fn assert_struct<T: AssertStruct>() {}
+
+    /// Resolves the return type of a function pointer.
+    pub trait FnOutput {
+        type Output;
+    }
+
+    impl<'a, R> FnOutput for fn(&'a ()) -> R {
+        type Output = R;
+    }
+
+    /// Names a flatc generated type with all its lifetimes set to `'static`, or to the given lifetime. \
+    /// flatc only declares a lifetime on tables and on `Args` structs with offset fields, but not on structs and enums,
+    /// so the type is named as the return type of a function pointer, where the lifetime can be elided in all cases.
+    #[macro_export]
+    #[doc(hidden)]
+    macro_rules! __named_type {
+        ($name:ident) => {
+            <fn(&'static ()) -> $name as $crate::__private::FnOutput>::Output
+        };
+        ($name:ident, $lt:lifetime) => {
+            <fn(&$lt ()) -> $name as $crate::__private::FnOutput>::Output
+        };
+    }
+
+    pub use __named_type as named_type;
+
+    /// Expands the passed items only if the `alloc` feature of this crate is enabled,
+    /// or the tokens of the `else` branch otherwise.
+    #[cfg(feature = "alloc")]
+    #[macro_export]
+    #[doc(hidden)]
+    macro_rules! __cfg_alloc {
+        ({ $($alloc:tt)* } else { $($no_alloc:tt)* }) => { $($alloc)* };
+        ($($alloc:tt)*) => { $($alloc)* };
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    #[macro_export]
+    #[doc(hidden)]
+    macro_rules! __cfg_alloc {
+        ({ $($alloc:tt)* } else { $($no_alloc:tt)* }) => { $($no_alloc)* };
+        ($($alloc:tt)*) => {};
+    }
+
+    #[cfg(feature = "inline")]
+    #[macro_export]
+    #[doc(hidden)]
+    macro_rules! __cfg_inline {
+        ($($inline:tt)*) => { $($inline)* };
+    }
+
+    #[cfg(not(feature = "inline"))]
+    #[macro_export]
+    #[doc(hidden)]
+    macro_rules! __cfg_inline {
+        ($($inline:tt)*) => {};
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[macro_export]
+    #[doc(hidden)]
+    macro_rules! __cfg_zeroize {
+        ($($zeroize:tt)*) => { $($zeroize)* };
+    }
+
+    #[cfg(not(feature = "zeroize"))]
+    #[macro_export]
+    #[doc(hidden)]
+    macro_rules! __cfg_zeroize {
+        ($($zeroize:tt)*) => {};
+    }
+
+    #[cfg(feature = "allocator-api2")]
+    #[macro_export]
+    #[doc(hidden)]
+    macro_rules! __cfg_allocator_api2 {
+        ($($allocator:tt)*) => { $($allocator)* };
+    }
+
+    #[cfg(not(feature = "allocator-api2"))]
+    #[macro_export]
+    #[doc(hidden)]
+    macro_rules! __cfg_allocator_api2 {
+        ($($allocator:tt)*) => {};
+    }
+
+    pub use __cfg_alloc as cfg_alloc;
+    pub use __cfg_allocator_api2 as cfg_allocator_api2;
+    pub use __cfg_inline as cfg_inline;
+    pub use __cfg_zeroize as cfg_zeroize;
+    pub use core::marker::PhantomData;
+    pub use core::ops::Deref;
+
+    /// Like `flatbuffers::buffer_has_identifier()`, but returns false instead of panicking on too short buffers.
+    #[inline]
+    pub fn has_identifier(data: &[u8], identifier: &str) -> bool {
+        data.get(4..8) == Some(identifier.as_bytes())
+    }
+
+    /// Verifies the FlatBuffer `T` rooted at `loc` and attaches the context to a failure.
+    pub fn verify_root<T: Verifiable>(data: &[u8], loc: usize, opts: &VerifierOptions, constructor: Constructor) -> Result<(), VerifyError> {
+        let mut v = Verifier::new(opts, data);
+
+        <ForwardsUOffset<T>>::run_verifier(&mut v, loc).map_err(|error| VerifyError {
+            root_type: core::any::type_name::<T>(),
+            len: data.len(),
+            constructor,
+            error,
+        })
+    }
+}
+
+/// The error returned when bytes fail the verification as a FlatBuffer. \
+/// Wraps the `InvalidFlatbuffer` of the verifier with the context it occurred in.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VerifyError {
+    /// The name of the root type as returned by `core::any::type_name()`, only meant for diagnostics.
+    pub root_type: &'static str,
+    /// The length of the verified buffer.
+    pub len: usize,
+    /// The method the bytes were verified by.
+    pub constructor: Constructor,
+    pub error: InvalidFlatbuffer,
+}
+
+impl Display for VerifyError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} of {} bytes as {} failed: {}", self.constructor, self.len, self.root_type, self.error)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for VerifyError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// The method a [VerifyError] occurred in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Constructor {
+    New,
+    NewWithOpts,
+    NewAt,
+    Verify,
+    VerifyWithOpts,
+    VerifyAt,
+    #[cfg(feature = "reflection")]
+    FromBfbs,
+}
+
+impl Display for Constructor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            Constructor::New => "new()",
+            Constructor::NewWithOpts => "new_with_opts()",
+            Constructor::NewAt => "new_at()",
+            Constructor::Verify => "verify()",
+            Constructor::VerifyWithOpts => "verify_with_opts()",
+            Constructor::VerifyAt => "verify_at()",
+            #[cfg(feature = "reflection")]
+            Constructor::FromBfbs => "from_bfbs()",
+        })
+    }
+}
+
+/// The error returned by the `decode()` function generated with the [flatbuffers_owned_enum!](flatbuffers_owned_enum) macro
+/// and by `Schema::from_bfbs()` of the `reflection` feature.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The buffer does not carry any of the known file identifiers.
+    UnknownIdentifier,
+    /// The buffer carries a known file identifier, but failed the verification.
+    InvalidFlatbuffer(VerifyError),
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            DecodeError::UnknownIdentifier => write!(f, "Unknown FlatBuffer file identifier"),
+            DecodeError::InvalidFlatbuffer(e) => Display::fmt(e, f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for DecodeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            DecodeError::UnknownIdentifier => None,
+            DecodeError::InvalidFlatbuffer(e) => Some(e),
+        }
+    }
+}
+
+impl From<VerifyError> for DecodeError {
+    fn from(e: VerifyError) -> Self {
+        DecodeError::InvalidFlatbuffer(e)
+    }
+}
+
+/// The error returned when loading a FlatBuffer from a file.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub enum FileError {
+    /// The file could not be opened or read.
+    Io(std::io::Error),
+    /// The file is `len` bytes long, exceeding the maximum size of `max` bytes.
+    TooLarge { len: u64, max: usize },
+    /// The file content failed the verification.
+    InvalidFlatbuffer(VerifyError),
+}
+
+#[cfg(feature = "std")]
+impl Display for FileError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            FileError::Io(e) => Display::fmt(e, f),
+            FileError::TooLarge { len, max } => write!(f, "File of {} bytes exceeds the maximum of {} bytes", len, max),
+            FileError::InvalidFlatbuffer(e) => Display::fmt(e, f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for FileError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            FileError::Io(e) => Some(e),
+            FileError::TooLarge { .. } => None,
+            FileError::InvalidFlatbuffer(e) => Some(e),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for FileError {
+    fn from(e: std::io::Error) -> Self {
+        FileError::Io(e)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<VerifyError> for FileError {
+    fn from(e: VerifyError) -> Self {
+        FileError::InvalidFlatbuffer(e)
+    }
+}
 
 /// This trait allows a `.follow()` method that returns a FlatBuffer with the lifetime of the provided byte slice.
 ///
@@ -96,6 +580,12 @@ pub use paste::paste;
 pub trait RelaxedFollowTrait {
     type Inner<'a>: Follow<'a>;
 
+    /// The `VerifierOptions` this FlatBuffer is verified with by default. \
+    /// Overridden by the `verifier` option of the [flatbuffers_owned!](flatbuffers_owned) macro.
+    fn default_options() -> VerifierOptions {
+        VerifierOptions::default()
+    }
+
     /// # Safety
     ///
     /// This function lacks verification. This method could yield a FlatBuffer with undefined behavior on field reads when corrupted FlatBuffer bytes are passed.
@@ -105,6 +595,11 @@ pub trait RelaxedFollowTrait {
     }
 }
 
+/// Allows vectors of tables to be used as the root of a FlatBuffer.
+impl<T: RelaxedFollowTrait> RelaxedFollowTrait for flatbuffers::Vector<'static, ForwardsUOffset<T>> {
+    type Inner<'a> = flatbuffers::Vector<'a, ForwardsUOffset<T::Inner<'a>>>;
+}
+
 /// This trait serves as the foundation for this crate. \
 /// It allows access to the underlying FlatBuffer using the [as_actual()](RelaxedFlatBufferTrait::as_actual) method. \
 /// The lifetimes used here are more relaxed than those in the flatc generated code. \
@@ -122,83 +617,1470 @@ pub trait RelaxedFollowTrait {
 /// ```
 /// # use flatbuffers_owned::RelaxedFlatBufferTrait;
 ///
-/// fn store_fbs<TBuffer>(flatbuffers: &[impl RelaxedFlatBufferTrait<TBuffer>]) {
+/// fn store_fbs(flatbuffers: &[impl RelaxedFlatBufferTrait]) {
 ///    for item in flatbuffers {
 ///         let bytes: &[u8] = item.deref();
 ///         // ... store the raw bytes somewhere.
 ///    }
 /// }
 /// ```
-pub unsafe trait RelaxedFlatBufferTrait<TBuffer>
+pub unsafe trait RelaxedFlatBufferTrait
     where Self: Deref<Target = [u8]> + Sized
 {
+    /// The buffer holding the raw FlatBuffer bytes, e.g. `Box<[u8]>` or `&[u8]`.
+    type Buffer: AsRef<[u8]>;
     type FlatBuffer: RelaxedFollowTrait + Verifiable;
 
     /// Initializes a actual FlatBuffer struct from the byte slice returned by the Self::deref() method.
     #[inline(always)]
-    fn as_actual(&self) -> <<<Self as RelaxedFlatBufferTrait<TBuffer>>::FlatBuffer as RelaxedFollowTrait>::Inner<'_> as Follow<'_>>::Inner {
+    fn as_actual(&self) -> <<<Self as RelaxedFlatBufferTrait>::FlatBuffer as RelaxedFollowTrait>::Inner<'_> as Follow<'_>>::Inner {
         unsafe { Self::FlatBuffer::follow(self, 0) }
     }
 
     /// Verifies the FlatBuffer data.
-    fn verify(data: &[u8]) -> Result<(), InvalidFlatbuffer> {
-        let opts = VerifierOptions::default();
-        let mut v = Verifier::new(&opts, data);
+    fn verify(data: &[u8]) -> Result<(), VerifyError> {
+        __private::verify_root::<Self::FlatBuffer>(data, 0, &Self::FlatBuffer::default_options(), Constructor::Verify)
+    }
+
+    /// Verifies the FlatBuffer data with the passed limits, e.g. a raised `max_depth` for deeply nested schemas.
+    fn verify_with_opts(data: &[u8], opts: &VerifierOptions) -> Result<(), VerifyError> {
+        __private::verify_root::<Self::FlatBuffer>(data, 0, opts, Constructor::VerifyWithOpts)
+    }
+
+    fn new(data: Self::Buffer) -> Result<Self, VerifyError>;
 
-        <ForwardsUOffset<Self::FlatBuffer>>::run_verifier(&mut v, 0)
+    /// Initializes the wrapper like [new()](RelaxedFlatBufferTrait::new), but verifies the data with the passed limits.
+    fn new_with_opts(data: Self::Buffer, opts: &VerifierOptions) -> Result<Self, VerifyError> {
+        __private::verify_root::<Self::FlatBuffer>(data.as_ref(), 0, opts, Constructor::NewWithOpts)?;
+
+        Ok(unsafe { Self::new_unchecked(data) })
     }
 
-    fn new(data: TBuffer) -> Result<Self, InvalidFlatbuffer>;
+    /// Initializes the wrapper without verifying the FlatBuffer data.
+    ///
+    /// # Safety
+    /// The caller must ensure that the data has already been verified as this FlatBuffer type and that it remains immutable.
+    unsafe fn new_unchecked(data: Self::Buffer) -> Self;
+
+    /// Returns a reference to the buffer, e.g. for cloning an `Arc<[u8]>` backing.
+    fn buffer(&self) -> &Self::Buffer;
+
+    /// Consumes the wrapper and returns the buffer, e.g. for returning it to a pool or forwarding it unchanged.
+    fn into_inner(self) -> Self::Buffer;
 }
 
-/// Use this macro on your FlatBuffers to generate the required code to start using this crate.
+/// The compile-time verification configuration of an [OwnedFlatBuffer]. \
+/// Used by `new()`, `build()` and `verify()` of the wrapper, while the `*_with_opts()` methods keep taking explicit options.
 ///
-/// After invoking the macro, you have two generated types for each of your passed FlatBuffers: \
-/// 1. A generic new-type struct named `Relaxed{FLATBUFFER_NAME}`, which implements [RelaxedFlatBufferTrait] and takes the generic `TBuffer: AsRef<[u8]>`. \
-/// 2. A type alias named `Owned{FLATBUFFER_NAME}, which aliases the `Relaxed{FLATBUFFER_NAME}` struct and sets `TBuffer` to `Box<[u8]>`.
+/// # Example
+/// ```
+/// struct DeepPolicy;
 ///
-/// # Usage
+/// unsafe impl VerifierPolicy for DeepPolicy {
+///     fn options<T: RelaxedFollowTrait>() -> VerifierOptions {
+///         VerifierOptions { max_depth: 256, ..T::default_options() }
+///     }
+/// }
+///
+/// type DeepMessage = RelaxedMessage<Box<[u8]>, DeepPolicy>;
 /// ```
-/// use flatbuffers_owned::flatbuffers_owned;
 ///
-/// flatbuffers_owned!(MyFirstFlatBuffer, MySecondFlatBuffer);
+/// # Safety
+/// A policy setting `VERIFY` to `false` must only be used for buffers that are guaranteed to be valid, e.g. built by the same process.
+pub unsafe trait VerifierPolicy {
+    /// Whether `new()` verifies the data at all.
+    const VERIFY: bool = true;
+
+    /// The options the FlatBuffer `T` is verified with. Defaults to the [default options](RelaxedFollowTrait::default_options) of `T`.
+    fn options<T: RelaxedFollowTrait>() -> VerifierOptions {
+        T::default_options()
+    }
+}
+
+/// Verifies all data with the [default options](RelaxedFollowTrait::default_options) of the FlatBuffer.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DefaultPolicy;
+
+unsafe impl VerifierPolicy for DefaultPolicy {}
+
+/// Skips the verification in `new()` and `build()`. \
+/// Only for trusted buffers, e.g. passed between threads of the same process.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TrustedPolicy;
+
+unsafe impl VerifierPolicy for TrustedPolicy {
+    const VERIFY: bool = false;
+}
+
+/// A [RelaxedFlatBufferTrait] implementor whose bytes stay at the same address when the wrapper is moved. \
+/// Features caching pointers into the bytes, like the [Cached] wrapper, require this trait.
+///
+/// Implemented for [OwnedFlatBuffer] wrappers over buffers implementing [StableBuffer](buffer::StableBuffer), e.g. `Box<[u8]>`, `Vec<u8>`, `Arc<[u8]>` or `&[u8]`,
+/// but not over inline buffers like `[u8; N]`.
+///
+/// # Safety
+/// The byte slice returned by `deref()` must stay at the same address and must not be modified until the wrapper is dropped, even if the wrapper is moved.
+pub unsafe trait StableRelaxedFlatBufferTrait: RelaxedFlatBufferTrait {}
+
+/// An object-safe companion of the [RelaxedFlatBufferTrait], allowing heterogeneous collections of wrapped FlatBuffers. \
+/// Implemented for all implementors of the [RelaxedFlatBufferTrait].
+///
+/// # Example
 /// ```
-#[macro_export]
-macro_rules! flatbuffers_owned {
-    ($struct_name:ident) => {
-        $crate::paste! {
-            impl $crate::RelaxedFollowTrait for $struct_name<'_> {
-                type Inner<'a> = $struct_name<'a>;
-            }
+/// use flatbuffers_owned::ErasedFlatBuffer;
+///
+/// let messages: Vec<Box<dyn ErasedFlatBuffer>> = vec![Box::new(owned_login), Box::new(owned_logout)];
+///
+/// for message in &messages {
+///     println!("{}: {} bytes", message.root_type_name(), message.len());
+/// }
+/// ```
+pub trait ErasedFlatBuffer: Deref<Target = [u8]> {
+    /// Returns the name of the root type as returned by `core::any::type_name()`. \
+    /// The exact format is not guaranteed to be stable, so it should only be used for diagnostics.
+    fn root_type_name(&self) -> &'static str;
 
-            #[derive(Clone, Debug, PartialEq, Eq, Hash)]
-            pub struct [<Relaxed $struct_name>]<TBuffer: AsRef<[u8]>>(TBuffer);
+    /// Verifies the wrapped bytes again as the root type.
+    fn re_verify(&self) -> Result<(), VerifyError>;
+}
+
+impl<T: RelaxedFlatBufferTrait> ErasedFlatBuffer for T {
+    fn root_type_name(&self) -> &'static str {
+        core::any::type_name::<T::FlatBuffer>()
+    }
+
+    fn re_verify(&self) -> Result<(), VerifyError> {
+        T::verify(self)
+    }
+}
 
-            unsafe impl <TBuffer: AsRef<[u8]>> RelaxedFlatBufferTrait<TBuffer> for [<Relaxed $struct_name>]<TBuffer> {
-                type FlatBuffer = $struct_name<'static>;
+/// A FlatBuffer root that can be created from its flatc generated `{FLATBUFFER_NAME}Args` struct. \
+/// Implemented by the [flatbuffers_owned!](flatbuffers_owned) macro for all passed structs, and for tables declared with the `create` option.
+pub trait CreateFlatBufferTrait: RelaxedFollowTrait + Verifiable + Sized {
+    /// The flatc generated `{FLATBUFFER_NAME}Args` struct of tables, or the struct itself for struct roots.
+    type Args;
 
-                fn new(data: TBuffer) -> Result<Self, flatbuffers::InvalidFlatbuffer> {
-                    Self::verify(data.as_ref())?;
+    /// Serializes the arguments into the builder and returns the offset of the root.
+    fn create(builder: &mut FlatBufferBuilder<'static>, args: &Self::Args) -> WIPOffset<Self>;
+}
 
-                    Ok(Self(data))
-                }
-            }
-            
-            impl <TBuffer: AsRef<[u8]>> std::ops::Deref for [<Relaxed $struct_name>]<TBuffer> {
-                type Target = [u8];
+/// The flatc generated object API (`--gen-object-api`) of a FlatBuffer table. \
+/// Implemented by the [flatbuffers_owned!](flatbuffers_owned) macro for tables declared with the `object_api` option. \
+/// Implement it by hand to unpack into a native struct of your own, e.g. one deriving serde for the `serde` feature.
+pub trait ObjectApiTrait: RelaxedFollowTrait + Verifiable + Sized {
+    /// The native struct, the flatc generated `{FLATBUFFER_NAME}T` for the `object_api` option.
+    type Native;
 
-                fn deref(&self) -> &Self::Target {
-                    self.0.as_ref()
-                }
-            }
+    fn unpack<'a>(actual: <<Self as RelaxedFollowTrait>::Inner<'a> as Follow<'a>>::Inner) -> Self::Native;
 
-            pub type [<Owned $struct_name>] = [<Relaxed $struct_name>]<Box<[u8]>>;
-        }
-    };
+    fn pack(builder: &mut FlatBufferBuilder<'static>, native: &Self::Native) -> WIPOffset<Self>;
+}
 
-    ($($struct_name:ident),*) => {
-        $(
-            $crate::flatbuffers_owned!($struct_name);
-        )*
-    };
-}
\ No newline at end of file
+/// Finishes a `FlatBufferBuilder` into a wrapper, taking over its internal buffer instead of copying the finished data.
+///
+/// # Example
+/// ```
+/// use flatbuffers_owned::FinishOwnedTrait;
+///
+/// let mut builder = FlatBufferBuilder::new();
+/// let root = Message::create(&mut builder, &MessageArgs { ... });
+///
+/// let message: RelaxedMessage<BuilderBuffer> = builder.finish_owned(root, None)?;
+/// ```
+#[cfg(feature = "alloc")]
+pub trait FinishOwnedTrait: Sized {
+    /// Finishes the FlatBuffer, takes over the buffer of the builder and verifies it.
+    fn finish_owned<T: RelaxedFollowTrait + Verifiable>(self, root: WIPOffset<T>, file_identifier: Option<&str>) -> Result<OwnedFlatBuffer<T, buffer::BuilderBuffer>, VerifyError>;
+
+    /// Finishes the FlatBuffer and takes over the buffer of the builder without verifying it.
+    ///
+    /// # Safety
+    /// The caller must ensure that the root has been created by this builder as the FlatBuffer `T`.
+    unsafe fn finish_owned_unchecked<T: RelaxedFollowTrait + Verifiable>(self, root: WIPOffset<T>, file_identifier: Option<&str>) -> OwnedFlatBuffer<T, buffer::BuilderBuffer>;
+}
+
+#[cfg(feature = "alloc")]
+impl FinishOwnedTrait for FlatBufferBuilder<'_> {
+    fn finish_owned<T: RelaxedFollowTrait + Verifiable>(self, root: WIPOffset<T>, file_identifier: Option<&str>) -> Result<OwnedFlatBuffer<T, buffer::BuilderBuffer>, VerifyError> {
+        // Safety:
+        // The bytes are verified by `new()` right away.
+        let unverified = unsafe { self.finish_owned_unchecked::<T>(root, file_identifier) };
+
+        OwnedFlatBuffer::new(unverified.into_inner())
+    }
+
+    unsafe fn finish_owned_unchecked<T: RelaxedFollowTrait + Verifiable>(mut self, root: WIPOffset<T>, file_identifier: Option<&str>) -> OwnedFlatBuffer<T, buffer::BuilderBuffer> {
+        self.finish(root, file_identifier);
+        let (buffer, head) = self.collapse();
+
+        OwnedFlatBuffer::new_unchecked(buffer::BuilderBuffer::from_collapsed(buffer, head))
+    }
+}
+
+/// The wrapper around the raw bytes of a verified FlatBuffer `T`, held in any buffer `B` that can convert to a byte slice reference.
+///
+/// The [flatbuffers_owned!](flatbuffers_owned) macro only implements the [RelaxedFollowTrait] for the passed FlatBuffers
+/// and emits the `Relaxed{FLATBUFFER_NAME}<TBuffer>` and `Owned{FLATBUFFER_NAME}` type aliases of this struct. \
+/// Generic code can therefore name this single type for all wrapped FlatBuffers.
+///
+/// The struct is `#[repr(transparent)]`, so it is guaranteed to have the exact same layout as `B`. \
+/// It is `Copy` whenever `B` is, e.g. for `&[u8]`.
+///
+/// The wrapper is `Send` and `Sync` exactly when `B` is, independent of `T` and `P`. \
+/// `Box<[u8]>`, `Vec<u8>`, `Arc<[u8]>` and `&[u8]` backed wrappers can therefore be moved into threads and async tasks, while `Rc<[u8]>` backed ones can not.
+///
+/// `PartialEq`, `Eq`, `PartialOrd`, `Ord` and `Hash` are implemented on the underlying byte slice, independent of the buffer type. \
+/// Together with `AsRef<[u8]>` and `Borrow<[u8]>`, this allows looking up wrappers in hash maps and sets by a raw `&[u8]`.
+///
+/// The [VerifierPolicy] `P` decides at compile time how `new()` and `build()` verify the data. \
+/// Distinct policies result in distinct types, e.g. `RelaxedMessage<Box<[u8]>, TrustedPolicy>` for buffers of an internal pipeline. \
+/// Like `Vec::new()` for the default allocator, the inherent constructors only exist for the [DefaultPolicy],
+/// so that `RelaxedMessage::new(data)` still infers its type. Wrappers with another policy are constructed through the [RelaxedFlatBufferTrait].
+#[repr(transparent)]
+pub struct OwnedFlatBuffer<T: RelaxedFollowTrait + Verifiable, B: AsRef<[u8]>, P: VerifierPolicy = DefaultPolicy> {
+    buffer: B,
+    flatbuffer: PhantomData<fn() -> (T, P)>,
+}
+
+impl<T: RelaxedFollowTrait + Verifiable, B: AsRef<[u8]>> OwnedFlatBuffer<T, B> {
+    /// Verifies the buffer as the FlatBuffer `T` and wraps it.
+    #[inline]
+    pub fn new(data: B) -> Result<Self, VerifyError> {
+        <Self as RelaxedFlatBufferTrait>::new(data)
+    }
+
+    /// Wraps the buffer without verifying it.
+    ///
+    /// # Safety
+    /// The caller must ensure that the data has already been verified as the FlatBuffer `T` and that it remains immutable.
+    #[inline]
+    pub unsafe fn new_unchecked(data: B) -> Self {
+        <Self as RelaxedFlatBufferTrait>::new_unchecked(data)
+    }
+
+    /// Verifies the buffer as the FlatBuffer `T` with the passed limits and wraps it.
+    #[inline]
+    pub fn new_with_opts(data: B, opts: &VerifierOptions) -> Result<Self, VerifyError> {
+        <Self as RelaxedFlatBufferTrait>::new_with_opts(data, opts)
+    }
+
+    /// Verifies the data as the FlatBuffer `T`.
+    #[inline]
+    pub fn verify(data: &[u8]) -> Result<(), VerifyError> {
+        <Self as RelaxedFlatBufferTrait>::verify(data)
+    }
+
+    /// Verifies the data as the FlatBuffer `T` with the passed limits.
+    #[inline]
+    pub fn verify_with_opts(data: &[u8], opts: &VerifierOptions) -> Result<(), VerifyError> {
+        <Self as RelaxedFlatBufferTrait>::verify_with_opts(data, opts)
+    }
+
+    /// Verifies the buffer as the FlatBuffer `T` and wraps it, copying the bytes into an aligned allocation only if they are not [aligned](OwnedFlatBuffer::is_aligned).
+    ///
+    /// Use this for buffers sliced out of the middle of a network frame, before passing them on to code expecting aligned FlatBuffers.
+    #[cfg(feature = "alloc")]
+    pub fn new_realigned(data: B) -> Result<OwnedFlatBuffer<T, buffer::Realigned<B>>, VerifyError> {
+        OwnedFlatBuffer::new(buffer::Realigned::new(data))
+    }
+}
+
+impl<T: RelaxedFollowTrait + Verifiable, B: AsRef<[u8]>, P: VerifierPolicy> OwnedFlatBuffer<T, B, P> {
+    /// Returns the actual FlatBuffer, borrowing from the wrapped buffer.
+    #[inline(always)]
+    pub fn as_actual(&self) -> <<T as RelaxedFollowTrait>::Inner<'_> as Follow<'_>>::Inner {
+        <Self as RelaxedFlatBufferTrait>::as_actual(self)
+    }
+
+    /// Returns a reference to the buffer.
+    #[inline]
+    pub fn buffer(&self) -> &B {
+        &self.buffer
+    }
+
+    /// Returns `true` if the buffer starts at an address aligned to the [ALIGNMENT](buffer::ALIGNMENT) of FlatBuffers.
+    #[inline]
+    pub fn is_aligned(&self) -> bool {
+        buffer::is_aligned(self.buffer.as_ref())
+    }
+
+    /// Consumes the wrapper and returns the buffer.
+    #[inline]
+    pub fn into_inner(self) -> B {
+        self.buffer
+    }
+
+    /// Copies the bytes into a fresh `Box<[u8]>`, e.g. for keeping a FlatBuffer borrowed from a network frame.
+    ///
+    /// The copied bytes are not verified again.
+    #[cfg(feature = "alloc")]
+    pub fn to_owned(&self) -> OwnedFlatBuffer<T, Box<[u8]>, P> {
+        OwnedFlatBuffer { buffer: self.buffer.as_ref().into(), flatbuffer: PhantomData }
+    }
+
+    /// Converts the buffer into another representation holding the same bytes, e.g. a `Vec<u8>` into an `Arc<[u8]>`.
+    ///
+    /// The bytes are not verified again. Debug builds assert that the converted buffer has the same length.
+    pub fn map_buffer<U: AsRef<[u8]>>(self, f: impl FnOnce(B) -> U) -> OwnedFlatBuffer<T, U, P> {
+        let len = self.buffer.as_ref().len();
+        let buffer = f(self.buffer);
+        debug_assert_eq!(buffer.as_ref().len(), len, "map_buffer() must not change the bytes of the buffer");
+
+        OwnedFlatBuffer { buffer, flatbuffer: PhantomData }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: CreateFlatBufferTrait, P: VerifierPolicy> OwnedFlatBuffer<T, Box<[u8]>, P> {
+    /// Builds a new owned FlatBuffer from its `{FLATBUFFER_NAME}Args`, or from the struct itself for struct roots.
+    ///
+    /// The FlatBuffer is built by a fresh `FlatBufferBuilder` and therefore not verified again.
+    pub fn create(args: &T::Args) -> Self {
+        let mut builder = FlatBufferBuilder::new();
+        let offset = T::create(&mut builder, args);
+        builder.finish(offset, None);
+
+        unsafe { Self::new_unchecked(builder.finished_data().into()) }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: RelaxedFollowTrait + Verifiable, P: VerifierPolicy> OwnedFlatBuffer<T, Box<[u8]>, P> {
+    /// Builds a new owned FlatBuffer using the passed closure.
+    ///
+    /// The closure receives a fresh `FlatBufferBuilder` and returns the offset of the root, which is then finished and verified according to the policy `P`.
+    pub fn build<F>(f: F) -> Result<Self, VerifyError>
+        where F: FnOnce(&mut FlatBufferBuilder<'static>) -> WIPOffset<T>
+    {
+        let mut builder = FlatBufferBuilder::new();
+        let offset = f(&mut builder);
+        builder.finish(offset, None);
+
+        Self::new(builder.finished_data().into())
+    }
+
+    /// Copies the finished data of the builder and verifies it according to the policy `P`.
+    ///
+    /// # Panics
+    /// Panics in debug builds if the builder is not finished. [try_from_builder()](Self::try_from_builder) never panics.
+    pub fn from_builder(builder: &FlatBufferBuilder<'_>) -> Result<Self, VerifyError> {
+        Self::new(builder.finished_data().into())
+    }
+
+    /// Takes over the internal buffer of the finished builder without copying it, and verifies it according to the policy `P`.
+    ///
+    /// # Panics
+    /// Panics in debug builds if the builder is not finished. [try_from_builder_owned()](Self::try_from_builder_owned) never panics.
+    pub fn from_builder_owned(builder: FlatBufferBuilder<'_>) -> Result<OwnedFlatBuffer<T, buffer::BuilderBuffer, P>, VerifyError> {
+        // Asserts that the builder is finished.
+        builder.finished_data();
+
+        Self::try_from_builder_owned(builder)
+    }
+
+    /// Like [from_builder()](Self::from_builder), but does not assert that the builder is finished. \
+    /// The `FlatBufferBuilder` does not expose whether it is finished, so the written data is verified like any other input
+    /// and an unfinished builder results in a [VerifyError], unless its data happens to form a valid FlatBuffer.
+    pub fn try_from_builder(builder: &FlatBufferBuilder<'_>) -> Result<Self, VerifyError> {
+        Self::new(builder.unfinished_data().into())
+    }
+
+    /// Like [from_builder_owned()](Self::from_builder_owned), but does not assert that the builder is finished. \
+    /// An unfinished builder results in a [VerifyError], unless its data happens to form a valid FlatBuffer.
+    pub fn try_from_builder_owned(builder: FlatBufferBuilder<'_>) -> Result<OwnedFlatBuffer<T, buffer::BuilderBuffer, P>, VerifyError> {
+        let (buffer, head) = builder.collapse();
+
+        <OwnedFlatBuffer<T, buffer::BuilderBuffer, P> as RelaxedFlatBufferTrait>::new(buffer::BuilderBuffer::from_collapsed(buffer, head))
+    }
+
+    /// Converts the wrapper into an `Arc<[u8]>` backed one, which is cheap to clone and share across threads.
+    ///
+    /// The bytes are moved into the reference-counted allocation once, but not verified again. \
+    /// Use [to_owned()](Self::to_owned) to copy them back into a `Box<[u8]>` backed wrapper.
+    pub fn into_shared(self) -> OwnedFlatBuffer<T, Arc<[u8]>, P> {
+        self.map_buffer(Arc::from)
+    }
+
+    /// Converts the wrapper into an `Rc<[u8]>` backed one, which is cheap to clone without atomic reference counting, but bound to its thread.
+    ///
+    /// The bytes are moved into the reference-counted allocation once, but not verified again. \
+    /// Use [to_owned()](Self::to_owned) to copy them back into a `Box<[u8]>` backed wrapper.
+    pub fn into_local(self) -> OwnedFlatBuffer<T, Rc<[u8]>, P> {
+        self.map_buffer(Rc::from)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: RelaxedFollowTrait + Verifiable, P: VerifierPolicy> OwnedFlatBuffer<T, Vec<u8>, P> {
+    /// Converts the wrapper into the `Box<[u8]>` backed `Owned{FLATBUFFER_NAME}` form.
+    ///
+    /// The spare capacity of the `Vec` is released, which may reallocate. The bytes are not verified again.
+    pub fn into_boxed(self) -> OwnedFlatBuffer<T, Box<[u8]>, P> {
+        self.map_buffer(Vec::into_boxed_slice)
+    }
+
+    /// Converts the wrapper into an `Arc<[u8]>` backed one, which is cheap to clone and share across threads.
+    ///
+    /// The bytes are moved into the reference-counted allocation once, but not verified again. \
+    /// Use [to_owned()](Self::to_owned) to copy them back into a `Box<[u8]>` backed wrapper.
+    pub fn into_shared(self) -> OwnedFlatBuffer<T, Arc<[u8]>, P> {
+        self.map_buffer(Arc::from)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: RelaxedFollowTrait + Verifiable, B: AsRef<[u8]> + Into<Vec<u8>>, P: VerifierPolicy> OwnedFlatBuffer<T, B, P> {
+    /// Consumes the wrapper and returns its bytes as a `Vec<u8>`, e.g. for reusing the allocation for the next message.
+    ///
+    /// `Vec<u8>` and `Box<[u8]>` buffers are converted without copying the bytes.
+    pub fn into_vec(self) -> Vec<u8> {
+        self.buffer.into()
+    }
+}
+
+/// Verifies the `Vec<u8>` as the FlatBuffer `T` according to the policy `P`, and boxes it without copying the bytes.
+#[cfg(feature = "alloc")]
+impl<T: RelaxedFollowTrait + Verifiable, P: VerifierPolicy> TryFrom<Vec<u8>> for OwnedFlatBuffer<T, Box<[u8]>, P> {
+    type Error = VerifyError;
+
+    fn try_from(data: Vec<u8>) -> Result<Self, VerifyError> {
+        <Self as RelaxedFlatBufferTrait>::new(data.into_boxed_slice())
+    }
+}
+
+/// Verifies the `Vec<u8>` as the FlatBuffer `T` according to the policy `P`, and wraps it.
+#[cfg(feature = "alloc")]
+impl<T: RelaxedFollowTrait + Verifiable, P: VerifierPolicy> TryFrom<Vec<u8>> for OwnedFlatBuffer<T, Vec<u8>, P> {
+    type Error = VerifyError;
+
+    fn try_from(data: Vec<u8>) -> Result<Self, VerifyError> {
+        <Self as RelaxedFlatBufferTrait>::new(data)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T: RelaxedFollowTrait + Verifiable, P: VerifierPolicy> OwnedFlatBuffer<T, Cow<'a, [u8]>, P> {
+    /// Promotes a borrowed buffer to `Cow::Owned`, so the wrapper escapes the lifetime of the borrowed bytes.
+    ///
+    /// Borrowed bytes are copied once, owned bytes are moved. The bytes are not verified again.
+    pub fn into_static(self) -> OwnedFlatBuffer<T, Cow<'static, [u8]>, P> {
+        self.map_buffer(|buffer| Cow::Owned(buffer.into_owned()))
+    }
+}
+
+impl<T: ObjectApiTrait, B: AsRef<[u8]>, P: VerifierPolicy> OwnedFlatBuffer<T, B, P> {
+    /// Unpacks the FlatBuffer into its flatc generated object API representation.
+    pub fn unpack(&self) -> T::Native {
+        T::unpack(self.as_actual())
+    }
+
+    /// Returns an updated copy of the FlatBuffer, leaving this one unchanged.
+    ///
+    /// The FlatBuffer is unpacked into its object API representation, modified by the closure,
+    /// packed into a new owned FlatBuffer and verified according to the policy `P`.
+    #[cfg(feature = "alloc")]
+    pub fn update(&self, f: impl FnOnce(&mut T::Native)) -> Result<OwnedFlatBuffer<T, Box<[u8]>, P>, VerifyError> {
+        let mut native = self.unpack();
+        f(&mut native);
+
+        let mut builder = FlatBufferBuilder::new();
+        let offset = T::pack(&mut builder, &native);
+        builder.finish(offset, None);
+
+        <OwnedFlatBuffer<T, Box<[u8]>, P> as RelaxedFlatBufferTrait>::new(builder.finished_data().into())
+    }
+
+    /// Re-serializes the FlatBuffer into a new minimal owned FlatBuffer, and returns it together with the number of bytes saved.
+    ///
+    /// This drops the slack of FlatBuffers carved out of larger frames or built with padding, unused vtables or unreferenced data. \
+    /// The FlatBuffer is packed through its object API by a fresh `FlatBufferBuilder` and therefore not verified again.
+    #[cfg(feature = "alloc")]
+    pub fn compact(&self) -> (OwnedFlatBuffer<T, Box<[u8]>, P>, usize) {
+        let compacted = OwnedFlatBuffer::<T, Box<[u8]>, P>::pack(&self.unpack());
+        let saved = self.buffer.as_ref().len().saturating_sub(compacted.len());
+
+        (compacted, saved)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: RelaxedFollowTrait + Verifiable, B: AsRef<[u8]>, P: VerifierPolicy> OwnedFlatBuffer<T, B, P> {
+    /// Detaches a sub-table into its own owned FlatBuffer, e.g. the `header` of an `Envelope` to store it separately.
+    ///
+    /// The closure selects the sub-table, which is then re-serialized as the root of a new FlatBuffer through its object API. \
+    /// The sub-table must therefore be declared with the `object_api` option. Returns `None` if the closure does.
+    /// ```
+    /// flatbuffers_owned!(Envelope, Header { object_api });
+    ///
+    /// let header: Option<OwnedHeader> = owned_envelope.extract(|envelope| envelope.header());
+    /// ```
+    pub fn extract<'a, S, F>(&'a self, f: F) -> Option<OwnedFlatBuffer<S, Box<[u8]>, P>>
+        where S: ObjectApiTrait,
+              F: FnOnce(<<T as RelaxedFollowTrait>::Inner<'a> as Follow<'a>>::Inner) -> Option<<<S as RelaxedFollowTrait>::Inner<'a> as Follow<'a>>::Inner>
+    {
+        f(self.as_actual()).map(|actual| OwnedFlatBuffer::pack(&S::unpack(actual)))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: ObjectApiTrait, P: VerifierPolicy> OwnedFlatBuffer<T, Box<[u8]>, P> {
+    /// Packs the object API representation into a new owned FlatBuffer.
+    ///
+    /// The FlatBuffer is built by a fresh `FlatBufferBuilder` and therefore not verified again.
+    pub fn pack(native: &T::Native) -> Self {
+        let mut builder = FlatBufferBuilder::new();
+        let offset = T::pack(&mut builder, native);
+        builder.finish(offset, None);
+
+        unsafe { Self::new_unchecked(builder.finished_data().into()) }
+    }
+}
+
+unsafe impl<T: RelaxedFollowTrait + Verifiable, B: AsRef<[u8]>, P: VerifierPolicy> RelaxedFlatBufferTrait for OwnedFlatBuffer<T, B, P> {
+    type Buffer = B;
+    type FlatBuffer = T;
+
+    fn verify(data: &[u8]) -> Result<(), VerifyError> {
+        __private::verify_root::<T>(data, 0, &P::options::<T>(), Constructor::Verify)
+    }
+
+    fn new(data: B) -> Result<Self, VerifyError> {
+        if P::VERIFY {
+            __private::verify_root::<T>(data.as_ref(), 0, &P::options::<T>(), Constructor::New)?;
+        }
+
+        Ok(Self { buffer: data, flatbuffer: PhantomData })
+    }
+
+    unsafe fn new_unchecked(data: B) -> Self {
+        Self { buffer: data, flatbuffer: PhantomData }
+    }
+
+    fn buffer(&self) -> &B {
+        &self.buffer
+    }
+
+    fn into_inner(self) -> B {
+        self.buffer
+    }
+}
+
+impl<T: RelaxedFollowTrait + Verifiable, B: AsRef<[u8]> + Clone, P: VerifierPolicy> Clone for OwnedFlatBuffer<T, B, P> {
+    fn clone(&self) -> Self {
+        Self { buffer: self.buffer.clone(), flatbuffer: PhantomData }
+    }
+}
+
+impl<T: RelaxedFollowTrait + Verifiable, B: AsRef<[u8]> + Copy, P: VerifierPolicy> Copy for OwnedFlatBuffer<T, B, P> {}
+
+impl<T: RelaxedFollowTrait + Verifiable, B: AsRef<[u8]> + Debug, P: VerifierPolicy> Debug for OwnedFlatBuffer<T, B, P> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("OwnedFlatBuffer").field(&self.buffer).finish()
+    }
+}
+
+impl<T: RelaxedFollowTrait + Verifiable, B: AsRef<[u8]>, P: VerifierPolicy> PartialEq for OwnedFlatBuffer<T, B, P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.buffer.as_ref() == other.buffer.as_ref()
+    }
+}
+
+impl<T: RelaxedFollowTrait + Verifiable, B: AsRef<[u8]>, P: VerifierPolicy> Eq for OwnedFlatBuffer<T, B, P> {}
+
+impl<T: RelaxedFollowTrait + Verifiable, B: AsRef<[u8]>, P: VerifierPolicy> PartialOrd for OwnedFlatBuffer<T, B, P> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: RelaxedFollowTrait + Verifiable, B: AsRef<[u8]>, P: VerifierPolicy> Ord for OwnedFlatBuffer<T, B, P> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.buffer.as_ref().cmp(other.buffer.as_ref())
+    }
+}
+
+impl<T: RelaxedFollowTrait + Verifiable, B: AsRef<[u8]>, P: VerifierPolicy> Hash for OwnedFlatBuffer<T, B, P> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        Hash::hash(self.buffer.as_ref(), state)
+    }
+}
+
+impl<T: RelaxedFollowTrait + Verifiable, B: AsRef<[u8]>, P: VerifierPolicy> AsRef<[u8]> for OwnedFlatBuffer<T, B, P> {
+    fn as_ref(&self) -> &[u8] {
+        self.buffer.as_ref()
+    }
+}
+
+impl<T: RelaxedFollowTrait + Verifiable, B: AsRef<[u8]>, P: VerifierPolicy> Borrow<[u8]> for OwnedFlatBuffer<T, B, P> {
+    fn borrow(&self) -> &[u8] {
+        self.buffer.as_ref()
+    }
+}
+
+impl<T: RelaxedFollowTrait + Verifiable, B: AsRef<[u8]>, P: VerifierPolicy> Deref for OwnedFlatBuffer<T, B, P> {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        self.buffer.as_ref()
+    }
+}
+
+// The wrapper reads its bytes through AsRef<[u8]>, whose address is guaranteed to be stable by the StableBuffer trait.
+unsafe impl<T: RelaxedFollowTrait + Verifiable, B: buffer::StableBuffer, P: VerifierPolicy> StableRelaxedFlatBufferTrait for OwnedFlatBuffer<T, B, P> {}
+
+// The wrapper must be Send and Sync for every FlatBuffer and policy, as long as the buffer is.
+#[allow(dead_code)]
+const _: () = {
+    fn assert_send_sync<S: Send + Sync>() {}
+
+    fn assert_wrappers<T: RelaxedFollowTrait + Verifiable, P: VerifierPolicy>() {
+        #[cfg(feature = "alloc")]
+        assert_send_sync::<OwnedFlatBuffer<T, Box<[u8]>, P>>();
+        #[cfg(feature = "alloc")]
+        assert_send_sync::<OwnedFlatBuffer<T, Vec<u8>, P>>();
+        #[cfg(feature = "alloc")]
+        assert_send_sync::<OwnedFlatBuffer<T, Arc<[u8]>, P>>();
+        assert_send_sync::<OwnedFlatBuffer<T, &'static [u8], P>>();
+    }
+};
+
+/// Raw FlatBuffer bytes, which have not been verified as the wrapper `T` yet.
+///
+/// Constructing it is free, so it can be created straight off the wire, moved across threads and queued. \
+/// The bytes can only be accessed as a FlatBuffer after converting it into the wrapper with [verify()](Unverified::verify).
+///
+/// # Example
+/// ```
+/// use flatbuffers_owned::Unverified;
+///
+/// let unverified = Unverified::<OwnedMessage>::new(message_bytes);
+/// queue.send(unverified);
+///
+/// // ... on the worker thread:
+/// let message: OwnedMessage = queue.recv().verify()?;
+/// ```
+#[repr(transparent)]
+pub struct Unverified<T: RelaxedFlatBufferTrait> {
+    buffer: T::Buffer,
+}
+
+impl<T: RelaxedFlatBufferTrait> Unverified<T> {
+    pub fn new(data: T::Buffer) -> Self {
+        Self { buffer: data }
+    }
+
+    /// Verifies the bytes and converts them into the wrapper.
+    pub fn verify(self) -> Result<T, VerifyError> {
+        T::new(self.buffer)
+    }
+
+    /// Verifies the bytes with the passed limits and converts them into the wrapper.
+    pub fn verify_with_opts(self, opts: &VerifierOptions) -> Result<T, VerifyError> {
+        T::new_with_opts(self.buffer, opts)
+    }
+
+    /// Converts the bytes into the wrapper without verifying them.
+    ///
+    /// # Safety
+    /// The caller must ensure that the data has already been verified as this FlatBuffer type and that it remains immutable.
+    pub unsafe fn assume_verified(self) -> T {
+        T::new_unchecked(self.buffer)
+    }
+
+    /// Returns the unverified raw bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.buffer.as_ref()
+    }
+
+    /// Returns the wrapped buffer.
+    pub fn into_inner(self) -> T::Buffer {
+        self.buffer
+    }
+}
+
+impl<T: RelaxedFlatBufferTrait> Clone for Unverified<T>
+    where T::Buffer: Clone
+{
+    fn clone(&self) -> Self {
+        Self { buffer: self.buffer.clone() }
+    }
+}
+
+impl<T: RelaxedFlatBufferTrait> Debug for Unverified<T>
+    where T::Buffer: Debug
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("Unverified").field(&self.buffer).finish()
+    }
+}
+
+/// A verified FlatBuffer `T` whose root is located at an arbitrary position `loc` of the buffer `B`, e.g. embedded in a larger record. \
+/// Unlike wrapping the sub-slice, the record does not need to be copied into its own allocation.
+///
+/// The FlatBuffer is verified relative to the start of the whole buffer, so `loc` has to keep the alignment of the FlatBuffer.
+///
+/// # Example
+/// ```
+/// use flatbuffers_owned::EmbeddedFlatBuffer;
+///
+/// let embedded = EmbeddedFlatBuffer::<Message<'static>, _>::new_at(record_bytes, RECORD_HEADER_LEN)?;
+/// let message = embedded.as_actual_at();
+/// ```
+pub struct EmbeddedFlatBuffer<T: RelaxedFollowTrait + Verifiable, B: AsRef<[u8]>, P: VerifierPolicy = DefaultPolicy> {
+    buffer: B,
+    loc: usize,
+    flatbuffer: PhantomData<fn() -> (T, P)>,
+}
+
+impl<T: RelaxedFollowTrait + Verifiable, B: AsRef<[u8]>, P: VerifierPolicy> EmbeddedFlatBuffer<T, B, P> {
+    /// Verifies the FlatBuffer `T` rooted at `loc` of the buffer according to the policy `P` and wraps it.
+    pub fn new_at(data: B, loc: usize) -> Result<Self, VerifyError> {
+        if P::VERIFY {
+            __private::verify_root::<T>(data.as_ref(), loc, &P::options::<T>(), Constructor::NewAt)?;
+        }
+
+        Ok(Self { buffer: data, loc, flatbuffer: PhantomData })
+    }
+
+    /// Wraps the buffer without verifying it.
+    ///
+    /// # Safety
+    /// The caller must ensure that the data has already been verified as the FlatBuffer `T` rooted at `loc` and that it remains immutable.
+    pub unsafe fn new_at_unchecked(data: B, loc: usize) -> Self {
+        Self { buffer: data, loc, flatbuffer: PhantomData }
+    }
+
+    /// Verifies the data as the FlatBuffer `T` rooted at `loc` with the options of the policy `P`.
+    pub fn verify_at(data: &[u8], loc: usize) -> Result<(), VerifyError> {
+        __private::verify_root::<T>(data, loc, &P::options::<T>(), Constructor::VerifyAt)
+    }
+
+    /// Returns the actual FlatBuffer rooted at `loc`, borrowing from the wrapped buffer.
+    #[inline(always)]
+    pub fn as_actual_at(&self) -> <<T as RelaxedFollowTrait>::Inner<'_> as Follow<'_>>::Inner {
+        unsafe { T::follow(self.buffer.as_ref(), self.loc) }
+    }
+
+    /// Returns the position of the root within the buffer.
+    pub fn loc(&self) -> usize {
+        self.loc
+    }
+
+    /// Returns a reference to the whole buffer.
+    pub fn buffer(&self) -> &B {
+        &self.buffer
+    }
+
+    /// Consumes the wrapper and returns the whole buffer.
+    pub fn into_inner(self) -> B {
+        self.buffer
+    }
+}
+
+impl<T: RelaxedFollowTrait + Verifiable, B: AsRef<[u8]> + Clone, P: VerifierPolicy> Clone for EmbeddedFlatBuffer<T, B, P> {
+    fn clone(&self) -> Self {
+        Self { buffer: self.buffer.clone(), loc: self.loc, flatbuffer: PhantomData }
+    }
+}
+
+impl<T: RelaxedFollowTrait + Verifiable, B: AsRef<[u8]> + Debug, P: VerifierPolicy> Debug for EmbeddedFlatBuffer<T, B, P> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("EmbeddedFlatBuffer").field("buffer", &self.buffer).field("loc", &self.loc).finish()
+    }
+}
+
+/// A wrapped FlatBuffer `W` that stores the location of its root, which is resolved once on construction. \
+/// [as_actual()](CachedRoot::as_actual) then skips reading the root offset, e.g. in hot loops accessing the FlatBuffer millions of times.
+///
+/// The wrapper `W` stays accessible through `Deref`.
+///
+/// # Example
+/// ```
+/// use flatbuffers_owned::CachedRoot;
+///
+/// let message = CachedRoot::new(OwnedMessage::new(message_bytes)?);
+///
+/// for _ in 0..1_000_000 {
+///     process(message.as_actual().id());
+/// }
+/// ```
+pub struct CachedRoot<W: RelaxedFlatBufferTrait> {
+    flatbuffer: W,
+    root: usize,
+}
+
+impl<W: RelaxedFlatBufferTrait> CachedRoot<W> {
+    pub fn new(flatbuffer: W) -> Self {
+        // The offset has been verified together with the wrapped FlatBuffer.
+        let root = flatbuffer.get(0..4)
+            .and_then(|offset| core::convert::TryInto::try_into(offset).ok())
+            .map(u32::from_le_bytes)
+            .unwrap_or_default() as usize;
+
+        Self { flatbuffer, root }
+    }
+
+    /// Returns the actual FlatBuffer from the cached root location, borrowing from the wrapped buffer.
+    #[inline(always)]
+    pub fn as_actual(&self) -> <<W::FlatBuffer as RelaxedFollowTrait>::Inner<'_> as Follow<'_>>::Inner {
+        unsafe { <<W::FlatBuffer as RelaxedFollowTrait>::Inner<'_> as Follow<'_>>::follow(&self.flatbuffer, self.root) }
+    }
+
+    /// Consumes the cache and returns the wrapper.
+    pub fn into_inner(self) -> W {
+        self.flatbuffer
+    }
+}
+
+impl<W: RelaxedFlatBufferTrait> Deref for CachedRoot<W> {
+    type Target = W;
+
+    fn deref(&self) -> &Self::Target {
+        &self.flatbuffer
+    }
+}
+
+impl<W: RelaxedFlatBufferTrait + Clone> Clone for CachedRoot<W> {
+    fn clone(&self) -> Self {
+        Self { flatbuffer: self.flatbuffer.clone(), root: self.root }
+    }
+}
+
+impl<W: RelaxedFlatBufferTrait + Debug> Debug for CachedRoot<W> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("CachedRoot").field("flatbuffer", &self.flatbuffer).field("root", &self.root).finish()
+    }
+}
+
+/// Shortens the lifetime of a followed FlatBuffer, which requires it to be covariant in its lifetime. \
+/// Implemented by the [flatbuffers_owned!](flatbuffers_owned) macro for tables declared with the `cached` option.
+pub trait CovariantFollowTrait: RelaxedFollowTrait {
+    fn shorten<'a>(actual: &'a <<Self as RelaxedFollowTrait>::Inner<'static> as Follow<'static>>::Inner) -> &'a <<Self as RelaxedFollowTrait>::Inner<'a> as Follow<'a>>::Inner;
+}
+
+/// A wrapped FlatBuffer `W` holding its actual FlatBuffer, which is constructed once on creation and borrowed by [get()](Cached::get). \
+/// This eliminates the repeated follows of `as_actual()` in tight loops.
+///
+/// The actual FlatBuffer borrows from the bytes of the wrapper, which therefore has to implement the [StableRelaxedFlatBufferTrait]. \
+/// This is the case for wrappers over `Box<[u8]>`, `Vec<u8>`, `Arc<[u8]>` or `&[u8]`. \
+/// The `Cached{FLATBUFFER_NAME}` type aliases are generated with the `cached` option of the [flatbuffers_owned!](flatbuffers_owned) macro.
+///
+/// # Example
+/// ```
+/// flatbuffers_owned!(Message { cached });
+///
+/// let message = CachedMessage::new(OwnedMessage::new(message_bytes)?);
+///
+/// for _ in 0..1_000_000 {
+///     process(message.get().id());
+/// }
+/// ```
+pub struct Cached<W: StableRelaxedFlatBufferTrait>
+    where W::FlatBuffer: CovariantFollowTrait
+{
+    actual: <<W::FlatBuffer as RelaxedFollowTrait>::Inner<'static> as Follow<'static>>::Inner,
+    flatbuffer: W,
+}
+
+impl<W: StableRelaxedFlatBufferTrait> Cached<W>
+    where W::FlatBuffer: CovariantFollowTrait
+{
+    pub fn new(flatbuffer: W) -> Self {
+        // The bytes stay at the same address until the wrapper is dropped, as guaranteed by the StableRelaxedFlatBufferTrait.
+        let bytes: &'static [u8] = unsafe { &*(&*flatbuffer as *const [u8]) };
+        let actual = unsafe { W::FlatBuffer::follow(bytes, 0) };
+
+        Self { actual, flatbuffer }
+    }
+
+    /// Returns the actual FlatBuffer constructed on creation.
+    #[inline(always)]
+    pub fn get(&self) -> &<<W::FlatBuffer as RelaxedFollowTrait>::Inner<'_> as Follow<'_>>::Inner {
+        W::FlatBuffer::shorten(&self.actual)
+    }
+
+    /// Consumes the cache and returns the wrapper.
+    pub fn into_inner(self) -> W {
+        self.flatbuffer
+    }
+}
+
+impl<W: StableRelaxedFlatBufferTrait> Deref for Cached<W>
+    where W::FlatBuffer: CovariantFollowTrait
+{
+    type Target = W;
+
+    fn deref(&self) -> &Self::Target {
+        &self.flatbuffer
+    }
+}
+
+impl<W: StableRelaxedFlatBufferTrait + Clone> Clone for Cached<W>
+    where W::FlatBuffer: CovariantFollowTrait
+{
+    fn clone(&self) -> Self {
+        Self::new(self.flatbuffer.clone())
+    }
+}
+
+impl<W: StableRelaxedFlatBufferTrait + Debug> Debug for Cached<W>
+    where W::FlatBuffer: CovariantFollowTrait
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("Cached").field(&self.flatbuffer).finish()
+    }
+}
+
+/// Use this macro on your FlatBuffers to generate the required code to start using this crate.
+///
+/// After invoking the macro, you have two generated type aliases of the generic [OwnedFlatBuffer] struct for each of your passed FlatBuffers: \
+/// 1. A generic type alias named `Relaxed{FLATBUFFER_NAME}`, which takes the generic `TBuffer: AsRef<[u8]>`. \
+/// 2. A type alias named `Owned{FLATBUFFER_NAME}`, which aliases the `Relaxed{FLATBUFFER_NAME}` type and sets `TBuffer` to `Box<[u8]>`.
+///
+/// With the `inline` feature, an `Inline{FLATBUFFER_NAME}` alias additionally sets `TBuffer` to the [InlineBuffer](buffer::InlineBuffer). \
+/// With the `zeroize` feature, a `Secret{FLATBUFFER_NAME}` alias additionally sets `TBuffer` to the [SecretBuffer](buffer::SecretBuffer). \
+/// With the `allocator-api2` feature, an `OwnedIn{FLATBUFFER_NAME}<A>` alias additionally sets `TBuffer` to the [BoxIn<A>](buffer::BoxIn) of a custom allocator.
+///
+/// Besides the aliases, the macro only implements the [RelaxedFollowTrait] for the passed FlatBuffers. \
+/// All methods, e.g. `new()`, `as_actual()` and `build()`, are provided by the [OwnedFlatBuffer] struct.
+/// The `create()` constructor is generated for tables declared with the `create` option.
+///
+/// As the wrappers are defined in this crate, foreign traits can not be implemented for them. \
+/// Traits of this crate, like `snapshot::Sequenced`, are instead implemented for the flatc generated FlatBuffer.
+/// Foreign traits can be implemented for a struct generated with the `newtype` option.
+///
+/// # Usage
+/// ```
+/// use flatbuffers_owned::flatbuffers_owned;
+///
+/// flatbuffers_owned!(MyFirstFlatBuffer, MySecondFlatBuffer);
+/// ```
+///
+/// # Attributes
+/// Attributes placed in front of a FlatBuffer name are attached to the generated type aliases. \
+/// `#[cfg]` and `#[cfg_attr]` attributes are attached to every other item generated for the FlatBuffer as well, including the trait implementations and the items of its options,
+/// so a FlatBuffer only generated by flatc with a feature enabled can be wrapped under the same condition. \
+/// Derives are not possible on type aliases, the `newtype` option below generates a struct to derive traits on instead.
+/// ```
+/// flatbuffers_owned!(
+///     /// A chat message.
+///     #[cfg(feature = "chat")]
+///     Message { object_api },
+///     #[allow(deprecated)]
+///     Vector<Message> as MessageBatch
+/// );
+/// ```
+///
+/// # Struct roots
+/// FlatBuffers having a flatc generated struct as their root are supported by prefixing the name with `struct`. \
+/// The `Owned{FLATBUFFER_NAME}` type then gets a `create()` constructor taking the struct, and `as_actual()` returns a reference to it.
+/// ```
+/// flatbuffers_owned!(struct Vec3, MyFlatBuffer);
+///
+/// let owned_vec3 = OwnedVec3::create(&Vec3::new(1.0, 2.0, 3.0));
+/// let vec3: &Vec3 = owned_vec3.as_actual();
+/// ```
+///
+/// # Vector roots
+/// FlatBuffers having a vector of tables as their root are supported with the `Vector<{FLATBUFFER_NAME}> as {ALIAS}` form. \
+/// This generates the `Relaxed{ALIAS}` and `Owned{ALIAS}` types, whose `as_actual()` returns a `flatbuffers::Vector` of the tables. \
+/// The table itself must be passed to the macro as well.
+/// ```
+/// flatbuffers_owned!(Message, Vector<Message> as MessageBatch);
+///
+/// let owned_batch = OwnedMessageBatch::new(batch_bytes)?;
+/// for message in owned_batch.as_actual() {
+///     // ...
+/// }
+/// ```
+///
+/// # Options
+/// Additional code can be generated by passing options in braces after the FlatBuffer name. \
+/// Methods specific to a single FlatBuffer are generated as extension traits named `{FLATBUFFER_NAME}{FIELD}Ext`, as the wrappers are defined in this crate.
+///
+/// `create`: Implements the [CreateFlatBufferTrait], which provides an `Owned{FLATBUFFER_NAME}::create()` constructor
+/// taking the flatc generated `{FLATBUFFER_NAME}Args`. The `Args` struct must be in scope where the macro is invoked.
+/// ```
+/// flatbuffers_owned!(Login { create });
+///
+/// let owned_login = OwnedLogin::create(&LoginArgs { user_id: 42, ..Default::default() });
+/// ```
+///
+/// `object_api`: Requires flatc to be run with `--gen-object-api`. \
+/// Implements the [ObjectApiTrait], which provides an `unpack()` method returning the native `{FLATBUFFER_NAME}T` struct
+/// and an `Owned{FLATBUFFER_NAME}::pack()` constructor taking it.
+/// ```
+/// flatbuffers_owned!(MyFirstFlatBuffer { object_api }, MySecondFlatBuffer);
+///
+/// let native: MyFirstFlatBufferT = owned.unpack();
+/// let owned = OwnedMyFirstFlatBuffer::pack(&native);
+/// ```
+///
+/// `union: {FIELD} { {VARIANTS} }`: Generates a `{FLATBUFFER_NAME}{FIELD}` enum holding the typed tables of the listed union variants, \
+/// and a `{FLATBUFFER_NAME}{FIELD}Ext` trait with a `{FIELD}()` method, which resolves the union field to this enum. \
+/// The trait must be in scope to call the method.
+/// ```
+/// flatbuffers_owned!(Envelope { union: payload { Login, Logout } });
+///
+/// match owned_envelope.payload() {
+///     Some(EnvelopePayload::Login(login)) => { /* ... */ },
+///     Some(EnvelopePayload::Logout(logout)) => { /* ... */ },
+///     None => { /* ... */ },
+/// }
+/// ```
+///
+/// `nested: {FIELD} -> {NESTED_FLATBUFFER_NAME}`: For `[ubyte]` fields declared with the `nested_flatbuffer` attribute. \
+/// Generates a `{FLATBUFFER_NAME}{FIELD}Ext` trait with a `{FIELD}_owned()` method, which copies the nested bytes into a new `Owned{NESTED_FLATBUFFER_NAME}` and verifies them. \
+/// The nested FlatBuffer must be passed to the macro as well.
+/// ```
+/// flatbuffers_owned!(Message, Envelope { nested: payload -> Message });
+///
+/// let owned_message: Option<OwnedMessage> = owned_envelope.payload_owned()?;
+/// ```
+///
+/// `verifier: { {FIELD}: {VALUE}, ... }`: Overrides fields of the `VerifierOptions` the FlatBuffer is verified with by default, \
+/// e.g. by `new()` and `verify()`. The remaining fields keep their defaults.
+/// ```
+/// flatbuffers_owned!(Telemetry { verifier: { max_tables: 10_000_000, max_depth: 128 } });
+///
+/// let owned_telemetry = OwnedTelemetry::new(telemetry_bytes)?;
+/// ```
+///
+/// `cached`: Generates a `Cached{FLATBUFFER_NAME}<TBuffer = Box<[u8]>>` alias of the [Cached] wrapper, \
+/// which constructs the actual FlatBuffer once and exposes it by reference through `get()`.
+/// ```
+/// flatbuffers_owned!(Message { cached });
+///
+/// let cached_message = CachedMessage::new(OwnedMessage::new(message_bytes)?);
+/// let message: &Message = cached_message.get();
+/// ```
+///
+/// `newtype: {ATTRIBUTES} {NEWTYPE_NAME}`: Generates a `#[repr(transparent)]` tuple struct around the `Owned{FLATBUFFER_NAME}` type, \
+/// which de-references to it and converts from and into it. \
+/// The attributes are attached to the struct, so traits can be derived on it, conditionally with `cfg_attr`. \
+/// Unlike the type aliases, the struct is defined in the invoking crate, so foreign traits can be implemented for it as well.
+/// ```
+/// flatbuffers_owned!(Message { newtype: #[derive(Clone, Debug, PartialEq, Eq, Hash)] #[cfg_attr(feature = "ord", derive(PartialOrd, Ord))] MessageRecord });
+///
+/// let record = MessageRecord::from(owned_message);
+/// let text = record.as_actual().text();
+/// ```
+///
+/// `register: {FILE_IDENTIFIER}`: Requires the `registry` feature. \
+/// Registers the `Owned{FLATBUFFER_NAME}` type under the passed file identifier in the global [decode registry](registry).
+/// ```
+/// flatbuffers_owned!(Login { register: LOGIN_IDENTIFIER }, Logout { register: "LOUT" });
+///
+/// let decoded = flatbuffers_owned::registry::decode(message_bytes)?;
+/// ```
+///
+/// `sample: {CLOSURE}`: Generates a hidden `__examples_{flatbuffer_name}` module with a `sample()` function, \
+/// which builds and verifies an `Owned{FLATBUFFER_NAME}` from the passed closure like `build()` does. \
+/// The module also contains a `#[test]` verifying the sample, so every wrapped type gets exercised by the test suite of the invoking crate. \
+/// The macro must be invoked at module level for this option.
+/// ```
+/// flatbuffers_owned!(Message { sample: |builder| {
+///     let text = builder.create_string("Hello, world!");
+///     Message::create(builder, &MessageArgs { text: Some(text) })
+/// } });
+///
+/// // e.g. in a doctest of the invoking crate:
+/// let message = my_crate::__examples_message::sample()?;
+/// ```
+///
+/// # Compile-time checks
+/// The passed type must be a flatc generated table (or struct, if prefixed with `struct`), i.e. it must implement `flatbuffers::Follow` and `flatbuffers::Verifiable`. \
+/// Passing anything else fails to compile with an error naming the offending type and the missing requirement.
+#[macro_export]
+macro_rules! flatbuffers_owned {
+    (@wrapper [$($attrs:tt)*] $struct_name:ident, $flatbuffer:ty) => {
+        $crate::paste! {
+            $($attrs)*
+            pub type [<Relaxed $struct_name>]<TBuffer, P = $crate::DefaultPolicy> = $crate::OwnedFlatBuffer<$flatbuffer, TBuffer, P>;
+
+            $crate::__private::cfg_alloc! {
+                $($attrs)*
+                pub type [<Owned $struct_name>] = [<Relaxed $struct_name>]<$crate::__private::Box<[u8]>>;
+            }
+
+            $crate::__private::cfg_inline! {
+                $($attrs)*
+                pub type [<Inline $struct_name>] = [<Relaxed $struct_name>]<$crate::buffer::InlineBuffer>;
+            }
+
+            $crate::__private::cfg_zeroize! {
+                $($attrs)*
+                pub type [<Secret $struct_name>] = [<Relaxed $struct_name>]<$crate::buffer::SecretBuffer>;
+            }
+
+            $crate::__private::cfg_allocator_api2! {
+                $($attrs)*
+                pub type [<OwnedIn $struct_name>]<A> = [<Relaxed $struct_name>]<$crate::buffer::BoxIn<A>>;
+            }
+        }
+    };
+
+    (@table [$($cfg:tt)*] [$($attrs:tt)*] $struct_name:ident [$($options:tt)*]) => {
+        $($cfg)*
+        const _: fn() = || {
+            #[allow(unused_imports)]
+            use $crate::__private::ProbeTable as _;
+
+            let kind = $crate::__private::Probe::<$crate::__private::named_type!($struct_name)>($crate::__private::PhantomData).kind();
+            $crate::__private::assert_table::<$crate::__private::named_type!($struct_name), _>(kind);
+        };
+
+        $($cfg)*
+        impl $crate::RelaxedFollowTrait for $struct_name<'_> {
+            type Inner<'a> = $crate::__private::named_type!($struct_name, 'a);
+
+            $crate::flatbuffers_owned!(@default_options $($options)*);
+        }
+
+        $crate::flatbuffers_owned!(@wrapper [$($cfg)* $($attrs)*] $struct_name, $crate::__private::named_type!($struct_name));
+    };
+
+    (@struct [$($cfg:tt)*] [$($attrs:tt)*] $struct_name:ident) => {
+        $($cfg)*
+        const _: fn() = || { $crate::__private::assert_struct::<$crate::__private::named_type!($struct_name)>(); };
+
+        $($cfg)*
+        impl $crate::RelaxedFollowTrait for $struct_name {
+            type Inner<'a> = $struct_name;
+        }
+
+        $crate::flatbuffers_owned!(@wrapper [$($cfg)* $($attrs)*] $struct_name, $struct_name);
+
+        $($cfg)*
+        impl $crate::CreateFlatBufferTrait for $struct_name {
+            type Args = $struct_name;
+
+            fn create(builder: &mut flatbuffers::FlatBufferBuilder<'static>, args: &Self::Args) -> flatbuffers::WIPOffset<Self> {
+                builder.push(args)
+            }
+        }
+    };
+
+    (@vector [$($cfg:tt)*] [$($attrs:tt)*] $struct_name:ident as $alias:ident) => {
+        $crate::flatbuffers_owned!(@wrapper [$($cfg)* $($attrs)*] $alias, flatbuffers::Vector<'static, flatbuffers::ForwardsUOffset<$crate::__private::named_type!($struct_name)>>);
+    };
+
+    (@default_options) => {};
+
+    (@default_options verifier: { $($field:ident: $value:expr),* $(,)? } $($rest:tt)*) => {
+        fn default_options() -> flatbuffers::VerifierOptions {
+            flatbuffers::VerifierOptions { $($field: $value,)* ..Default::default() }
+        }
+    };
+
+    (@default_options $skip:tt $($rest:tt)*) => {
+        $crate::flatbuffers_owned!(@default_options $($rest)*);
+    };
+
+    (@options [$($cfg:tt)*] $struct_name:ident) => {};
+
+    (@options [$($cfg:tt)*] $struct_name:ident verifier: { $($verifier:tt)* } $(, $($rest:tt)*)?) => {
+        $crate::flatbuffers_owned!(@options [$($cfg)*] $struct_name $($($rest)*)?);
+    };
+
+    (@options [$($cfg:tt)*] $struct_name:ident create $(, $($rest:tt)*)?) => {
+        $crate::paste! {
+            $($cfg)*
+            impl $crate::CreateFlatBufferTrait for $struct_name<'static> {
+                type Args = $crate::__private::named_type!([<$struct_name Args>]);
+
+                fn create(builder: &mut flatbuffers::FlatBufferBuilder<'static>, args: &Self::Args) -> flatbuffers::WIPOffset<Self> {
+                    $struct_name::create(builder, args)
+                }
+            }
+        }
+
+        $crate::flatbuffers_owned!(@options [$($cfg)*] $struct_name $($($rest)*)?);
+    };
+
+    (@options [$($cfg:tt)*] $struct_name:ident object_api $(, $($rest:tt)*)?) => {
+        $crate::paste! {
+            $($cfg)*
+            impl $crate::ObjectApiTrait for $struct_name<'static> {
+                type Native = [<$struct_name T>];
+
+                fn unpack<'a>(actual: <<Self as $crate::RelaxedFollowTrait>::Inner<'a> as flatbuffers::Follow<'a>>::Inner) -> Self::Native {
+                    actual.unpack()
+                }
+
+                fn pack(builder: &mut flatbuffers::FlatBufferBuilder<'static>, native: &Self::Native) -> flatbuffers::WIPOffset<Self> {
+                    native.pack(builder)
+                }
+            }
+        }
+
+        $crate::flatbuffers_owned!(@options [$($cfg)*] $struct_name $($($rest)*)?);
+    };
+
+    (@options [$($cfg:tt)*] $struct_name:ident union: $field:ident { $($variant:ident),* $(,)? } $(, $($rest:tt)*)?) => {
+        $crate::paste! {
+            $($cfg)*
+            #[derive(Clone, Copy, Debug)]
+            pub enum [<$struct_name $field:camel>]<'a> {
+                $(
+                    $variant($variant<'a>),
+                )*
+            }
+
+            $($cfg)*
+            #[doc = concat!("Resolves the `", stringify!($field), "` union of the wrapped `", stringify!($struct_name), "` FlatBuffer.")]
+            pub trait [<$struct_name $field:camel Ext>] {
+                #[doc = concat!("Resolves the `", stringify!($field), "` union to its typed variant. Returns `None` if the union is not set or holds an unknown variant.")]
+                fn $field(&self) -> Option<[<$struct_name $field:camel>]<'_>>;
+            }
+
+            $($cfg)*
+            impl <TBuffer: AsRef<[u8]>, P: $crate::VerifierPolicy> [<$struct_name $field:camel Ext>] for [<Relaxed $struct_name>]<TBuffer, P> {
+                fn $field(&self) -> Option<[<$struct_name $field:camel>]<'_>> {
+                    let actual = self.as_actual();
+
+                    $(
+                        if let Some(variant) = actual.[<$field _as_ $variant:snake>]() {
+                            return Some([<$struct_name $field:camel>]::$variant(variant));
+                        }
+                    )*
+
+                    None
+                }
+            }
+        }
+
+        $crate::flatbuffers_owned!(@options [$($cfg)*] $struct_name $($($rest)*)?);
+    };
+
+    (@options [$($cfg:tt)*] $struct_name:ident nested: $field:ident -> $nested:ident $(, $($rest:tt)*)?) => {
+        $crate::__private::cfg_alloc! {
+            $crate::paste! {
+                $($cfg)*
+                #[doc = concat!("Extracts the `", stringify!($field), "` nested FlatBuffer of the wrapped `", stringify!($struct_name), "` FlatBuffer.")]
+                pub trait [<$struct_name $field:camel Ext>] {
+                    #[doc = concat!("Copies the `", stringify!($field), "` nested FlatBuffer into a new `Owned", stringify!($nested), "` and verifies it. Returns `Ok(None)` if the field is not set.")]
+                    fn [<$field _owned>](&self) -> Result<Option<[<Owned $nested>]>, $crate::VerifyError>;
+                }
+
+                $($cfg)*
+                impl <TBuffer: AsRef<[u8]>, P: $crate::VerifierPolicy> [<$struct_name $field:camel Ext>] for [<Relaxed $struct_name>]<TBuffer, P> {
+                    fn [<$field _owned>](&self) -> Result<Option<[<Owned $nested>]>, $crate::VerifyError> {
+                        match self.as_actual().$field() {
+                            Some(bytes) => [<Owned $nested>]::new(bytes.bytes().into()).map(Some),
+                            None => Ok(None),
+                        }
+                    }
+                }
+            }
+        }
+
+        $crate::flatbuffers_owned!(@options [$($cfg)*] $struct_name $($($rest)*)?);
+    };
+
+    (@options [$($cfg:tt)*] $struct_name:ident cached $(, $($rest:tt)*)?) => {
+        $($cfg)*
+        impl $crate::CovariantFollowTrait for $struct_name<'_> {
+            fn shorten<'a>(actual: &'a $struct_name<'static>) -> &'a $struct_name<'a> {
+                actual
+            }
+        }
+
+        $crate::paste! {
+            $crate::__private::cfg_alloc! {
+                {
+                    $($cfg)*
+                    pub type [<Cached $struct_name>]<TBuffer = $crate::__private::Box<[u8]>, P = $crate::DefaultPolicy> = $crate::Cached<[<Relaxed $struct_name>]<TBuffer, P>>;
+                } else {
+                    $($cfg)*
+                    pub type [<Cached $struct_name>]<TBuffer, P = $crate::DefaultPolicy> = $crate::Cached<[<Relaxed $struct_name>]<TBuffer, P>>;
+                }
+            }
+        }
+
+        $crate::flatbuffers_owned!(@options [$($cfg)*] $struct_name $($($rest)*)?);
+    };
+
+    (@options [$($cfg:tt)*] $struct_name:ident newtype: $(#[$attr:meta])* $newtype:ident $(, $($rest:tt)*)?) => {
+        $crate::__private::cfg_alloc! {
+            $crate::paste! {
+                $($cfg)*
+                $(#[$attr])*
+                #[repr(transparent)]
+                pub struct $newtype(pub [<Owned $struct_name>]);
+
+                $($cfg)*
+                impl $crate::__private::Deref for $newtype {
+                    type Target = [<Owned $struct_name>];
+
+                    fn deref(&self) -> &Self::Target {
+                        &self.0
+                    }
+                }
+
+                $($cfg)*
+                impl From<[<Owned $struct_name>]> for $newtype {
+                    fn from(owned: [<Owned $struct_name>]) -> Self {
+                        $newtype(owned)
+                    }
+                }
+
+                $($cfg)*
+                impl From<$newtype> for [<Owned $struct_name>] {
+                    fn from(newtype: $newtype) -> Self {
+                        newtype.0
+                    }
+                }
+            }
+        }
+
+        $crate::flatbuffers_owned!(@options [$($cfg)*] $struct_name $($($rest)*)?);
+    };
+
+    (@options [$($cfg:tt)*] $struct_name:ident register: $identifier:expr $(, $($rest:tt)*)?) => {
+        $crate::paste! {
+            $($cfg)*
+            const _: () = {
+                $crate::__private::inventory::submit! {
+                    $crate::registry::Registration::new::<[<Owned $struct_name>]>(stringify!($struct_name), $identifier)
+                }
+            };
+        }
+
+        $crate::flatbuffers_owned!(@options [$($cfg)*] $struct_name $($($rest)*)?);
+    };
+
+    (@options [$($cfg:tt)*] $struct_name:ident sample: $sample:expr $(, $($rest:tt)*)?) => {
+        $crate::__private::cfg_alloc! {
+            $crate::paste! {
+                $($cfg)*
+                #[doc(hidden)]
+                pub mod [<__examples_ $struct_name:snake>] {
+                    use super::*;
+
+                    /// Builds and verifies the sample FlatBuffer passed to the `sample` option.
+                    pub fn sample() -> Result<[<Owned $struct_name>], $crate::VerifyError> {
+                        [<Owned $struct_name>]::build($sample)
+                    }
+
+                    #[cfg(test)]
+                    #[test]
+                    fn sample_round_trip() -> Result<(), $crate::VerifyError> {
+                        let sample = sample()?;
+                        let copied = [<Owned $struct_name>]::new(sample.to_vec().into())?;
+
+                        assert_eq!(copied, sample);
+                        Ok(())
+                    }
+                }
+            }
+        }
+
+        $crate::flatbuffers_owned!(@options [$($cfg)*] $struct_name $($($rest)*)?);
+    };
+
+    (@split [$($cfg:tt)*] [$($attrs:tt)*] [#[cfg $($predicate:tt)*] $($rest:tt)*] $($item:tt)*) => {
+        $crate::flatbuffers_owned!(@split [$($cfg)* #[cfg $($predicate)*]] [$($attrs)*] [$($rest)*] $($item)*);
+    };
+
+    (@split [$($cfg:tt)*] [$($attrs:tt)*] [#[cfg_attr $($predicate:tt)*] $($rest:tt)*] $($item:tt)*) => {
+        $crate::flatbuffers_owned!(@split [$($cfg)* #[cfg_attr $($predicate)*]] [$($attrs)*] [$($rest)*] $($item)*);
+    };
+
+    (@split [$($cfg:tt)*] [$($attrs:tt)*] [#[$($attr:tt)*] $($rest:tt)*] $($item:tt)*) => {
+        $crate::flatbuffers_owned!(@split [$($cfg)*] [$($attrs)* #[$($attr)*]] [$($rest)*] $($item)*);
+    };
+
+    (@split [$($cfg:tt)*] [$($attrs:tt)*] [] table $struct_name:ident [$($options:tt)*]) => {
+        $crate::flatbuffers_owned!(@table [$($cfg)*] [$($attrs)*] $struct_name [$($options)*]);
+        $crate::flatbuffers_owned!(@options [$($cfg)*] $struct_name $($options)*);
+    };
+
+    (@split [$($cfg:tt)*] [$($attrs:tt)*] [] $kind:ident $($item:tt)*) => {
+        $crate::flatbuffers_owned!(@$kind [$($cfg)*] [$($attrs)*] $($item)*);
+    };
+
+    (@list) => {};
+
+    (@list $(#[$($attr:tt)*])* Vector<$struct_name:ident> as $alias:ident $(, $($rest:tt)*)?) => {
+        $crate::flatbuffers_owned!(@split [] [] [$(#[$($attr)*])*] vector $struct_name as $alias);
+        $crate::flatbuffers_owned!(@list $($($rest)*)?);
+    };
+
+    (@list $(#[$($attr:tt)*])* struct $struct_name:ident $(, $($rest:tt)*)?) => {
+        $crate::flatbuffers_owned!(@split [] [] [$(#[$($attr)*])*] struct $struct_name);
+        $crate::flatbuffers_owned!(@list $($($rest)*)?);
+    };
+
+    (@list $(#[$($attr:tt)*])* $struct_name:ident $({ $($options:tt)* })? $(, $($rest:tt)*)?) => {
+        $crate::flatbuffers_owned!(@split [] [] [$(#[$($attr)*])*] table $struct_name [$($($options)*)?]);
+        $crate::flatbuffers_owned!(@list $($($rest)*)?);
+    };
+
+    ($($input:tt)*) => {
+        $crate::flatbuffers_owned!(@list $($input)*);
+    };
+}
+
+/// Generates an enum over multiple wrapped FlatBuffers, which are told apart by their file identifiers.
+///
+/// Each variant holds the `Owned{FLATBUFFER_NAME}` type of a FlatBuffer, so the [flatbuffers_owned!](flatbuffers_owned) macro must be invoked for every listed FlatBuffer first. \
+/// The generated `decode()` constructor inspects the file identifier of the passed bytes, verifies them as the matching FlatBuffer and returns the variant. \
+/// The enum de-references to the underlying [u8] byte slice.
+///
+/// # Usage
+/// ```
+/// use flatbuffers_owned::{flatbuffers_owned, flatbuffers_owned_enum};
+///
+/// flatbuffers_owned!(Login, Logout);
+///
+/// flatbuffers_owned_enum!(AnyMessage {
+///     Login: LOGIN_IDENTIFIER,
+///     Logout: "LOUT",
+/// });
+///
+/// match AnyMessage::decode(message_bytes)? {
+///     AnyMessage::Login(login) => { /* ... */ },
+///     AnyMessage::Logout(logout) => { /* ... */ },
+/// }
+/// ```
+#[macro_export]
+macro_rules! flatbuffers_owned_enum {
+    ($enum_name:ident { $($struct_name:ident: $identifier:expr),* $(,)? }) => {
+        $crate::paste! {
+            #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+            pub enum $enum_name {
+                $(
+                    $struct_name([<Owned $struct_name>]),
+                )*
+            }
+
+            impl $enum_name {
+                /// Inspects the file identifier and verifies the bytes as the matching FlatBuffer.
+                pub fn decode(data: $crate::__private::Box<[u8]>) -> Result<Self, $crate::DecodeError> {
+                    $(
+                        if $crate::__private::has_identifier(&data, $identifier) {
+                            let owned = <[<Owned $struct_name>] as $crate::RelaxedFlatBufferTrait>::new(data)?;
+
+                            return Ok($enum_name::$struct_name(owned));
+                        }
+                    )*
+
+                    Err($crate::DecodeError::UnknownIdentifier)
+                }
+            }
+
+            impl $crate::__private::Deref for $enum_name {
+                type Target = [u8];
+
+                fn deref(&self) -> &Self::Target {
+                    match self {
+                        $(
+                            $enum_name::$struct_name(owned) => owned,
+                        )*
+                    }
+                }
+            }
+        }
+    };
+}
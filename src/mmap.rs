@@ -0,0 +1,35 @@
+//! Memory-mapped wrappers.
+//!
+//! Requires the `mmap` feature.
+
+use std::fs::File;
+use std::path::Path;
+use flatbuffers::Verifiable;
+use memmap2::Mmap;
+use crate::buffer::StableBuffer;
+use crate::{FileError, OwnedFlatBuffer, RelaxedFlatBufferTrait, RelaxedFollowTrait, VerifierPolicy};
+
+impl<T: RelaxedFollowTrait + Verifiable, P: VerifierPolicy> OwnedFlatBuffer<T, Box<[u8]>, P> {
+    /// Memory-maps the file read-only and verifies the FlatBuffer in place, without copying it into memory.
+    ///
+    /// The returned wrapper implements the [StableRelaxedFlatBufferTrait](crate::StableRelaxedFlatBufferTrait),
+    /// so it can be used with the [Cached](crate::Cached) wrapper.
+    ///
+    /// # Example
+    /// ```
+    /// let cache: RelaxedMessage<Mmap> = unsafe { OwnedMessage::from_file_mmap("cache.bin")? };
+    /// ```
+    ///
+    /// # Safety
+    /// The file must not be modified, e.g. by another process, while it is mapped.
+    /// Otherwise the verified bytes could change underneath the wrapper.
+    pub unsafe fn from_file_mmap(path: impl AsRef<Path>) -> Result<OwnedFlatBuffer<T, Mmap, P>, FileError> {
+        let file = File::open(path)?;
+        let mmap = Mmap::map(&file)?;
+
+        Ok(<OwnedFlatBuffer<T, Mmap, P> as RelaxedFlatBufferTrait>::new(mmap)?)
+    }
+}
+
+// The bytes live in the mapping, which does not move with the `Mmap`.
+unsafe impl StableBuffer for Mmap {}
@@ -0,0 +1,296 @@
+//! Buffer and builder pools for recycling allocations.
+//!
+//! Services receiving many messages per second spend a significant share of their time allocating and freeing a buffer per message.
+//! The [BufferPool] hands out [PooledBuffer]s instead, which return their allocation to the pool when they are dropped,
+//! e.g. together with the wrapper holding them.
+//!
+//! Services sending many messages likewise allocate a new `FlatBufferBuilder` per message.
+//! The [BuilderPool] lends out builders and takes back their grown internal buffers for the next message. \
+//! Every thread has a default builder pool, used by [build_local()](crate::OwnedFlatBuffer::build_local).
+//!
+//! Requires the `pool` feature.
+//!
+//! # Example
+//! ```
+//! use flatbuffers_owned::pool::BufferPool;
+//!
+//! let pool = BufferPool::new(64);
+//!
+//! loop {
+//!     let message = OwnedMessage::new_pooled(&pool, receive_message_bytes())?;
+//!     // ... process message, its buffer returns to the pool on drop
+//! }
+//!
+//! let message = OwnedMessage::build_local(|builder| {
+//!     let text = builder.create_string("Hello, world!");
+//!     Message::create(builder, &MessageArgs { text: Some(text) })
+//! })?;
+//! ```
+
+use std::fmt::{Debug, Formatter};
+use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, Mutex, MutexGuard, Weak};
+use flatbuffers::{FlatBufferBuilder, Verifiable, WIPOffset};
+use stable_deref_trait::StableDeref;
+use crate::buffer::StableBuffer;
+use crate::{OwnedFlatBuffer, RelaxedFlatBufferTrait, RelaxedFollowTrait, VerifierPolicy, VerifyError};
+
+struct Shared {
+    capacity: usize,
+    buffers: Mutex<Vec<Vec<u8>>>,
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, Vec<Vec<u8>>> {
+        // The idle buffers stay consistent even if a panic poisoned the lock.
+        self.buffers.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn recycle(&self, mut buffer: Vec<u8>) {
+        let mut buffers = self.lock();
+
+        if buffers.len() < self.capacity {
+            buffer.clear();
+            buffers.push(buffer);
+        }
+    }
+}
+
+/// Hands out [PooledBuffer]s and takes their allocations back when they are dropped.
+///
+/// The pool is `Send` and `Sync`, and cloning it only increments a reference count. \
+/// Buffers may outlive the pool, their allocation is then freed on drop.
+#[derive(Clone)]
+pub struct BufferPool {
+    shared: Arc<Shared>,
+}
+
+impl BufferPool {
+    /// Creates a pool retaining up to `capacity` idle buffers.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            shared: Arc::new(Shared {
+                capacity,
+                buffers: Mutex::new(Vec::new()),
+            }),
+        }
+    }
+
+    /// Returns a zeroed buffer of the passed length, e.g. for reading a message into it through `AsMut<[u8]>`.
+    pub fn acquire(&self, len: usize) -> PooledBuffer {
+        let mut buffer = self.take();
+        buffer.resize(len, 0);
+
+        self.wrap(buffer)
+    }
+
+    /// Returns a buffer holding a copy of the bytes.
+    pub fn copy_from_slice(&self, bytes: &[u8]) -> PooledBuffer {
+        let mut buffer = self.take();
+        buffer.extend_from_slice(bytes);
+
+        self.wrap(buffer)
+    }
+
+    /// Returns the number of idle buffers.
+    pub fn len(&self) -> usize {
+        self.shared.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Frees all idle buffers.
+    pub fn clear(&self) {
+        self.shared.lock().clear();
+    }
+
+    fn take(&self) -> Vec<u8> {
+        self.shared.lock().pop().unwrap_or_default()
+    }
+
+    fn wrap(&self, buffer: Vec<u8>) -> PooledBuffer {
+        PooledBuffer { buffer, pool: Arc::downgrade(&self.shared) }
+    }
+}
+
+impl Debug for BufferPool {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BufferPool")
+            .field("capacity", &self.shared.capacity)
+            .field("idle", &self.len())
+            .finish()
+    }
+}
+
+/// A buffer handed out by a [BufferPool], returning its allocation to the pool when it is dropped.
+pub struct PooledBuffer {
+    buffer: Vec<u8>,
+    pool: Weak<Shared>,
+}
+
+impl Deref for PooledBuffer {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.buffer
+    }
+}
+
+impl DerefMut for PooledBuffer {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        &mut self.buffer
+    }
+}
+
+impl AsRef<[u8]> for PooledBuffer {
+    fn as_ref(&self) -> &[u8] {
+        &self.buffer
+    }
+}
+
+impl AsMut<[u8]> for PooledBuffer {
+    fn as_mut(&mut self) -> &mut [u8] {
+        &mut self.buffer
+    }
+}
+
+// The bytes live in the heap allocation of the `Vec`, which does not move with the buffer.
+unsafe impl StableDeref for PooledBuffer {}
+unsafe impl StableBuffer for PooledBuffer {}
+
+impl Drop for PooledBuffer {
+    fn drop(&mut self) {
+        if let Some(pool) = self.pool.upgrade() {
+            pool.recycle(std::mem::take(&mut self.buffer));
+        }
+    }
+}
+
+impl Debug for PooledBuffer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("PooledBuffer").field(&self.buffer).finish()
+    }
+}
+
+/// The default number of builders a [BuilderPool] retains.
+pub const DEFAULT_MAX_BUILDERS: usize = 8;
+
+/// The default size up to which a [BuilderPool] retains the buffer of a builder, 1 MiB.
+pub const DEFAULT_MAX_RETAINED_BYTES: usize = 1024 * 1024;
+
+thread_local! {
+    static LOCAL_BUILDERS: BuilderPool = BuilderPool::default();
+}
+
+/// Lends out `FlatBufferBuilder`s and takes back their internal buffers once they are done.
+///
+/// A returned builder is reset and only retained if its buffer did not grow beyond the `max_retained_bytes`,
+/// so a single huge message does not pin its memory for the lifetime of the pool. \
+/// The pool is `Send` and `Sync`, the lock is only held while taking and returning a buffer, never while building.
+#[derive(Debug)]
+pub struct BuilderPool {
+    max_builders: usize,
+    max_retained_bytes: usize,
+    buffers: Mutex<Vec<Vec<u8>>>,
+}
+
+impl BuilderPool {
+    /// Creates a pool retaining up to `max_builders` builder buffers of up to `max_retained_bytes` each.
+    pub fn new(max_builders: usize, max_retained_bytes: usize) -> Self {
+        Self {
+            max_builders,
+            max_retained_bytes,
+            buffers: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Calls the closure with the default builder pool of the current thread.
+    pub fn with_local<R>(f: impl FnOnce(&BuilderPool) -> R) -> R {
+        LOCAL_BUILDERS.with(f)
+    }
+
+    /// Lends a builder to the closure and takes its buffer back afterwards.
+    ///
+    /// The builder is fresh or reset, and must not be kept beyond the closure. \
+    /// Builders are lent out independently, so the closure may borrow further builders, e.g. for nested FlatBuffers.
+    pub fn with_builder<R>(&self, f: impl FnOnce(&mut FlatBufferBuilder<'static>) -> R) -> R {
+        let mut builder = match self.lock().pop() {
+            Some(buffer) => FlatBufferBuilder::from_vec(buffer),
+            None => FlatBufferBuilder::new(),
+        };
+
+        let result = f(&mut builder);
+
+        // Clears the dirty part of the buffer, which the builder expects to be zeroed.
+        builder.reset();
+        let (buffer, _) = builder.collapse();
+
+        if buffer.len() <= self.max_retained_bytes {
+            let mut buffers = self.lock();
+
+            if buffers.len() < self.max_builders {
+                buffers.push(buffer);
+            }
+        }
+
+        result
+    }
+
+    /// Returns the number of idle builder buffers.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Frees all idle builder buffers.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<Vec<u8>>> {
+        // The idle buffers stay consistent even if a panic poisoned the lock.
+        self.buffers.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Default for BuilderPool {
+    /// Creates a pool retaining up to [DEFAULT_MAX_BUILDERS] builder buffers of up to [DEFAULT_MAX_RETAINED_BYTES] each.
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_BUILDERS, DEFAULT_MAX_RETAINED_BYTES)
+    }
+}
+
+impl<T: RelaxedFollowTrait + Verifiable, P: VerifierPolicy> OwnedFlatBuffer<T, Box<[u8]>, P> {
+    /// Copies the bytes into a buffer of the pool and verifies it according to the policy `P`.
+    ///
+    /// The buffer returns to the pool when the wrapper is dropped, or right away if the verification fails.
+    pub fn new_pooled(pool: &BufferPool, bytes: &[u8]) -> Result<OwnedFlatBuffer<T, PooledBuffer, P>, VerifyError> {
+        <OwnedFlatBuffer<T, PooledBuffer, P> as RelaxedFlatBufferTrait>::new(pool.copy_from_slice(bytes))
+    }
+
+    /// Builds a new owned FlatBuffer like [build()](OwnedFlatBuffer::build), but with a builder lent out by the pool.
+    ///
+    /// The finished data is copied into an exactly sized `Box<[u8]>`, while the builder buffer returns to the pool.
+    pub fn build_pooled<F>(pool: &BuilderPool, f: F) -> Result<Self, VerifyError>
+        where F: FnOnce(&mut FlatBufferBuilder<'static>) -> WIPOffset<T>
+    {
+        pool.with_builder(|builder| {
+            let offset = f(builder);
+            builder.finish(offset, None);
+
+            <Self as RelaxedFlatBufferTrait>::new(builder.finished_data().into())
+        })
+    }
+
+    /// Builds a new owned FlatBuffer like [build_pooled()](OwnedFlatBuffer::build_pooled), with the default builder pool of the current thread.
+    pub fn build_local<F>(f: F) -> Result<Self, VerifyError>
+        where F: FnOnce(&mut FlatBufferBuilder<'static>) -> WIPOffset<T>
+    {
+        BuilderPool::with_local(|pool| Self::build_pooled(pool, f))
+    }
+}
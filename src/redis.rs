@@ -0,0 +1,58 @@
+//! redis `ToRedisArgs` and `FromRedisValue` implementations, storing FlatBuffers as Redis strings.
+//!
+//! Wrappers are written as their raw bytes. \
+//! `Owned{FLATBUFFER_NAME}` wrappers are read from bulk string replies and verified before they are returned,
+//! a failed verification is reported as a `TypeError` carrying the [VerifyError](crate::VerifyError) message. \
+//! A nil reply, e.g. for a missing key, is reported as a separate `TypeError`. Read keys that may be missing as `Option<Owned{FLATBUFFER_NAME}>`.
+//!
+//! Requires the `redis` feature.
+//!
+//! # Example
+//! ```
+//! use redis::Commands;
+//!
+//! connection.set(key, &owned_message)?;
+//!
+//! let message: OwnedMessage = connection.get(key)?;
+//! let maybe_message: Option<OwnedMessage> = connection.get(maybe_missing_key)?;
+//! ```
+
+use flatbuffers::Verifiable;
+use redis::{ErrorKind, FromRedisValue, RedisError, RedisResult, RedisWrite, ToRedisArgs, Value};
+use crate::{OwnedFlatBuffer, RelaxedFlatBufferTrait, RelaxedFollowTrait, VerifierPolicy, VerifyError};
+
+/// Writes the bytes as a single argument.
+impl<T: RelaxedFollowTrait + Verifiable, B: AsRef<[u8]>, P: VerifierPolicy> ToRedisArgs for OwnedFlatBuffer<T, B, P> {
+    fn write_redis_args<W: ?Sized + RedisWrite>(&self, out: &mut W) {
+        out.write_arg(self.as_ref());
+    }
+}
+
+/// Reads the reply as bytes and verifies them according to the policy `P`. \
+/// Fails on a nil reply, which `Option<Self>` reads as `None` instead.
+impl<T: RelaxedFollowTrait + Verifiable, P: VerifierPolicy> FromRedisValue for OwnedFlatBuffer<T, Box<[u8]>, P> {
+    fn from_redis_value(v: &Value) -> RedisResult<Self> {
+        if let Value::Nil = v {
+            return Err(nil_reply());
+        }
+
+        verify(Vec::from_redis_value(v)?)
+    }
+
+    fn from_owned_redis_value(v: Value) -> RedisResult<Self> {
+        if let Value::Nil = v {
+            return Err(nil_reply());
+        }
+
+        verify(Vec::from_owned_redis_value(v)?)
+    }
+}
+
+fn nil_reply() -> RedisError {
+    RedisError::from((ErrorKind::TypeError, "Nil reply instead of a FlatBuffer", "read keys that may be missing as `Option<Owned{FLATBUFFER_NAME}>`".to_string()))
+}
+
+fn verify<T: RelaxedFollowTrait + Verifiable, P: VerifierPolicy>(data: Vec<u8>) -> RedisResult<OwnedFlatBuffer<T, Box<[u8]>, P>> {
+    <OwnedFlatBuffer<T, Box<[u8]>, P> as RelaxedFlatBufferTrait>::new(data.into_boxed_slice())
+        .map_err(|e: VerifyError| RedisError::from((ErrorKind::TypeError, "Invalid FlatBuffer", e.to_string())))
+}
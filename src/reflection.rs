@@ -0,0 +1,594 @@
+//! Binary FlatBuffers schemas loaded at runtime.
+//!
+//! `flatc --binary --schema` compiles a `.fbs` schema into a `.bfbs` file, itself a FlatBuffer of flatc's `reflection.fbs`. \
+//! [Schema::from_bfbs()] verifies such a file and parses it into an owned [Schema] of its tables, structs, fields and enums,
+//! the foundation for schema-driven processing of FlatBuffers whose types are only known at runtime.
+//!
+//! Requires the `reflection` feature.
+//!
+//! # Example
+//! ```
+//! use flatbuffers_owned::reflection::Schema;
+//!
+//! let schema = Schema::from_bfbs(&std::fs::read("message.bfbs")?)?;
+//!
+//! for field in schema.root_table().unwrap().fields_by_id() {
+//!     println!("{}: {:?}", field.name, field.ty.base_type);
+//! }
+//! ```
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use flatbuffers::{Follow, ForwardsUOffset, Vector, VerifierOptions};
+use crate::{__private, Constructor, DecodeError};
+
+pub mod compatibility;
+pub mod dynamic;
+#[cfg(feature = "json")]
+pub mod json;
+
+/// The file identifier of binary schemas.
+pub const BFBS_IDENTIFIER: &str = "BFBS";
+
+/// The type of a field, an element of a vector or array, or the underlying type of an enum.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum BaseType {
+    None,
+    /// The type field of a union.
+    UType,
+    Bool,
+    Byte,
+    UByte,
+    Short,
+    UShort,
+    Int,
+    UInt,
+    Long,
+    ULong,
+    Float,
+    Double,
+    String,
+    Vector,
+    /// A table or struct, see [Type::index].
+    Obj,
+    Union,
+    /// A fixed length array within a struct.
+    Array,
+    Vector64,
+    /// A type added to `reflection.fbs` after this version of the crate.
+    Unknown(i8),
+}
+
+impl BaseType {
+    fn from_raw(raw: i8) -> Self {
+        match raw {
+            0 => BaseType::None,
+            1 => BaseType::UType,
+            2 => BaseType::Bool,
+            3 => BaseType::Byte,
+            4 => BaseType::UByte,
+            5 => BaseType::Short,
+            6 => BaseType::UShort,
+            7 => BaseType::Int,
+            8 => BaseType::UInt,
+            9 => BaseType::Long,
+            10 => BaseType::ULong,
+            11 => BaseType::Float,
+            12 => BaseType::Double,
+            13 => BaseType::String,
+            14 => BaseType::Vector,
+            15 => BaseType::Obj,
+            16 => BaseType::Union,
+            17 => BaseType::Array,
+            18 => BaseType::Vector64,
+            raw => BaseType::Unknown(raw),
+        }
+    }
+
+    /// Returns whether the type is a boolean, integer or floating point scalar.
+    pub fn is_scalar(self) -> bool {
+        matches!(self, BaseType::UType | BaseType::Bool | BaseType::Byte | BaseType::UByte | BaseType::Short | BaseType::UShort
+            | BaseType::Int | BaseType::UInt | BaseType::Long | BaseType::ULong | BaseType::Float | BaseType::Double)
+    }
+
+    /// Returns whether the type is an integer scalar, including booleans and union types.
+    pub fn is_integer(self) -> bool {
+        self.is_scalar() && !matches!(self, BaseType::Float | BaseType::Double)
+    }
+
+    /// Returns the size of a scalar in bytes, or `None` for all other types.
+    pub fn scalar_size(self) -> Option<usize> {
+        match self {
+            BaseType::UType | BaseType::Bool | BaseType::Byte | BaseType::UByte => Some(1),
+            BaseType::Short | BaseType::UShort => Some(2),
+            BaseType::Int | BaseType::UInt | BaseType::Float => Some(4),
+            BaseType::Long | BaseType::ULong | BaseType::Double => Some(8),
+            _ => None,
+        }
+    }
+}
+
+/// The full type of a field.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Type {
+    pub base_type: BaseType,
+    /// The element type of vectors and arrays, [BaseType::None] otherwise.
+    pub element: BaseType,
+    /// The position of the object in [Schema::objects] for tables and structs,
+    /// of the enum in [Schema::enums] for enums and unions, `None` for all other types.
+    pub index: Option<usize>,
+    /// The length of a fixed length array.
+    pub fixed_length: u16,
+    /// The size of the type in bytes, e.g. 4 for the offset of a table.
+    pub base_size: u32,
+    /// The size of an element of a vector or array in bytes.
+    pub element_size: u32,
+}
+
+impl Default for Type {
+    /// The defaults of `reflection.fbs`.
+    fn default() -> Self {
+        Self { base_type: BaseType::None, element: BaseType::None, index: None, fixed_length: 0, base_size: 4, element_size: 0 }
+    }
+}
+
+/// A user-defined attribute, like `(priority: 1)`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct KeyValue {
+    pub key: String,
+    pub value: Option<String>,
+}
+
+/// A field of a table or struct.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Field {
+    pub name: String,
+    pub ty: Type,
+    /// The field id, which determines the position of the field in the vtable of a table.
+    pub id: u16,
+    /// The offset of the field within the vtable of a table or within a struct.
+    pub offset: u16,
+    pub default_integer: i64,
+    pub default_real: f64,
+    pub deprecated: bool,
+    pub required: bool,
+    pub key: bool,
+    /// Whether the scalar field has no default, declared with `= null`.
+    pub optional: bool,
+    /// The padding behind the field within a struct.
+    pub padding: u16,
+    pub attributes: Vec<KeyValue>,
+    pub documentation: Vec<String>,
+}
+
+/// A table or struct.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Object {
+    /// The fully qualified name, e.g. `my_game.Monster`.
+    pub name: String,
+    /// The fields, sorted by name.
+    pub fields: Vec<Field>,
+    pub is_struct: bool,
+    pub minalign: i32,
+    /// The size of a struct in bytes.
+    pub bytesize: i32,
+    pub attributes: Vec<KeyValue>,
+    pub documentation: Vec<String>,
+}
+
+impl Object {
+    /// Returns the field with the name.
+    pub fn field(&self, name: &str) -> Option<&Field> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+
+    /// Returns the fields in the order of their ids, the order they are declared in.
+    pub fn fields_by_id(&self) -> Vec<&Field> {
+        let mut fields = self.fields.iter().collect::<Vec<_>>();
+        fields.sort_by_key(|field| field.id);
+
+        fields
+    }
+}
+
+/// A value of an enum or a member of a union.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EnumVal {
+    pub name: String,
+    pub value: i64,
+    /// The type of a union member.
+    pub union_type: Option<Type>,
+    pub attributes: Vec<KeyValue>,
+    pub documentation: Vec<String>,
+}
+
+/// An enum or union.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Enum {
+    /// The fully qualified name, e.g. `my_game.Color`.
+    pub name: String,
+    /// The values, sorted by value.
+    pub values: Vec<EnumVal>,
+    pub is_union: bool,
+    pub underlying_type: Type,
+    pub attributes: Vec<KeyValue>,
+    pub documentation: Vec<String>,
+}
+
+impl Enum {
+    /// Returns the value with the name.
+    pub fn value_by_name(&self, name: &str) -> Option<&EnumVal> {
+        self.values.iter().find(|value| value.name == name)
+    }
+
+    /// Returns the first value with the numeric value.
+    pub fn value_of(&self, value: i64) -> Option<&EnumVal> {
+        self.values.iter().find(|enum_val| enum_val.value == value)
+    }
+}
+
+/// A schema loaded from a `.bfbs` file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Schema {
+    /// The tables and structs, sorted by name.
+    pub objects: Vec<Object>,
+    /// The enums and unions, sorted by name.
+    pub enums: Vec<Enum>,
+    pub file_ident: Option<String>,
+    pub file_ext: Option<String>,
+    /// The position of the root type in [objects](Schema::objects).
+    pub root_table: Option<usize>,
+}
+
+impl Schema {
+    /// Verifies and parses a binary schema.
+    ///
+    /// Fails with [DecodeError::UnknownIdentifier] if the data does not carry the [BFBS_IDENTIFIER].
+    pub fn from_bfbs(data: &[u8]) -> Result<Self, DecodeError> {
+        if !__private::has_identifier(data, BFBS_IDENTIFIER) {
+            return Err(DecodeError::UnknownIdentifier);
+        }
+
+        __private::verify_root::<fbs::Schema>(data, 0, &VerifierOptions::default(), Constructor::FromBfbs)?;
+
+        // Safety:
+        // The data was verified as a reflection schema above.
+        let schema = unsafe { <ForwardsUOffset<fbs::Schema>>::follow(data, 0) };
+
+        let objects = schema.objects().iter().map(parse_object).collect::<Vec<_>>();
+        let root_table = schema.root_table()
+            .and_then(|root| objects.iter().position(|object| object.name == root.name()));
+
+        Ok(Self {
+            enums: schema.enums().iter().map(parse_enum).collect(),
+            objects,
+            file_ident: schema.file_ident().map(str::to_string),
+            file_ext: schema.file_ext().map(str::to_string),
+            root_table,
+        })
+    }
+
+    /// Returns the table or struct with the fully qualified name.
+    pub fn object(&self, name: &str) -> Option<&Object> {
+        self.objects.iter().find(|object| object.name == name)
+    }
+
+    /// Returns the enum or union with the fully qualified name.
+    pub fn enum_by_name(&self, name: &str) -> Option<&Enum> {
+        self.enums.iter().find(|enum_def| enum_def.name == name)
+    }
+
+    /// Returns the root type of the schema.
+    pub fn root_table(&self) -> Option<&Object> {
+        self.objects.get(self.root_table?)
+    }
+
+    /// Returns the table or struct of a field type of [BaseType::Obj], or of a vector or array of them.
+    pub fn object_of(&self, ty: &Type) -> Option<&Object> {
+        match (ty.base_type, ty.element) {
+            (BaseType::Obj, _) | (BaseType::Vector | BaseType::Vector64 | BaseType::Array, BaseType::Obj) => self.objects.get(ty.index?),
+            _ => None,
+        }
+    }
+
+    /// Returns the enum of an enum typed field, or the union of a union or union type field.
+    pub fn enum_of(&self, ty: &Type) -> Option<&Enum> {
+        match (ty.base_type, ty.element) {
+            (BaseType::Obj, _) | (BaseType::Vector | BaseType::Vector64 | BaseType::Array, BaseType::Obj) => None,
+            _ => self.enums.get(ty.index?),
+        }
+    }
+}
+
+fn parse_type(ty: fbs::Type<'_>) -> Type {
+    Type {
+        base_type: BaseType::from_raw(ty.base_type()),
+        element: BaseType::from_raw(ty.element()),
+        index: usize::try_from(ty.index()).ok(),
+        fixed_length: ty.fixed_length(),
+        base_size: ty.base_size(),
+        element_size: ty.element_size(),
+    }
+}
+
+fn parse_attributes(attributes: Option<Vector<'_, ForwardsUOffset<fbs::KeyValue<'_>>>>) -> Vec<KeyValue> {
+    attributes.iter().flatten()
+        .map(|attribute| KeyValue { key: attribute.key().to_string(), value: attribute.value().map(str::to_string) })
+        .collect()
+}
+
+fn parse_documentation(documentation: Option<Vector<'_, ForwardsUOffset<&str>>>) -> Vec<String> {
+    documentation.iter().flatten().map(str::to_string).collect()
+}
+
+fn parse_field(field: fbs::Field<'_>) -> Field {
+    Field {
+        name: field.name().to_string(),
+        ty: field.type_().map(parse_type).unwrap_or_default(),
+        id: field.id(),
+        offset: field.offset(),
+        default_integer: field.default_integer(),
+        default_real: field.default_real(),
+        deprecated: field.deprecated(),
+        required: field.required(),
+        key: field.key(),
+        optional: field.optional(),
+        padding: field.padding(),
+        attributes: parse_attributes(field.attributes()),
+        documentation: parse_documentation(field.documentation()),
+    }
+}
+
+fn parse_object(object: fbs::Object<'_>) -> Object {
+    Object {
+        name: object.name().to_string(),
+        fields: object.fields().iter().map(parse_field).collect(),
+        is_struct: object.is_struct(),
+        minalign: object.minalign(),
+        bytesize: object.bytesize(),
+        attributes: parse_attributes(object.attributes()),
+        documentation: parse_documentation(object.documentation()),
+    }
+}
+
+fn parse_enum(enum_def: fbs::Enum<'_>) -> Enum {
+    Enum {
+        name: enum_def.name().to_string(),
+        values: enum_def.values().iter()
+            .map(|value| EnumVal {
+                name: value.name().to_string(),
+                value: value.value(),
+                union_type: value.union_type().map(parse_type),
+                attributes: parse_attributes(value.attributes()),
+                documentation: parse_documentation(value.documentation()),
+            })
+            .collect(),
+        is_union: enum_def.is_union(),
+        underlying_type: enum_def.underlying_type().map(parse_type).unwrap_or_default(),
+        attributes: parse_attributes(enum_def.attributes()),
+        documentation: parse_documentation(enum_def.documentation()),
+    }
+}
+
+/// Readers for the tables of `reflection.fbs`, in the shape of flatc generated code.
+///
+/// Required strings and vectors are returned as empty defaults if missing, which the verifier already rules out.
+mod fbs {
+    use flatbuffers::{Follow, ForwardsUOffset, InvalidFlatbuffer, Table, Verifiable, Verifier, Vector};
+
+    macro_rules! table {
+        ($name:ident { $($field:ident: $ty:ty = $vt:expr, $required:expr;)* }) => {
+            #[derive(Copy, Clone)]
+            pub struct $name<'a> {
+                table: Table<'a>,
+            }
+
+            impl<'a> Follow<'a> for $name<'a> {
+                type Inner = $name<'a>;
+
+                #[inline]
+                unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+                    Self { table: Table::new(buf, loc) }
+                }
+            }
+
+            impl Verifiable for $name<'_> {
+                fn run_verifier(v: &mut Verifier, pos: usize) -> Result<(), InvalidFlatbuffer> {
+                    v.visit_table(pos)?
+                        $(.visit_field::<$ty>(stringify!($field), $vt, $required)?)*
+                        .finish();
+                    Ok(())
+                }
+            }
+        };
+    }
+
+    macro_rules! scalar {
+        ($name:ident: $ty:ty = $vt:expr, $default:expr) => {
+            #[inline]
+            pub fn $name(&self) -> $ty {
+                // Safety: The table was verified before it was followed.
+                unsafe { self.table.get::<$ty>($vt, Some($default)) }.unwrap_or($default)
+            }
+        };
+    }
+
+    macro_rules! string {
+        ($name:ident = $vt:expr) => {
+            #[inline]
+            pub fn $name(&self) -> Option<&'a str> {
+                // Safety: The table was verified before it was followed.
+                unsafe { self.table.get::<ForwardsUOffset<&str>>($vt, None) }
+            }
+        };
+        ($name:ident = $vt:expr, required) => {
+            #[inline]
+            pub fn $name(&self) -> &'a str {
+                // Safety: The table was verified before it was followed.
+                unsafe { self.table.get::<ForwardsUOffset<&str>>($vt, None) }.unwrap_or_default()
+            }
+        };
+    }
+
+    macro_rules! table_field {
+        ($name:ident: $ty:ident = $vt:expr) => {
+            #[inline]
+            pub fn $name(&self) -> Option<$ty<'a>> {
+                // Safety: The table was verified before it was followed.
+                unsafe { self.table.get::<ForwardsUOffset<$ty>>($vt, None) }
+            }
+        };
+    }
+
+    macro_rules! vector {
+        ($name:ident: $ty:ty = $vt:expr) => {
+            #[inline]
+            pub fn $name(&self) -> Option<Vector<'a, ForwardsUOffset<$ty>>> {
+                // Safety: The table was verified before it was followed.
+                unsafe { self.table.get::<ForwardsUOffset<Vector<'a, ForwardsUOffset<$ty>>>>($vt, None) }
+            }
+        };
+        ($name:ident: $ty:ty = $vt:expr, required) => {
+            #[inline]
+            pub fn $name(&self) -> Vector<'a, ForwardsUOffset<$ty>> {
+                // Safety: The table was verified before it was followed.
+                unsafe { self.table.get::<ForwardsUOffset<Vector<'a, ForwardsUOffset<$ty>>>>($vt, None) }.unwrap_or_default()
+            }
+        };
+    }
+
+    type Strings<'a> = Vector<'a, ForwardsUOffset<&'a str>>;
+    type Tables<'a, T> = Vector<'a, ForwardsUOffset<T>>;
+
+    table!(Type {
+        base_type: i8 = 4, false;
+        element: i8 = 6, false;
+        index: i32 = 8, false;
+        fixed_length: u16 = 10, false;
+        base_size: u32 = 12, false;
+        element_size: u32 = 14, false;
+    });
+
+    impl Type<'_> {
+        scalar!(base_type: i8 = 4, 0);
+        scalar!(element: i8 = 6, 0);
+        scalar!(index: i32 = 8, -1);
+        scalar!(fixed_length: u16 = 10, 0);
+        scalar!(base_size: u32 = 12, 4);
+        scalar!(element_size: u32 = 14, 0);
+    }
+
+    table!(KeyValue {
+        key: ForwardsUOffset<&str> = 4, true;
+        value: ForwardsUOffset<&str> = 6, false;
+    });
+
+    impl<'a> KeyValue<'a> {
+        string!(key = 4, required);
+        string!(value = 6);
+    }
+
+    table!(EnumVal {
+        name: ForwardsUOffset<&str> = 4, true;
+        value: i64 = 6, false;
+        union_type: ForwardsUOffset<Type> = 10, false;
+        documentation: ForwardsUOffset<Strings> = 12, false;
+        attributes: ForwardsUOffset<Tables<KeyValue>> = 14, false;
+    });
+
+    impl<'a> EnumVal<'a> {
+        string!(name = 4, required);
+        scalar!(value: i64 = 6, 0);
+        table_field!(union_type: Type = 10);
+        vector!(documentation: &'a str = 12);
+        vector!(attributes: KeyValue<'a> = 14);
+    }
+
+    table!(Enum {
+        name: ForwardsUOffset<&str> = 4, true;
+        values: ForwardsUOffset<Tables<EnumVal>> = 6, true;
+        is_union: bool = 8, false;
+        underlying_type: ForwardsUOffset<Type> = 10, true;
+        attributes: ForwardsUOffset<Tables<KeyValue>> = 12, false;
+        documentation: ForwardsUOffset<Strings> = 14, false;
+    });
+
+    impl<'a> Enum<'a> {
+        string!(name = 4, required);
+        vector!(values: EnumVal<'a> = 6, required);
+        scalar!(is_union: bool = 8, false);
+        table_field!(underlying_type: Type = 10);
+        vector!(attributes: KeyValue<'a> = 12);
+        vector!(documentation: &'a str = 14);
+    }
+
+    table!(Field {
+        name: ForwardsUOffset<&str> = 4, true;
+        type_: ForwardsUOffset<Type> = 6, true;
+        id: u16 = 8, false;
+        offset: u16 = 10, false;
+        default_integer: i64 = 12, false;
+        default_real: f64 = 14, false;
+        deprecated: bool = 16, false;
+        required: bool = 18, false;
+        key: bool = 20, false;
+        attributes: ForwardsUOffset<Tables<KeyValue>> = 22, false;
+        documentation: ForwardsUOffset<Strings> = 24, false;
+        optional: bool = 26, false;
+        padding: u16 = 28, false;
+    });
+
+    impl<'a> Field<'a> {
+        string!(name = 4, required);
+        table_field!(type_: Type = 6);
+        scalar!(id: u16 = 8, 0);
+        scalar!(offset: u16 = 10, 0);
+        scalar!(default_integer: i64 = 12, 0);
+        scalar!(default_real: f64 = 14, 0.0);
+        scalar!(deprecated: bool = 16, false);
+        scalar!(required: bool = 18, false);
+        scalar!(key: bool = 20, false);
+        vector!(attributes: KeyValue<'a> = 22);
+        vector!(documentation: &'a str = 24);
+        scalar!(optional: bool = 26, false);
+        scalar!(padding: u16 = 28, 0);
+    }
+
+    table!(Object {
+        name: ForwardsUOffset<&str> = 4, true;
+        fields: ForwardsUOffset<Tables<Field>> = 6, true;
+        is_struct: bool = 8, false;
+        minalign: i32 = 10, false;
+        bytesize: i32 = 12, false;
+        attributes: ForwardsUOffset<Tables<KeyValue>> = 14, false;
+        documentation: ForwardsUOffset<Strings> = 16, false;
+    });
+
+    impl<'a> Object<'a> {
+        string!(name = 4, required);
+        vector!(fields: Field<'a> = 6, required);
+        scalar!(is_struct: bool = 8, false);
+        scalar!(minalign: i32 = 10, 0);
+        scalar!(bytesize: i32 = 12, 0);
+        vector!(attributes: KeyValue<'a> = 14);
+        vector!(documentation: &'a str = 16);
+    }
+
+    table!(Schema {
+        objects: ForwardsUOffset<Tables<Object>> = 4, true;
+        enums: ForwardsUOffset<Tables<Enum>> = 6, true;
+        file_ident: ForwardsUOffset<&str> = 8, false;
+        file_ext: ForwardsUOffset<&str> = 10, false;
+        root_table: ForwardsUOffset<Object> = 12, false;
+    });
+
+    impl<'a> Schema<'a> {
+        vector!(objects: Object<'a> = 4, required);
+        vector!(enums: Enum<'a> = 6, required);
+        string!(file_ident = 8);
+        string!(file_ext = 10);
+        table_field!(root_table: Object = 12);
+    }
+}
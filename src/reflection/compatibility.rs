@@ -0,0 +1,347 @@
+//! Compatibility checks between two versions of a schema.
+//!
+//! [check()] compares an old and a new [Schema] and reports every change relevant to FlatBuffers written with the old one,
+//! e.g. to fail a service startup or an integration test on an accidental breaking schema change. \
+//! Table fields are matched by their id, the slot in the vtable, tables, structs and enums by their fully qualified name.
+//!
+//! A change is breaking if buffers written with the old schema are no longer readable, or are read differently, under the new one.
+//!
+//! # Example
+//! ```
+//! use flatbuffers_owned::reflection::{compatibility, Schema};
+//!
+//! let old = Schema::from_bfbs(&std::fs::read("monster.v1.bfbs")?)?;
+//! let new = Schema::from_bfbs(&std::fs::read("monster.v2.bfbs")?)?;
+//!
+//! let report = compatibility::check(&old, &new);
+//! assert!(report.is_compatible(), "Breaking schema changes:\n{}", report);
+//! ```
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+use super::{BaseType, Enum, Field, Object, Schema, Type};
+
+/// Compares the old and the new version of a schema.
+pub fn check(old: &Schema, new: &Schema) -> Report {
+    let mut checker = Checker { old, new, changes: Vec::new() };
+    checker.check_schema();
+
+    Report { changes: checker.changes }
+}
+
+/// The changes between two versions of a schema, returned by [check()].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Report {
+    pub changes: Vec<Change>,
+}
+
+impl Report {
+    /// Returns whether buffers written with the old schema remain readable under the new one.
+    pub fn is_compatible(&self) -> bool {
+        !self.changes.iter().any(Change::is_breaking)
+    }
+
+    /// Returns the breaking changes.
+    pub fn breaking(&self) -> impl Iterator<Item = &Change> {
+        self.changes.iter().filter(|change| change.is_breaking())
+    }
+}
+
+impl Display for Report {
+    /// Lists the changes, one per line.
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{}", change)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A change of a table, struct, field, enum or of the schema itself.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Change {
+    /// The fully qualified name of the changed item, e.g. `game.Monster.hp`, or an empty path for the schema itself.
+    pub path: String,
+    pub kind: ChangeKind,
+}
+
+impl Change {
+    /// Returns whether buffers written with the old schema are no longer readable, or are read differently, under the new one.
+    pub fn is_breaking(&self) -> bool {
+        !matches!(
+            self.kind,
+            ChangeKind::TableAdded
+                | ChangeKind::EnumAdded
+                | ChangeKind::FieldAdded
+                | ChangeKind::FieldDeprecated
+                | ChangeKind::FieldRenamed { .. }
+                | ChangeKind::EnumValueAdded
+        )
+    }
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let severity = if self.is_breaking() { "breaking" } else { "compatible" };
+
+        match self.path.is_empty() {
+            true => write!(f, "{}: {}", severity, self.kind),
+            false => write!(f, "{}: {}: {}", severity, self.path, self.kind),
+        }
+    }
+}
+
+/// The kind of a [Change].
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum ChangeKind {
+    /// A table or struct was added.
+    TableAdded,
+    /// A table or struct was removed.
+    TableRemoved,
+    /// A table became a struct or the other way around.
+    TableKindChanged,
+    /// The size, alignment or fields of a struct changed, which moves its inline data.
+    StructLayoutChanged,
+    /// An optional field was added.
+    FieldAdded,
+    /// A required field was added, which old buffers lack.
+    RequiredFieldAdded,
+    /// A field was removed instead of deprecated.
+    FieldRemoved,
+    /// A field was deprecated.
+    FieldDeprecated,
+    /// A field id was given to a field with another name, which only affects name-based access like JSON.
+    FieldRenamed { old_name: String },
+    /// The type of a field changed.
+    FieldTypeChanged { old: String, new: String },
+    /// An optional field became required, which old buffers may lack.
+    FieldMadeRequired,
+    /// The default of a scalar field changed, which changes the value read for old buffers omitting it.
+    DefaultChanged,
+    /// An enum or union was added.
+    EnumAdded,
+    /// An enum or union was removed.
+    EnumRemoved,
+    /// The underlying type of an enum changed.
+    UnderlyingTypeChanged { old: String, new: String },
+    /// An enum value or union member was added.
+    EnumValueAdded,
+    /// An enum value or union member was removed.
+    EnumValueRemoved,
+    /// An enum value or union member was given another number.
+    EnumValueChanged { old: i64, new: i64 },
+    /// A union member was given another table.
+    UnionMemberChanged,
+    /// The root type changed.
+    RootTypeChanged { old: Option<String>, new: Option<String> },
+    /// The file identifier changed, which old buffers fail to be identified by.
+    FileIdentifierChanged { old: Option<String>, new: Option<String> },
+}
+
+impl Display for ChangeKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ChangeKind::TableAdded => write!(f, "table added"),
+            ChangeKind::TableRemoved => write!(f, "table removed"),
+            ChangeKind::TableKindChanged => write!(f, "changed between table and struct"),
+            ChangeKind::StructLayoutChanged => write!(f, "struct layout changed"),
+            ChangeKind::FieldAdded => write!(f, "field added"),
+            ChangeKind::RequiredFieldAdded => write!(f, "required field added"),
+            ChangeKind::FieldRemoved => write!(f, "field removed instead of deprecated"),
+            ChangeKind::FieldDeprecated => write!(f, "field deprecated"),
+            ChangeKind::FieldRenamed { old_name } => write!(f, "field renamed from {}", old_name),
+            ChangeKind::FieldTypeChanged { old, new } => write!(f, "type changed from {} to {}", old, new),
+            ChangeKind::FieldMadeRequired => write!(f, "field made required"),
+            ChangeKind::DefaultChanged => write!(f, "default changed"),
+            ChangeKind::EnumAdded => write!(f, "enum added"),
+            ChangeKind::EnumRemoved => write!(f, "enum removed"),
+            ChangeKind::UnderlyingTypeChanged { old, new } => write!(f, "underlying type changed from {} to {}", old, new),
+            ChangeKind::EnumValueAdded => write!(f, "value added"),
+            ChangeKind::EnumValueRemoved => write!(f, "value removed"),
+            ChangeKind::EnumValueChanged { old, new } => write!(f, "value changed from {} to {}", old, new),
+            ChangeKind::UnionMemberChanged => write!(f, "union member changed its table"),
+            ChangeKind::RootTypeChanged { old, new } => write!(f, "root type changed from {:?} to {:?}", old, new),
+            ChangeKind::FileIdentifierChanged { old, new } => write!(f, "file identifier changed from {:?} to {:?}", old, new),
+        }
+    }
+}
+
+struct Checker<'a> {
+    old: &'a Schema,
+    new: &'a Schema,
+    changes: Vec<Change>,
+}
+
+impl<'a> Checker<'a> {
+    fn push(&mut self, path: impl Into<String>, kind: ChangeKind) {
+        self.changes.push(Change { path: path.into(), kind });
+    }
+
+    fn check_schema(&mut self) {
+        let (old, new) = (self.old, self.new);
+
+        if self.old.file_ident != self.new.file_ident {
+            self.push("", ChangeKind::FileIdentifierChanged { old: self.old.file_ident.clone(), new: self.new.file_ident.clone() });
+        }
+
+        let old_root = self.old.root_table().map(|object| object.name.clone());
+        let new_root = self.new.root_table().map(|object| object.name.clone());
+        if old_root != new_root {
+            self.push("", ChangeKind::RootTypeChanged { old: old_root, new: new_root });
+        }
+
+        for old_object in &self.old.objects {
+            match self.new.object(&old_object.name) {
+                Some(new_object) => self.check_object(old_object, new_object),
+                None => self.push(old_object.name.as_str(), ChangeKind::TableRemoved),
+            }
+        }
+        for new_object in new.objects.iter().filter(|object| old.object(&object.name).is_none()) {
+            self.push(new_object.name.as_str(), ChangeKind::TableAdded);
+        }
+
+        for old_enum in &self.old.enums {
+            match self.new.enum_by_name(&old_enum.name) {
+                Some(new_enum) => self.check_enum(old_enum, new_enum),
+                None => self.push(old_enum.name.as_str(), ChangeKind::EnumRemoved),
+            }
+        }
+        for new_enum in new.enums.iter().filter(|enumeration| old.enum_by_name(&enumeration.name).is_none()) {
+            self.push(new_enum.name.as_str(), ChangeKind::EnumAdded);
+        }
+    }
+
+    fn check_object(&mut self, old: &Object, new: &Object) {
+        if old.is_struct != new.is_struct {
+            return self.push(old.name.as_str(), ChangeKind::TableKindChanged);
+        }
+
+        if old.is_struct {
+            let same_layout = old.bytesize == new.bytesize
+                && old.minalign == new.minalign
+                && old.fields.len() == new.fields.len()
+                && old.fields.iter().all(|old_field| {
+                    field_by_id(new, old_field.id).is_some_and(|new_field| {
+                        old_field.offset == new_field.offset && self.same_type(&old_field.ty, &new_field.ty)
+                    })
+                });
+
+            if !same_layout {
+                self.push(old.name.as_str(), ChangeKind::StructLayoutChanged);
+            }
+            return;
+        }
+
+        for old_field in old.fields_by_id() {
+            let path = format!("{}.{}", old.name, old_field.name);
+
+            match field_by_id(new, old_field.id) {
+                Some(new_field) => self.check_field(path, old_field, new_field),
+                None => self.push(path, ChangeKind::FieldRemoved),
+            }
+        }
+        for new_field in new.fields_by_id().into_iter().filter(|field| field_by_id(old, field.id).is_none()) {
+            let kind = if new_field.required { ChangeKind::RequiredFieldAdded } else { ChangeKind::FieldAdded };
+            self.push(format!("{}.{}", new.name, new_field.name), kind);
+        }
+    }
+
+    fn check_field(&mut self, path: String, old: &Field, new: &Field) {
+        if old.name != new.name {
+            self.push(path.as_str(), ChangeKind::FieldRenamed { old_name: old.name.clone() });
+        }
+
+        if !self.same_type(&old.ty, &new.ty) {
+            let kind = ChangeKind::FieldTypeChanged { old: type_name(self.old, &old.ty), new: type_name(self.new, &new.ty) };
+            return self.push(path, kind);
+        }
+
+        if new.required && !old.required {
+            self.push(path.as_str(), ChangeKind::FieldMadeRequired);
+        }
+
+        let default_changed = old.optional != new.optional
+            || old.default_integer != new.default_integer
+            || old.default_real.to_bits() != new.default_real.to_bits();
+        if default_changed && old.ty.base_type.is_scalar() {
+            self.push(path.as_str(), ChangeKind::DefaultChanged);
+        }
+
+        if new.deprecated && !old.deprecated {
+            self.push(path, ChangeKind::FieldDeprecated);
+        }
+    }
+
+    fn check_enum(&mut self, old: &Enum, new: &Enum) {
+        if old.underlying_type.base_type != new.underlying_type.base_type {
+            let kind = ChangeKind::UnderlyingTypeChanged {
+                old: format!("{:?}", old.underlying_type.base_type),
+                new: format!("{:?}", new.underlying_type.base_type),
+            };
+            self.push(old.name.as_str(), kind);
+        }
+
+        for old_value in &old.values {
+            let path = format!("{}.{}", old.name, old_value.name);
+
+            match new.value_by_name(&old_value.name) {
+                None => self.push(path, ChangeKind::EnumValueRemoved),
+                Some(new_value) if new_value.value != old_value.value => {
+                    self.push(path, ChangeKind::EnumValueChanged { old: old_value.value, new: new_value.value })
+                },
+                Some(new_value) => {
+                    let old_member = old_value.union_type.as_ref().and_then(|ty| self.old.object_of(ty)).map(|object| &object.name);
+                    let new_member = new_value.union_type.as_ref().and_then(|ty| self.new.object_of(ty)).map(|object| &object.name);
+
+                    if old.is_union && old_member != new_member {
+                        self.push(path, ChangeKind::UnionMemberChanged);
+                    }
+                },
+            }
+        }
+        for new_value in new.values.iter().filter(|value| old.value_by_name(&value.name).is_none()) {
+            self.push(format!("{}.{}", new.name, new_value.name), ChangeKind::EnumValueAdded);
+        }
+    }
+
+    fn same_type(&self, old: &Type, new: &Type) -> bool {
+        old.base_type == new.base_type
+            && old.element == new.element
+            && old.fixed_length == new.fixed_length
+            && referenced_name(self.old, old) == referenced_name(self.new, new)
+    }
+}
+
+fn field_by_id(object: &Object, id: u16) -> Option<&Field> {
+    object.fields.iter().find(|field| field.id == id)
+}
+
+/// Returns the name of the table, struct or union a type refers to. \
+/// The enum of a scalar is ignored, as it does not change the encoding.
+fn referenced_name<'a>(schema: &'a Schema, ty: &Type) -> Option<&'a str> {
+    let is_union = |base_type| matches!(base_type, BaseType::Union | BaseType::UType);
+
+    match schema.object_of(ty) {
+        Some(object) => Some(&object.name),
+        None if is_union(ty.base_type) || is_union(ty.element) => schema.enum_of(ty).map(|enumeration| enumeration.name.as_str()),
+        None => None,
+    }
+}
+
+fn type_name(schema: &Schema, ty: &Type) -> String {
+    let element = match referenced_name(schema, ty) {
+        Some(name) => String::from(name),
+        None if ty.element == BaseType::None => format!("{:?}", ty.base_type),
+        None => format!("{:?}", ty.element),
+    };
+
+    match ty.base_type {
+        BaseType::Vector | BaseType::Vector64 => format!("[{}]", element),
+        BaseType::Array => format!("[{}:{}]", element, ty.fixed_length),
+        _ => element,
+    }
+}
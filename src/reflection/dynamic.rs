@@ -0,0 +1,839 @@
+//! Schema-driven access to FlatBuffers whose type is only known at runtime.
+//!
+//! [DynamicFlatBuffer] verifies a buffer against a table of a [Schema] and exposes its fields by name,
+//! for generic tooling like routers, filters or metric extractors that cannot depend on generated code. \
+//! [get_field()](DynamicFlatBuffer::get_field) resolves paths like `user.name` or `friends[2].hp`,
+//! [DynamicTable::fields()] iterates over the present fields of a table and [DynamicVector] indexes vectors.
+//!
+//! All reads are bounds-checked, the verification only rules out malformed buffers up front.
+//!
+//! # Example
+//! ```
+//! use flatbuffers_owned::reflection::dynamic::DynamicFlatBuffer;
+//!
+//! let message = DynamicFlatBuffer::new(bytes, &schema)?;
+//!
+//! if message.get_field("user.name")?.and_then(|name| name.as_str()) == Some("admin") {
+//!     route_to_admin(message.into_inner());
+//! }
+//! ```
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::convert::{TryFrom, TryInto};
+use core::fmt::{Debug, Display, Formatter};
+use core::ops::Deref;
+use flatbuffers::{InvalidFlatbuffer, VerifierOptions};
+use super::{BaseType, Field, Object, Schema, Type};
+
+#[cfg(feature = "std")]
+use std::error::Error;
+
+/// A buffer verified against a table of a [Schema].
+///
+/// The schema is held by anything dereferencing to it, e.g. `&Schema` or `Arc<Schema>`.
+/// The buffer may be any byte buffer, including the wrappers of this crate.
+pub struct DynamicFlatBuffer<B, S> {
+    buffer: B,
+    schema: S,
+    object: usize,
+}
+
+impl<B: AsRef<[u8]>, S: Deref<Target = Schema>> DynamicFlatBuffer<B, S> {
+    /// Verifies the buffer as the root type of the schema.
+    pub fn new(buffer: B, schema: S) -> Result<Self, DynamicError> {
+        let object = schema.root_table.ok_or(DynamicError::NoRootTable)?;
+
+        Self::with_object(buffer, schema, object, &VerifierOptions::default())
+    }
+
+    /// Verifies the buffer as the table with the fully qualified name.
+    pub fn new_as(buffer: B, schema: S, type_name: &str) -> Result<Self, DynamicError> {
+        let object = schema.objects.iter()
+            .position(|object| object.name == type_name && !object.is_struct)
+            .ok_or_else(|| DynamicError::UnknownTable(type_name.to_string()))?;
+
+        Self::with_object(buffer, schema, object, &VerifierOptions::default())
+    }
+
+    /// Like [new()](Self::new), but verifies with the limits of custom options.
+    pub fn new_with_opts(buffer: B, schema: S, opts: &VerifierOptions) -> Result<Self, DynamicError> {
+        let object = schema.root_table.ok_or(DynamicError::NoRootTable)?;
+
+        Self::with_object(buffer, schema, object, opts)
+    }
+
+    fn with_object(buffer: B, schema: S, object: usize, opts: &VerifierOptions) -> Result<Self, DynamicError> {
+        let data = buffer.as_ref();
+        let mut verifier = Verifier { schema: &schema, data, opts, depth: 0, tables: 0, apparent_size: 0 };
+
+        match schema.objects.get(object) {
+            Some(root) => verifier.verify_table(root, follow(data, 0))
+                .map_err(|failure| failure.into_error(&root.name))?,
+            None => return Err(DynamicError::NoRootTable),
+        }
+
+        Ok(Self { buffer, schema, object })
+    }
+
+    /// Returns the root table.
+    pub fn root(&self) -> DynamicTable<'_> {
+        let view = View { schema: &self.schema, data: self.buffer.as_ref() };
+
+        DynamicTable {
+            view,
+            object: self.object(),
+            loc: follow(view.data, 0).unwrap_or_default(),
+        }
+    }
+
+    /// Returns the value at the path of field names, separated by dots and optionally indexing vectors, e.g. `friends[2].name`.
+    ///
+    /// Returns `None` if a table along the path does not contain the field or an index is out of bounds.
+    /// Absent scalars return their default like generated accessors do, unless they are optional.
+    pub fn get_field(&self, path: &str) -> Result<Option<Value<'_>>, FieldError> {
+        self.root().get_path(path)
+    }
+
+    /// Returns the table of the schema this buffer was verified as.
+    pub fn object(&self) -> &Object {
+        // The index was checked in the constructor, only a schema changing behind `S` would lose the table.
+        self.schema.objects.get(self.object).unwrap_or(&EMPTY_OBJECT)
+    }
+
+    pub fn schema(&self) -> &S {
+        &self.schema
+    }
+
+    pub fn into_inner(self) -> B {
+        self.buffer
+    }
+}
+
+static EMPTY_OBJECT: Object = Object {
+    name: String::new(),
+    fields: Vec::new(),
+    is_struct: false,
+    minalign: 1,
+    bytesize: 0,
+    attributes: Vec::new(),
+    documentation: Vec::new(),
+};
+
+impl<B: AsRef<[u8]>, S> Deref for DynamicFlatBuffer<B, S> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.buffer.as_ref()
+    }
+}
+
+impl<B: AsRef<[u8]>, S> AsRef<[u8]> for DynamicFlatBuffer<B, S> {
+    fn as_ref(&self) -> &[u8] {
+        self.buffer.as_ref()
+    }
+}
+
+impl<B: AsRef<[u8]> + Clone, S: Clone> Clone for DynamicFlatBuffer<B, S> {
+    fn clone(&self) -> Self {
+        Self { buffer: self.buffer.clone(), schema: self.schema.clone(), object: self.object }
+    }
+}
+
+impl<B: AsRef<[u8]>, S: Deref<Target = Schema>> Debug for DynamicFlatBuffer<B, S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("DynamicFlatBuffer")
+            .field("object", &self.object().name)
+            .field("len", &self.buffer.as_ref().len())
+            .finish()
+    }
+}
+
+/// A value read from a [DynamicFlatBuffer].
+#[derive(Clone, Copy, Debug)]
+#[non_exhaustive]
+pub enum Value<'a> {
+    Bool(bool),
+    Byte(i8),
+    UByte(u8),
+    Short(i16),
+    UShort(u16),
+    Int(i32),
+    UInt(u32),
+    Long(i64),
+    ULong(u64),
+    Float(f32),
+    Double(f64),
+    String(&'a str),
+    /// A table, or the member table of a union.
+    Table(DynamicTable<'a>),
+    Struct(DynamicStruct<'a>),
+    /// A vector, or a fixed length array within a struct.
+    Vector(DynamicVector<'a>),
+}
+
+impl<'a> Value<'a> {
+    pub fn as_bool(&self) -> Option<bool> {
+        match *self {
+            Value::Bool(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns any integer that fits into an `i64`.
+    pub fn as_i64(&self) -> Option<i64> {
+        match *self {
+            Value::Byte(value) => Some(i64::from(value)),
+            Value::UByte(value) => Some(i64::from(value)),
+            Value::Short(value) => Some(i64::from(value)),
+            Value::UShort(value) => Some(i64::from(value)),
+            Value::Int(value) => Some(i64::from(value)),
+            Value::UInt(value) => Some(i64::from(value)),
+            Value::Long(value) => Some(value),
+            Value::ULong(value) => i64::try_from(value).ok(),
+            _ => None,
+        }
+    }
+
+    /// Returns any integer that fits into a `u64`.
+    pub fn as_u64(&self) -> Option<u64> {
+        match *self {
+            Value::ULong(value) => Some(value),
+            _ => self.as_i64().and_then(|value| u64::try_from(value).ok()),
+        }
+    }
+
+    /// Returns any integer or floating point number as an `f64`, possibly rounded.
+    pub fn as_f64(&self) -> Option<f64> {
+        match *self {
+            Value::Float(value) => Some(f64::from(value)),
+            Value::Double(value) => Some(value),
+            Value::Long(value) => Some(value as f64),
+            Value::ULong(value) => Some(value as f64),
+            _ => self.as_i64().map(|value| value as f64),
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&'a str> {
+        match *self {
+            Value::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_table(&self) -> Option<DynamicTable<'a>> {
+        match *self {
+            Value::Table(table) => Some(table),
+            _ => None,
+        }
+    }
+
+    pub fn as_struct(&self) -> Option<DynamicStruct<'a>> {
+        match *self {
+            Value::Struct(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_vector(&self) -> Option<DynamicVector<'a>> {
+        match *self {
+            Value::Vector(vector) => Some(vector),
+            _ => None,
+        }
+    }
+}
+
+/// The schema and the verified bytes shared by all views into a [DynamicFlatBuffer].
+#[derive(Clone, Copy)]
+struct View<'a> {
+    schema: &'a Schema,
+    data: &'a [u8],
+}
+
+impl<'a> View<'a> {
+    /// Reads the inline value of the type `base_type` at `pos`, `ty` being the type of the field or vector.
+    fn value(self, base_type: BaseType, ty: &'a Type, pos: usize) -> Option<Value<'a>> {
+        let data = self.data;
+
+        Some(match base_type {
+            BaseType::Bool => Value::Bool(read::<1>(data, pos).ok()? != [0]),
+            BaseType::Byte => Value::Byte(i8::from_le_bytes(read(data, pos).ok()?)),
+            BaseType::UByte | BaseType::UType => Value::UByte(u8::from_le_bytes(read(data, pos).ok()?)),
+            BaseType::Short => Value::Short(i16::from_le_bytes(read(data, pos).ok()?)),
+            BaseType::UShort => Value::UShort(u16::from_le_bytes(read(data, pos).ok()?)),
+            BaseType::Int => Value::Int(i32::from_le_bytes(read(data, pos).ok()?)),
+            BaseType::UInt => Value::UInt(u32::from_le_bytes(read(data, pos).ok()?)),
+            BaseType::Long => Value::Long(i64::from_le_bytes(read(data, pos).ok()?)),
+            BaseType::ULong => Value::ULong(u64::from_le_bytes(read(data, pos).ok()?)),
+            BaseType::Float => Value::Float(f32::from_le_bytes(read(data, pos).ok()?)),
+            BaseType::Double => Value::Double(f64::from_le_bytes(read(data, pos).ok()?)),
+            BaseType::String => Value::String(string(data, follow(data, pos).ok()?).ok()?),
+            BaseType::Obj => {
+                let object = self.schema.objects.get(ty.index?)?;
+
+                if object.is_struct {
+                    Value::Struct(DynamicStruct { view: self, object, loc: pos })
+                } else {
+                    Value::Table(DynamicTable { view: self, object, loc: follow(data, pos).ok()? })
+                }
+            },
+            BaseType::Vector => {
+                let loc = follow(data, pos).ok()?;
+                let len = to_usize(u32::from_le_bytes(read(data, loc).ok()?));
+
+                Value::Vector(DynamicVector { view: self, ty, start: loc.checked_add(4)?, len })
+            },
+            BaseType::Array => Value::Vector(DynamicVector { view: self, ty, start: pos, len: usize::from(ty.fixed_length) }),
+            _ => return None,
+        })
+    }
+
+    /// Returns the size of an inline value of the type `base_type`.
+    fn inline_size(self, base_type: BaseType, ty: &Type) -> Option<usize> {
+        match base_type {
+            BaseType::String | BaseType::Vector | BaseType::Union => Some(4),
+            BaseType::Obj => {
+                let object = self.schema.objects.get(ty.index?)?;
+
+                if object.is_struct { usize::try_from(object.bytesize).ok() } else { Some(4) }
+            },
+            BaseType::Array => self.inline_size(ty.element, ty)?.checked_mul(usize::from(ty.fixed_length)),
+            base_type => base_type.scalar_size(),
+        }
+    }
+}
+
+/// A table of a [DynamicFlatBuffer].
+#[derive(Clone, Copy)]
+pub struct DynamicTable<'a> {
+    view: View<'a>,
+    object: &'a Object,
+    loc: usize,
+}
+
+impl<'a> DynamicTable<'a> {
+    /// Returns the table of the schema.
+    pub fn object(&self) -> &'a Object {
+        self.object
+    }
+
+    /// Returns the value of the field.
+    ///
+    /// Absent scalars return their default, unless they are optional. Other absent fields return `None`.
+    pub fn get(&self, name: &str) -> Result<Option<Value<'a>>, FieldError> {
+        let field = self.object.field(name).ok_or_else(|| FieldError::UnknownField(name.to_string()))?;
+
+        Ok(self.value(field).or_else(|| self.default(field)))
+    }
+
+    /// Returns the value at the path of field names, relative to this table.
+    pub fn get_path(&self, path: &str) -> Result<Option<Value<'a>>, FieldError> {
+        let mut value = Value::Table(*self);
+        let mut start = 0usize;
+
+        for segment in path.split('.') {
+            let end = start.saturating_add(segment.len());
+            let prefix = || path.get(..end).unwrap_or(path).to_string();
+            start = end.saturating_add(1);
+
+            let (name, index) = parse_segment(segment).ok_or_else(|| FieldError::InvalidPath(prefix()))?;
+
+            let next = match value {
+                Value::Table(table) => table.get(name),
+                Value::Struct(value) => value.get(name),
+                _ => return Err(FieldError::NotATable(prefix())),
+            }.map_err(|_| FieldError::UnknownField(prefix()))?;
+
+            value = match (next, index) {
+                (None, _) => return Ok(None),
+                (Some(next), None) => next,
+                (Some(Value::Vector(vector)), Some(index)) => match vector.get(index) {
+                    Some(element) => element,
+                    None => return Ok(None),
+                },
+                (Some(_), Some(_)) => return Err(FieldError::NotAVector(prefix())),
+            };
+        }
+
+        Ok(Some(value))
+    }
+
+    /// Iterates over the present fields in the order of their ids.
+    pub fn fields(&self) -> impl Iterator<Item = (&'a Field, Value<'a>)> + 'a {
+        let table = *self;
+
+        self.object.fields_by_id().into_iter()
+            .filter_map(move |field| table.value(field).map(|value| (field, value)))
+    }
+
+    fn field_pos(&self, field: &Field) -> Option<usize> {
+        field_pos(self.view.data, self.loc, field.offset).ok().flatten()
+    }
+
+    fn value(&self, field: &'a Field) -> Option<Value<'a>> {
+        let pos = self.field_pos(field)?;
+
+        if field.ty.base_type != BaseType::Union {
+            return self.view.value(field.ty.base_type, &field.ty, pos);
+        }
+
+        let member = self.union_member(field)?;
+
+        Some(Value::Table(DynamicTable { view: self.view, object: member, loc: follow(self.view.data, pos).ok()? }))
+    }
+
+    /// Resolves the member table of a union through the `{FIELD_NAME}_type` field.
+    fn union_member(&self, field: &Field) -> Option<&'a Object> {
+        let type_field = self.object.field(&format!("{}_type", field.name))?;
+        let union_type = u8::from_le_bytes(read(self.view.data, self.field_pos(type_field)?).ok()?);
+
+        let member = self.view.schema.enum_of(&field.ty)?.value_of(i64::from(union_type))?.union_type.as_ref()?;
+
+        self.view.schema.object_of(member).filter(|object| !object.is_struct)
+    }
+
+    fn default(&self, field: &Field) -> Option<Value<'a>> {
+        if field.optional {
+            return None;
+        }
+
+        let integer = field.default_integer;
+
+        Some(match field.ty.base_type {
+            BaseType::Bool => Value::Bool(integer != 0),
+            BaseType::Byte => Value::Byte(integer as i8),
+            BaseType::UByte | BaseType::UType => Value::UByte(integer as u8),
+            BaseType::Short => Value::Short(integer as i16),
+            BaseType::UShort => Value::UShort(integer as u16),
+            BaseType::Int => Value::Int(integer as i32),
+            BaseType::UInt => Value::UInt(integer as u32),
+            BaseType::Long => Value::Long(integer),
+            BaseType::ULong => Value::ULong(integer as u64),
+            BaseType::Float => Value::Float(field.default_real as f32),
+            BaseType::Double => Value::Double(field.default_real),
+            _ => return None,
+        })
+    }
+}
+
+impl Debug for DynamicTable<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("DynamicTable").field("object", &self.object.name).field("loc", &self.loc).finish()
+    }
+}
+
+/// A struct of a [DynamicFlatBuffer].
+#[derive(Clone, Copy)]
+pub struct DynamicStruct<'a> {
+    view: View<'a>,
+    object: &'a Object,
+    loc: usize,
+}
+
+impl<'a> DynamicStruct<'a> {
+    /// Returns the struct of the schema.
+    pub fn object(&self) -> &'a Object {
+        self.object
+    }
+
+    /// Returns the value of the field. All fields of a struct are present.
+    pub fn get(&self, name: &str) -> Result<Option<Value<'a>>, FieldError> {
+        let field = self.object.field(name).ok_or_else(|| FieldError::UnknownField(name.to_string()))?;
+
+        Ok(self.value(field))
+    }
+
+    /// Iterates over the fields in the order of their ids.
+    pub fn fields(&self) -> impl Iterator<Item = (&'a Field, Value<'a>)> + 'a {
+        let value = *self;
+
+        self.object.fields_by_id().into_iter()
+            .filter_map(move |field| value.value(field).map(|value| (field, value)))
+    }
+
+    fn value(&self, field: &'a Field) -> Option<Value<'a>> {
+        self.view.value(field.ty.base_type, &field.ty, self.loc.checked_add(usize::from(field.offset))?)
+    }
+}
+
+impl Debug for DynamicStruct<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("DynamicStruct").field("object", &self.object.name).field("loc", &self.loc).finish()
+    }
+}
+
+/// A vector, or a fixed length array within a struct, of a [DynamicFlatBuffer].
+#[derive(Clone, Copy)]
+pub struct DynamicVector<'a> {
+    view: View<'a>,
+    ty: &'a Type,
+    start: usize,
+    len: usize,
+}
+
+impl<'a> DynamicVector<'a> {
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the type of the elements.
+    pub fn element_type(&self) -> BaseType {
+        self.ty.element
+    }
+
+    /// Returns the element at the index, or `None` if it is out of bounds.
+    pub fn get(&self, index: usize) -> Option<Value<'a>> {
+        if index >= self.len {
+            return None;
+        }
+
+        let size = self.view.inline_size(self.ty.element, self.ty)?;
+        let pos = size.checked_mul(index)?.checked_add(self.start)?;
+
+        self.view.value(self.ty.element, self.ty, pos)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Value<'a>> + 'a {
+        let vector = *self;
+
+        (0..self.len).filter_map(move |index| vector.get(index))
+    }
+}
+
+impl Debug for DynamicVector<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+fn parse_segment(segment: &str) -> Option<(&str, Option<usize>)> {
+    match segment.strip_suffix(']') {
+        Some(rest) => {
+            let (name, index) = rest.split_once('[')?;
+            Some((name, Some(index.parse().ok()?)))
+        },
+        None => Some((segment, None)),
+    }
+}
+
+/// The error returned when a [DynamicFlatBuffer] cannot be created.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DynamicError {
+    /// The schema does not declare a root type.
+    NoRootTable,
+    /// The schema has no table with the name.
+    UnknownTable(String),
+    /// No schema is registered for the file identifier of the buffer.
+    UnknownIdentifier,
+    /// The required field at the path is missing.
+    MissingField { path: String },
+    /// The buffer failed the verification at the path.
+    InvalidFlatbuffer { path: String, error: InvalidFlatbuffer },
+}
+
+impl Display for DynamicError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            DynamicError::NoRootTable => write!(f, "The schema does not declare a root type"),
+            DynamicError::UnknownTable(name) => write!(f, "Unknown table {}", name),
+            DynamicError::UnknownIdentifier => write!(f, "No schema is registered for the file identifier"),
+            DynamicError::MissingField { path } => write!(f, "Missing required field {}", path),
+            DynamicError::InvalidFlatbuffer { path, error } => write!(f, "Verification of {} failed: {}", path, error),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for DynamicError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            DynamicError::InvalidFlatbuffer { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// The error returned when a field cannot be resolved by name.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FieldError {
+    /// The table or struct at the path has no field with the name.
+    UnknownField(String),
+    /// The value at the path is neither a table nor a struct, so it has no fields.
+    NotATable(String),
+    /// The value at the path is indexed, but is not a vector.
+    NotAVector(String),
+    /// The path segment is malformed, e.g. `friends[x]`.
+    InvalidPath(String),
+}
+
+impl Display for FieldError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            FieldError::UnknownField(path) => write!(f, "Unknown field {}", path),
+            FieldError::NotATable(path) => write!(f, "{} is not a table or struct", path),
+            FieldError::NotAVector(path) => write!(f, "{} is not a vector", path),
+            FieldError::InvalidPath(path) => write!(f, "Invalid path {}", path),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for FieldError {}
+
+fn to_usize(value: u32) -> usize {
+    usize::try_from(value).unwrap_or(usize::MAX)
+}
+
+fn out_of_bounds(pos: usize, len: usize) -> InvalidFlatbuffer {
+    InvalidFlatbuffer::RangeOutOfBounds { range: pos..pos.saturating_add(len), error_trace: Default::default() }
+}
+
+fn read<const N: usize>(data: &[u8], pos: usize) -> Result<[u8; N], InvalidFlatbuffer> {
+    pos.checked_add(N)
+        .and_then(|end| data.get(pos..end))
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| out_of_bounds(pos, N))
+}
+
+/// Follows the `uoffset` at `pos`.
+fn follow(data: &[u8], pos: usize) -> Result<usize, InvalidFlatbuffer> {
+    let offset = to_usize(u32::from_le_bytes(read(data, pos)?));
+
+    pos.checked_add(offset)
+        .filter(|loc| *loc < data.len())
+        .ok_or_else(|| out_of_bounds(pos.saturating_add(offset), 1))
+}
+
+fn vtable(data: &[u8], loc: usize) -> Result<usize, InvalidFlatbuffer> {
+    let soffset = i32::from_le_bytes(read(data, loc)?);
+
+    // Signed offsets are subtracted.
+    let vtable = match u32::try_from(soffset) {
+        Ok(offset) => loc.checked_sub(to_usize(offset)),
+        Err(_) => loc.checked_add(to_usize(soffset.unsigned_abs())),
+    };
+
+    vtable.filter(|vtable| *vtable < data.len())
+        .ok_or(InvalidFlatbuffer::SignedOffsetOutOfBounds { soffset, position: loc, error_trace: Default::default() })
+}
+
+/// Returns the position of a field of the table at `loc`, or `None` if the field is absent.
+fn field_pos(data: &[u8], loc: usize, voffset: u16) -> Result<Option<usize>, InvalidFlatbuffer> {
+    let vtable = vtable(data, loc)?;
+    let vtable_len = u16::from_le_bytes(read(data, vtable)?);
+
+    if voffset.saturating_add(2) > vtable_len {
+        return Ok(None);
+    }
+
+    let offset = u16::from_le_bytes(read(data, vtable.saturating_add(usize::from(voffset)))?);
+
+    match offset {
+        0 => Ok(None),
+        offset => Ok(Some(loc.saturating_add(usize::from(offset)))),
+    }
+}
+
+fn string(data: &[u8], loc: usize) -> Result<&str, InvalidFlatbuffer> {
+    let len = to_usize(u32::from_le_bytes(read(data, loc)?));
+    let start = loc.saturating_add(4);
+    let range = start..start.saturating_add(len);
+
+    let bytes = data.get(range.clone()).ok_or_else(|| out_of_bounds(start, len))?;
+
+    core::str::from_utf8(bytes).map_err(|error| InvalidFlatbuffer::Utf8Error { error, range, error_trace: Default::default() })
+}
+
+/// A verification failure and the path it occurred at, collected back to front while unwinding.
+struct Failure {
+    path: Vec<String>,
+    kind: FailureKind,
+}
+
+enum FailureKind {
+    Missing,
+    Invalid(InvalidFlatbuffer),
+}
+
+impl Failure {
+    fn missing() -> Self {
+        Failure { path: Vec::new(), kind: FailureKind::Missing }
+    }
+
+    fn within(mut self, segment: String) -> Self {
+        self.path.push(segment);
+        self
+    }
+
+    fn into_error(self, root: &str) -> DynamicError {
+        let mut path = root.to_string();
+
+        for segment in self.path.iter().rev() {
+            if !segment.starts_with('[') {
+                path.push('.');
+            }
+
+            path.push_str(segment);
+        }
+
+        match self.kind {
+            FailureKind::Missing => DynamicError::MissingField { path },
+            FailureKind::Invalid(error) => DynamicError::InvalidFlatbuffer { path, error },
+        }
+    }
+}
+
+impl From<InvalidFlatbuffer> for Failure {
+    fn from(error: InvalidFlatbuffer) -> Self {
+        Failure { path: Vec::new(), kind: FailureKind::Invalid(error) }
+    }
+}
+
+/// Verifies a buffer against the schema within the limits of the `VerifierOptions`, like the verifier of generated code.
+struct Verifier<'a> {
+    schema: &'a Schema,
+    data: &'a [u8],
+    opts: &'a VerifierOptions,
+    depth: usize,
+    tables: usize,
+    apparent_size: usize,
+}
+
+impl Verifier<'_> {
+    fn range(&mut self, pos: usize, len: usize) -> Result<(), InvalidFlatbuffer> {
+        if pos.checked_add(len).map_or(true, |end| end > self.data.len()) {
+            return Err(out_of_bounds(pos, len));
+        }
+
+        self.apparent_size = self.apparent_size.saturating_add(len);
+
+        if self.apparent_size > self.opts.max_apparent_size {
+            return Err(InvalidFlatbuffer::ApparentSizeTooLarge);
+        }
+
+        Ok(())
+    }
+
+    fn verify_table(&mut self, object: &Object, loc: Result<usize, InvalidFlatbuffer>) -> Result<(), Failure> {
+        let loc = loc?;
+
+        self.depth = self.depth.saturating_add(1);
+        self.tables = self.tables.saturating_add(1);
+
+        if self.depth > self.opts.max_depth {
+            return Err(InvalidFlatbuffer::DepthLimitReached.into());
+        }
+
+        if self.tables > self.opts.max_tables {
+            return Err(InvalidFlatbuffer::TooManyTables.into());
+        }
+
+        let vtable = vtable(self.data, loc)?;
+        let vtable_len = u16::from_le_bytes(read(self.data, vtable)?);
+        let table_len = u16::from_le_bytes(read(self.data, vtable.saturating_add(2))?);
+        self.range(vtable, usize::from(vtable_len))?;
+        self.range(loc, usize::from(table_len))?;
+
+        for field in &object.fields {
+            self.verify_field(object, field, loc).map_err(|failure| failure.within(field.name.clone()))?;
+        }
+
+        self.depth = self.depth.saturating_sub(1);
+
+        Ok(())
+    }
+
+    fn verify_field(&mut self, object: &Object, field: &Field, loc: usize) -> Result<(), Failure> {
+        let pos = match field_pos(self.data, loc, field.offset)? {
+            Some(pos) => pos,
+            None if field.required => return Err(Failure::missing()),
+            None => return Ok(()),
+        };
+
+        if field.ty.base_type != BaseType::Union {
+            return self.verify_inline(field.ty.base_type, &field.ty, pos);
+        }
+
+        self.range(pos, 4)?;
+
+        // The member table of a union is selected by the `{FIELD_NAME}_type` field.
+        let union_type = object.field(&format!("{}_type", field.name))
+            .map(|type_field| field_pos(self.data, loc, type_field.offset))
+            .transpose()?
+            .flatten()
+            .map(|pos| read::<1>(self.data, pos))
+            .transpose()?;
+
+        let member = union_type
+            .and_then(|union_type| self.schema.enum_of(&field.ty)?.value_of(i64::from(u8::from_le_bytes(union_type))))
+            .and_then(|member| self.schema.object_of(member.union_type.as_ref()?));
+
+        match member {
+            Some(member) if !member.is_struct => self.verify_table(member, follow(self.data, pos)),
+            _ => Err(InvalidFlatbuffer::InconsistentUnion { field: "union", field_type: "union_type", error_trace: Default::default() }.into()),
+        }
+    }
+
+    fn verify_inline(&mut self, base_type: BaseType, ty: &Type, pos: usize) -> Result<(), Failure> {
+        let view = View { schema: self.schema, data: self.data };
+
+        // Types added to reflection.fbs after this version of the crate are skipped, their values read as absent.
+        let size = match view.inline_size(base_type, ty) {
+            Some(size) => size,
+            None => return Ok(()),
+        };
+        self.range(pos, size)?;
+
+        match base_type {
+            BaseType::String => self.verify_string(follow(self.data, pos)?),
+            BaseType::Vector => self.verify_vector(ty, follow(self.data, pos)?),
+            BaseType::Obj => match self.schema.objects.get(ty.index.unwrap_or(usize::MAX)) {
+                Some(object) if !object.is_struct => self.verify_table(object, follow(self.data, pos)),
+                _ => Ok(()),
+            },
+            _ => Ok(()),
+        }
+    }
+
+    fn verify_string(&mut self, loc: usize) -> Result<(), Failure> {
+        let len = string(self.data, loc)?.len();
+        self.range(loc, len.saturating_add(4))?;
+
+        let terminator = loc.saturating_add(4).saturating_add(len);
+
+        if !self.opts.ignore_missing_null_terminator && self.data.get(terminator) != Some(&0) {
+            let start = loc.saturating_add(4);
+            return Err(InvalidFlatbuffer::MissingNullTerminator { range: start..terminator, error_trace: Default::default() }.into());
+        }
+
+        Ok(())
+    }
+
+    fn verify_vector(&mut self, ty: &Type, loc: usize) -> Result<(), Failure> {
+        let view = View { schema: self.schema, data: self.data };
+        let len = to_usize(u32::from_le_bytes(read(self.data, loc)?));
+
+        let size = match view.inline_size(ty.element, ty) {
+            Some(size) => size,
+            None => return Ok(()),
+        };
+        let start = loc.saturating_add(4);
+        self.range(start, len.saturating_mul(size))?;
+
+        if matches!(ty.element, BaseType::String | BaseType::Obj) {
+            for index in 0..len {
+                let pos = start.saturating_add(index.saturating_mul(size));
+                self.verify_inline(ty.element, ty, pos).map_err(|failure| failure.within(format!("[{}]", index)))?;
+            }
+        }
+
+        Ok(())
+    }
+}
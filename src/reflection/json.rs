@@ -0,0 +1,502 @@
+//! JSON import of FlatBuffers through a runtime [Schema].
+//!
+//! [Schema::json_to_flatbuffer()] parses FlatBuffers JSON, as written by `flatc --json --strict-json`, against the schema
+//! and builds the binary FlatBuffer of its root type. [from_json()] additionally verifies the result as a wrapper,
+//! e.g. to create fixtures or accept messages from admin endpoints in human-editable form.
+//!
+//! Enum and union type values may be given by name, `bit_flags` values as space separated names.
+//! Unions are given as a `{FIELD_NAME}_type` field next to the table of the member.
+//! Unknown fields and missing required fields are rejected like flatc does.
+//! Vectors of unions and 64-bit offsets are not supported.
+//!
+//! Requires the `json` feature.
+//!
+//! # Example
+//! ```
+//! use flatbuffers_owned::reflection::{json, Schema};
+//!
+//! let schema = Schema::from_bfbs(&std::fs::read("monster.bfbs")?)?;
+//!
+//! let monster: OwnedMonster = json::from_json(&schema, r#"{ "name": "Orc", "hp": 80, "color": "Red" }"#)?;
+//! ```
+
+use std::cmp::Reverse;
+use std::convert::{TryFrom, TryInto};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use flatbuffers::{FlatBufferBuilder, Push, UnionWIPOffset, VOffsetT, WIPOffset};
+use serde_json::{Map, Value};
+use crate::{RelaxedFlatBufferTrait, VerifyError};
+use super::{BaseType, Field, Object, Schema, Type};
+
+/// Parses the JSON as the root type of the schema and verifies the FlatBuffer as the wrapper `F`.
+///
+/// The buffer of the wrapper has to be constructible from a `Vec<u8>`, e.g. `Box<[u8]>`, `Arc<[u8]>` or `Bytes`.
+/// Fails with [JsonError::InvalidFlatbuffer] if the root type of the schema does not match the FlatBuffer of the wrapper.
+pub fn from_json<F>(schema: &Schema, json: &str) -> Result<F, JsonError>
+    where F: RelaxedFlatBufferTrait,
+          F::Buffer: From<Vec<u8>>
+{
+    Ok(F::new(F::Buffer::from(schema.json_to_flatbuffer(json)?))?)
+}
+
+impl Schema {
+    /// Parses the JSON as the root type of the schema and builds its FlatBuffer,
+    /// finished with the file identifier of the schema if it declares one.
+    pub fn json_to_flatbuffer(&self, json: &str) -> Result<Vec<u8>, JsonError> {
+        let root = self.root_table().ok_or(JsonError::NoRootTable)?;
+
+        self.encode(root, json)
+    }
+
+    /// Parses the JSON as the table with the fully qualified name and builds its FlatBuffer.
+    pub fn json_to_flatbuffer_as(&self, type_name: &str, json: &str) -> Result<Vec<u8>, JsonError> {
+        let object = self.object(type_name)
+            .filter(|object| !object.is_struct)
+            .ok_or_else(|| JsonError::UnknownTable(type_name.to_string()))?;
+
+        self.encode(object, json)
+    }
+
+    fn encode(&self, object: &Object, json: &str) -> Result<Vec<u8>, JsonError> {
+        let value = serde_json::from_str::<Value>(json)?;
+
+        let mut encoder = Encoder { schema: self, fbb: FlatBufferBuilder::new() };
+        let root = encoder.table(object, &value, "")?;
+
+        let file_ident = self.file_ident.as_deref().filter(|ident| ident.len() == 4);
+        encoder.fbb.finish(WIPOffset::<UnionWIPOffset>::new(root), file_ident);
+
+        Ok(encoder.fbb.finished_data().to_vec())
+    }
+}
+
+/// The error returned when JSON cannot be converted into a FlatBuffer.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum JsonError {
+    /// The text is not valid JSON.
+    Syntax(serde_json::Error),
+    /// The schema does not declare a root type.
+    NoRootTable,
+    /// The schema has no table with the name.
+    UnknownTable(String),
+    /// The field at the path is not declared in the schema.
+    UnknownField { path: String },
+    /// The required field at the path is missing.
+    MissingField { path: String },
+    /// The value at the path does not fit the type of its field, e.g. a string for an integer, an integer out of range or an unknown enum value.
+    InvalidValue { path: String, expected: &'static str },
+    /// The type of the field at the path is not supported, e.g. a vector of unions.
+    Unsupported { path: String },
+    /// The built FlatBuffer failed the verification as the requested wrapper.
+    InvalidFlatbuffer(VerifyError),
+}
+
+impl Display for JsonError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JsonError::Syntax(e) => Display::fmt(e, f),
+            JsonError::NoRootTable => write!(f, "The schema does not declare a root type"),
+            JsonError::UnknownTable(name) => write!(f, "Unknown table {}", name),
+            JsonError::UnknownField { path } => write!(f, "Unknown field {}", path),
+            JsonError::MissingField { path } => write!(f, "Missing required field {}", path),
+            JsonError::InvalidValue { path, expected } => write!(f, "Expected {} for {}", expected, path),
+            JsonError::Unsupported { path } => write!(f, "Unsupported type of field {}", path),
+            JsonError::InvalidFlatbuffer(e) => Display::fmt(e, f),
+        }
+    }
+}
+
+impl Error for JsonError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            JsonError::Syntax(e) => Some(e),
+            JsonError::InvalidFlatbuffer(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for JsonError {
+    fn from(e: serde_json::Error) -> Self {
+        JsonError::Syntax(e)
+    }
+}
+
+impl From<VerifyError> for JsonError {
+    fn from(e: VerifyError) -> Self {
+        JsonError::InvalidFlatbuffer(e)
+    }
+}
+
+fn child_path(path: &str, name: &str) -> String {
+    if path.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", path, name)
+    }
+}
+
+fn invalid(path: &str, expected: &'static str) -> JsonError {
+    JsonError::InvalidValue { path: path.to_string(), expected }
+}
+
+fn unsupported(path: &str) -> JsonError {
+    JsonError::Unsupported { path: path.to_string() }
+}
+
+fn alignment(align: i32) -> usize {
+    match align {
+        ..=1 => 1,
+        2 => 2,
+        3..=4 => 4,
+        _ => 8,
+    }
+}
+
+/// An unsigned integer pushed as a chunk of little-endian bytes, sized like the alignment of the bytes.
+trait Chunk: Push + Copy + 'static {
+    const SIZE: usize;
+
+    fn from_le(bytes: &[u8]) -> Self;
+}
+
+macro_rules! chunk {
+    ($($ty:ty),*) => {$(
+        impl Chunk for $ty {
+            const SIZE: usize = core::mem::size_of::<$ty>();
+
+            fn from_le(bytes: &[u8]) -> Self {
+                <$ty>::from_le_bytes(bytes.try_into().unwrap_or_default())
+            }
+        }
+    )*};
+}
+
+chunk!(u8, u16, u32, u64);
+
+/// A scalar or struct of a table, pushed after its offset fields are built.
+struct Inline<'a> {
+    field: &'a Field,
+    bytes: Vec<u8>,
+    align: usize,
+}
+
+struct Encoder<'s> {
+    schema: &'s Schema,
+    fbb: FlatBufferBuilder<'static>,
+}
+
+impl<'s> Encoder<'s> {
+    /// Builds the table and returns its offset.
+    fn table(&mut self, object: &Object, value: &Value, path: &str) -> Result<u32, JsonError> {
+        let map = value.as_object().ok_or_else(|| invalid(path, "an object"))?;
+        self.check_fields(object, map, path)?;
+
+        let mut inline = Vec::new();
+        let mut offsets = Vec::new();
+
+        for field in &object.fields {
+            let field_path = child_path(path, &field.name);
+
+            let value = match map.get(&field.name).filter(|value| !value.is_null()) {
+                Some(value) => value,
+                None if field.required => return Err(JsonError::MissingField { path: field_path }),
+                None => continue,
+            };
+
+            match field.ty.base_type {
+                BaseType::String => {
+                    let string = value.as_str().ok_or_else(|| invalid(&field_path, "a string"))?;
+                    offsets.push((field.offset, self.fbb.create_string(string).value()));
+                },
+                BaseType::Vector => offsets.push((field.offset, self.vector(&field.ty, value, &field_path)?)),
+                BaseType::Union => offsets.push((field.offset, self.union(field, map, value, &field_path)?)),
+                BaseType::Obj => {
+                    let object = self.object_of(&field.ty, &field_path)?;
+
+                    if object.is_struct {
+                        let mut bytes = Vec::new();
+                        self.write_struct(object, value, &field_path, &mut bytes)?;
+                        inline.push(Inline { field, bytes, align: alignment(object.minalign) });
+                    } else {
+                        offsets.push((field.offset, self.table(object, value, &field_path)?));
+                    }
+                },
+                base_type if base_type.is_scalar() => {
+                    let mut bytes = Vec::new();
+                    self.write_scalar(base_type, field.ty.index, value, &field_path, &mut bytes)?;
+
+                    if field.optional || bytes != default_bytes(field) {
+                        let align = bytes.len();
+                        inline.push(Inline { field, bytes, align });
+                    }
+                },
+                _ => return Err(JsonError::Unsupported { path: field_path }),
+            }
+        }
+
+        // Larger values first, keeping the padding between the fields small.
+        inline.sort_by_key(|inline| Reverse(inline.align));
+
+        let start = self.fbb.start_table();
+
+        for Inline { field, bytes, align } in &inline {
+            self.push_slot_bytes(field.offset, bytes, *align);
+        }
+
+        for (slot, offset) in offsets {
+            self.fbb.push_slot_always(slot, WIPOffset::<UnionWIPOffset>::new(offset));
+        }
+
+        Ok(self.fbb.end_table(start).value())
+    }
+
+    fn check_fields(&self, object: &Object, map: &Map<String, Value>, path: &str) -> Result<(), JsonError> {
+        match map.keys().find(|name| object.field(name).map_or(true, |field| field.deprecated)) {
+            Some(name) => Err(JsonError::UnknownField { path: child_path(path, name) }),
+            None => Ok(()),
+        }
+    }
+
+    fn object_of(&self, ty: &Type, path: &str) -> Result<&'s Object, JsonError> {
+        self.schema.object_of(ty).ok_or_else(|| unsupported(path))
+    }
+
+    /// Builds the member table of a union, selected by the `{FIELD_NAME}_type` field next to it.
+    fn union(&mut self, field: &Field, map: &Map<String, Value>, value: &Value, path: &str) -> Result<u32, JsonError> {
+        let type_field = format!("{}_type", field.name);
+        let type_path = format!("{}_type", path);
+
+        let union_type = match map.get(&type_field) {
+            Some(union_type) => self.integer(field.ty.index, union_type, &type_path)?,
+            None => return Err(JsonError::MissingField { path: type_path }),
+        };
+
+        let member = i64::try_from(union_type).ok()
+            .and_then(|union_type| self.schema.enum_of(&field.ty)?.value_of(union_type))
+            .and_then(|member| member.union_type.as_ref())
+            .ok_or_else(|| invalid(&type_path, "a member of the union"))?;
+
+        match member.base_type {
+            BaseType::Obj => {
+                let object = self.object_of(member, path)?;
+
+                if object.is_struct {
+                    return Err(unsupported(path));
+                }
+
+                self.table(object, value, path)
+            },
+            _ => Err(unsupported(path)),
+        }
+    }
+
+    /// Builds the vector and returns its offset.
+    fn vector(&mut self, ty: &Type, value: &Value, path: &str) -> Result<u32, JsonError> {
+        let elements = value.as_array().ok_or_else(|| invalid(path, "an array"))?;
+        let element_path = |i: usize| format!("{}[{}]", path, i);
+
+        match ty.element {
+            BaseType::String => {
+                let strings = elements.iter().enumerate()
+                    .map(|(i, element)| element.as_str().ok_or_else(|| invalid(&element_path(i), "a string")))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let offsets = strings.iter().map(|string| self.fbb.create_string(string)).collect::<Vec<_>>();
+
+                Ok(self.fbb.create_vector(&offsets).value())
+            },
+            BaseType::Obj => {
+                let object = self.object_of(ty, path)?;
+
+                if object.is_struct {
+                    let mut bytes = Vec::new();
+
+                    for (i, element) in elements.iter().enumerate() {
+                        self.write_struct(object, element, &element_path(i), &mut bytes)?;
+                    }
+
+                    Ok(self.push_vector_bytes(&bytes, alignment(object.minalign), elements.len()))
+                } else {
+                    let offsets = elements.iter().enumerate()
+                        .map(|(i, element)| self.table(object, element, &element_path(i)).map(WIPOffset::<UnionWIPOffset>::new))
+                        .collect::<Result<Vec<_>, _>>()?;
+
+                    Ok(self.fbb.create_vector(&offsets).value())
+                }
+            },
+            element if element.is_scalar() => {
+                let mut bytes = Vec::new();
+
+                for (i, value) in elements.iter().enumerate() {
+                    self.write_scalar(element, ty.index, value, &element_path(i), &mut bytes)?;
+                }
+
+                Ok(self.push_vector_bytes(&bytes, element.scalar_size().unwrap_or(1), elements.len()))
+            },
+            _ => Err(unsupported(path)),
+        }
+    }
+
+    /// Appends the struct with its padding to the bytes. All fields of a struct are required.
+    fn write_struct(&self, object: &Object, value: &Value, path: &str, out: &mut Vec<u8>) -> Result<(), JsonError> {
+        let map = value.as_object().ok_or_else(|| invalid(path, "an object"))?;
+        self.check_fields(object, map, path)?;
+
+        let start = out.len();
+
+        for field in object.fields_by_id() {
+            let field_path = child_path(path, &field.name);
+            let value = map.get(&field.name)
+                .filter(|value| !value.is_null())
+                .ok_or_else(|| JsonError::MissingField { path: field_path.clone() })?;
+
+            out.resize(start.saturating_add(usize::from(field.offset)), 0);
+
+            match (field.ty.base_type, field.ty.element) {
+                (BaseType::Obj, _) => self.write_struct(self.object_of(&field.ty, &field_path)?, value, &field_path, out)?,
+                (BaseType::Array, element) => {
+                    let elements = value.as_array()
+                        .filter(|elements| elements.len() == usize::from(field.ty.fixed_length))
+                        .ok_or_else(|| invalid(&field_path, "an array of the fixed length"))?;
+
+                    for (i, value) in elements.iter().enumerate() {
+                        let element_path = format!("{}[{}]", field_path, i);
+
+                        if element == BaseType::Obj {
+                            self.write_struct(self.object_of(&field.ty, &field_path)?, value, &element_path, out)?;
+                        } else if element.is_scalar() {
+                            self.write_scalar(element, field.ty.index, value, &element_path, out)?;
+                        } else {
+                            return Err(unsupported(&field_path));
+                        }
+                    }
+                },
+                (base_type, _) if base_type.is_scalar() => self.write_scalar(base_type, field.ty.index, value, &field_path, out)?,
+                _ => return Err(JsonError::Unsupported { path: field_path }),
+            }
+        }
+
+        out.resize(start.saturating_add(usize::try_from(object.bytesize).unwrap_or_default()), 0);
+
+        Ok(())
+    }
+
+    /// Appends the little-endian bytes of the scalar, resolving enum names through the enum at `index`.
+    fn write_scalar(&self, base_type: BaseType, index: Option<usize>, value: &Value, path: &str, out: &mut Vec<u8>) -> Result<(), JsonError> {
+        macro_rules! integer {
+            ($ty:ty) => {{
+                let integer = self.integer(index, value, path)?;
+                let integer = <$ty>::try_from(integer).map_err(|_| invalid(path, concat!("an integer in the range of ", stringify!($ty))))?;
+                out.extend_from_slice(&integer.to_le_bytes());
+            }};
+        }
+
+        match base_type {
+            BaseType::Bool => match value {
+                Value::Bool(value) => out.push(u8::from(*value)),
+                _ => integer!(u8),
+            },
+            BaseType::Byte => integer!(i8),
+            BaseType::UByte | BaseType::UType => integer!(u8),
+            BaseType::Short => integer!(i16),
+            BaseType::UShort => integer!(u16),
+            BaseType::Int => integer!(i32),
+            BaseType::UInt => integer!(u32),
+            BaseType::Long => integer!(i64),
+            BaseType::ULong => integer!(u64),
+            BaseType::Float => out.extend_from_slice(&(float(value, path)? as f32).to_le_bytes()),
+            BaseType::Double => out.extend_from_slice(&float(value, path)?.to_le_bytes()),
+            _ => return Err(unsupported(path)),
+        }
+
+        Ok(())
+    }
+
+    /// Parses an integer, a boolean, or the space separated names of values of the enum at `index`.
+    fn integer(&self, index: Option<usize>, value: &Value, path: &str) -> Result<i128, JsonError> {
+        match value {
+            Value::Bool(value) => Ok(i128::from(*value)),
+            Value::Number(number) => number.as_i64().map(i128::from)
+                .or_else(|| number.as_u64().map(i128::from))
+                .ok_or_else(|| invalid(path, "an integer")),
+            Value::String(names) => {
+                let enum_def = index.and_then(|index| self.schema.enums.get(index))
+                    .ok_or_else(|| invalid(path, "an integer"))?;
+
+                names.split_whitespace()
+                    .map(|name| enum_def.value_by_name(name).map(|value| i128::from(value.value)))
+                    .try_fold(0, |flags, value| value.map(|value| flags | value))
+                    .ok_or_else(|| invalid(path, "a value of the enum"))
+            },
+            _ => Err(invalid(path, "an integer")),
+        }
+    }
+
+    /// Pushes the bytes of a scalar or struct into the current table, aligned to `align`.
+    fn push_slot_bytes(&mut self, slot: VOffsetT, bytes: &[u8], align: usize) {
+        match align {
+            1 => push_slot_chunks::<u8>(&mut self.fbb, slot, bytes),
+            2 => push_slot_chunks::<u16>(&mut self.fbb, slot, bytes),
+            4 => push_slot_chunks::<u32>(&mut self.fbb, slot, bytes),
+            _ => push_slot_chunks::<u64>(&mut self.fbb, slot, bytes),
+        }
+    }
+
+    /// Pushes a vector of `len` scalars or structs and returns its offset.
+    fn push_vector_bytes(&mut self, bytes: &[u8], align: usize, len: usize) -> u32 {
+        match align {
+            1 => push_vector_chunks::<u8>(&mut self.fbb, bytes, len),
+            2 => push_vector_chunks::<u16>(&mut self.fbb, bytes, len),
+            4 => push_vector_chunks::<u32>(&mut self.fbb, bytes, len),
+            _ => push_vector_chunks::<u64>(&mut self.fbb, bytes, len),
+        }
+    }
+}
+
+fn float(value: &Value, path: &str) -> Result<f64, JsonError> {
+    match value {
+        Value::Number(number) => number.as_f64(),
+        // Like flatc, accepts "nan", "inf" and "-inf".
+        Value::String(string) => string.parse().ok(),
+        _ => None,
+    }.ok_or_else(|| invalid(path, "a number"))
+}
+
+fn default_bytes(field: &Field) -> Vec<u8> {
+    let integer = field.default_integer;
+
+    match field.ty.base_type {
+        BaseType::Bool => vec![u8::from(integer != 0)],
+        BaseType::Byte | BaseType::UByte | BaseType::UType => vec![integer as u8],
+        BaseType::Short | BaseType::UShort => (integer as u16).to_le_bytes().to_vec(),
+        BaseType::Int | BaseType::UInt => (integer as u32).to_le_bytes().to_vec(),
+        BaseType::Float => (field.default_real as f32).to_le_bytes().to_vec(),
+        BaseType::Double => field.default_real.to_le_bytes().to_vec(),
+        _ => integer.to_le_bytes().to_vec(),
+    }
+}
+
+/// Pushes the bytes back to front, so the builder keeps them contiguous, and tracks the first chunk as the field.
+fn push_slot_chunks<C: Chunk>(fbb: &mut FlatBufferBuilder<'_>, slot: VOffsetT, bytes: &[u8]) {
+    let mut chunks = bytes.chunks(C::SIZE).map(C::from_le).rev().peekable();
+
+    while let Some(chunk) = chunks.next() {
+        if chunks.peek().is_some() {
+            fbb.push(chunk);
+        } else {
+            fbb.push_slot_always(slot, chunk);
+        }
+    }
+}
+
+fn push_vector_chunks<C: Chunk>(fbb: &mut FlatBufferBuilder<'_>, bytes: &[u8], len: usize) -> u32 {
+    fbb.start_vector::<C>(bytes.len().checked_div(C::SIZE).unwrap_or_default());
+
+    for chunk in bytes.chunks(C::SIZE).map(C::from_le).rev() {
+        fbb.push(chunk);
+    }
+
+    fbb.end_vector::<C>(len).value()
+}
@@ -0,0 +1,319 @@
+//! Global decode registry.
+//!
+//! Wrapped FlatBuffers declared with the `register` option of the [flatbuffers_owned!](crate::flatbuffers_owned) macro
+//! are collected into a process-wide registry at link time. \
+//! This allows decoding arbitrary incoming buffers by their file identifier from the set of all registered types,
+//! without maintaining a hand-written match or a [flatbuffers_owned_enum!](crate::flatbuffers_owned_enum).
+//!
+//! Requires the `registry` feature.
+//!
+//! # Example
+//! ```
+//! use flatbuffers_owned::flatbuffers_owned;
+//! use flatbuffers_owned::registry;
+//!
+//! flatbuffers_owned!(Login { register: LOGIN_IDENTIFIER }, Logout { register: "LOUT" });
+//!
+//! let decoded = registry::decode(message_bytes)?;
+//! if let Some(login) = decoded.downcast_ref::<OwnedLogin>() {
+//!     // ...
+//! }
+//! ```
+//!
+//! # Dynamic types
+//! Types unknown at compile time, e.g. loaded by plugins, are registered at runtime into a [DynamicResolver]. \
+//! Any [TypeResolver] can be passed to [decode_with()], and resolvers are chained as tuples.
+//! ```
+//! use flatbuffers_owned::registry::{self, DynamicResolver, StaticResolver};
+//!
+//! let plugins = DynamicResolver::new();
+//! plugins.register("PLGN", registry::decoder::<OwnedPluginMessage>());
+//!
+//! let decoded = registry::decode_with(&(StaticResolver, &plugins), message_bytes)?;
+//! ```
+//!
+//! # Schemas
+//! With the `reflection` feature, a [SchemaRegistry] maps file identifiers and type names to `.bfbs` schemas loaded at runtime. \
+//! Buffers without a compiled type are then accessed through a [DynamicFlatBuffer](crate::reflection::dynamic::DynamicFlatBuffer).
+//! ```
+//! use flatbuffers_owned::registry::{self, SchemaRegistry, StaticResolver};
+//!
+//! let schemas = SchemaRegistry::new();
+//! schemas.load_bfbs(&std::fs::read("monster.bfbs")?)?;
+//!
+//! let monster = schemas.decode_dynamic(message_bytes)?;
+//! let hp = monster.get_field("hp")?;
+//!
+//! // Registered decoders chain with the other resolvers.
+//! let decoded = registry::decode_with(&(StaticResolver, &schemas), message_bytes)?;
+//! ```
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+#[cfg(feature = "reflection")]
+use std::sync::Arc;
+use crate::{DecodeError, RelaxedFlatBufferTrait, VerifyError};
+#[cfg(feature = "reflection")]
+use crate::reflection::Schema;
+#[cfg(feature = "reflection")]
+use crate::reflection::dynamic::{DynamicError, DynamicFlatBuffer};
+
+/// Verifies the bytes as a FlatBuffer and returns its boxed `Owned{FLATBUFFER_NAME}` wrapper.
+pub type DecodeFn = fn(Box<[u8]>) -> Result<Box<dyn Any + Send + Sync>, VerifyError>;
+
+/// A wrapped FlatBuffer registered with the `register` macro option.
+#[derive(Debug)]
+pub struct Registration {
+    /// The name of the FlatBuffer, e.g. `Message`.
+    pub name: &'static str,
+    /// The file identifier the FlatBuffer is decoded by.
+    pub identifier: &'static str,
+    decode: DecodeFn,
+}
+
+impl Registration {
+    #[doc(hidden)]
+    pub const fn new<T>(name: &'static str, identifier: &'static str) -> Self
+        where T: RelaxedFlatBufferTrait<Buffer = Box<[u8]>> + Send + Sync + 'static
+    {
+        Self {
+            name,
+            identifier,
+            decode: decode_as::<T>,
+        }
+    }
+
+    /// Verifies the bytes as the registered FlatBuffer and returns its boxed `Owned{FLATBUFFER_NAME}` wrapper.
+    pub fn decode(&self, data: Box<[u8]>) -> Result<Box<dyn Any + Send + Sync>, VerifyError> {
+        (self.decode)(data)
+    }
+}
+
+inventory::collect!(Registration);
+
+fn decode_as<T>(data: Box<[u8]>) -> Result<Box<dyn Any + Send + Sync>, VerifyError>
+    where T: RelaxedFlatBufferTrait<Buffer = Box<[u8]>> + Send + Sync + 'static
+{
+    Ok(Box::new(T::new(data)?))
+}
+
+/// Returns the [DecodeFn] of the passed `Owned{FLATBUFFER_NAME}` type, e.g. for registering it into a [DynamicResolver].
+pub fn decoder<T>() -> DecodeFn
+    where T: RelaxedFlatBufferTrait<Buffer = Box<[u8]>> + Send + Sync + 'static
+{
+    decode_as::<T>
+}
+
+/// Returns all registered FlatBuffers, in no particular order.
+pub fn registrations() -> impl Iterator<Item = &'static Registration> {
+    inventory::iter::<Registration>.into_iter()
+}
+
+/// Returns the registration for the passed file identifier, if any.
+pub fn lookup(identifier: &str) -> Option<&'static Registration> {
+    registrations().find(|registration| registration.identifier == identifier)
+}
+
+/// Inspects the file identifier and verifies the bytes as the matching registered FlatBuffer.
+///
+/// The returned `Owned{FLATBUFFER_NAME}` wrapper can be recovered with `downcast()` or `downcast_ref()`.
+pub fn decode(data: Box<[u8]>) -> Result<Box<dyn Any + Send + Sync>, DecodeError> {
+    decode_with(&StaticResolver, data)
+}
+
+/// Inspects the file identifier and verifies the bytes as the FlatBuffer resolved by the passed [TypeResolver].
+pub fn decode_with<R: TypeResolver + ?Sized>(resolver: &R, data: Box<[u8]>) -> Result<Box<dyn Any + Send + Sync>, DecodeError> {
+    let decode = identifier(&data)
+        .and_then(|identifier| resolver.resolve(identifier))
+        .ok_or(DecodeError::UnknownIdentifier)?;
+
+    Ok(decode(data)?)
+}
+
+fn identifier(data: &[u8]) -> Option<&str> {
+    data.get(4..8).and_then(|identifier| std::str::from_utf8(identifier).ok())
+}
+
+/// Resolves a file identifier to the [DecodeFn] of a FlatBuffer type.
+///
+/// Implemented by the [StaticResolver] for the types registered with the `register` macro option,
+/// by the [DynamicResolver] for types registered at runtime, and by tuples of resolvers, which are asked in order.
+pub trait TypeResolver {
+    fn resolve(&self, identifier: &str) -> Option<DecodeFn>;
+}
+
+impl<R: TypeResolver + ?Sized> TypeResolver for &R {
+    fn resolve(&self, identifier: &str) -> Option<DecodeFn> {
+        (**self).resolve(identifier)
+    }
+}
+
+impl<A: TypeResolver, B: TypeResolver> TypeResolver for (A, B) {
+    fn resolve(&self, identifier: &str) -> Option<DecodeFn> {
+        self.0.resolve(identifier).or_else(|| self.1.resolve(identifier))
+    }
+}
+
+/// Resolves the types registered at link time with the `register` macro option.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StaticResolver;
+
+impl TypeResolver for StaticResolver {
+    fn resolve(&self, identifier: &str) -> Option<DecodeFn> {
+        lookup(identifier).map(|registration| registration.decode)
+    }
+}
+
+/// Resolves types registered at runtime, e.g. by plugins.
+///
+/// The resolver is `Send` and `Sync`, so plugins can register their types while other threads are decoding.
+#[derive(Debug, Default)]
+pub struct DynamicResolver {
+    types: RwLock<HashMap<String, DecodeFn>>,
+}
+
+impl DynamicResolver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the [DecodeFn] under the passed file identifier and returns the previously registered one.
+    pub fn register(&self, identifier: impl Into<String>, decode: DecodeFn) -> Option<DecodeFn> {
+        self.write().insert(identifier.into(), decode)
+    }
+
+    /// Removes the [DecodeFn] registered under the passed file identifier.
+    pub fn unregister(&self, identifier: &str) -> Option<DecodeFn> {
+        self.write().remove(identifier)
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, HashMap<String, DecodeFn>> {
+        // The map stays consistent even if a panic poisoned the lock.
+        self.types.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, HashMap<String, DecodeFn>> {
+        self.types.write().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl TypeResolver for DynamicResolver {
+    fn resolve(&self, identifier: &str) -> Option<DecodeFn> {
+        self.read().get(identifier).copied()
+    }
+}
+
+/// Maps file identifiers and type names to runtime [schemas](Schema) and to [DecodeFn]s.
+///
+/// Resolves incoming buffers by their file identifier, either into a [DynamicFlatBuffer] through a registered schema,
+/// or, being a [TypeResolver], into a typed `Owned{FLATBUFFER_NAME}` wrapper through a registered [DecodeFn]. \
+/// The registry is `Send` and `Sync`, so schemas can be registered while other threads are decoding.
+///
+/// Requires the `reflection` feature.
+#[cfg(feature = "reflection")]
+#[derive(Debug, Default)]
+pub struct SchemaRegistry {
+    entries: RwLock<SchemaEntries>,
+}
+
+#[cfg(feature = "reflection")]
+#[derive(Debug, Default)]
+struct SchemaEntries {
+    identifiers: HashMap<String, Arc<Schema>>,
+    names: HashMap<String, Arc<Schema>>,
+    decoders: HashMap<String, DecodeFn>,
+}
+
+#[cfg(feature = "reflection")]
+impl SchemaRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the schema under its file identifier, if declared, and under the names of all its tables.
+    ///
+    /// Previous registrations of the same identifier or names are replaced.
+    pub fn register(&self, schema: impl Into<Arc<Schema>>) -> Arc<Schema> {
+        let schema = schema.into();
+        self.insert(schema.file_ident.clone(), schema)
+    }
+
+    /// Like [register()](Self::register), but registers the schema under the passed file identifier instead of the declared one.
+    pub fn register_as(&self, identifier: impl Into<String>, schema: impl Into<Arc<Schema>>) -> Arc<Schema> {
+        self.insert(Some(identifier.into()), schema.into())
+    }
+
+    fn insert(&self, identifier: Option<String>, schema: Arc<Schema>) -> Arc<Schema> {
+        let mut entries = self.write();
+
+        if let Some(identifier) = identifier {
+            entries.identifiers.insert(identifier, schema.clone());
+        }
+        for object in schema.objects.iter().filter(|object| !object.is_struct) {
+            entries.names.insert(object.name.clone(), schema.clone());
+        }
+
+        schema
+    }
+
+    /// Loads a binary `.bfbs` schema and [registers](Self::register) it.
+    pub fn load_bfbs(&self, bfbs: &[u8]) -> Result<Arc<Schema>, DecodeError> {
+        Ok(self.register(Schema::from_bfbs(bfbs)?))
+    }
+
+    /// Registers the [DecodeFn] under the passed file identifier and returns the previously registered one.
+    pub fn register_decoder(&self, identifier: impl Into<String>, decode: DecodeFn) -> Option<DecodeFn> {
+        self.write().decoders.insert(identifier.into(), decode)
+    }
+
+    /// Removes the schema and the [DecodeFn] registered under the passed file identifier, and returns the schema.
+    ///
+    /// The type names of the schema are unregistered as well.
+    pub fn unregister(&self, identifier: &str) -> Option<Arc<Schema>> {
+        let mut entries = self.write();
+
+        entries.decoders.remove(identifier);
+        let schema = entries.identifiers.remove(identifier)?;
+        entries.names.retain(|_, registered| !Arc::ptr_eq(registered, &schema));
+
+        Some(schema)
+    }
+
+    /// Returns the schema registered under the passed file identifier.
+    pub fn schema(&self, identifier: &str) -> Option<Arc<Schema>> {
+        self.read().identifiers.get(identifier).cloned()
+    }
+
+    /// Returns the schema declaring the table with the fully qualified name, e.g. `game.Monster`.
+    pub fn schema_by_name(&self, type_name: &str) -> Option<Arc<Schema>> {
+        self.read().names.get(type_name).cloned()
+    }
+
+    /// Returns the schema registered under the file identifier of the buffer.
+    pub fn identify(&self, data: &[u8]) -> Option<Arc<Schema>> {
+        identifier(data).and_then(|identifier| self.schema(identifier))
+    }
+
+    /// Verifies the buffer as the root table of the schema registered under its file identifier.
+    pub fn decode_dynamic<B: AsRef<[u8]>>(&self, data: B) -> Result<DynamicFlatBuffer<B, Arc<Schema>>, DynamicError> {
+        let schema = self.identify(data.as_ref()).ok_or(DynamicError::UnknownIdentifier)?;
+
+        DynamicFlatBuffer::new(data, schema)
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, SchemaEntries> {
+        self.entries.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, SchemaEntries> {
+        self.entries.write().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(feature = "reflection")]
+impl TypeResolver for SchemaRegistry {
+    fn resolve(&self, identifier: &str) -> Option<DecodeFn> {
+        self.read().decoders.get(identifier).copied()
+    }
+}
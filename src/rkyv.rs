@@ -0,0 +1,108 @@
+//! rkyv interop for owned FlatBuffers.
+//!
+//! Wrappers implement rkyv's `Archive`, `Serialize` and `Deserialize`, so they can be embedded in rkyv-archived structures. \
+//! The archived form is an [ArchivedFlatBuffer], holding the raw bytes of the FlatBuffer like an archived `Vec<u8>`. \
+//! The bytes are verified as the FlatBuffer again whenever they are accessed through [as_flatbuffer()](ArchivedFlatBuffer::as_flatbuffer),
+//! or deserialized into an `Owned{FLATBUFFER_NAME}`, since rkyv's validation only covers the byte vector itself.
+//!
+//! Requires the `rkyv` feature.
+//!
+//! # Example
+//! ```
+//! #[derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)]
+//! struct Record {
+//!     id: u64,
+//!     message: OwnedMessage,
+//! }
+//!
+//! let bytes = rkyv::to_bytes::<rkyv::rancor::Error>(&record)?;
+//!
+//! let archived = rkyv::access::<ArchivedRecord, rkyv::rancor::Error>(&bytes)?;
+//! let message = archived.message.as_flatbuffer()?;
+//! println!("{}", message.as_actual().text());
+//! ```
+
+use core::fmt::{Debug, Formatter};
+use core::marker::PhantomData;
+use ::rkyv::bytecheck::CheckBytes;
+use ::rkyv::rancor::{Fallible, Source};
+use ::rkyv::ser::{Allocator, Writer};
+use ::rkyv::vec::{ArchivedVec, VecResolver};
+use ::rkyv::{Archive, Deserialize, Place, Portable, Serialize};
+use flatbuffers::Verifiable;
+use crate::{DefaultPolicy, OwnedFlatBuffer, RelaxedFlatBufferTrait, RelaxedFollowTrait, VerifierPolicy, VerifyError};
+
+/// The archived form of an `OwnedFlatBuffer<T, B, P>`: the raw bytes of the FlatBuffer `T`, verified according to the policy `P` on access.
+#[repr(transparent)]
+pub struct ArchivedFlatBuffer<T, P = DefaultPolicy> {
+    bytes: ArchivedVec<u8>,
+    flatbuffer: PhantomData<fn() -> (T, P)>,
+}
+
+impl<T, P> ArchivedFlatBuffer<T, P> {
+    /// Returns the raw bytes, which are not verified as the FlatBuffer `T` yet.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        self.bytes.as_slice()
+    }
+}
+
+impl<T: RelaxedFollowTrait + Verifiable, P: VerifierPolicy> ArchivedFlatBuffer<T, P> {
+    /// Verifies the archived bytes as the FlatBuffer `T` according to the policy `P` and wraps them without copying.
+    pub fn as_flatbuffer(&self) -> Result<OwnedFlatBuffer<T, &[u8], P>, VerifyError> {
+        <OwnedFlatBuffer<T, &[u8], P> as RelaxedFlatBufferTrait>::new(self.as_bytes())
+    }
+}
+
+impl<T, P> Debug for ArchivedFlatBuffer<T, P> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("ArchivedFlatBuffer").field(&self.as_bytes()).finish()
+    }
+}
+
+// The struct is a transparent wrapper of the portable ArchivedVec<u8>.
+unsafe impl<T, P> Portable for ArchivedFlatBuffer<T, P> {}
+
+// Checks the byte vector only, the FlatBuffer is verified on access.
+unsafe impl<T, P, C> CheckBytes<C> for ArchivedFlatBuffer<T, P>
+    where ArchivedVec<u8>: CheckBytes<C>,
+          C: Fallible + ?Sized
+{
+    unsafe fn check_bytes(value: *const Self, context: &mut C) -> Result<(), C::Error> {
+        <ArchivedVec<u8> as CheckBytes<C>>::check_bytes(value.cast(), context)
+    }
+}
+
+impl<T: RelaxedFollowTrait + Verifiable, B: AsRef<[u8]>, P: VerifierPolicy> Archive for OwnedFlatBuffer<T, B, P> {
+    type Archived = ArchivedFlatBuffer<T, P>;
+    type Resolver = VecResolver;
+
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        // The archived struct has the exact layout of its ArchivedVec<u8>.
+        let bytes = unsafe { out.cast_unchecked::<ArchivedVec<u8>>() };
+        ArchivedVec::<u8>::resolve_from_len(self.as_ref().len(), resolver, bytes);
+    }
+}
+
+impl<T, B, P, S> Serialize<S> for OwnedFlatBuffer<T, B, P>
+    where T: RelaxedFollowTrait + Verifiable,
+          B: AsRef<[u8]>,
+          P: VerifierPolicy,
+          S: Fallible + Allocator + Writer + ?Sized
+{
+    fn serialize(&self, serializer: &mut S) -> Result<VecResolver, S::Error> {
+        ArchivedVec::<u8>::serialize_from_slice(self.as_ref(), serializer)
+    }
+}
+
+/// Copies the archived bytes into an `Owned{FLATBUFFER_NAME}` and verifies them according to the policy `P`.
+impl<T, P, D> Deserialize<OwnedFlatBuffer<T, Box<[u8]>, P>, D> for ArchivedFlatBuffer<T, P>
+    where T: RelaxedFollowTrait + Verifiable,
+          P: VerifierPolicy,
+          D: Fallible + ?Sized,
+          D::Error: Source
+{
+    fn deserialize(&self, _: &mut D) -> Result<OwnedFlatBuffer<T, Box<[u8]>, P>, D::Error> {
+        <OwnedFlatBuffer<T, Box<[u8]>, P> as RelaxedFlatBufferTrait>::new(self.as_bytes().into()).map_err(D::Error::new)
+    }
+}
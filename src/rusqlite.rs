@@ -0,0 +1,34 @@
+//! rusqlite `ToSql` and `FromSql` implementations, storing FlatBuffers in `BLOB` columns.
+//!
+//! Wrappers bind as blobs without copying the bytes. \
+//! `Owned{FLATBUFFER_NAME}` wrappers are read from blob columns and verified before they are returned,
+//! a failed verification is reported as `FromSqlError::Other` holding the [VerifyError](crate::VerifyError).
+//!
+//! Requires the `rusqlite` feature.
+//!
+//! # Example
+//! ```
+//! connection.execute("INSERT INTO messages (id, message) VALUES (?1, ?2)", (id, &owned_message))?;
+//!
+//! let message: OwnedMessage = connection.query_row("SELECT message FROM messages WHERE id = ?1", [id], |row| row.get(0))?;
+//! ```
+
+use flatbuffers::Verifiable;
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+use crate::{OwnedFlatBuffer, RelaxedFlatBufferTrait, RelaxedFollowTrait, VerifierPolicy};
+
+/// Binds the bytes as a blob without copying them.
+impl<T: RelaxedFollowTrait + Verifiable, B: AsRef<[u8]>, P: VerifierPolicy> ToSql for OwnedFlatBuffer<T, B, P> {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::Borrowed(ValueRef::Blob(self.as_ref())))
+    }
+}
+
+/// Copies the blob and verifies it according to the policy `P`.
+impl<T: RelaxedFollowTrait + Verifiable, P: VerifierPolicy> FromSql for OwnedFlatBuffer<T, Box<[u8]>, P> {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        let data = value.as_blob()?;
+
+        <Self as RelaxedFlatBufferTrait>::new(data.into()).map_err(|e| FromSqlError::Other(Box::new(e)))
+    }
+}
@@ -0,0 +1,21 @@
+//! Zeroizing wrappers for sensitive FlatBuffers.
+//!
+//! Requires the `zeroize` feature.
+
+use alloc::boxed::Box;
+use flatbuffers::Verifiable;
+use zeroize::{ZeroizeOnDrop, Zeroizing};
+use crate::buffer::SecretBuffer;
+use crate::{OwnedFlatBuffer, RelaxedFollowTrait, VerifierPolicy};
+
+// Dropping the wrapper drops the buffer, which zeroizes itself.
+impl<T: RelaxedFollowTrait + Verifiable, B: AsRef<[u8]> + ZeroizeOnDrop, P: VerifierPolicy> ZeroizeOnDrop for OwnedFlatBuffer<T, B, P> {}
+
+impl<T: RelaxedFollowTrait + Verifiable, P: VerifierPolicy> OwnedFlatBuffer<T, Box<[u8]>, P> {
+    /// Converts the wrapper into a `Secret{FLATBUFFER_NAME}`, which zeroizes its bytes when it is dropped.
+    ///
+    /// The boxed bytes are moved, not copied, so no unzeroized copy is left behind. The bytes are not verified again.
+    pub fn into_secret(self) -> OwnedFlatBuffer<T, SecretBuffer, P> {
+        self.map_buffer(Zeroizing::new)
+    }
+}
@@ -0,0 +1,111 @@
+//! Structured serde support through the flatc object API.
+//!
+//! Wrappers of FlatBuffers implementing the [ObjectApiTrait] are serialized field by field,
+//! by unpacking them into their native struct, and deserialized by packing the native struct again.
+//! This renders owned FlatBuffers as structured JSON or CBOR, e.g. for debugging and admin tooling.
+//!
+//! The native struct has to implement `Serialize` and `Deserialize`, which the `{FLATBUFFER_NAME}T` structs generated by flatc do not. \
+//! Either add the derives when post-processing the generated code, or implement the [ObjectApiTrait] by hand
+//! with your own native struct instead of declaring the `object_api` option:
+//! ```
+//! #[derive(Serialize, Deserialize)]
+//! pub struct MessageView {
+//!     pub text: Option<String>,
+//! }
+//!
+//! flatbuffers_owned!(Message);
+//!
+//! impl ObjectApiTrait for Message<'static> {
+//!     type Native = MessageView;
+//!
+//!     fn unpack<'a>(actual: Message<'a>) -> MessageView {
+//!         MessageView { text: actual.text().map(str::to_string) }
+//!     }
+//!
+//!     fn pack(builder: &mut FlatBufferBuilder<'static>, native: &MessageView) -> WIPOffset<Self> {
+//!         let text = native.text.as_deref().map(|text| builder.create_string(text));
+//!         Message::create(builder, &MessageArgs { text })
+//!     }
+//! }
+//! ```
+//!
+//! [Structured] wraps a single wrapper, the [structured] module plugs into `#[serde(with)]` fields.
+//!
+//! Requires the `serde` feature.
+//!
+//! # Example
+//! ```
+//! use flatbuffers_owned::serde::Structured;
+//!
+//! let json = serde_json::to_string(&Structured(&owned_message))?;
+//! let Structured(owned_message): Structured<OwnedMessage> = serde_json::from_str(&json)?;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct AdminView {
+//!     #[serde(with = "flatbuffers_owned::serde::structured")]
+//!     message: OwnedMessage,
+//! }
+//! ```
+
+use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+use crate::{ObjectApiTrait, OwnedFlatBuffer, VerifierPolicy};
+
+/// Serializes and deserializes the wrapper `W` through the object API of its FlatBuffer.
+///
+/// Serializing accepts a wrapper over any buffer, by value or by reference.
+/// Deserializing packs a new `Owned{FLATBUFFER_NAME}`, which is built by a fresh builder and therefore not verified again.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Structured<W>(pub W);
+
+impl<T: ObjectApiTrait, B: AsRef<[u8]>, P: VerifierPolicy> Serialize for Structured<OwnedFlatBuffer<T, B, P>>
+    where T::Native: Serialize
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        structured::serialize(&self.0, serializer)
+    }
+}
+
+impl<T: ObjectApiTrait, B: AsRef<[u8]>, P: VerifierPolicy> Serialize for Structured<&OwnedFlatBuffer<T, B, P>>
+    where T::Native: Serialize
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        structured::serialize(self.0, serializer)
+    }
+}
+
+impl<'de, T: ObjectApiTrait, P: VerifierPolicy> Deserialize<'de> for Structured<OwnedFlatBuffer<T, Box<[u8]>, P>>
+    where T::Native: Deserialize<'de>
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        structured::deserialize(deserializer).map(Structured)
+    }
+}
+
+/// The functions for `#[serde(with = "flatbuffers_owned::serde::structured")]` fields holding a wrapper.
+pub mod structured {
+    use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use crate::{ObjectApiTrait, OwnedFlatBuffer, VerifierPolicy};
+
+    type Packed<T, P> = OwnedFlatBuffer<T, Box<[u8]>, P>;
+
+    /// Serializes the unpacked native struct of the wrapper.
+    pub fn serialize<T, B, P, S>(flatbuffer: &OwnedFlatBuffer<T, B, P>, serializer: S) -> Result<S::Ok, S::Error>
+        where T: ObjectApiTrait,
+              T::Native: Serialize,
+              B: AsRef<[u8]>,
+              P: VerifierPolicy,
+              S: Serializer
+    {
+        flatbuffer.unpack().serialize(serializer)
+    }
+
+    /// Deserializes the native struct and packs it into a new owned FlatBuffer.
+    pub fn deserialize<'de, T, P, D>(deserializer: D) -> Result<Packed<T, P>, D::Error>
+        where T: ObjectApiTrait,
+              T::Native: Deserialize<'de>,
+              P: VerifierPolicy,
+              D: Deserializer<'de>
+    {
+        T::Native::deserialize(deserializer).map(|native| OwnedFlatBuffer::pack(&native))
+    }
+}
@@ -0,0 +1,148 @@
+//! Shared-memory segment buffers for cross-process use.
+//!
+//! A publisher writes a FlatBuffer into a named POSIX shared-memory segment with [SharedSegment::create()]. \
+//! Subscribers map the segment with [SharedSegment::open()] and verify and read the FlatBuffer in place, without copying it.
+//!
+//! A [SharedSegment] is a reference counted read-only mapping, so it can be cloned cheaply into any number of wrappers. \
+//! The segment stays mapped while any clone, and therefore any wrapper, exists.
+//!
+//! Requires the `shm` feature and a unix target.
+//!
+//! # Example
+//! ```
+//! use flatbuffers_owned::shm::SharedSegment;
+//!
+//! // Publisher
+//! let segment = unsafe { SharedSegment::create("/telemetry", &message_bytes)? };
+//!
+//! // Subscriber
+//! let segment = unsafe { SharedSegment::open("/telemetry")? };
+//! let message = RelaxedMessage::new(segment)?;
+//! ```
+
+use std::ffi::CString;
+use std::fmt::{Debug, Formatter};
+use std::fs::File;
+use std::io;
+use std::ops::Deref;
+use std::os::unix::io::FromRawFd;
+use std::sync::Arc;
+use memmap2::{Mmap, MmapMut};
+use stable_deref_trait::StableDeref;
+use crate::buffer::StableBuffer;
+
+/// A read-only mapping of a named POSIX shared-memory segment, usable as the buffer of any wrapper.
+///
+/// Cloning only increments a reference count, all clones share the same mapping.
+#[derive(Clone)]
+pub struct SharedSegment {
+    name: Arc<str>,
+    mmap: Arc<Mmap>,
+}
+
+impl SharedSegment {
+    /// Creates the named segment, writes the bytes into it and maps it read-only.
+    ///
+    /// Fails with `io::ErrorKind::AlreadyExists` if the segment already exists. \
+    /// The segment is not modified after this call returns, so subscribers may map it right away.
+    /// It lives until it is removed with [unlink()](SharedSegment::unlink), even after all processes unmapped it.
+    ///
+    /// # Safety
+    /// The segment must not be modified, e.g. by another process opening it writable, while it is mapped.
+    /// Otherwise the verified bytes could change underneath the wrapper.
+    pub unsafe fn create(name: &str, data: &[u8]) -> io::Result<Self> {
+        let file = shm_open(name, libc::O_CREAT | libc::O_EXCL | libc::O_RDWR)?;
+        file.set_len(data.len() as u64)?;
+
+        if !data.is_empty() {
+            // Safety: The segment was just created exclusively, and the writable mapping is dropped before any bytes are handed out.
+            let mut mmap = unsafe { MmapMut::map_mut(&file)? };
+            mmap.copy_from_slice(data);
+            mmap.flush()?;
+        }
+
+        Ok(Self {
+            name: name.into(),
+            // Safety: The caller guarantees that the segment is not modified while it is mapped.
+            mmap: Arc::new(unsafe { Mmap::map(&file)? }),
+        })
+    }
+
+    /// Maps the named segment read-only.
+    ///
+    /// # Safety
+    /// The segment must not be modified, e.g. by another process opening it writable, while it is mapped.
+    /// Otherwise the verified bytes could change underneath the wrapper.
+    pub unsafe fn open(name: &str) -> io::Result<Self> {
+        let file = shm_open(name, libc::O_RDONLY)?;
+
+        Ok(Self {
+            name: name.into(),
+            // Safety: The caller guarantees that the segment is not modified while it is mapped.
+            mmap: Arc::new(unsafe { Mmap::map(&file)? }),
+        })
+    }
+
+    /// Removes the named segment. \
+    /// Existing mappings, and therefore all wrappers, stay valid until they are dropped.
+    pub fn unlink(name: &str) -> io::Result<()> {
+        let name = segment_name(name)?;
+
+        // Safety: `name` is a valid nul-terminated string that outlives the call.
+        if unsafe { libc::shm_unlink(name.as_ptr()) } == -1 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(())
+    }
+
+    /// Returns the name the segment was created or opened with.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl Deref for SharedSegment {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.mmap
+    }
+}
+
+impl AsRef<[u8]> for SharedSegment {
+    fn as_ref(&self) -> &[u8] {
+        &self.mmap
+    }
+}
+
+// The bytes live in the mapping behind the `Arc`, which does not move with the segment.
+unsafe impl StableDeref for SharedSegment {}
+unsafe impl StableBuffer for SharedSegment {}
+
+impl Debug for SharedSegment {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SharedSegment")
+            .field("name", &self.name)
+            .field("len", &self.mmap.len())
+            .finish()
+    }
+}
+
+fn segment_name(name: &str) -> io::Result<CString> {
+    CString::new(name).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+}
+
+fn shm_open(name: &str, flags: libc::c_int) -> io::Result<File> {
+    let name = segment_name(name)?;
+
+    // Safety: `name` is a valid nul-terminated string that outlives the call.
+    // The mode is passed as `c_uint`, since `shm_open` is variadic on some platforms.
+    let fd = unsafe { libc::shm_open(name.as_ptr(), flags, 0o600 as libc::c_uint) };
+    if fd == -1 {
+        return Err(io::Error::last_os_error());
+    }
+
+    // Safety: `shm_open` succeeded, so `fd` is an open file descriptor owned by nobody else.
+    Ok(unsafe { File::from_raw_fd(fd) })
+}
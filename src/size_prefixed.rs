@@ -0,0 +1,681 @@
+//! Streams of size-prefixed FlatBuffers.
+//!
+//! A size-prefixed FlatBuffer starts with its length as a little-endian `u32`, followed by the FlatBuffer itself,
+//! as written by `FlatBufferBuilder::finish_size_prefixed()`. \
+//! Such frames are commonly stored back to back, e.g. in a message log or a batch received at once.
+//!
+//! [iter_size_prefixed()] iterates the frames of a byte slice, [iter_size_prefixed_shared()] the frames of a shared buffer like `Arc<[u8]>`,
+//! yielding zero-copy wrappers that keep the buffer alive. \
+//! The [SizePrefixedReader] decodes the frames of any `std::io::Read`, e.g. a file or a socket,
+//! the [SizePrefixedWriter] encodes them into any `std::io::Write`.
+//! With the `tokio` feature, the [AsyncSizePrefixedReader] and [AsyncSizePrefixedWriter] do the same for tokio's `AsyncRead` and `AsyncWrite`,
+//! with the `tokio-util` feature, the [FlatBufferCodec] for tokio-util's `Framed` transports.
+//!
+//! With the `crc32` feature, frames can carry a checksum of their FlatBuffer, see [Framing::Crc32].
+//!
+//! The builder aligns a size-prefixed FlatBuffer including its prefix.
+//! Every frame is therefore wrapped as an [EmbeddedFlatBuffer] rooted behind the prefix, not as the sub-slice of the FlatBuffer alone,
+//! whose fields would be verified as unaligned.
+//!
+//! # Example
+//! ```
+//! use flatbuffers_owned::size_prefixed::iter_size_prefixed;
+//!
+//! for message in iter_size_prefixed::<Message<'static>>(&log_bytes) {
+//!     let message = message?;
+//!     println!("{}", message.as_actual_at().text());
+//! }
+//! ```
+
+use core::convert::TryFrom;
+use core::fmt::{Debug, Display, Formatter};
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+use core::ops::Range;
+#[cfg(feature = "std")]
+use std::error::Error;
+#[cfg(feature = "std")]
+use std::io::{self, Read, Write};
+use flatbuffers::{Verifiable, SIZE_SIZEPREFIX};
+use crate::buffer::SlicedBuffer;
+#[cfg(feature = "std")]
+use crate::RelaxedFlatBufferTrait;
+use crate::{DefaultPolicy, EmbeddedFlatBuffer, RelaxedFollowTrait, VerifierPolicy, VerifyError};
+
+/// The default maximum size of a message read by a [SizePrefixedReader], 16 MiB.
+#[cfg(feature = "std")]
+pub const DEFAULT_MAX_MESSAGE_SIZE: usize = 16 * 1024 * 1024;
+
+#[cfg(feature = "tokio-util")]
+pub use crate::codec::FlatBufferCodec;
+#[cfg(feature = "tokio")]
+pub use crate::tokio_io::{AsyncSizePrefixedReader, AsyncSizePrefixedWriter};
+
+/// The length of the size prefix in bytes.
+pub const PREFIX_LEN: usize = SIZE_SIZEPREFIX;
+
+/// A size-prefixed FlatBuffer `T` borrowing its frame, rooted behind the size prefix.
+pub type Frame<'a, T, P = DefaultPolicy> = EmbeddedFlatBuffer<T, &'a [u8], P>;
+
+/// The layout of a frame behind its size prefix, which always holds the length of the rest of the frame.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Framing {
+    /// The FlatBuffer directly follows the size prefix, as written by `FlatBufferBuilder::finish_size_prefixed()`.
+    #[default]
+    Plain,
+    /// A little-endian CRC32 of the FlatBuffer follows the size prefix, then the FlatBuffer.
+    ///
+    /// The checksum is checked before the FlatBuffer is verified, so torn writes, e.g. at the end of an append-only file,
+    /// are reported as a checksum mismatch instead of an invalid FlatBuffer. \
+    /// Requires the `crc32` feature.
+    #[cfg(feature = "crc32")]
+    Crc32,
+}
+
+impl Framing {
+    /// Returns the position of the FlatBuffer within a frame.
+    pub fn root_loc(self) -> usize {
+        match self {
+            Framing::Plain => PREFIX_LEN,
+            #[cfg(feature = "crc32")]
+            Framing::Crc32 => PREFIX_LEN.saturating_add(CHECKSUM_LEN),
+        }
+    }
+
+    /// Checks the checksum of the frame, if any, and returns the position of its FlatBuffer. \
+    /// Returns `None` if the checksum does not match, or the frame is too short to hold one.
+    #[cfg_attr(not(feature = "crc32"), allow(unused_variables))]
+    pub(crate) fn check(self, frame: &[u8]) -> Option<usize> {
+        match self {
+            Framing::Plain => Some(PREFIX_LEN),
+            #[cfg(feature = "crc32")]
+            Framing::Crc32 => {
+                let loc = self.root_loc();
+                let checksum = frame.get(PREFIX_LEN..loc).and_then(|checksum| <[u8; CHECKSUM_LEN]>::try_from(checksum).ok())?;
+
+                if u32::from_le_bytes(checksum) != crc32fast::hash(frame.get(loc..)?) {
+                    return None;
+                }
+
+                Some(loc)
+            },
+        }
+    }
+}
+
+/// The length of the checksum of a frame in bytes.
+#[cfg(feature = "crc32")]
+const CHECKSUM_LEN: usize = 4;
+
+/// The error returned for a frame of a size-prefixed stream.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FrameError {
+    /// The data ends within the frame starting at `offset`, which needs `len` bytes including the prefix,
+    /// but only `available` bytes are left. \
+    /// The iterator ends after this error, since the following frames cannot be located.
+    Truncated { offset: usize, len: usize, available: usize },
+    /// The checksum of the frame starting at `offset` does not match its FlatBuffer. The iterator continues with the next frame.
+    ChecksumMismatch { offset: usize },
+    /// The frame failed the verification. The iterator continues with the next frame.
+    InvalidFlatbuffer(VerifyError),
+}
+
+impl Display for FrameError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            FrameError::Truncated { offset, len, available } => {
+                write!(f, "Truncated frame at offset {}: {} bytes needed, {} available", offset, len, available)
+            },
+            FrameError::ChecksumMismatch { offset } => write!(f, "Checksum mismatch of the frame at offset {}", offset),
+            FrameError::InvalidFlatbuffer(e) => Display::fmt(e, f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for FrameError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            FrameError::Truncated { .. } => None,
+            FrameError::ChecksumMismatch { .. } => None,
+            FrameError::InvalidFlatbuffer(e) => Some(e),
+        }
+    }
+}
+
+impl From<VerifyError> for FrameError {
+    fn from(e: VerifyError) -> Self {
+        FrameError::InvalidFlatbuffer(e)
+    }
+}
+
+/// Locates the frame starting at `offset`, or returns `None` at the end of the data.
+pub(crate) fn next_frame(data: &[u8], offset: usize) -> Option<Result<Range<usize>, FrameError>> {
+    let rest = data.get(offset..).filter(|rest| !rest.is_empty())?;
+
+    let prefix = match rest.get(..PREFIX_LEN).and_then(|prefix| <[u8; PREFIX_LEN]>::try_from(prefix).ok()) {
+        Some(prefix) => prefix,
+        None => return Some(Err(FrameError::Truncated { offset, len: PREFIX_LEN, available: rest.len() })),
+    };
+
+    let size = u32::from_le_bytes(prefix) as usize;
+    let len = size.saturating_add(PREFIX_LEN);
+
+    if rest.len() < len {
+        return Some(Err(FrameError::Truncated { offset, len, available: rest.len() }));
+    }
+
+    Some(Ok(offset..offset.saturating_add(len)))
+}
+
+/// Iterates the size-prefixed FlatBuffers `T` of a byte slice, verifying each according to the policy `P`.
+///
+/// Created by [iter_size_prefixed()].
+pub struct SizePrefixedIter<'a, T: RelaxedFollowTrait + Verifiable, P: VerifierPolicy = DefaultPolicy> {
+    data: &'a [u8],
+    offset: usize,
+    framing: Framing,
+    flatbuffer: PhantomData<fn() -> (T, P)>,
+}
+
+impl<'a, T: RelaxedFollowTrait + Verifiable, P: VerifierPolicy> SizePrefixedIter<'a, T, P> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data, offset: 0, framing: Framing::Plain, flatbuffer: PhantomData }
+    }
+
+    /// Sets the layout of the frames, [Framing::Plain] by default.
+    pub fn with_framing(mut self, framing: Framing) -> Self {
+        self.framing = framing;
+        self
+    }
+
+    /// Returns the offset of the next frame, or the length of the data once all frames were yielded.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl<'a, T: RelaxedFollowTrait + Verifiable, P: VerifierPolicy> Iterator for SizePrefixedIter<'a, T, P> {
+    type Item = Result<Frame<'a, T, P>, FrameError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let range = match next_frame(self.data, self.offset)? {
+            Ok(range) => range,
+            Err(e) => {
+                self.offset = self.data.len();
+                return Some(Err(e));
+            }
+        };
+
+        let offset = range.start;
+        self.offset = range.end;
+        let frame = self.data.get(range).unwrap_or_default();
+
+        let loc = match self.framing.check(frame) {
+            Some(loc) => loc,
+            None => return Some(Err(FrameError::ChecksumMismatch { offset })),
+        };
+
+        Some(EmbeddedFlatBuffer::new_at(frame, loc).map_err(FrameError::from))
+    }
+}
+
+impl<T: RelaxedFollowTrait + Verifiable, P: VerifierPolicy> FusedIterator for SizePrefixedIter<'_, T, P> {}
+
+/// Iterates the size-prefixed FlatBuffers `T` of a shared buffer, verifying each according to the policy `P`.
+///
+/// Every frame is yielded as a [SlicedBuffer] holding a clone of the buffer, so with `Arc<[u8]>` no bytes are copied. \
+/// Created by [iter_size_prefixed_shared()].
+pub struct SharedSizePrefixedIter<T: RelaxedFollowTrait + Verifiable, B: AsRef<[u8]> + Clone, P: VerifierPolicy = DefaultPolicy> {
+    data: B,
+    offset: usize,
+    framing: Framing,
+    flatbuffer: PhantomData<fn() -> (T, P)>,
+}
+
+impl<T: RelaxedFollowTrait + Verifiable, B: AsRef<[u8]> + Clone, P: VerifierPolicy> SharedSizePrefixedIter<T, B, P> {
+    pub fn new(data: B) -> Self {
+        Self { data, offset: 0, framing: Framing::Plain, flatbuffer: PhantomData }
+    }
+
+    /// Sets the layout of the frames, [Framing::Plain] by default.
+    pub fn with_framing(mut self, framing: Framing) -> Self {
+        self.framing = framing;
+        self
+    }
+
+    /// Returns the offset of the next frame, or the length of the data once all frames were yielded.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl<T: RelaxedFollowTrait + Verifiable, B: AsRef<[u8]> + Clone, P: VerifierPolicy> Iterator for SharedSizePrefixedIter<T, B, P> {
+    type Item = Result<EmbeddedFlatBuffer<T, SlicedBuffer<B>, P>, FrameError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let data = self.data.as_ref();
+
+        let range = match next_frame(data, self.offset)? {
+            Ok(range) => range,
+            Err(e) => {
+                self.offset = data.len();
+                return Some(Err(e));
+            }
+        };
+
+        self.offset = range.end;
+        let available = data.len();
+
+        let loc = match data.get(range.clone()).and_then(|frame| self.framing.check(frame)) {
+            Some(loc) => loc,
+            None => return Some(Err(FrameError::ChecksumMismatch { offset: range.start })),
+        };
+
+        // The range was checked by `next_frame()`.
+        let frame = match SlicedBuffer::new(self.data.clone(), range.clone()) {
+            Some(frame) => frame,
+            None => {
+                self.offset = available;
+                return Some(Err(FrameError::Truncated { offset: range.start, len: range.len(), available }));
+            }
+        };
+
+        Some(EmbeddedFlatBuffer::new_at(frame, loc).map_err(FrameError::from))
+    }
+}
+
+impl<T: RelaxedFollowTrait + Verifiable, B: AsRef<[u8]> + Clone, P: VerifierPolicy> FusedIterator for SharedSizePrefixedIter<T, B, P> {}
+
+/// Iterates the size-prefixed FlatBuffers `T` stored back to back in the byte slice.
+///
+/// Each frame is verified on its own, a frame failing the verification does not end the iteration.
+/// Trailing bytes too short for a whole frame are reported once as [FrameError::Truncated].
+pub fn iter_size_prefixed<T: RelaxedFollowTrait + Verifiable>(data: &[u8]) -> SizePrefixedIter<'_, T> {
+    SizePrefixedIter::new(data)
+}
+
+/// Iterates the size-prefixed FlatBuffers `T` stored back to back in the shared buffer, e.g. an `Arc<[u8]>`.
+///
+/// Behaves like [iter_size_prefixed()], but the yielded wrappers own a clone of the buffer instead of borrowing it.
+pub fn iter_size_prefixed_shared<T: RelaxedFollowTrait + Verifiable, B: AsRef<[u8]> + Clone>(data: B) -> SharedSizePrefixedIter<T, B> {
+    SharedSizePrefixedIter::new(data)
+}
+
+/// The error returned when reading a size-prefixed stream.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum StreamError {
+    /// Reading from the stream failed. \
+    /// A stream ending within a frame fails with `io::ErrorKind::UnexpectedEof`.
+    Io(io::Error),
+    /// The size prefix announces a message of `len` bytes, which exceeds the maximum message size.
+    TooLarge { len: usize, max: usize },
+    /// The checksum of the frame does not match its FlatBuffer.
+    ChecksumMismatch,
+    /// The message failed the verification.
+    InvalidFlatbuffer(VerifyError),
+}
+
+#[cfg(feature = "std")]
+impl Display for StreamError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            StreamError::Io(e) => Display::fmt(e, f),
+            StreamError::TooLarge { len, max } => write!(f, "Message of {} bytes exceeds the maximum of {} bytes", len, max),
+            StreamError::ChecksumMismatch => write!(f, "Checksum mismatch of the frame"),
+            StreamError::InvalidFlatbuffer(e) => Display::fmt(e, f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for StreamError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            StreamError::Io(e) => Some(e),
+            StreamError::TooLarge { .. } => None,
+            StreamError::ChecksumMismatch => None,
+            StreamError::InvalidFlatbuffer(e) => Some(e),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<io::Error> for StreamError {
+    fn from(e: io::Error) -> Self {
+        StreamError::Io(e)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<VerifyError> for StreamError {
+    fn from(e: VerifyError) -> Self {
+        StreamError::InvalidFlatbuffer(e)
+    }
+}
+
+/// Reads size-prefixed FlatBuffers `T` from a `std::io::Read` and verifies them according to the policy `P`.
+///
+/// As an iterator, every message is copied into its own exactly sized `Box<[u8]>`.
+/// [read_message()](SizePrefixedReader::read_message) instead lends out the message in a buffer that is reused for the next one. \
+/// The iterator ends at the end of the stream, or after an I/O error or a message exceeding the maximum size,
+/// since the following frames cannot be located anymore.
+/// A message failing the checksum or the verification is skipped.
+///
+/// The reader does not buffer, wrap unbuffered sources like a `File` or `TcpStream` in a `BufReader`.
+///
+/// # Example
+/// ```
+/// use flatbuffers_owned::size_prefixed::SizePrefixedReader;
+///
+/// let reader = SizePrefixedReader::<Message<'static>, _>::new(BufReader::new(stream));
+///
+/// for message in reader {
+///     let message = message?;
+///     println!("{}", message.as_actual_at().text());
+/// }
+/// ```
+#[cfg(feature = "std")]
+pub struct SizePrefixedReader<T: RelaxedFollowTrait + Verifiable, R: Read, P: VerifierPolicy = DefaultPolicy> {
+    reader: R,
+    buffer: Vec<u8>,
+    max_message_size: usize,
+    framing: Framing,
+    done: bool,
+    flatbuffer: PhantomData<fn() -> (T, P)>,
+}
+
+#[cfg(feature = "std")]
+impl<T: RelaxedFollowTrait + Verifiable, R: Read, P: VerifierPolicy> SizePrefixedReader<T, R, P> {
+    /// Creates a reader accepting messages of up to [DEFAULT_MAX_MESSAGE_SIZE] bytes.
+    pub fn new(reader: R) -> Self {
+        Self::with_max_message_size(reader, DEFAULT_MAX_MESSAGE_SIZE)
+    }
+
+    /// Creates a reader accepting messages of up to `max_message_size` bytes, excluding the size prefix. \
+    /// The buffer for a message is only allocated once its size was checked.
+    pub fn with_max_message_size(reader: R, max_message_size: usize) -> Self {
+        Self {
+            reader,
+            buffer: Vec::new(),
+            max_message_size,
+            framing: Framing::Plain,
+            done: false,
+            flatbuffer: PhantomData,
+        }
+    }
+
+    /// Sets the layout of the frames, [Framing::Plain] by default.
+    pub fn with_framing(mut self, framing: Framing) -> Self {
+        self.framing = framing;
+        self
+    }
+
+    /// Reads the next message into the reused buffer and verifies it. \
+    /// Returns `Ok(None)` at the end of the stream.
+    pub fn read_message(&mut self) -> Result<Option<Frame<'_, T, P>>, StreamError> {
+        if !self.read_frame()? {
+            return Ok(None);
+        }
+
+        let loc = self.framing.check(&self.buffer).ok_or(StreamError::ChecksumMismatch)?;
+
+        Ok(Some(EmbeddedFlatBuffer::new_at(self.buffer.as_slice(), loc)?))
+    }
+
+    /// Returns the maximum size of a message, excluding the size prefix.
+    pub fn max_message_size(&self) -> usize {
+        self.max_message_size
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    /// Consumes the reader and returns the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Reads the next frame including its prefix into the buffer, or returns `false` at the end of the stream.
+    fn read_frame(&mut self) -> Result<bool, StreamError> {
+        let mut prefix = [0u8; PREFIX_LEN];
+        let mut filled = 0;
+
+        while filled < PREFIX_LEN {
+            match self.reader.read(prefix.get_mut(filled..).unwrap_or_default()) {
+                Ok(0) if filled == 0 => return Ok(false),
+                Ok(0) => return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into()),
+                Ok(n) => filled = filled.saturating_add(n),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {},
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        let len = u32::from_le_bytes(prefix) as usize;
+        if len > self.max_message_size {
+            return Err(StreamError::TooLarge { len, max: self.max_message_size });
+        }
+
+        self.buffer.clear();
+        self.buffer.extend_from_slice(&prefix);
+        self.buffer.resize(len.saturating_add(PREFIX_LEN), 0);
+        self.reader.read_exact(self.buffer.get_mut(PREFIX_LEN..).unwrap_or_default())?;
+
+        Ok(true)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: RelaxedFollowTrait + Verifiable, R: Read, P: VerifierPolicy> Iterator for SizePrefixedReader<T, R, P> {
+    type Item = Result<EmbeddedFlatBuffer<T, Box<[u8]>, P>, StreamError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        match self.read_frame() {
+            Ok(true) => {},
+            Ok(false) => {
+                self.done = true;
+                return None;
+            },
+            Err(e) => {
+                self.done = true;
+                return Some(Err(e));
+            }
+        }
+
+        let loc = match self.framing.check(&self.buffer) {
+            Some(loc) => loc,
+            None => return Some(Err(StreamError::ChecksumMismatch)),
+        };
+
+        Some(EmbeddedFlatBuffer::new_at(Box::from(self.buffer.as_slice()), loc).map_err(StreamError::from))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: RelaxedFollowTrait + Verifiable, R: Read, P: VerifierPolicy> FusedIterator for SizePrefixedReader<T, R, P> {}
+
+#[cfg(feature = "std")]
+impl<T: RelaxedFollowTrait + Verifiable, R: Read + Debug, P: VerifierPolicy> Debug for SizePrefixedReader<T, R, P> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SizePrefixedReader")
+            .field("reader", &self.reader)
+            .field("max_message_size", &self.max_message_size)
+            .field("framing", &self.framing)
+            .finish()
+    }
+}
+
+/// When a [SizePrefixedWriter] flushes the underlying writer.
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FlushPolicy {
+    /// Only flushes on [flush()](SizePrefixedWriter::flush).
+    #[default]
+    Manual,
+    /// Flushes after every message, e.g. for low latency sockets.
+    EveryMessage,
+    /// Flushes after every `n` messages.
+    EveryMessages(usize),
+}
+
+/// The length of the header [write_message()](SizePrefixedWriter::write_message) puts in front of a FlatBuffer.
+#[cfg(feature = "std")]
+const HEADER_LEN: usize = 16;
+
+/// Writes FlatBuffers as size-prefixed frames into a `std::io::Write`, readable by the [SizePrefixedReader] and [iter_size_prefixed()].
+///
+/// The writer does not buffer, wrap unbuffered sinks like a `File` or `TcpStream` in a `BufWriter`.
+///
+/// # Example
+/// ```
+/// use flatbuffers_owned::size_prefixed::{FlushPolicy, SizePrefixedWriter};
+///
+/// let mut writer = SizePrefixedWriter::with_flush_policy(BufWriter::new(stream), FlushPolicy::EveryMessage);
+///
+/// for message in messages {
+///     writer.write_message(&message)?;
+/// }
+/// ```
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct SizePrefixedWriter<W: Write> {
+    writer: W,
+    flush_policy: FlushPolicy,
+    framing: Framing,
+    unflushed: usize,
+}
+
+#[cfg(feature = "std")]
+impl<W: Write> SizePrefixedWriter<W> {
+    /// Creates a writer with the [FlushPolicy::Manual].
+    pub fn new(writer: W) -> Self {
+        Self::with_flush_policy(writer, FlushPolicy::Manual)
+    }
+
+    pub fn with_flush_policy(writer: W, flush_policy: FlushPolicy) -> Self {
+        Self { writer, flush_policy, framing: Framing::Plain, unflushed: 0 }
+    }
+
+    /// Sets the layout of the frames, [Framing::Plain] by default.
+    pub fn with_framing(mut self, framing: Framing) -> Self {
+        self.framing = framing;
+        self
+    }
+
+    /// Writes the FlatBuffer of the wrapper as a size-prefixed frame.
+    ///
+    /// A FlatBuffer is aligned relative to its own start, which a plain 4-byte prefix in front of it would break.
+    /// With the [Framing::Plain], the FlatBuffer is therefore written behind a header of 16 bytes, holding the size prefix,
+    /// a root offset pointing into the unchanged FlatBuffer and a copy of its file identifier.
+    /// With the [Framing::Crc32], the size prefix and the checksum already keep the alignment. \
+    /// Buffers finished with `FlatBufferBuilder::finish_size_prefixed()` can be written as they are with [write_frame()](SizePrefixedWriter::write_frame).
+    pub fn write_message<F: RelaxedFlatBufferTrait>(&mut self, flatbuffer: &F) -> io::Result<()> {
+        let data: &[u8] = flatbuffer;
+
+        let (header, header_len) = match frame_header(data, self.framing) {
+            Some(header) => header,
+            None => return Err(io::Error::new(io::ErrorKind::InvalidInput, "FlatBuffer too short or too large for a size-prefixed frame")),
+        };
+
+        self.writer.write_all(header.get(..header_len).unwrap_or_default())?;
+        self.writer.write_all(data)?;
+
+        self.written()
+    }
+
+    /// Writes a buffer that already starts with its size prefix, e.g. finished with `FlatBufferBuilder::finish_size_prefixed()`.
+    ///
+    /// Fails with `io::ErrorKind::InvalidInput` if the size prefix does not match the length of the buffer. \
+    /// The frame is written as it is, regardless of the framing of the writer.
+    pub fn write_frame(&mut self, frame: &[u8]) -> io::Result<()> {
+        match next_frame(frame, 0) {
+            Some(Ok(range)) if range.end == frame.len() => {},
+            _ => return Err(io::Error::new(io::ErrorKind::InvalidInput, "Size prefix does not match the length of the frame")),
+        }
+
+        self.writer.write_all(frame)?;
+
+        self.written()
+    }
+
+    /// Flushes the underlying writer.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.unflushed = 0;
+        self.writer.flush()
+    }
+
+    pub fn flush_policy(&self) -> FlushPolicy {
+        self.flush_policy
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    /// Consumes the writer and returns the underlying writer, without flushing it.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    fn written(&mut self) -> io::Result<()> {
+        self.unflushed = self.unflushed.saturating_add(1);
+
+        match self.flush_policy {
+            FlushPolicy::Manual => Ok(()),
+            FlushPolicy::EveryMessage => self.flush(),
+            FlushPolicy::EveryMessages(n) if self.unflushed >= n => self.flush(),
+            FlushPolicy::EveryMessages(_) => Ok(()),
+        }
+    }
+}
+
+/// Returns the header to write in front of the FlatBuffer and its length,
+/// or `None` if the data is too short for a root offset or the frame too large for the size prefix.
+#[cfg(feature = "std")]
+pub(crate) fn frame_header(data: &[u8], framing: Framing) -> Option<([u8; HEADER_LEN], usize)> {
+    match framing {
+        Framing::Plain => plain_frame_header(data).map(|header| (header, HEADER_LEN)),
+        #[cfg(feature = "crc32")]
+        Framing::Crc32 => {
+            let loc = framing.root_loc();
+            let size = u32::try_from(data.len()).ok()?.checked_add(CHECKSUM_LEN as u32)?;
+
+            let mut header = [0u8; HEADER_LEN];
+            header.get_mut(..PREFIX_LEN)?.copy_from_slice(&size.to_le_bytes());
+            header.get_mut(PREFIX_LEN..loc)?.copy_from_slice(&crc32fast::hash(data).to_le_bytes());
+
+            Some((header, loc))
+        },
+    }
+}
+
+/// Returns the header re-rooting the FlatBuffer behind it.
+#[cfg(feature = "std")]
+fn plain_frame_header(data: &[u8]) -> Option<[u8; HEADER_LEN]> {
+    let root = u32::from_le_bytes(<[u8; 4]>::try_from(data.get(..4)?).ok()?);
+
+    let header_offset = (HEADER_LEN - PREFIX_LEN) as u32;
+    let size = u32::try_from(data.len()).ok()?.checked_add(header_offset)?;
+    let root = root.checked_add(header_offset)?;
+
+    let mut header = [0u8; HEADER_LEN];
+    header.get_mut(..4)?.copy_from_slice(&size.to_le_bytes());
+    header.get_mut(4..8)?.copy_from_slice(&root.to_le_bytes());
+    if let Some(identifier) = data.get(4..8) {
+        header.get_mut(8..12)?.copy_from_slice(identifier);
+    }
+
+    Some(header)
+}
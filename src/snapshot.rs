@@ -0,0 +1,293 @@
+//! Snapshot + incremental update streams.
+//!
+//! Many state-sync feeds (e.g. market data) publish a full snapshot message followed by incremental update messages.
+//! The [SnapshotStream] pairs a snapshot type with an update type and decides for every received update,
+//! whether it must be applied, can be dropped, or whether a sequence gap requires a new snapshot.
+//!
+//! # Example
+//! ```
+//! use flatbuffers_owned::snapshot::{Sequenced, SnapshotStream};
+//!
+//! impl Sequenced for BookSnapshot<'_> {
+//!     fn sequence(&self) -> u64 { self.sequence() }
+//! }
+//!
+//! impl Sequenced for BookUpdate<'_> {
+//!     fn sequence(&self) -> u64 { self.sequence() }
+//! }
+//!
+//! let mut stream = SnapshotStream::<OwnedBookSnapshot, OwnedBookUpdate>::new();
+//!
+//! for update in stream.push_snapshot(snapshot) {
+//!     apply(update);
+//! }
+//!
+//! match stream.push_update(update) {
+//!     Ok(Some(update)) => apply(update),
+//!     Ok(None) => {}, // buffered or stale
+//!     Err(gap) => request_new_snapshot(gap),
+//! }
+//! ```
+//!
+//! ## Concurrent producers
+//! With active-active producers, two snapshots (or two updates with the same sequence number) can arrive for the same state. \
+//! A [Merge] hook resolves those conflicts. By default, the snapshot with the highest sequence number wins and duplicate updates are dropped. \
+//! Snapshots older than the already applied state never reach the hook and are dropped.
+//! ```
+//! use flatbuffers_owned::snapshot::{Merged, SnapshotStream};
+//!
+//! let mut stream = SnapshotStream::<OwnedBookSnapshot, OwnedBookUpdate, _>::with_merge(|current: &OwnedBookSnapshot, incoming: &OwnedBookSnapshot| {
+//!     if incoming.as_actual().timestamp() > current.as_actual().timestamp() {
+//!         Merged::Incoming
+//!     } else {
+//!         Merged::Current
+//!     }
+//! });
+//! ```
+
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use flatbuffers::{Follow, Verifiable};
+use crate::{OwnedFlatBuffer, RelaxedFollowTrait, VerifierPolicy};
+
+/// A message carrying a sequence number.
+///
+/// Updates are expected to carry consecutive sequence numbers. \
+/// A snapshot carries the sequence number of the last update it already includes.
+///
+/// Implement it for the flatc generated FlatBuffer, the [OwnedFlatBuffer] wrappers then implement it as well.
+pub trait Sequenced {
+    fn sequence(&self) -> u64;
+}
+
+impl<T, B, P> Sequenced for OwnedFlatBuffer<T, B, P>
+    where T: RelaxedFollowTrait + Verifiable,
+          B: AsRef<[u8]>,
+          P: VerifierPolicy,
+          for<'a> <T::Inner<'a> as Follow<'a>>::Inner: Sequenced
+{
+    fn sequence(&self) -> u64 {
+        self.as_actual().sequence()
+    }
+}
+
+/// Returned by [SnapshotStream::push_update()] when an update skipped one or more sequence numbers.
+///
+/// The stream drops its snapshot after a gap and buffers further updates until the next snapshot is pushed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SequenceGap {
+    pub expected: u64,
+    pub received: u64,
+}
+
+impl Display for SequenceGap {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Sequence gap: expected update {}, but received {}", self.expected, self.received)
+    }
+}
+
+impl Error for SequenceGap {}
+
+/// The result of merging two concurrent snapshots.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Merged<T> {
+    /// Keep the current snapshot and the updates applied on top of it.
+    Current,
+    /// Replace the current snapshot with the incoming one.
+    Incoming,
+    /// Replace the current snapshot with a new one, e.g. merged via the object API.
+    Replaced(T),
+}
+
+/// Resolves concurrent snapshots and updates of a [SnapshotStream].
+///
+/// Implemented for closures taking the current and the incoming snapshot.
+pub trait Merge<T, U> {
+    /// Called when a snapshot is pushed while another snapshot is present. \
+    /// The incoming snapshot is either newer than the last applied update, or carries the same sequence number as the current snapshot.
+    fn merge_snapshots(&mut self, current: &T, incoming: &T) -> Merged<T>;
+
+    /// Called when an update carries the sequence number of the last update applied on top of the current snapshot. \
+    /// Duplicates of updates the snapshot already includes are dropped without calling the hook. \
+    /// Returning the update applies it again, returning `None` drops it.
+    fn merge_updates(&mut self, snapshot: &T, incoming: U) -> Option<U> {
+        let _ = (snapshot, incoming);
+        None
+    }
+}
+
+/// The incoming snapshot always wins, also on ties, duplicate updates are dropped.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LatestWins;
+
+impl<T, U> Merge<T, U> for LatestWins {
+    fn merge_snapshots(&mut self, _current: &T, _incoming: &T) -> Merged<T> {
+        Merged::Incoming
+    }
+}
+
+/// The default [Merge] hook. Keeps the snapshot with the higher sequence number. On ties, the current snapshot is kept.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct HighestSequence;
+
+impl<T: Sequenced, U> Merge<T, U> for HighestSequence {
+    fn merge_snapshots(&mut self, current: &T, incoming: &T) -> Merged<T> {
+        if incoming.sequence() > current.sequence() {
+            Merged::Incoming
+        } else {
+            Merged::Current
+        }
+    }
+}
+
+impl<T, U, F> Merge<T, U> for F
+    where F: FnMut(&T, &T) -> Merged<T>
+{
+    fn merge_snapshots(&mut self, current: &T, incoming: &T) -> Merged<T> {
+        self(current, incoming)
+    }
+}
+
+/// Pairs a snapshot message type `T` with an update message type `U`.
+///
+/// Updates received before the first snapshot are buffered. \
+/// Once a snapshot is pushed, all buffered updates that are not already included in the snapshot are released in order. \
+/// Conflicts between concurrent producers are resolved by the [Merge] hook `M`.
+#[derive(Debug)]
+pub struct SnapshotStream<T, U, M = HighestSequence> {
+    snapshot: Option<T>,
+    sequence: u64,
+    pending: VecDeque<U>,
+    merge: M,
+}
+
+impl<T: Sequenced, U: Sequenced> SnapshotStream<T, U> {
+    pub fn new() -> Self {
+        Self::with_merge(HighestSequence)
+    }
+}
+
+impl<T: Sequenced, U: Sequenced, M: Merge<T, U>> SnapshotStream<T, U, M> {
+    /// Creates a stream resolving concurrent snapshots and updates with the passed [Merge] hook.
+    pub fn with_merge(merge: M) -> Self {
+        Self {
+            snapshot: None,
+            sequence: 0,
+            pending: VecDeque::new(),
+            merge,
+        }
+    }
+
+    /// Returns the current snapshot, if one has been pushed since the last gap.
+    pub fn snapshot(&self) -> Option<&T> {
+        self.snapshot.as_ref()
+    }
+
+    /// Returns the sequence number of the last applied snapshot or update.
+    pub fn sequence(&self) -> Option<u64> {
+        self.snapshot.as_ref().map(|_| self.sequence)
+    }
+
+    /// Replaces the current snapshot and returns the buffered updates that have to be applied on top of it, in order.
+    ///
+    /// If a snapshot is already present, a snapshot that is older than the last applied update,
+    /// or that carries its sequence number after updates were applied on top of the current snapshot, is dropped. \
+    /// Otherwise, the [Merge] hook decides which one is kept. \
+    /// Buffered updates already included in the snapshot are dropped. \
+    /// Releasing stops at the first buffered update that does not directly follow the previous one.
+    pub fn push_snapshot(&mut self, snapshot: T) -> Vec<U> {
+        if let Some(current) = &self.snapshot {
+            let sequence = snapshot.sequence();
+
+            // The applied updates are not included in a stale snapshot, so keeping it would lose them.
+            if sequence < self.sequence || (sequence == self.sequence && self.sequence > current.sequence()) {
+                return Vec::new();
+            }
+        }
+
+        let merged = match self.snapshot.take() {
+            Some(current) => match self.merge.merge_snapshots(&current, &snapshot) {
+                Merged::Current => {
+                    self.snapshot = Some(current);
+                    return Vec::new();
+                },
+                Merged::Incoming => snapshot,
+                Merged::Replaced(replaced) => replaced,
+            },
+            None => snapshot,
+        };
+
+        self.sequence = merged.sequence();
+        self.snapshot = Some(merged);
+
+        let mut ready = Vec::new();
+        while let Some(update) = self.pending.pop_front() {
+            let sequence = update.sequence();
+
+            if sequence <= self.sequence {
+                ready.extend(self.merge_duplicate(update));
+                continue;
+            }
+
+            if sequence != self.sequence.saturating_add(1) {
+                self.pending.push_front(update);
+                break;
+            }
+
+            self.sequence = sequence;
+            ready.push(update);
+        }
+
+        ready
+    }
+
+    /// Pushes an update received from the stream.
+    ///
+    /// Returns `Ok(Some(update))` if the update directly follows the current sequence and has to be applied. \
+    /// Returns `Ok(None)` if the update was buffered because no snapshot is present, or dropped because it is already included. \
+    /// A duplicate of the last applied update is passed to the [Merge] hook, which may return it to apply it again. \
+    /// Returns a [SequenceGap] if updates were missed. In that case, the snapshot is dropped until a new one is pushed.
+    pub fn push_update(&mut self, update: U) -> Result<Option<U>, SequenceGap> {
+        if self.snapshot.is_none() {
+            self.pending.push_back(update);
+            return Ok(None);
+        }
+
+        let sequence = update.sequence();
+        if sequence <= self.sequence {
+            return Ok(self.merge_duplicate(update));
+        }
+
+        if sequence != self.sequence.saturating_add(1) {
+            let gap = SequenceGap { expected: self.sequence.saturating_add(1), received: sequence };
+
+            self.snapshot = None;
+            self.pending.clear();
+            self.pending.push_back(update);
+
+            return Err(gap);
+        }
+
+        self.sequence = sequence;
+        Ok(Some(update))
+    }
+
+    /// Passes an update carrying the last applied sequence number to the [Merge] hook. \
+    /// Older updates and updates included in the snapshot are dropped.
+    fn merge_duplicate(&mut self, update: U) -> Option<U> {
+        let snapshot = self.snapshot.as_ref()?;
+
+        if update.sequence() != self.sequence || self.sequence <= snapshot.sequence() {
+            return None;
+        }
+
+        self.merge.merge_updates(snapshot, update)
+    }
+}
+
+impl<T: Sequenced, U: Sequenced, M: Merge<T, U> + Default> Default for SnapshotStream<T, U, M> {
+    fn default() -> Self {
+        Self::with_merge(M::default())
+    }
+}
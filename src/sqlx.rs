@@ -0,0 +1,91 @@
+//! sqlx `Type`, `Encode` and `Decode` implementations, storing FlatBuffers in `BYTEA` and `BLOB` columns.
+//!
+//! Wrappers bind directly as query arguments of every database whose driver supports `Vec<u8>`. \
+//! `Owned{FLATBUFFER_NAME}` wrappers decode from rows by copying the column, `&[u8]` backed ones borrow it from the row. \
+//! Either way, the bytes are verified as the FlatBuffer before the wrapper is returned,
+//! and a failed verification is reported as a decode error of the column.
+//!
+//! Requires the `sqlx` feature.
+//!
+//! # Example
+//! ```
+//! sqlx::query("INSERT INTO messages (id, message) VALUES ($1, $2)")
+//!     .bind(id)
+//!     .bind(&owned_message)
+//!     .execute(&pool)
+//!     .await?;
+//!
+//! let message: OwnedMessage = sqlx::query_scalar("SELECT message FROM messages WHERE id = $1")
+//!     .bind(id)
+//!     .fetch_one(&pool)
+//!     .await?;
+//! ```
+
+use flatbuffers::Verifiable;
+use sqlx_core::database::Database;
+use sqlx_core::decode::Decode;
+use sqlx_core::encode::{Encode, IsNull};
+use sqlx_core::error::BoxDynError;
+use sqlx_core::types::Type;
+use crate::{OwnedFlatBuffer, RelaxedFlatBufferTrait, RelaxedFollowTrait, VerifierPolicy};
+
+impl<T, B, P, DB> Type<DB> for OwnedFlatBuffer<T, B, P>
+    where T: RelaxedFollowTrait + Verifiable,
+          B: AsRef<[u8]>,
+          P: VerifierPolicy,
+          DB: Database,
+          Vec<u8>: Type<DB>
+{
+    fn type_info() -> DB::TypeInfo {
+        <Vec<u8> as Type<DB>>::type_info()
+    }
+
+    fn compatible(ty: &DB::TypeInfo) -> bool {
+        <Vec<u8> as Type<DB>>::compatible(ty)
+    }
+}
+
+/// Copies the bytes into the arguments of the query.
+impl<'q, T, B, P, DB> Encode<'q, DB> for OwnedFlatBuffer<T, B, P>
+    where T: RelaxedFollowTrait + Verifiable,
+          B: AsRef<[u8]>,
+          P: VerifierPolicy,
+          DB: Database,
+          Vec<u8>: Encode<'q, DB>
+{
+    fn encode_by_ref(&self, buf: &mut <DB as Database>::ArgumentBuffer<'q>) -> Result<IsNull, BoxDynError> {
+        <Vec<u8> as Encode<'q, DB>>::encode(self.as_ref().to_vec(), buf)
+    }
+
+    fn size_hint(&self) -> usize {
+        self.as_ref().len()
+    }
+}
+
+/// Copies the column and verifies it according to the policy `P`.
+impl<'r, T, P, DB> Decode<'r, DB> for OwnedFlatBuffer<T, Box<[u8]>, P>
+    where T: RelaxedFollowTrait + Verifiable,
+          P: VerifierPolicy,
+          DB: Database,
+          Vec<u8>: Decode<'r, DB>
+{
+    fn decode(value: <DB as Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
+        let data = <Vec<u8> as Decode<'r, DB>>::decode(value)?;
+
+        Ok(<Self as RelaxedFlatBufferTrait>::new(data.into_boxed_slice())?)
+    }
+}
+
+/// Borrows the column from the row and verifies it according to the policy `P`.
+impl<'r, T, P, DB> Decode<'r, DB> for OwnedFlatBuffer<T, &'r [u8], P>
+    where T: RelaxedFollowTrait + Verifiable,
+          P: VerifierPolicy,
+          DB: Database,
+          &'r [u8]: Decode<'r, DB>
+{
+    fn decode(value: <DB as Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
+        let data = <&'r [u8] as Decode<'r, DB>>::decode(value)?;
+
+        Ok(<Self as RelaxedFlatBufferTrait>::new(data)?)
+    }
+}
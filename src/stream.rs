@@ -0,0 +1,156 @@
+//! Verifying adapters for `futures` streams of FlatBuffers.
+//!
+//! Message-based transports like WebSockets or gRPC streams deliver every FlatBuffer as its own buffer, e.g. as `Bytes`.
+//! [verify_flatbuffers()](VerifyStreamTrait::verify_flatbuffers) turns such a stream into a [VerifiedStream] of wrappers,
+//! verifying every item on its own. \
+//! An item failing the verification is yielded as an error, the stream continues with the next item.
+//! [skip_invalid()](VerifiedStream::skip_invalid) drops these items instead, passing their errors to a callback, e.g. for logging.
+//!
+//! Requires the `futures` feature.
+//!
+//! # Example
+//! ```
+//! use flatbuffers_owned::stream::VerifyStreamTrait;
+//!
+//! let mut messages = websocket_binary_messages
+//!     .verify_flatbuffers::<Message<'static>>()
+//!     .skip_invalid(|e| log::warn!("Dropped invalid message: {}", e));
+//!
+//! while let Some(message) = messages.next().await {
+//!     println!("{}", message.as_actual().text());
+//! }
+//! ```
+
+use std::fmt::{Debug, Formatter};
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use flatbuffers::Verifiable;
+use futures_core::{ready, Stream};
+use crate::{DefaultPolicy, OwnedFlatBuffer, RelaxedFlatBufferTrait, RelaxedFollowTrait, VerifierPolicy, VerifyError};
+
+/// Adds [verify_flatbuffers()](VerifyStreamTrait::verify_flatbuffers) to all streams of byte buffers.
+pub trait VerifyStreamTrait: Stream + Sized
+    where Self::Item: AsRef<[u8]>
+{
+    /// Verifies every item as the FlatBuffer `T` and wraps it, without copying the bytes.
+    fn verify_flatbuffers<T: RelaxedFollowTrait + Verifiable>(self) -> VerifiedStream<T, Self> {
+        VerifiedStream::new(self)
+    }
+}
+
+impl<S: Stream> VerifyStreamTrait for S
+    where S::Item: AsRef<[u8]>
+{}
+
+/// A stream verifying every item of the stream `S` as the FlatBuffer `T` according to the policy `P`.
+///
+/// Created by [verify_flatbuffers()](VerifyStreamTrait::verify_flatbuffers), or by [new()](VerifiedStream::new) for a custom policy.
+pub struct VerifiedStream<T: RelaxedFollowTrait + Verifiable, S: Stream, P: VerifierPolicy = DefaultPolicy>
+    where S::Item: AsRef<[u8]>
+{
+    stream: S,
+    flatbuffer: PhantomData<fn() -> (T, P)>,
+}
+
+impl<T: RelaxedFollowTrait + Verifiable, S: Stream, P: VerifierPolicy> VerifiedStream<T, S, P>
+    where S::Item: AsRef<[u8]>
+{
+    pub fn new(stream: S) -> Self {
+        Self { stream, flatbuffer: PhantomData }
+    }
+
+    /// Drops the items failing the verification and passes their errors to the callback instead of yielding them.
+    pub fn skip_invalid<F: FnMut(VerifyError)>(self, on_invalid: F) -> SkipInvalid<T, S, F, P> {
+        SkipInvalid { stream: self.stream, on_invalid, flatbuffer: PhantomData }
+    }
+
+    /// Consumes the adapter and returns the underlying stream.
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+}
+
+impl<T: RelaxedFollowTrait + Verifiable, S: Stream, P: VerifierPolicy> Stream for VerifiedStream<T, S, P>
+    where S::Item: AsRef<[u8]>
+{
+    type Item = Result<OwnedFlatBuffer<T, S::Item, P>, VerifyError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        // Safety:
+        // The stream is pinned structurally, it is never moved out of the pinned adapter.
+        let stream = unsafe { self.map_unchecked_mut(|adapter| &mut adapter.stream) };
+
+        Poll::Ready(ready!(stream.poll_next(cx)).map(<OwnedFlatBuffer<T, S::Item, P> as RelaxedFlatBufferTrait>::new))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.stream.size_hint()
+    }
+}
+
+impl<T: RelaxedFollowTrait + Verifiable, S: Stream + Debug, P: VerifierPolicy> Debug for VerifiedStream<T, S, P>
+    where S::Item: AsRef<[u8]>
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VerifiedStream").field("stream", &self.stream).finish()
+    }
+}
+
+/// A stream yielding only the items of the stream `S` passing the verification as the FlatBuffer `T`.
+///
+/// Created by [skip_invalid()](VerifiedStream::skip_invalid).
+pub struct SkipInvalid<T: RelaxedFollowTrait + Verifiable, S: Stream, F: FnMut(VerifyError), P: VerifierPolicy = DefaultPolicy>
+    where S::Item: AsRef<[u8]>
+{
+    stream: S,
+    on_invalid: F,
+    flatbuffer: PhantomData<fn() -> (T, P)>,
+}
+
+impl<T: RelaxedFollowTrait + Verifiable, S: Stream, F: FnMut(VerifyError), P: VerifierPolicy> SkipInvalid<T, S, F, P>
+    where S::Item: AsRef<[u8]>
+{
+    /// Consumes the adapter and returns the underlying stream.
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+}
+
+impl<T: RelaxedFollowTrait + Verifiable, S: Stream, F: FnMut(VerifyError), P: VerifierPolicy> Stream for SkipInvalid<T, S, F, P>
+    where S::Item: AsRef<[u8]>
+{
+    type Item = OwnedFlatBuffer<T, S::Item, P>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        // Safety:
+        // The stream is pinned structurally, it is never moved out of the pinned adapter.
+        // The callback is not pinned, only a mutable reference to it is used.
+        let adapter = unsafe { self.get_unchecked_mut() };
+        let mut stream = unsafe { Pin::new_unchecked(&mut adapter.stream) };
+
+        loop {
+            let item = match ready!(stream.as_mut().poll_next(cx)) {
+                Some(item) => item,
+                None => return Poll::Ready(None),
+            };
+
+            match <OwnedFlatBuffer<T, S::Item, P> as RelaxedFlatBufferTrait>::new(item) {
+                Ok(flatbuffer) => return Poll::Ready(Some(flatbuffer)),
+                Err(e) => (adapter.on_invalid)(e),
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.stream.size_hint().1)
+    }
+}
+
+impl<T: RelaxedFollowTrait + Verifiable, S: Stream + Debug, F: FnMut(VerifyError), P: VerifierPolicy> Debug for SkipInvalid<T, S, F, P>
+    where S::Item: AsRef<[u8]>
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SkipInvalid").field("stream", &self.stream).finish()
+    }
+}
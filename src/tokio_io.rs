@@ -0,0 +1,292 @@
+//! The async twins of the [SizePrefixedReader](crate::size_prefixed::SizePrefixedReader) and [SizePrefixedWriter](crate::size_prefixed::SizePrefixedWriter) for tokio.
+//!
+//! Requires the `tokio` feature.
+
+use std::fmt::{Debug, Formatter};
+use std::io;
+use std::marker::PhantomData;
+use flatbuffers::Verifiable;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use crate::size_prefixed::{frame_header, next_frame, FlushPolicy, Framing, StreamError, DEFAULT_MAX_MESSAGE_SIZE, PREFIX_LEN};
+use crate::{DefaultPolicy, EmbeddedFlatBuffer, RelaxedFlatBufferTrait, RelaxedFollowTrait, VerifierPolicy};
+
+/// Reads size-prefixed FlatBuffers `T` from a tokio `AsyncRead` and verifies them according to the policy `P`.
+///
+/// Every message is read into its own exactly sized `Box<[u8]>`, which is only allocated once its size was checked
+/// against the maximum message size.
+///
+/// [next_message()](AsyncSizePrefixedReader::next_message) is cancellation safe:
+/// The bytes of a partially read message are kept in the reader, so it can be used in a `tokio::select!` loop
+/// and the next call continues where the cancelled one stopped.
+///
+/// # Example
+/// ```
+/// use flatbuffers_owned::size_prefixed::AsyncSizePrefixedReader;
+///
+/// let mut reader = AsyncSizePrefixedReader::<Message<'static>, _>::new(BufReader::new(stream));
+///
+/// while let Some(message) = reader.next_message().await? {
+///     println!("{}", message.as_actual_at().text());
+/// }
+/// ```
+pub struct AsyncSizePrefixedReader<T: RelaxedFollowTrait + Verifiable, R: AsyncRead + Unpin, P: VerifierPolicy = DefaultPolicy> {
+    reader: R,
+    max_message_size: usize,
+    framing: Framing,
+    prefix: [u8; PREFIX_LEN],
+    frame: Vec<u8>,
+    filled: usize,
+    flatbuffer: PhantomData<fn() -> (T, P)>,
+}
+
+impl<T: RelaxedFollowTrait + Verifiable, R: AsyncRead + Unpin, P: VerifierPolicy> AsyncSizePrefixedReader<T, R, P> {
+    /// Creates a reader accepting messages of up to [DEFAULT_MAX_MESSAGE_SIZE] bytes.
+    pub fn new(reader: R) -> Self {
+        Self::with_max_message_size(reader, DEFAULT_MAX_MESSAGE_SIZE)
+    }
+
+    /// Creates a reader accepting messages of up to `max_message_size` bytes, excluding the size prefix.
+    pub fn with_max_message_size(reader: R, max_message_size: usize) -> Self {
+        Self {
+            reader,
+            max_message_size,
+            framing: Framing::Plain,
+            prefix: [0; PREFIX_LEN],
+            frame: Vec::new(),
+            filled: 0,
+            flatbuffer: PhantomData,
+        }
+    }
+
+    /// Sets the layout of the frames, [Framing::Plain] by default.
+    pub fn with_framing(mut self, framing: Framing) -> Self {
+        self.framing = framing;
+        self
+    }
+
+    /// Reads the next message and verifies it. \
+    /// Returns `Ok(None)` at the end of the stream.
+    ///
+    /// A message failing the checksum or the verification is consumed, so the next call continues with the following message.
+    /// After an I/O error or a message exceeding the maximum size the following frames cannot be located anymore.
+    pub async fn next_message(&mut self) -> Result<Option<EmbeddedFlatBuffer<T, Box<[u8]>, P>>, StreamError> {
+        let frame = match self.read_frame().await? {
+            Some(frame) => frame,
+            None => return Ok(None),
+        };
+
+        let loc = self.framing.check(&frame).ok_or(StreamError::ChecksumMismatch)?;
+
+        Ok(Some(EmbeddedFlatBuffer::new_at(frame, loc)?))
+    }
+
+    /// Returns the maximum size of a message, excluding the size prefix.
+    pub fn max_message_size(&self) -> usize {
+        self.max_message_size
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    /// Consumes the reader and returns the underlying reader. \
+    /// The bytes of a partially read message are lost.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Reads the next frame including its prefix, or returns `None` at the end of the stream.
+    ///
+    /// All progress is stored in the reader before every await point, which makes this cancellation safe.
+    async fn read_frame(&mut self) -> Result<Option<Box<[u8]>>, StreamError> {
+        while self.filled < PREFIX_LEN {
+            let n = self.reader.read(self.prefix.get_mut(self.filled..).unwrap_or_default()).await?;
+
+            if n == 0 {
+                if self.filled == 0 {
+                    return Ok(None);
+                }
+
+                return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+            }
+
+            self.filled = self.filled.saturating_add(n);
+        }
+
+        if self.frame.is_empty() {
+            let len = u32::from_le_bytes(self.prefix) as usize;
+            if len > self.max_message_size {
+                return Err(StreamError::TooLarge { len, max: self.max_message_size });
+            }
+
+            let mut frame = Vec::with_capacity(len.saturating_add(PREFIX_LEN));
+            frame.extend_from_slice(&self.prefix);
+            frame.resize(len.saturating_add(PREFIX_LEN), 0);
+            self.frame = frame;
+        }
+
+        while self.filled < self.frame.len() {
+            let n = self.reader.read(self.frame.get_mut(self.filled..).unwrap_or_default()).await?;
+
+            if n == 0 {
+                return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+            }
+
+            self.filled = self.filled.saturating_add(n);
+        }
+
+        self.filled = 0;
+
+        Ok(Some(std::mem::take(&mut self.frame).into_boxed_slice()))
+    }
+}
+
+impl<T: RelaxedFollowTrait + Verifiable, R: AsyncRead + Unpin + Debug, P: VerifierPolicy> Debug for AsyncSizePrefixedReader<T, R, P> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AsyncSizePrefixedReader")
+            .field("reader", &self.reader)
+            .field("max_message_size", &self.max_message_size)
+            .field("framing", &self.framing)
+            .finish()
+    }
+}
+
+/// Writes FlatBuffers as size-prefixed frames into a tokio `AsyncWrite`, readable by the [AsyncSizePrefixedReader].
+///
+/// The frames are written exactly like by the [SizePrefixedWriter](crate::size_prefixed::SizePrefixedWriter).
+/// Every write waits until the underlying writer accepted the whole frame, which applies its backpressure to the caller.
+///
+/// The writes are cancellation safe:
+/// A frame is staged in the writer before it is written, and the rest of a cancelled frame is written by the next write or flush.
+/// The stream therefore never contains a torn frame, but the message of a cancelled write is still sent.
+///
+/// # Example
+/// ```
+/// use flatbuffers_owned::size_prefixed::{AsyncSizePrefixedWriter, FlushPolicy};
+///
+/// let mut writer = AsyncSizePrefixedWriter::with_flush_policy(BufWriter::new(stream), FlushPolicy::EveryMessage);
+///
+/// for message in messages {
+///     writer.write_message(&message).await?;
+/// }
+/// ```
+#[derive(Debug)]
+pub struct AsyncSizePrefixedWriter<W: AsyncWrite + Unpin> {
+    writer: W,
+    flush_policy: FlushPolicy,
+    framing: Framing,
+    unflushed: usize,
+    pending: Vec<u8>,
+    written: usize,
+}
+
+impl<W: AsyncWrite + Unpin> AsyncSizePrefixedWriter<W> {
+    /// Creates a writer with the [FlushPolicy::Manual].
+    pub fn new(writer: W) -> Self {
+        Self::with_flush_policy(writer, FlushPolicy::Manual)
+    }
+
+    pub fn with_flush_policy(writer: W, flush_policy: FlushPolicy) -> Self {
+        Self {
+            writer,
+            flush_policy,
+            framing: Framing::Plain,
+            unflushed: 0,
+            pending: Vec::new(),
+            written: 0,
+        }
+    }
+
+    /// Sets the layout of the frames, [Framing::Plain] by default.
+    pub fn with_framing(mut self, framing: Framing) -> Self {
+        self.framing = framing;
+        self
+    }
+
+    /// Writes the FlatBuffer of the wrapper as a size-prefixed frame,
+    /// like [SizePrefixedWriter::write_message()](crate::size_prefixed::SizePrefixedWriter::write_message).
+    pub async fn write_message<F: RelaxedFlatBufferTrait>(&mut self, flatbuffer: &F) -> io::Result<()> {
+        self.write_pending().await?;
+
+        let data: &[u8] = flatbuffer;
+        let (header, header_len) = match frame_header(data, self.framing) {
+            Some(header) => header,
+            None => return Err(io::Error::new(io::ErrorKind::InvalidInput, "FlatBuffer too short or too large for a size-prefixed frame")),
+        };
+
+        self.pending.extend_from_slice(header.get(..header_len).unwrap_or_default());
+        self.pending.extend_from_slice(data);
+
+        self.write_staged().await
+    }
+
+    /// Writes a buffer that already starts with its size prefix, e.g. finished with `FlatBufferBuilder::finish_size_prefixed()`.
+    ///
+    /// Fails with `io::ErrorKind::InvalidInput` if the size prefix does not match the length of the buffer.
+    pub async fn write_frame(&mut self, frame: &[u8]) -> io::Result<()> {
+        self.write_pending().await?;
+
+        match next_frame(frame, 0) {
+            Some(Ok(range)) if range.end == frame.len() => {},
+            _ => return Err(io::Error::new(io::ErrorKind::InvalidInput, "Size prefix does not match the length of the frame")),
+        }
+
+        self.pending.extend_from_slice(frame);
+
+        self.write_staged().await
+    }
+
+    /// Writes the rest of a cancelled frame and flushes the underlying writer.
+    pub async fn flush(&mut self) -> io::Result<()> {
+        self.write_pending().await?;
+
+        self.unflushed = 0;
+        self.writer.flush().await
+    }
+
+    pub fn flush_policy(&self) -> FlushPolicy {
+        self.flush_policy
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    /// Consumes the writer and returns the underlying writer, without flushing it. \
+    /// The rest of a cancelled frame is lost.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    async fn write_staged(&mut self) -> io::Result<()> {
+        self.write_pending().await?;
+        self.unflushed = self.unflushed.saturating_add(1);
+
+        match self.flush_policy {
+            FlushPolicy::Manual => Ok(()),
+            FlushPolicy::EveryMessage => self.flush().await,
+            FlushPolicy::EveryMessages(n) if self.unflushed >= n => self.flush().await,
+            FlushPolicy::EveryMessages(_) => Ok(()),
+        }
+    }
+
+    /// Writes the staged frame, storing the progress before every await point.
+    async fn write_pending(&mut self) -> io::Result<()> {
+        while self.written < self.pending.len() {
+            let n = self.writer.write(self.pending.get(self.written..).unwrap_or_default()).await?;
+
+            if n == 0 {
+                return Err(io::Error::from(io::ErrorKind::WriteZero));
+            }
+
+            self.written = self.written.saturating_add(n);
+        }
+
+        self.pending.clear();
+        self.written = 0;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,102 @@
+//! Conversions between FlatBuffers and tungstenite WebSocket messages.
+//!
+//! Binary messages convert into wrappers with `TryFrom`, verifying the payload.
+//! Wrappers convert into binary messages with `From`, either by reference, copying the bytes,
+//! or by value, moving buffers like `Bytes`, `Vec<u8>` or `Box<[u8]>` into the message without a copy.
+//!
+//! Requires the `tungstenite` feature.
+//!
+//! # Example
+//! ```
+//! use flatbuffers_owned::websocket::MessageError;
+//!
+//! while let Some(message) = socket.next().await {
+//!     match OwnedMessage::try_from(message?) {
+//!         Ok(message) => socket.send(Message::from(&reply_to(message))).await?,
+//!         Err(MessageError::NotBinary(Message::Close(_))) => break,
+//!         Err(e) => log::warn!("Dropped message: {}", e),
+//!     }
+//! }
+//! ```
+
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use bytes::Bytes;
+use flatbuffers::Verifiable;
+use tungstenite::Message;
+use crate::{OwnedFlatBuffer, RelaxedFlatBufferTrait, RelaxedFollowTrait, VerifierPolicy, VerifyError};
+
+/// The error returned when converting a WebSocket message into a wrapper.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum MessageError {
+    /// The message is not a binary message, e.g. a text or close message. The message is returned unchanged.
+    NotBinary(Message),
+    /// The payload of the binary message failed the verification.
+    InvalidFlatbuffer(VerifyError),
+}
+
+impl Display for MessageError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MessageError::NotBinary(_) => write!(f, "Expected a binary WebSocket message"),
+            MessageError::InvalidFlatbuffer(e) => Display::fmt(e, f),
+        }
+    }
+}
+
+impl Error for MessageError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            MessageError::NotBinary(_) => None,
+            MessageError::InvalidFlatbuffer(e) => Some(e),
+        }
+    }
+}
+
+impl From<VerifyError> for MessageError {
+    fn from(e: VerifyError) -> Self {
+        MessageError::InvalidFlatbuffer(e)
+    }
+}
+
+fn binary_payload(message: Message) -> Result<Bytes, MessageError> {
+    match message {
+        Message::Binary(payload) => Ok(payload),
+        message => Err(MessageError::NotBinary(message)),
+    }
+}
+
+/// Verifies the payload of a binary message without copying it.
+impl<T: RelaxedFollowTrait + Verifiable, P: VerifierPolicy> TryFrom<Message> for OwnedFlatBuffer<T, Bytes, P> {
+    type Error = MessageError;
+
+    fn try_from(message: Message) -> Result<Self, MessageError> {
+        Ok(<Self as RelaxedFlatBufferTrait>::new(binary_payload(message)?)?)
+    }
+}
+
+/// Verifies the payload of a binary message, moving it into a `Box<[u8]>`. \
+/// The payload is only copied if it is still shared, e.g. with the receive buffer.
+impl<T: RelaxedFollowTrait + Verifiable, P: VerifierPolicy> TryFrom<Message> for OwnedFlatBuffer<T, Box<[u8]>, P> {
+    type Error = MessageError;
+
+    fn try_from(message: Message) -> Result<Self, MessageError> {
+        let payload = Vec::from(binary_payload(message)?).into_boxed_slice();
+
+        Ok(<Self as RelaxedFlatBufferTrait>::new(payload)?)
+    }
+}
+
+impl<T: RelaxedFollowTrait + Verifiable, B: AsRef<[u8]>, P: VerifierPolicy> From<&OwnedFlatBuffer<T, B, P>> for Message {
+    fn from(flatbuffer: &OwnedFlatBuffer<T, B, P>) -> Self {
+        Message::Binary(Bytes::copy_from_slice(flatbuffer))
+    }
+}
+
+impl<T: RelaxedFollowTrait + Verifiable, B: AsRef<[u8]> + Into<Bytes>, P: VerifierPolicy> From<OwnedFlatBuffer<T, B, P>> for Message {
+    fn from(flatbuffer: OwnedFlatBuffer<T, B, P>) -> Self {
+        Message::Binary(flatbuffer.into_inner().into())
+    }
+}
@@ -0,0 +1,62 @@
+mod common;
+
+use actix_web::body::MessageBody;
+use actix_web::http::StatusCode;
+use actix_web::test::TestRequest;
+use actix_web::{web, FromRequest, Responder, ResponseError};
+use bytes::Bytes;
+use common::generated_fbs::foo::Foo;
+use common::build_foo;
+use flatbuffers_owned::actix::{FlatBuffer, FlatBufferConfig, FlatBufferPayloadError, CONTENT_TYPE};
+use flatbuffers_owned::flatbuffers_owned;
+
+flatbuffers_owned!(Foo);
+
+async fn extract<F>(request: TestRequest) -> Result<FlatBuffer<F>, FlatBufferPayloadError>
+    where FlatBuffer<F>: FromRequest<Error = FlatBufferPayloadError>
+{
+    let (req, mut payload) = request.to_http_parts();
+
+    FlatBuffer::<F>::from_request(&req, &mut payload).await
+}
+
+#[tokio::test]
+async fn extract_flatbuffer() {
+    let FlatBuffer(owned_foo) = extract::<OwnedFoo>(TestRequest::post().set_payload(build_foo::<Foo>(42).to_vec())).await.unwrap();
+    assert_eq!(owned_foo.as_actual().a(), 42);
+
+    let FlatBuffer(shared_foo) = extract::<RelaxedFoo<Bytes>>(TestRequest::post().set_payload(build_foo::<Foo>(42).to_vec())).await.unwrap();
+    assert_eq!(shared_foo.as_actual().b(), Some("Hello, world!"));
+}
+
+#[tokio::test]
+async fn extract_invalid() {
+    let error = extract::<OwnedFoo>(TestRequest::post().set_payload(vec![0xFF; 16])).await.unwrap_err();
+
+    assert!(matches!(error, FlatBufferPayloadError::InvalidFlatbuffer(_)));
+    assert_eq!(error.status_code(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn extract_limit() {
+    let bytes = build_foo::<Foo>(42).to_vec();
+
+    let request = TestRequest::post().app_data(FlatBufferConfig::default().limit(bytes.len())).set_payload(bytes.clone());
+    assert!(extract::<OwnedFoo>(request).await.is_ok());
+
+    let request = TestRequest::post().app_data(web::Data::new(FlatBufferConfig::default().limit(8))).set_payload(bytes);
+    let error = extract::<OwnedFoo>(request).await.unwrap_err();
+
+    assert!(matches!(error, FlatBufferPayloadError::Overflow { limit: 8 }));
+    assert_eq!(error.status_code(), StatusCode::PAYLOAD_TOO_LARGE);
+}
+
+#[tokio::test]
+async fn respond() {
+    let owned_foo: OwnedFoo = build_foo(42);
+    let response = FlatBuffer(owned_foo.clone()).respond_to(&TestRequest::default().to_http_request());
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers().get(actix_web::http::header::CONTENT_TYPE).unwrap(), CONTENT_TYPE);
+    assert_eq!(&response.into_body().try_into_bytes().unwrap()[..], &owned_foo[..]);
+}
@@ -0,0 +1,60 @@
+mod common;
+
+use common::generated_fbs::foo::Foo;
+use common::get_foo_bytes;
+use flatbuffers_owned::flatbuffers_owned;
+use flatbuffers_owned::buffer::{self, AlignedBuffer, Realigned};
+
+flatbuffers_owned!(Foo);
+
+// Places the bytes into a frame right after a one byte header, so they start at an unaligned address.
+fn get_frame() -> AlignedBuffer {
+    let mut frame = vec![0xAA];
+    frame.extend_from_slice(&get_foo_bytes());
+
+    AlignedBuffer::copy_from_slice(&frame)
+}
+
+#[test]
+fn aligned_buffer() {
+    let bytes = get_foo_bytes();
+    let aligned = AlignedBuffer::copy_from_slice(&bytes);
+
+    assert!(buffer::is_aligned(&aligned));
+    assert_eq!(&*aligned, &*bytes);
+}
+
+#[test]
+fn is_aligned() {
+    let frame = get_frame();
+
+    assert!(buffer::is_aligned(&frame));
+
+    let unaligned_foo = RelaxedFoo::new(&frame[1..]).unwrap();
+    assert!(!unaligned_foo.is_aligned());
+}
+
+#[test]
+fn new_realigned_copies_unaligned() {
+    let frame = get_frame();
+
+    let foo = RelaxedFoo::new_realigned(&frame[1..]).unwrap();
+    assert!(foo.is_aligned());
+    assert!(foo.buffer().is_copied());
+    assert_eq!(foo.as_actual().a(), 42);
+    assert_eq!(foo.as_actual().b(), Some("Hello, world!"));
+}
+
+#[test]
+fn new_realigned_keeps_aligned() {
+    let bytes = AlignedBuffer::copy_from_slice(&get_foo_bytes());
+
+    let foo = RelaxedFoo::new_realigned(&bytes[..]).unwrap();
+    assert!(matches!(foo.buffer(), Realigned::Aligned(_)));
+    assert_eq!(foo.as_actual().a(), 42);
+}
+
+#[test]
+fn new_realigned_invalid() {
+    assert!(RelaxedFoo::new_realigned(&[0xFF; 16][..]).is_err());
+}
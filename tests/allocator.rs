@@ -0,0 +1,59 @@
+mod common;
+
+use std::alloc::Layout;
+use std::ptr::NonNull;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use allocator_api2::alloc::{AllocError, Allocator, Global};
+use common::generated_fbs::foo::Foo;
+use common::get_foo_bytes;
+use flatbuffers_owned::flatbuffers_owned;
+
+flatbuffers_owned!(Foo);
+
+/// Counts the live allocations made through it.
+#[derive(Default)]
+struct CountingAllocator {
+    live: AtomicUsize,
+}
+
+unsafe impl Allocator for &CountingAllocator {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.live.fetch_add(1, Ordering::SeqCst);
+        Global.allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        self.live.fetch_sub(1, Ordering::SeqCst);
+        Global.deallocate(ptr, layout)
+    }
+}
+
+#[test]
+fn new_in() {
+    let allocator = CountingAllocator::default();
+
+    let foo: OwnedInFoo<&CountingAllocator> = OwnedFoo::new_in(&get_foo_bytes(), &allocator).unwrap();
+    assert_eq!(allocator.live.load(Ordering::SeqCst), 1);
+    assert_eq!(foo.as_actual().a(), 42);
+    assert_eq!(foo.as_actual().b(), Some("Hello, world!"));
+
+    drop(foo);
+    assert_eq!(allocator.live.load(Ordering::SeqCst), 0);
+}
+
+#[test]
+fn new_in_invalid() {
+    let allocator = CountingAllocator::default();
+
+    assert!(OwnedFoo::new_in(&[0xFF; 16], &allocator).is_err());
+    assert_eq!(allocator.live.load(Ordering::SeqCst), 0);
+}
+
+#[test]
+fn to_owned_in() {
+    let foo = OwnedFoo::new(get_foo_bytes()).unwrap();
+
+    let global_foo = foo.to_owned_in(Global);
+    assert_eq!(global_foo.as_actual().a(), 42);
+    assert_eq!(&*global_foo, &*foo);
+}
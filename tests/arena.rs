@@ -0,0 +1,73 @@
+mod common;
+
+use common::generated_fbs::foo::Foo;
+use common::build_foo_bytes;
+use flatbuffers_owned::flatbuffers_owned;
+use flatbuffers_owned::arena::FlatBufferArena;
+use flatbuffers_owned::buffer;
+
+flatbuffers_owned!(Foo { cached });
+
+#[test]
+fn new_in_arena() {
+    let arena = FlatBufferArena::new();
+
+    let foos = (0..100)
+        .map(|a| OwnedFoo::new_in_arena(&arena, &build_foo_bytes(a, Some("Hello, world!"), None)))
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    for (a, foo) in (0..).zip(&foos) {
+        assert_eq!(foo.as_actual().a(), a);
+        assert_eq!(foo.as_actual().b(), Some("Hello, world!"));
+        assert!(foo.is_aligned());
+    }
+
+    let cached_foo = CachedFoo::new(foos[7]);
+    assert_eq!(cached_foo.get().a(), 7);
+}
+
+#[test]
+fn new_in_arena_invalid() {
+    let arena = FlatBufferArena::new();
+
+    assert!(OwnedFoo::new_in_arena(&arena, &[0xFF; 16]).is_err());
+}
+
+#[test]
+fn spills_into_new_chunks() {
+    let bytes = build_foo_bytes(42, Some("Hello, world!"), None);
+    let arena = FlatBufferArena::with_chunk_size(bytes.len() * 2);
+
+    let slices = (0..5).map(|_| arena.alloc(&bytes)).collect::<Vec<_>>();
+    for slice in &slices {
+        assert_eq!(&**slice, &*bytes);
+        assert!(buffer::is_aligned(slice));
+    }
+
+    assert!(arena.allocated_bytes() >= bytes.len() * 5);
+}
+
+#[test]
+fn oversized() {
+    let arena = FlatBufferArena::with_chunk_size(8);
+    let bytes = build_foo_bytes(42, Some("Hello, world!"), None);
+
+    let foo = OwnedFoo::new_in_arena(&arena, &bytes).unwrap();
+    assert_eq!(foo.as_actual().a(), 42);
+}
+
+#[test]
+fn reset_reuses_chunks() {
+    let mut arena = FlatBufferArena::with_chunk_size(1024);
+    let bytes = build_foo_bytes(42, Some("Hello, world!"), None);
+
+    let address = arena.alloc(&bytes).as_ptr();
+    let allocated = arena.allocated_bytes();
+
+    arena.reset();
+
+    let foo = OwnedFoo::new_in_arena(&arena, &bytes).unwrap();
+    assert_eq!(foo.as_ptr(), address);
+    assert_eq!(arena.allocated_bytes(), allocated);
+}
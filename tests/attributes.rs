@@ -0,0 +1,42 @@
+mod common;
+
+use common::generated_fbs::foo::{Foo, FooArgs};
+use flatbuffers_owned::flatbuffers_owned;
+
+flatbuffers_owned!(
+    /// The wrapped Foo table.
+    #[allow(dead_code)]
+    #[cfg_attr(all(), allow(deprecated))]
+    Foo { create, newtype: #[cfg_attr(all(), derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash))] #[cfg_attr(any(), derive(Default))] FooRecord },
+    #[cfg(all())]
+    Vector<Foo> as FooBatch,
+    // None of these FlatBuffers exist, so the invocation only compiles if their cfg removes every generated item.
+    #[cfg(any())]
+    Chat { create, object_api, cached, verifier: { max_depth: 8 }, union: payload { Login }, nested: inner -> Foo, newtype: ChatRecord, sample: |builder| Chat::create(builder, &ChatArgs::default()) },
+    #[cfg(any())]
+    struct Position,
+    #[cfg(any())]
+    Vector<Chat> as ChatBatch
+);
+
+#[test]
+fn derived_on_newtype() {
+    let smaller = FooRecord::from(OwnedFoo::create(&FooArgs { a: 1, b: None }));
+    let greater = FooRecord(OwnedFoo::create(&FooArgs { a: 2, b: None }));
+
+    assert_eq!(smaller.clone(), smaller);
+    assert_ne!(smaller, greater);
+    assert_eq!(smaller.cmp(&greater), smaller.0.cmp(&greater.0));
+    assert_eq!(format!("{:?}", smaller), format!("FooRecord({:?})", smaller.0));
+    assert_eq!(greater.as_actual().a(), 2);
+
+    let owned: OwnedFoo = greater.into();
+    assert_eq!(owned.as_actual().a(), 2);
+}
+
+#[test]
+fn cfg_enabled_vector_root() {
+    let batch = OwnedFooBatch::build(|builder| builder.create_vector::<flatbuffers::ForwardsUOffset<Foo>>(&[])).unwrap();
+
+    assert_eq!(batch.as_actual().len(), 0);
+}
@@ -0,0 +1,54 @@
+mod common;
+
+use axum_core::body::Body;
+use axum_core::extract::{FromRequest, Request};
+use axum_core::response::IntoResponse;
+use bytes::Bytes;
+use common::generated_fbs::foo::Foo;
+use common::build_foo;
+use http::StatusCode;
+use flatbuffers_owned::axum::{FlatBuffer, FlatBufferRejection, CONTENT_TYPE};
+use flatbuffers_owned::flatbuffers_owned;
+
+flatbuffers_owned!(Foo);
+
+fn request(body: impl Into<Body>) -> Request {
+    Request::new(body.into())
+}
+
+#[tokio::test]
+async fn extract() {
+    let FlatBuffer(owned_foo) = FlatBuffer::<OwnedFoo>::from_request(request(build_foo::<Foo>(42).to_vec()), &()).await.unwrap();
+    assert_eq!(owned_foo.as_actual().a(), 42);
+
+    let FlatBuffer(shared_foo) = FlatBuffer::<RelaxedFoo<Bytes>>::from_request(request(build_foo::<Foo>(42).to_vec()), &()).await.unwrap();
+    assert_eq!(shared_foo.as_actual().b(), Some("Hello, world!"));
+}
+
+#[tokio::test]
+async fn extract_invalid() {
+    let rejection = FlatBuffer::<OwnedFoo>::from_request(request(vec![0xFF; 16]), &()).await.unwrap_err();
+    assert!(matches!(rejection, FlatBufferRejection::InvalidFlatbuffer(_)));
+
+    assert_eq!(rejection.into_response().status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn extract_too_large() {
+    let rejection = FlatBuffer::<OwnedFoo>::from_request(request(vec![0; 3 * 1024 * 1024]), &()).await.unwrap_err();
+    assert!(matches!(rejection, FlatBufferRejection::Body(_)));
+
+    assert_eq!(rejection.into_response().status(), StatusCode::PAYLOAD_TOO_LARGE);
+}
+
+#[tokio::test]
+async fn respond() {
+    let owned_foo: OwnedFoo = build_foo(42);
+    let response = FlatBuffer(owned_foo.clone()).into_response();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[http::header::CONTENT_TYPE], CONTENT_TYPE);
+
+    let body = Bytes::from_request(request(response.into_body()), &()).await.unwrap();
+    assert_eq!(&body[..], &owned_foo[..]);
+}
@@ -1,28 +1,12 @@
-#[allow(dead_code, unused_imports)]
-pub mod generated_fbs {
-    pub mod foo;
-}
+mod common;
 
 use flatbuffers::{FlatBufferBuilder};
-use generated_fbs::foo::{Foo, FooArgs};
+use common::generated_fbs::foo::{Foo, FooArgs};
+use common::get_foo_bytes;
 use flatbuffers_owned::{flatbuffers_owned, RelaxedFlatBufferTrait};
 
 // Create OwnedFoo type alias
-flatbuffers_owned!(Foo);
-
-fn get_foo_bytes() -> Box<[u8]> {
-    let mut builder = FlatBufferBuilder::new();
-    let b = builder.create_string("Hello, world!");
-
-    let offset = Foo::create(&mut builder, &FooArgs {
-        a: 42,
-        b: Some(b),
-    });
-
-    builder.finish(offset, None);
-
-    builder.finished_data().into()
-}
+flatbuffers_owned!(Foo { create });
 
 #[test]
 fn init_foo() {
@@ -48,6 +32,38 @@ fn create_owned_foo() {
     assert_eq!(foo.b().unwrap(), "Hello, world!");
 }
 
+#[test]
+fn create_owned_foo_from_args() {
+    let owned_foo = OwnedFoo::create(&FooArgs {
+        a: 42,
+        b: None,
+    });
+
+    assert!(OwnedFoo::verify(&owned_foo).is_ok());
+
+    let foo = owned_foo.as_actual();
+
+    assert_eq!(foo.a(), 42);
+    assert!(foo.b().is_none());
+}
+
+#[test]
+fn build_owned_foo() {
+    let owned_foo = OwnedFoo::build(|builder| {
+        let b = builder.create_string("Hello, world!");
+
+        Foo::create(builder, &FooArgs {
+            a: 42,
+            b: Some(b),
+        })
+    }).expect("Failed to build Foo");
+
+    let foo = owned_foo.as_actual();
+
+    assert_eq!(foo.a(), 42);
+    assert_eq!(foo.b().unwrap(), "Hello, world!");
+}
+
 #[test]
 fn fail_invalid_foo_bytes() {
     let mut foo_bytes = get_foo_bytes();
@@ -59,12 +75,321 @@ fn fail_invalid_foo_bytes() {
 // This is more a compile- than a runtime-time test.
 #[test]
 fn working_generic_function() {
-    fn test<TBuffer, TFlatBuffer>(flatbuffer: TFlatBuffer)
-        where TFlatBuffer: RelaxedFlatBufferTrait<TBuffer>
+    fn test<TFlatBuffer>(flatbuffer: TFlatBuffer)
+        where TFlatBuffer: RelaxedFlatBufferTrait
     {
         assert!(TFlatBuffer::verify(flatbuffer.deref()).is_ok());
     }
 
     let foo = OwnedFoo::new(get_foo_bytes()).unwrap();
     test(foo);
-}
\ No newline at end of file
+}
+#[test]
+fn transparent_layout() {
+    use std::mem::{align_of, size_of};
+
+    assert_eq!(size_of::<OwnedFoo>(), size_of::<Box<[u8]>>());
+    assert_eq!(align_of::<OwnedFoo>(), align_of::<Box<[u8]>>());
+    assert_eq!(size_of::<RelaxedFoo<&[u8]>>(), size_of::<&[u8]>());
+}
+
+#[test]
+fn copy_borrowed_foo() {
+    let foo_bytes = get_foo_bytes();
+    let borrowed_foo = RelaxedFoo::new(&foo_bytes[..]).unwrap();
+
+    let foos: Vec<RelaxedFoo<&[u8]>> = (0..3).map(|_| borrowed_foo).collect();
+
+    assert_eq!(foos.len(), 3);
+    assert_eq!(borrowed_foo.as_actual().a(), 42);
+}
+
+#[test]
+fn lookup_by_bytes() {
+    use std::collections::HashMap;
+
+    let foo_bytes = get_foo_bytes();
+    let mut map = HashMap::new();
+    map.insert(OwnedFoo::new(foo_bytes.clone()).unwrap(), "meta");
+
+    assert_eq!(map.get(&foo_bytes[..]), Some(&"meta"));
+    assert_eq!(RelaxedFoo::new(&foo_bytes[..]).unwrap().as_ref(), &foo_bytes[..]);
+}
+
+#[test]
+fn name_generic_wrapper() {
+    use flatbuffers_owned::OwnedFlatBuffer;
+
+    fn len<T, B>(flatbuffer: &OwnedFlatBuffer<T, B>) -> usize
+        where T: flatbuffers_owned::RelaxedFollowTrait + flatbuffers::Verifiable,
+              B: AsRef<[u8]>
+    {
+        flatbuffer.len()
+    }
+
+    let foo_bytes = get_foo_bytes();
+    let owned_foo: OwnedFlatBuffer<Foo<'static>, Box<[u8]>> = OwnedFoo::new(foo_bytes.clone()).unwrap();
+
+    assert_eq!(len(&owned_foo), foo_bytes.len());
+    assert_eq!(len(&RelaxedFoo::new(&foo_bytes[..]).unwrap()), foo_bytes.len());
+}
+
+#[test]
+fn verify_with_opts() {
+    use flatbuffers::VerifierOptions;
+
+    let foo_bytes = get_foo_bytes();
+    let strict = VerifierOptions { max_tables: 0, ..Default::default() };
+
+    assert!(OwnedFoo::verify_with_opts(&foo_bytes, &VerifierOptions::default()).is_ok());
+    assert!(OwnedFoo::verify_with_opts(&foo_bytes, &strict).is_err());
+    assert!(OwnedFoo::new_with_opts(foo_bytes.clone(), &strict).is_err());
+    assert_eq!(OwnedFoo::new_with_opts(foo_bytes, &VerifierOptions::default()).unwrap().as_actual().a(), 42);
+}
+
+#[test]
+fn into_inner() {
+    let foo_bytes = get_foo_bytes();
+    let owned_foo = OwnedFoo::new(foo_bytes.clone()).unwrap();
+
+    assert_eq!(owned_foo.into_inner(), foo_bytes);
+    assert_eq!(RelaxedFoo::new(&foo_bytes[..]).unwrap().into_inner(), &foo_bytes[..]);
+}
+
+#[test]
+fn buffer() {
+    use std::sync::Arc;
+
+    let foo_bytes: Arc<[u8]> = get_foo_bytes().into();
+    let shared_foo = RelaxedFoo::new(foo_bytes.clone()).unwrap();
+    let shared_buffer = Arc::clone(shared_foo.buffer());
+
+    assert!(Arc::ptr_eq(&shared_buffer, &foo_bytes));
+    assert_eq!(Arc::strong_count(&foo_bytes), 3);
+}
+
+#[test]
+fn map_buffer() {
+    use std::sync::Arc;
+
+    let foo_bytes = get_foo_bytes();
+    let vec_foo = RelaxedFoo::new(foo_bytes.to_vec()).unwrap();
+    let shared_foo: RelaxedFoo<Arc<[u8]>> = vec_foo.map_buffer(Arc::from);
+
+    assert_eq!(shared_foo.as_actual().a(), 42);
+    assert_eq!(&shared_foo[..], &foo_bytes[..]);
+}
+
+#[test]
+fn to_owned() {
+    let owned_foo: OwnedFoo;
+    {
+        let foo_bytes = get_foo_bytes();
+        let borrowed_foo = RelaxedFoo::new(&foo_bytes[..]).unwrap();
+
+        owned_foo = borrowed_foo.to_owned();
+    }
+
+    assert_eq!(owned_foo.as_actual().a(), 42);
+    assert_eq!(owned_foo.as_actual().b(), Some("Hello, world!"));
+}
+
+#[test]
+fn into_shared() {
+    let owned_foo = OwnedFoo::new(get_foo_bytes()).unwrap();
+    let shared_foo = owned_foo.into_shared();
+    let cloned_foo = shared_foo.clone();
+
+    assert_eq!(cloned_foo.as_actual().a(), 42);
+
+    let unique_foo: OwnedFoo = shared_foo.to_owned();
+    assert_eq!(unique_foo.as_actual().b(), Some("Hello, world!"));
+}
+
+#[test]
+fn verify_error_context() {
+    #[cfg(feature = "std")]
+    use std::error::Error;
+    use flatbuffers_owned::Constructor;
+
+    let mut foo_bytes = get_foo_bytes();
+    foo_bytes[0] = 0xFF;
+    let len = foo_bytes.len();
+
+    let e = OwnedFoo::new(foo_bytes).unwrap_err();
+
+    assert!(e.root_type.contains("foo::Foo"));
+    assert_eq!(e.len, len);
+    assert_eq!(e.constructor, Constructor::New);
+    #[cfg(feature = "std")]
+    assert!(e.source().is_some());
+    assert!(e.to_string().starts_with("new() of"));
+}
+
+#[test]
+fn cached_root() {
+    use flatbuffers_owned::CachedRoot;
+
+    let cached_foo = CachedRoot::new(OwnedFoo::new(get_foo_bytes()).unwrap());
+
+    assert_eq!(cached_foo.as_actual().a(), 42);
+    assert_eq!(cached_foo.as_actual().b(), Some("Hello, world!"));
+    assert_eq!(cached_foo.into_inner().as_actual().a(), 42);
+}
+
+#[test]
+fn into_local() {
+    use std::rc::Rc;
+
+    let owned_foo = OwnedFoo::new(get_foo_bytes()).unwrap();
+    let local_foo: RelaxedFoo<Rc<[u8]>> = owned_foo.into_local();
+    let cloned_foo = local_foo.clone();
+
+    assert_eq!(cloned_foo.as_actual().a(), 42);
+    assert_eq!(Rc::strong_count(local_foo.buffer()), 2);
+
+    let unique_foo: OwnedFoo = local_foo.to_owned();
+    assert_eq!(unique_foo.as_actual().b(), Some("Hello, world!"));
+}
+
+#[test]
+fn into_static() {
+    use std::borrow::Cow;
+
+    fn keep(foo: RelaxedFoo<Cow<'_, [u8]>>) -> RelaxedFoo<Cow<'static, [u8]>> {
+        foo.into_static()
+    }
+
+    let kept_foo;
+    {
+        let foo_bytes = get_foo_bytes();
+        kept_foo = keep(RelaxedFoo::new(Cow::Borrowed(&foo_bytes[..])).unwrap());
+    }
+
+    assert!(matches!(kept_foo.buffer(), Cow::Owned(_)));
+    assert_eq!(kept_foo.as_actual().a(), 42);
+
+    let owned_foo = keep(RelaxedFoo::new(Cow::Owned(get_foo_bytes().into_vec())).unwrap());
+    assert_eq!(owned_foo.as_actual().b(), Some("Hello, world!"));
+}
+
+#[test]
+fn vec_buffer() {
+    use std::convert::TryFrom;
+
+    let bytes = get_foo_bytes().into_vec();
+    let address = bytes.as_ptr();
+
+    let vec_foo: RelaxedFoo<Vec<u8>> = RelaxedFoo::try_from(bytes).unwrap();
+    assert_eq!(vec_foo.as_actual().a(), 42);
+
+    let owned_foo: OwnedFoo = vec_foo.into_boxed();
+    assert_eq!(owned_foo.as_ptr(), address);
+
+    let bytes = owned_foo.into_vec();
+    assert_eq!(bytes.as_ptr(), address);
+
+    let owned_foo = OwnedFoo::try_from(bytes).unwrap();
+    assert_eq!(owned_foo.as_actual().b(), Some("Hello, world!"));
+
+    assert!(OwnedFoo::try_from(vec![0xFF; 16]).is_err());
+}
+
+#[test]
+fn vec_into_shared() {
+    let vec_foo = RelaxedFoo::new(get_foo_bytes().into_vec()).unwrap();
+    let shared_foo = vec_foo.into_shared();
+
+    assert_eq!(shared_foo.clone().as_actual().a(), 42);
+}
+
+#[test]
+fn finish_owned() {
+    use flatbuffers_owned::FinishOwnedTrait;
+    use flatbuffers_owned::buffer::BuilderBuffer;
+
+    let mut builder = FlatBufferBuilder::new();
+    let b = builder.create_string("Hello, world!");
+    let offset = Foo::create(&mut builder, &FooArgs { a: 42, b: Some(b) });
+
+    let foo: RelaxedFoo<BuilderBuffer> = builder.finish_owned(offset, None).unwrap();
+    assert_eq!(foo.as_actual().a(), 42);
+    assert_eq!(foo.as_actual().b(), Some("Hello, world!"));
+    assert_eq!(&*foo, &*get_foo_bytes());
+
+    let head = foo.buffer().head();
+    let buffer = foo.into_inner().into_inner();
+    assert!(head > 0 && head < buffer.len());
+
+    // The taken over buffer can be reused by the next builder.
+    let mut builder = FlatBufferBuilder::from_vec(buffer);
+    let offset = Foo::create(&mut builder, &FooArgs { a: 7, b: None });
+
+    let foo: RelaxedFoo<BuilderBuffer> = unsafe { builder.finish_owned_unchecked(offset, None) };
+    assert_eq!(foo.as_actual().a(), 7);
+}
+
+#[test]
+fn from_builder() {
+    let mut builder = FlatBufferBuilder::new();
+    let b = builder.create_string("Hello, world!");
+    let offset = Foo::create(&mut builder, &FooArgs { a: 42, b: Some(b) });
+    builder.finish(offset, None);
+
+    let copied_foo = OwnedFoo::from_builder(&builder).unwrap();
+    assert_eq!(copied_foo.as_actual().a(), 42);
+
+    let stolen_foo = OwnedFoo::from_builder_owned(builder).unwrap();
+    assert_eq!(stolen_foo.as_actual().b(), Some("Hello, world!"));
+    assert_eq!(&*stolen_foo, &*copied_foo);
+}
+
+#[test]
+#[should_panic]
+fn from_builder_unfinished() {
+    let builder = FlatBufferBuilder::new();
+
+    let _ = OwnedFoo::from_builder(&builder);
+}
+
+#[test]
+fn try_from_builder_unfinished() {
+    let mut builder = FlatBufferBuilder::new();
+    assert!(OwnedFoo::try_from_builder(&builder).is_err());
+
+    Foo::create(&mut builder, &FooArgs { a: 42, b: None });
+    assert!(OwnedFoo::try_from_builder(&builder).is_err());
+    assert!(OwnedFoo::try_from_builder_owned(builder).is_err());
+}
+
+#[test]
+fn try_from_builder() {
+    let mut builder = FlatBufferBuilder::new();
+    let offset = Foo::create(&mut builder, &FooArgs { a: 42, b: None });
+    builder.finish(offset, None);
+
+    assert_eq!(OwnedFoo::try_from_builder(&builder).unwrap().as_actual().a(), 42);
+    assert_eq!(OwnedFoo::try_from_builder_owned(builder).unwrap().as_actual().a(), 42);
+}
+
+#[test]
+fn builder_buffer() {
+    use flatbuffers_owned::buffer::BuilderBuffer;
+
+    let mut builder = FlatBufferBuilder::new();
+    let b = builder.create_string("Hello, world!");
+    let offset = Foo::create(&mut builder, &FooArgs { a: 42, b: Some(b) });
+    builder.finish(offset, None);
+
+    let (buffer, head) = builder.collapse();
+    let address = buffer.as_ptr();
+    assert!(BuilderBuffer::new(buffer.clone(), buffer.len() + 1).is_none());
+
+    let foo = RelaxedFoo::new(BuilderBuffer::new(buffer, head).unwrap()).unwrap();
+    assert_eq!(foo.as_actual().a(), 42);
+    assert_eq!(foo.buffer().head(), head);
+
+    let bytes = foo.into_vec();
+    assert_eq!(bytes.as_ptr(), address);
+    assert_eq!(bytes, get_foo_bytes().into_vec());
+}
@@ -0,0 +1,51 @@
+mod common;
+
+use std::thread;
+use bytes::{BufMut, Bytes, BytesMut};
+use common::generated_fbs::foo::Foo;
+use common::build_foo_bytes;
+use flatbuffers_owned::flatbuffers_owned;
+
+flatbuffers_owned!(Foo);
+
+#[test]
+fn from_bytes_mut() {
+    let first = build_foo_bytes(1, Some("Hello, world!"), None);
+    let second = build_foo_bytes(2, Some("Hello, world!"), None);
+
+    let mut buffer = BytesMut::new();
+    buffer.put_slice(&first);
+    buffer.put_slice(&second);
+    let address = buffer.as_ptr();
+
+    let first_foo = OwnedFoo::from_bytes_mut(buffer.split_to(first.len())).unwrap();
+    let second_foo = OwnedFoo::from_bytes_mut(buffer.split_to(second.len())).unwrap();
+
+    assert_eq!(first_foo.as_ptr(), address);
+    assert_eq!(first_foo.as_actual().a(), 1);
+    assert_eq!(second_foo.as_actual().a(), 2);
+    assert!(buffer.is_empty());
+
+    let cloned_foo = second_foo.clone();
+    thread::spawn(move || assert_eq!(cloned_foo.as_actual().b(), Some("Hello, world!"))).join().unwrap();
+}
+
+#[test]
+fn from_bytes_mut_invalid() {
+    let mut buffer = BytesMut::new();
+    buffer.put_slice(&[0xFF; 16]);
+
+    assert!(OwnedFoo::from_bytes_mut(buffer).is_err());
+}
+
+#[test]
+fn freeze() {
+    let buffer = BytesMut::from(&build_foo_bytes(42, Some("Hello, world!"), None)[..]);
+    let address = buffer.as_ptr();
+
+    let foo = RelaxedFoo::new(buffer).unwrap();
+    let frozen_foo: RelaxedFoo<Bytes> = foo.freeze();
+
+    assert_eq!(frozen_foo.as_ptr(), address);
+    assert_eq!(frozen_foo.as_actual().a(), 42);
+}
@@ -0,0 +1,88 @@
+mod common;
+
+use std::sync::Arc;
+use flatbuffers::VerifierOptions;
+use common::generated_fbs::foo::Foo;
+use common::generated_fbs::bar::Bar;
+use common::get_foo_bytes;
+use flatbuffers_owned::{flatbuffers_owned, RelaxedFollowTrait, VerifierPolicy};
+use flatbuffers_owned::cache::VerificationCache;
+
+flatbuffers_owned!(Foo, Bar);
+
+struct NoTablesPolicy;
+
+unsafe impl VerifierPolicy for NoTablesPolicy {
+    fn options<T: RelaxedFollowTrait>() -> VerifierOptions {
+        VerifierOptions { max_tables: 0, ..Default::default() }
+    }
+}
+
+#[test]
+fn remembers_shared_buffers() {
+    let cache = VerificationCache::new(8);
+    let bytes: Arc<[u8]> = get_foo_bytes().into();
+
+    let first: RelaxedFoo<Arc<[u8]>> = cache.new_verified(bytes.clone()).unwrap();
+    let second: RelaxedFoo<Arc<[u8]>> = cache.new_verified(bytes.clone()).unwrap();
+
+    assert_eq!(cache.len(), 1);
+    assert_eq!(first.as_actual().a(), 42);
+    assert_eq!(second.as_actual().b().unwrap(), "Hello, world!");
+}
+
+#[test]
+fn keys_by_flatbuffer_type() {
+    let cache = VerificationCache::new(8);
+    let bytes: Arc<[u8]> = get_foo_bytes().into();
+
+    let _: RelaxedFoo<Arc<[u8]>> = cache.new_verified(bytes.clone()).unwrap();
+    // The Foo bytes happen to be a valid Bar as well, but must be verified again.
+    let _: RelaxedBar<Arc<[u8]>> = cache.new_verified(bytes).unwrap();
+
+    assert_eq!(cache.len(), 2);
+}
+
+#[test]
+fn keys_by_verifier_policy() {
+    let cache = VerificationCache::new(8);
+    let bytes: Arc<[u8]> = get_foo_bytes().into();
+
+    let _: RelaxedFoo<Arc<[u8]>> = cache.new_verified(bytes.clone()).unwrap();
+    // The stricter policy must not be skipped because the default policy accepted the buffer.
+    assert!(cache.new_verified::<RelaxedFoo<Arc<[u8]>, NoTablesPolicy>, _>(bytes).is_err());
+
+    assert_eq!(cache.len(), 1);
+}
+
+#[test]
+fn skips_unshared_buffers() {
+    let cache = VerificationCache::new(8);
+    let bytes = get_foo_bytes();
+
+    let _: OwnedFoo = cache.new_verified(bytes).unwrap();
+
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn evicts_and_clears() {
+    let cache = VerificationCache::new(2);
+    for _ in 0..3 {
+        let _: RelaxedFoo<Arc<[u8]>> = cache.new_verified(get_foo_bytes().into()).unwrap();
+    }
+    assert_eq!(cache.len(), 2);
+
+    cache.clear();
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn fail_invalid_bytes() {
+    let cache = VerificationCache::new(8);
+    let mut bytes = get_foo_bytes().to_vec();
+    bytes[0] = 0xff;
+
+    assert!(cache.new_verified::<RelaxedFoo<Arc<[u8]>>, _>(Arc::from(bytes)).is_err());
+    assert!(cache.is_empty());
+}
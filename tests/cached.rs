@@ -0,0 +1,110 @@
+mod common;
+
+use std::marker::PhantomData;
+use std::ops::Deref;
+use std::sync::Arc;
+use common::generated_fbs::foo::Foo;
+use common::get_foo_bytes;
+use flatbuffers_owned::buffer::StableBuffer;
+use flatbuffers_owned::{flatbuffers_owned, StableDeref, StableRelaxedFlatBufferTrait};
+
+flatbuffers_owned!(Foo { cached });
+
+#[test]
+fn cached_get() {
+    let cached_foo = CachedFoo::new(OwnedFoo::new(get_foo_bytes()).unwrap());
+    let foo: &Foo = cached_foo.get();
+
+    assert_eq!(foo.a(), 42);
+    assert_eq!(foo.b(), Some("Hello, world!"));
+}
+
+#[test]
+fn cached_moved() {
+    let cached_foo = CachedFoo::new(OwnedFoo::new(get_foo_bytes()).unwrap());
+    let moved = Box::new(cached_foo);
+
+    assert_eq!(moved.get().b(), Some("Hello, world!"));
+    assert_eq!(moved.as_actual().a(), 42);
+}
+
+#[test]
+fn cached_shared() {
+    let shared_bytes: Arc<[u8]> = get_foo_bytes().into();
+    let cached_foo: CachedFoo<Arc<[u8]>> = CachedFoo::new(RelaxedFoo::new(shared_bytes).unwrap());
+    let cloned_foo = cached_foo.clone();
+    drop(cached_foo);
+
+    assert_eq!(cloned_foo.get().a(), 42);
+    assert_eq!(cloned_foo.into_inner().as_actual().a(), 42);
+}
+
+#[test]
+fn stable_wrappers() {
+    fn assert_stable<W: StableRelaxedFlatBufferTrait>() {}
+
+    assert_stable::<OwnedFoo>();
+    assert_stable::<RelaxedFoo<Vec<u8>>>();
+    assert_stable::<RelaxedFoo<Arc<[u8]>>>();
+    assert_stable::<RelaxedFoo<&'static [u8]>>();
+}
+
+/// Dereferences to stable heap bytes, but returns its own inline bytes from `as_ref()`.
+struct DisagreeingBuffer {
+    inline: [u8; 64],
+    heap: Box<[u8]>,
+}
+
+impl Deref for DisagreeingBuffer {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.heap
+    }
+}
+
+impl AsRef<[u8]> for DisagreeingBuffer {
+    fn as_ref(&self) -> &[u8] {
+        &self.inline
+    }
+}
+
+unsafe impl StableDeref for DisagreeingBuffer {}
+
+/// Holds a shared buffer, whose bytes do not move with it.
+struct SharedBuffer(Arc<[u8]>);
+
+impl AsRef<[u8]> for SharedBuffer {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+unsafe impl StableBuffer for SharedBuffer {}
+
+trait NotStable {
+    const STABLE: bool = false;
+}
+
+impl<W> NotStable for W {}
+
+struct Probe<W>(PhantomData<W>);
+
+#[allow(dead_code)]
+impl<W: StableRelaxedFlatBufferTrait> Probe<W> {
+    const STABLE: bool = true;
+}
+
+#[test]
+fn stable_deref_is_not_enough() {
+    const { assert!(Probe::<OwnedFoo>::STABLE) };
+    const { assert!(!Probe::<RelaxedFoo<DisagreeingBuffer>>::STABLE) };
+}
+
+#[test]
+fn custom_stable_buffer() {
+    let cached_foo: CachedFoo<SharedBuffer> = CachedFoo::new(RelaxedFoo::new(SharedBuffer(get_foo_bytes().into())).unwrap());
+    let moved = Box::new(cached_foo);
+
+    assert_eq!(moved.get().b(), Some("Hello, world!"));
+}
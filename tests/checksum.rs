@@ -0,0 +1,86 @@
+mod common;
+
+use common::generated_fbs::record::{Record, RecordArgs};
+use flatbuffers_owned::buffer::AlignedBuffer;
+use flatbuffers_owned::flatbuffers_owned;
+use flatbuffers_owned::size_prefixed::{FrameError, Framing, SizePrefixedIter, SizePrefixedReader, SizePrefixedWriter, StreamError, PREFIX_LEN};
+
+flatbuffers_owned!(Record);
+
+fn build_record(id: u64) -> OwnedRecord {
+    OwnedRecord::build(|builder| Record::create(builder, &RecordArgs { id, foo: None })).unwrap()
+}
+
+fn write_log(ids: &[u64]) -> Vec<u8> {
+    let mut writer = SizePrefixedWriter::new(Vec::new()).with_framing(Framing::Crc32);
+    for &id in ids {
+        writer.write_message(&build_record(id)).unwrap();
+    }
+
+    writer.into_inner()
+}
+
+#[test]
+fn round_trip() {
+    let log = AlignedBuffer::copy_from_slice(&write_log(&[1, 2, 3]));
+
+    let records = SizePrefixedIter::<Record<'static>>::new(&log)
+        .with_framing(Framing::Crc32)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    assert_eq!(records.iter().map(|record| record.as_actual_at().id()).collect::<Vec<_>>(), [1, 2, 3]);
+    assert_eq!(records[0].loc(), Framing::Crc32.root_loc());
+    assert_eq!(records[1].buffer().len(), build_record(2).len() + PREFIX_LEN + 4);
+}
+
+#[test]
+fn checksum_mismatch() {
+    let mut log = write_log(&[1, 2, 3]);
+    let frame_len = build_record(1).len() + Framing::Crc32.root_loc();
+    // Flips a bit of the id of the second record.
+    log[frame_len * 2 - 4] ^= 1;
+    let log = AlignedBuffer::copy_from_slice(&log);
+
+    let results = SizePrefixedIter::<Record<'static>>::new(&log).with_framing(Framing::Crc32).collect::<Vec<_>>();
+
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[1].as_ref().unwrap_err(), &FrameError::ChecksumMismatch { offset: frame_len });
+    assert_eq!(results[2].as_ref().unwrap().as_actual_at().id(), 3);
+}
+
+#[test]
+fn torn_write() {
+    let log = write_log(&[1, 2]);
+    let torn = AlignedBuffer::copy_from_slice(&log[..log.len() - 5]);
+
+    let results = SizePrefixedIter::<Record<'static>>::new(&torn).with_framing(Framing::Crc32).collect::<Vec<_>>();
+
+    assert_eq!(results.len(), 2);
+    assert!(matches!(results[1], Err(FrameError::Truncated { .. })));
+}
+
+#[test]
+fn reader() {
+    let mut log = write_log(&[1, 2, 3]);
+    let frame_len = build_record(1).len() + Framing::Crc32.root_loc();
+    log[frame_len + PREFIX_LEN] ^= 1;
+
+    let results = SizePrefixedReader::<Record<'static>, _>::new(&log[..]).with_framing(Framing::Crc32).collect::<Vec<_>>();
+
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0].as_ref().unwrap().as_actual_at().id(), 1);
+    assert!(matches!(results[1], Err(StreamError::ChecksumMismatch)));
+    assert_eq!(results[2].as_ref().unwrap().as_actual_at().id(), 3);
+}
+
+#[test]
+fn too_short() {
+    // A frame holding only two bytes, too short for a checksum.
+    let log = [2, 0, 0, 0, 0, 0];
+
+    let results = SizePrefixedIter::<Record<'static>>::new(&log).with_framing(Framing::Crc32).collect::<Vec<_>>();
+
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].as_ref().unwrap_err(), &FrameError::ChecksumMismatch { offset: 0 });
+}
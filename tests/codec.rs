@@ -0,0 +1,90 @@
+mod common;
+
+use bytes::BytesMut;
+use flatbuffers::FlatBufferBuilder;
+use futures::{SinkExt, StreamExt};
+use common::generated_fbs::record::{Record, RecordArgs};
+use flatbuffers_owned::flatbuffers_owned;
+use flatbuffers_owned::size_prefixed::{FlatBufferCodec, StreamError};
+use tokio_util::codec::{Decoder, Encoder, FramedRead, FramedWrite};
+
+flatbuffers_owned!(Record);
+
+fn build_record(id: u64) -> OwnedRecord {
+    OwnedRecord::build(|builder| Record::create(builder, &RecordArgs { id, foo: None })).unwrap()
+}
+
+#[tokio::test]
+async fn framed() {
+    let (client, server) = tokio::io::duplex(64);
+
+    let writer = tokio::spawn(async move {
+        let mut framed = FramedWrite::new(client, FlatBufferCodec::<Record<'static>>::new());
+
+        for id in 1..=100 {
+            framed.send(&build_record(id)).await.unwrap();
+        }
+    });
+
+    let ids = FramedRead::new(server, FlatBufferCodec::<Record<'static>>::new())
+        .map(|record| record.unwrap().as_actual_at().id())
+        .collect::<Vec<_>>()
+        .await;
+
+    writer.await.unwrap();
+    assert_eq!(ids, (1..=100).collect::<Vec<_>>());
+}
+
+#[test]
+fn decode_partial() {
+    let mut codec = FlatBufferCodec::<Record<'static>>::new();
+    let mut encoded = BytesMut::new();
+    codec.encode(&build_record(1), &mut encoded).unwrap();
+    codec.encode(&build_record(2), &mut encoded).unwrap();
+
+    let mut src = BytesMut::new();
+    let mut ids = Vec::new();
+    for byte in encoded.iter() {
+        src.extend_from_slice(&[*byte]);
+
+        if let Some(record) = codec.decode(&mut src).unwrap() {
+            ids.push(record.as_actual_at().id());
+        }
+    }
+
+    assert_eq!(ids, [1, 2]);
+    assert!(src.is_empty());
+}
+
+#[test]
+fn decode_size_prefixed() {
+    let mut builder = FlatBufferBuilder::new();
+    let offset = Record::create(&mut builder, &RecordArgs { id: 7, foo: None });
+    builder.finish_size_prefixed(offset, None);
+
+    let mut src = BytesMut::from(builder.finished_data());
+    let record = FlatBufferCodec::<Record<'static>>::new().decode(&mut src).unwrap().unwrap();
+
+    assert_eq!(record.as_actual_at().id(), 7);
+}
+
+#[test]
+fn decode_too_large() {
+    let mut codec = FlatBufferCodec::<Record<'static>>::with_max_message_size(8);
+    let mut src = BytesMut::new();
+    codec.encode(&build_record(1), &mut src).unwrap();
+
+    assert!(matches!(codec.decode(&mut src), Err(StreamError::TooLarge { max: 8, .. })));
+}
+
+#[test]
+fn decode_invalid() {
+    let mut codec = FlatBufferCodec::<Record<'static>>::new();
+    let mut src = BytesMut::new();
+    codec.encode(&build_record(1), &mut src).unwrap();
+    // Points the root offset out of the frame.
+    src[4..8].copy_from_slice(&u32::MAX.to_le_bytes());
+
+    assert!(matches!(codec.decode(&mut src), Err(StreamError::InvalidFlatbuffer(_))));
+    assert!(src.is_empty());
+}
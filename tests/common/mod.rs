@@ -0,0 +1,34 @@
+//! Helpers shared by the integration tests, included with `mod common;`.
+
+#![allow(dead_code)]
+
+use flatbuffers::{FlatBufferBuilder, Verifiable};
+use flatbuffers_owned::{OwnedFlatBuffer, RelaxedFollowTrait};
+use generated_fbs::foo::{Foo, FooArgs};
+
+#[allow(dead_code, unused_imports, clippy::all, mismatched_lifetime_syntaxes)]
+#[path = "../generated_fbs/mod.rs"]
+pub mod generated_fbs;
+
+/// Builds a finished `Foo { a: 42, b: "Hello, world!" }`.
+pub fn get_foo_bytes() -> Box<[u8]> {
+    build_foo_bytes(42, Some("Hello, world!"), None)
+}
+
+/// Builds a finished `Foo` with the passed fields and file identifier.
+pub fn build_foo_bytes(a: u32, b: Option<&str>, identifier: Option<&str>) -> Box<[u8]> {
+    let mut builder = FlatBufferBuilder::new();
+    let b = b.map(|b| builder.create_string(b));
+
+    let offset = Foo::create(&mut builder, &FooArgs { a, b });
+    builder.finish(offset, identifier);
+
+    builder.finished_data().into()
+}
+
+/// Builds an owned `Foo { a, b: "Hello, world!" }`.
+///
+/// Generic over the root type, as every test declares its own `OwnedFoo` with `flatbuffers_owned!`.
+pub fn build_foo<T: RelaxedFollowTrait + Verifiable>(a: u32) -> OwnedFlatBuffer<T, Box<[u8]>> {
+    OwnedFlatBuffer::new(build_foo_bytes(a, Some("Hello, world!"), None)).unwrap()
+}
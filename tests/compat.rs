@@ -0,0 +1,28 @@
+#![allow(deprecated)]
+
+mod common;
+
+use common::generated_fbs::foo::Foo;
+use common::get_foo_bytes;
+use flatbuffers_owned::flatbuffers_owned;
+use flatbuffers_owned::compat::RelaxedFlatBufferTrait;
+
+flatbuffers_owned!(Foo);
+
+// Generic code written against the 0.2 trait signature.
+fn first_a<TBuffer, TFlatBuffer>(data: TBuffer) -> u32
+    where TFlatBuffer: RelaxedFlatBufferTrait<TBuffer, FlatBuffer = Foo<'static>>
+{
+    let flatbuffer = TFlatBuffer::new(data).unwrap();
+    assert!(TFlatBuffer::verify(&flatbuffer).is_ok());
+
+    flatbuffer.as_actual().a()
+}
+
+#[test]
+fn old_trait_signature() {
+    let foo_bytes = get_foo_bytes();
+
+    assert_eq!(first_a::<_, OwnedFoo>(foo_bytes.clone()), 42);
+    assert_eq!(first_a::<_, RelaxedFoo<&[u8]>>(&foo_bytes[..]), 42);
+}
@@ -0,0 +1,224 @@
+mod common;
+
+use flatbuffers::FlatBufferBuilder;
+use flatbuffers_owned::reflection::compatibility::{self, Change, ChangeKind};
+use flatbuffers_owned::reflection::Schema;
+use common::generated_fbs::reflection::*;
+
+#[derive(Clone, Copy)]
+struct FieldSpec {
+    name: &'static str,
+    id: u16,
+    base_type: i8,
+    index: i32,
+    required: bool,
+    deprecated: bool,
+    default_integer: i64,
+}
+
+fn spec(name: &'static str, id: u16, base_type: i8) -> FieldSpec {
+    FieldSpec { name, id, base_type, index: -1, required: false, deprecated: false, default_integer: 0 }
+}
+
+struct ObjectSpec {
+    name: &'static str,
+    bytesize: i32,
+    fields: Vec<FieldSpec>,
+}
+
+fn build(objects: &[ObjectSpec], enums: &[(&str, &[(&str, i64)])], root: usize, file_ident: &str) -> Schema {
+    let mut fbb = FlatBufferBuilder::new();
+
+    let objects = objects.iter()
+        .map(|object| {
+            let is_struct = object.bytesize > 0;
+            let fields = object.fields.iter()
+                .map(|field| {
+                    let name = fbb.create_string(field.name);
+                    let type_ = create_type(&mut fbb, &TypeArgs { base_type: field.base_type, index: field.index, ..Default::default() });
+                    let offset = if is_struct { 4 * field.id } else { 4 + 2 * field.id };
+                    create_field(&mut fbb, &FieldArgs {
+                        name: Some(name),
+                        type_: Some(type_),
+                        id: field.id,
+                        offset,
+                        required: field.required,
+                        deprecated: field.deprecated,
+                        default_integer: field.default_integer,
+                        ..Default::default()
+                    })
+                })
+                .collect::<Vec<_>>();
+            let fields = fbb.create_vector(&fields);
+            let name = fbb.create_string(object.name);
+            create_object(&mut fbb, &ObjectArgs { name: Some(name), fields: Some(fields), is_struct, minalign: 4, bytesize: object.bytesize, ..Default::default() })
+        })
+        .collect::<Vec<_>>();
+
+    let enums = enums.iter()
+        .map(|(name, values)| {
+            let values = values.iter()
+                .map(|(name, value)| {
+                    let name = fbb.create_string(name);
+                    create_enum_val(&mut fbb, &EnumValArgs { name: Some(name), value: *value, ..Default::default() })
+                })
+                .collect::<Vec<_>>();
+            let values = fbb.create_vector(&values);
+            let underlying_type = create_type(&mut fbb, &TypeArgs { base_type: base_type::UBYTE, index: 0, ..Default::default() });
+            let name = fbb.create_string(name);
+            create_enum(&mut fbb, &EnumArgs { name: Some(name), values: Some(values), underlying_type: Some(underlying_type), ..Default::default() })
+        })
+        .collect::<Vec<_>>();
+
+    let objects_vector = fbb.create_vector(&objects);
+    let enums = fbb.create_vector(&enums);
+    let file_ident = fbb.create_string(file_ident);
+    let schema = create_schema(&mut fbb, &SchemaArgs {
+        objects: Some(objects_vector),
+        enums: Some(enums),
+        file_ident: Some(file_ident),
+        root_table: Some(objects[root]),
+        ..Default::default()
+    });
+    finish_schema_buffer(&mut fbb, schema);
+
+    Schema::from_bfbs(fbb.finished_data()).unwrap()
+}
+
+// namespace game;
+// enum Color : ubyte { Red, Green, Blue }
+// struct Vec3 { x: float; y: float; z: float; }
+// table Monster { name: string (required); hp: short = 100; color: Color; pos: Vec3; }
+fn monster_fields() -> Vec<FieldSpec> {
+    vec![
+        FieldSpec { required: true, ..spec("name", 0, base_type::STRING) },
+        FieldSpec { default_integer: 100, ..spec("hp", 1, base_type::SHORT) },
+        FieldSpec { index: 0, ..spec("color", 2, base_type::UBYTE) },
+        FieldSpec { index: 1, ..spec("pos", 3, base_type::OBJ) },
+    ]
+}
+
+fn vec3_fields() -> Vec<FieldSpec> {
+    ["x", "y", "z"].iter().enumerate().map(|(id, name)| spec(name, id as u16, base_type::FLOAT)).collect()
+}
+
+const COLORS: &[(&str, i64)] = &[("Red", 0), ("Green", 1), ("Blue", 2)];
+
+fn v1() -> Schema {
+    let objects = [
+        ObjectSpec { name: "game.Monster", bytesize: 0, fields: monster_fields() },
+        ObjectSpec { name: "game.Vec3", bytesize: 12, fields: vec3_fields() },
+    ];
+
+    build(&objects, &[("game.Color", COLORS)], 0, "MONS")
+}
+
+fn change(path: &str, kind: ChangeKind) -> Change {
+    Change { path: path.to_string(), kind }
+}
+
+#[test]
+fn identical() {
+    let report = compatibility::check(&v1(), &v1());
+
+    assert!(report.changes.is_empty());
+    assert!(report.is_compatible());
+    assert_eq!(report.to_string(), "");
+}
+
+#[test]
+fn compatible_changes() {
+    let mut monster = monster_fields();
+    monster[1].name = "health";
+    monster[2].deprecated = true;
+    monster.push(spec("mana", 4, base_type::INT));
+
+    let objects = [
+        ObjectSpec { name: "game.Monster", bytesize: 0, fields: monster },
+        ObjectSpec { name: "game.Vec3", bytesize: 12, fields: vec3_fields() },
+        ObjectSpec { name: "game.Weapon", bytesize: 0, fields: vec![spec("damage", 0, base_type::SHORT)] },
+    ];
+    let colors = [("Red", 0), ("Green", 1), ("Blue", 2), ("Yellow", 3)];
+    let v2 = build(&objects, &[("game.Color", &colors), ("game.Class", &[("Warrior", 0)])], 0, "MONS");
+
+    let report = compatibility::check(&v1(), &v2);
+
+    assert!(report.is_compatible(), "{}", report);
+    assert_eq!(report.breaking().count(), 0);
+    assert_eq!(report.changes, [
+        change("game.Monster.hp", ChangeKind::FieldRenamed { old_name: "hp".to_string() }),
+        change("game.Monster.color", ChangeKind::FieldDeprecated),
+        change("game.Monster.mana", ChangeKind::FieldAdded),
+        change("game.Weapon", ChangeKind::TableAdded),
+        change("game.Color.Yellow", ChangeKind::EnumValueAdded),
+        change("game.Class", ChangeKind::EnumAdded),
+    ]);
+}
+
+#[test]
+fn breaking_changes() {
+    let mut monster = monster_fields();
+    monster.remove(0);
+    monster[0].base_type = base_type::INT;
+    monster[1].default_integer = 2;
+    monster.push(FieldSpec { required: true, ..spec("guild", 4, base_type::STRING) });
+
+    let mut vec3 = vec3_fields();
+    vec3.push(spec("w", 3, base_type::FLOAT));
+
+    let objects = [
+        ObjectSpec { name: "game.Monster", bytesize: 0, fields: monster },
+        ObjectSpec { name: "game.Vec4", bytesize: 16, fields: vec3 },
+    ];
+    let v2 = build(&objects, &[("game.Color", &[("Green", 1), ("Blue", 5)])], 0, "MON2");
+
+    let report = compatibility::check(&v1(), &v2);
+
+    assert!(!report.is_compatible());
+    assert_eq!(report.changes, [
+        change("", ChangeKind::FileIdentifierChanged { old: Some("MONS".to_string()), new: Some("MON2".to_string()) }),
+        change("game.Monster.name", ChangeKind::FieldRemoved),
+        change("game.Monster.hp", ChangeKind::FieldTypeChanged { old: "Short".to_string(), new: "Int".to_string() }),
+        change("game.Monster.color", ChangeKind::DefaultChanged),
+        change("game.Monster.pos", ChangeKind::FieldTypeChanged { old: "game.Vec3".to_string(), new: "game.Vec4".to_string() }),
+        change("game.Monster.guild", ChangeKind::RequiredFieldAdded),
+        change("game.Vec3", ChangeKind::TableRemoved),
+        change("game.Vec4", ChangeKind::TableAdded),
+        change("game.Color.Red", ChangeKind::EnumValueRemoved),
+        change("game.Color.Blue", ChangeKind::EnumValueChanged { old: 2, new: 5 }),
+    ]);
+    assert_eq!(report.breaking().count(), 9);
+    assert_eq!(report.breaking().next().unwrap().to_string(), r#"breaking: file identifier changed from Some("MONS") to Some("MON2")"#);
+}
+
+#[test]
+fn struct_layout() {
+    let mut vec3 = vec3_fields();
+    vec3[2].base_type = base_type::INT;
+
+    let objects = [
+        ObjectSpec { name: "game.Monster", bytesize: 0, fields: monster_fields() },
+        ObjectSpec { name: "game.Vec3", bytesize: 12, fields: vec3 },
+    ];
+    let v2 = build(&objects, &[("game.Color", COLORS)], 0, "MONS");
+
+    let report = compatibility::check(&v1(), &v2);
+
+    assert_eq!(report.changes, [change("game.Vec3", ChangeKind::StructLayoutChanged)]);
+    assert_eq!(report.to_string(), "breaking: game.Vec3: struct layout changed\n");
+}
+
+#[test]
+fn root_type() {
+    let objects = [
+        ObjectSpec { name: "game.Monster", bytesize: 0, fields: monster_fields() },
+        ObjectSpec { name: "game.Vec3", bytesize: 12, fields: vec3_fields() },
+        ObjectSpec { name: "game.Weapon", bytesize: 0, fields: vec![spec("damage", 0, base_type::SHORT)] },
+    ];
+    let v2 = build(&objects, &[("game.Color", COLORS)], 2, "MONS");
+
+    let report = compatibility::check(&v1(), &v2);
+
+    assert!(!report.is_compatible());
+    assert_eq!(report.changes[0], change("", ChangeKind::RootTypeChanged { old: Some("game.Monster".to_string()), new: Some("game.Weapon".to_string()) }));
+}
@@ -0,0 +1,4 @@
+#[test]
+fn macro_diagnostics() {
+    trybuild::TestCases::new().compile_fail("tests/ui/*.rs");
+}
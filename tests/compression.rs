@@ -0,0 +1,64 @@
+mod common;
+
+use common::generated_fbs::foo::Foo;
+use common::build_foo_bytes;
+use flatbuffers_owned::compression::DecompressError;
+use flatbuffers_owned::flatbuffers_owned;
+
+flatbuffers_owned!(Foo);
+
+fn compressible_foo() -> OwnedFoo {
+    OwnedFoo::new(build_foo_bytes(42, Some(&"Hello, world! ".repeat(64)), None)).unwrap()
+}
+
+#[test]
+fn zstd() {
+    let owned_foo = compressible_foo();
+    let compressed = owned_foo.to_zstd(3).unwrap();
+    assert!(compressed.len() < owned_foo.len());
+
+    let decompressed = OwnedFoo::from_zstd(&compressed).unwrap();
+    assert_eq!(decompressed.as_actual().a(), 42);
+    assert_eq!(&*decompressed, &*owned_foo);
+}
+
+#[test]
+fn zstd_limit() {
+    let owned_foo = compressible_foo();
+    let compressed = owned_foo.to_zstd(3).unwrap();
+
+    assert!(OwnedFoo::from_zstd_with_limit(&compressed, owned_foo.len()).is_ok());
+    assert_eq!(OwnedFoo::from_zstd_with_limit(&compressed, owned_foo.len() - 1).unwrap_err(), DecompressError::TooLarge { max: owned_foo.len() - 1 });
+}
+
+#[test]
+fn zstd_corrupt() {
+    assert_eq!(OwnedFoo::from_zstd(&[1, 2, 3, 4, 5]).unwrap_err(), DecompressError::Corrupt);
+}
+
+#[test]
+fn lz4() {
+    let owned_foo = compressible_foo();
+    let compressed = owned_foo.to_lz4();
+    assert!(compressed.len() < owned_foo.len());
+
+    let decompressed = OwnedFoo::from_lz4(&compressed).unwrap();
+    assert_eq!(decompressed.as_actual().a(), 42);
+    assert_eq!(&*decompressed, &*owned_foo);
+}
+
+#[test]
+fn lz4_limit() {
+    let owned_foo = compressible_foo();
+    let compressed = owned_foo.to_lz4();
+
+    assert_eq!(OwnedFoo::from_lz4_with_limit(&compressed, 16).unwrap_err(), DecompressError::TooLarge { max: 16 });
+    assert_eq!(OwnedFoo::from_lz4(&compressed[..3]).unwrap_err(), DecompressError::Corrupt);
+}
+
+#[test]
+fn invalid_flatbuffer() {
+    let compressed = lz4_flex::compress_prepend_size(&[0xff; 32]);
+
+    assert!(matches!(OwnedFoo::from_lz4(&compressed), Err(DecompressError::InvalidFlatbuffer(_))));
+}
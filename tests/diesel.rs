@@ -0,0 +1,75 @@
+mod common;
+
+use common::generated_fbs::foo::Foo;
+use common::build_foo;
+use diesel::prelude::*;
+use diesel::result::Error;
+use diesel::sqlite::SqliteConnection;
+use flatbuffers_owned::{flatbuffers_owned, VerifyError};
+
+flatbuffers_owned!(Foo);
+
+diesel::table! {
+    foos (id) {
+        id -> Integer,
+        foo -> Binary,
+    }
+}
+
+#[derive(Queryable, Insertable)]
+#[diesel(table_name = foos)]
+struct StoredFoo {
+    id: i32,
+    foo: OwnedFoo,
+}
+
+fn connect() -> SqliteConnection {
+    let mut connection = SqliteConnection::establish(":memory:").unwrap();
+    diesel::sql_query("CREATE TABLE foos (id INTEGER PRIMARY KEY NOT NULL, foo BLOB NOT NULL)")
+        .execute(&mut connection)
+        .unwrap();
+
+    connection
+}
+
+#[test]
+fn insert_and_load() {
+    let mut connection = connect();
+    let borrowed_bytes = build_foo::<Foo>(7).into_vec();
+    let borrowed_foo = RelaxedFoo::new(&borrowed_bytes[..]).unwrap();
+
+    diesel::insert_into(foos::table)
+        .values(&StoredFoo { id: 1, foo: build_foo(42) })
+        .execute(&mut connection)
+        .unwrap();
+    diesel::insert_into(foos::table)
+        .values((foos::id.eq(2), foos::foo.eq(borrowed_foo)))
+        .execute(&mut connection)
+        .unwrap();
+
+    let stored = foos::table.order(foos::id).load::<StoredFoo>(&mut connection).unwrap();
+    assert_eq!(stored.len(), 2);
+    assert_eq!(stored[0].foo.as_actual().a(), 42);
+    assert_eq!(stored[1].foo.as_actual().b(), Some("Hello, world!"));
+
+    let foo_seven: OwnedFoo = foos::table.find(2).select(foos::foo).first(&mut connection).unwrap();
+    assert_eq!(&*foo_seven, &borrowed_bytes[..]);
+
+    let filtered = foos::table.filter(foos::foo.eq(&foo_seven)).select(foos::id).first::<i32>(&mut connection).unwrap();
+    assert_eq!(filtered, 2);
+}
+
+#[test]
+fn verify_on_load() {
+    let mut connection = connect();
+
+    diesel::insert_into(foos::table)
+        .values((foos::id.eq(1), foos::foo.eq(&[0xffu8, 0xff, 0xff, 0xff][..])))
+        .execute(&mut connection)
+        .unwrap();
+
+    match foos::table.find(1).select(foos::foo).first::<OwnedFoo>(&mut connection) {
+        Err(Error::DeserializationError(e)) => assert!(std::error::Error::source(&*e).and_then(|source| source.downcast_ref::<VerifyError>()).is_some()),
+        result => panic!("Expected a deserialization error, got {:?}", result.map(|_| ())),
+    }
+}
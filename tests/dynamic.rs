@@ -0,0 +1,227 @@
+mod common;
+
+use std::sync::Arc;
+use flatbuffers::{FlatBufferBuilder, WIPOffset};
+use flatbuffers_owned::flatbuffers_owned;
+use flatbuffers_owned::reflection::dynamic::{DynamicError, DynamicFlatBuffer, FieldError, Value};
+use flatbuffers_owned::reflection::Schema;
+use common::generated_fbs::foo::{Foo, FooArgs};
+use common::generated_fbs::reflection::*;
+
+flatbuffers_owned!(Foo { create });
+
+type Builder = FlatBufferBuilder<'static>;
+
+fn field(fbb: &mut Builder, name: &str, id: u16, ty: TypeArgs, args: FieldArgs<'static>) -> WIPOffset<FieldOffset> {
+    let name = fbb.create_string(name);
+    let type_ = create_type(fbb, &ty);
+    create_field(fbb, &FieldArgs { name: Some(name), type_: Some(type_), id, offset: 4 + 2 * id, ..args })
+}
+
+fn object(fbb: &mut Builder, name: &str, fields: &[WIPOffset<FieldOffset>], args: ObjectArgs<'static>) -> WIPOffset<ObjectOffset> {
+    let name = fbb.create_string(name);
+    let fields = fbb.create_vector(fields);
+    create_object(fbb, &ObjectArgs { name: Some(name), fields: Some(fields), ..args })
+}
+
+fn enumeration(fbb: &mut Builder, name: &str, values: &[(&str, Option<TypeArgs>)], underlying_type: i8, is_union: bool) -> WIPOffset<EnumOffset> {
+    let values = values.iter().enumerate()
+        .map(|(value, (name, union_type))| {
+            let name = fbb.create_string(name);
+            let union_type = union_type.as_ref().map(|ty| create_type(fbb, ty));
+            create_enum_val(fbb, &EnumValArgs { name: Some(name), value: value as i64, union_type, ..Default::default() })
+        })
+        .collect::<Vec<_>>();
+    let values = fbb.create_vector(&values);
+    let underlying_type = create_type(fbb, &TypeArgs { base_type: underlying_type, base_size: 1, ..Default::default() });
+    let name = fbb.create_string(name);
+    create_enum(fbb, &EnumArgs { name: Some(name), values: Some(values), is_union, underlying_type: Some(underlying_type), ..Default::default() })
+}
+
+fn finish(mut fbb: Builder, objects: &[WIPOffset<ObjectOffset>], enums: &[WIPOffset<EnumOffset>], root: usize, file_ident: Option<&str>) -> Schema {
+    let objects_vector = fbb.create_vector(objects);
+    let enums = fbb.create_vector(enums);
+    let file_ident = file_ident.map(|ident| fbb.create_string(ident));
+    let schema = create_schema(&mut fbb, &SchemaArgs {
+        objects: Some(objects_vector),
+        enums: Some(enums),
+        file_ident,
+        root_table: Some(objects[root]),
+        ..Default::default()
+    });
+    finish_schema_buffer(&mut fbb, schema);
+
+    Schema::from_bfbs(fbb.finished_data()).unwrap()
+}
+
+// table Foo { a: uint32; b: string; }
+// root_type Foo;
+fn foo_schema() -> Schema {
+    let mut fbb = FlatBufferBuilder::new();
+
+    let a = field(&mut fbb, "a", 0, TypeArgs { base_type: base_type::UINT, ..Default::default() }, Default::default());
+    let b = field(&mut fbb, "b", 1, TypeArgs { base_type: base_type::STRING, ..Default::default() }, Default::default());
+    let foo_object = object(&mut fbb, "Foo", &[a, b], ObjectArgs { minalign: 4, ..Default::default() });
+
+    finish(fbb, &[foo_object], &[], 0, None)
+}
+
+// namespace game;
+// enum Color : ubyte { Red, Green, Blue }
+// union Equipment { Weapon }
+// struct Vec3 { x: float; y: float; z: float; }
+// table Weapon { name: string; damage: short; }
+// table Monster { name: string (required); hp: short = 100; pos: Vec3; color: Color = Blue; inventory: [ubyte]; weapons: [Weapon]; equipped: Equipment; path: [Vec3]; }
+// root_type Monster;
+// file_identifier "MONS";
+fn monster_schema() -> Schema {
+    let mut fbb = FlatBufferBuilder::new();
+    let ty = |base_type, index| TypeArgs { base_type, index, ..Default::default() };
+    let vector = |element, index| TypeArgs { base_type: base_type::VECTOR, element, index, ..Default::default() };
+
+    let monster = {
+        let fields = [
+            field(&mut fbb, "color", 3, ty(base_type::UBYTE, 0), FieldArgs { default_integer: 2, ..Default::default() }),
+            field(&mut fbb, "equipped", 7, ty(base_type::UNION, 1), Default::default()),
+            field(&mut fbb, "equipped_type", 6, ty(base_type::UTYPE, 1), Default::default()),
+            field(&mut fbb, "hp", 1, ty(base_type::SHORT, -1), FieldArgs { default_integer: 100, ..Default::default() }),
+            field(&mut fbb, "inventory", 4, vector(base_type::UBYTE, -1), Default::default()),
+            field(&mut fbb, "name", 0, ty(base_type::STRING, -1), FieldArgs { required: true, ..Default::default() }),
+            field(&mut fbb, "path", 8, vector(base_type::OBJ, 1), Default::default()),
+            field(&mut fbb, "pos", 2, ty(base_type::OBJ, 1), Default::default()),
+            field(&mut fbb, "weapons", 5, vector(base_type::OBJ, 2), Default::default()),
+        ];
+        object(&mut fbb, "game.Monster", &fields, ObjectArgs { minalign: 4, ..Default::default() })
+    };
+
+    let vec3 = {
+        let fields = ["x", "y", "z"].iter().enumerate()
+            .map(|(id, name)| {
+                let name = fbb.create_string(name);
+                let type_ = create_type(&mut fbb, &ty(base_type::FLOAT, -1));
+                create_field(&mut fbb, &FieldArgs { name: Some(name), type_: Some(type_), id: id as u16, offset: 4 * id as u16, ..Default::default() })
+            })
+            .collect::<Vec<_>>();
+        object(&mut fbb, "game.Vec3", &fields, ObjectArgs { is_struct: true, minalign: 4, bytesize: 12, ..Default::default() })
+    };
+
+    let weapon = {
+        let fields = [
+            field(&mut fbb, "damage", 1, ty(base_type::SHORT, -1), Default::default()),
+            field(&mut fbb, "name", 0, ty(base_type::STRING, -1), Default::default()),
+        ];
+        object(&mut fbb, "game.Weapon", &fields, ObjectArgs { minalign: 2, ..Default::default() })
+    };
+
+    let color = enumeration(&mut fbb, "game.Color", &[("Red", None), ("Green", None), ("Blue", None)], base_type::UBYTE, false);
+    let equipment = enumeration(&mut fbb, "game.Equipment", &[("NONE", None), ("Weapon", Some(ty(base_type::OBJ, 2)))], base_type::UTYPE, true);
+
+    finish(fbb, &[monster, vec3, weapon], &[color, equipment], 0, Some("MONS"))
+}
+
+const MONSTER_JSON: &str = r#"{
+    "name": "Orc",
+    "pos": { "x": 1.0, "y": 2.5, "z": -3.0 },
+    "color": "Red",
+    "inventory": [1, 2, 3],
+    "weapons": [{ "name": "Axe", "damage": 5 }, { "name": "Bow" }],
+    "equipped_type": "Weapon",
+    "equipped": { "name": "Sword", "damage": 9 },
+    "path": [{ "x": 0, "y": 0, "z": 0 }, { "x": 1, "y": 1, "z": 1 }]
+}"#;
+
+#[test]
+fn wrap_owned_flatbuffer() {
+    let schema = foo_schema();
+    let owned_foo = OwnedFoo::create(&FooArgs { a: 42, b: None });
+
+    let dynamic = DynamicFlatBuffer::new(owned_foo, &schema).unwrap();
+
+    assert_eq!(dynamic.object().name, "Foo");
+    assert_eq!(dynamic.get_field("a").unwrap().and_then(|a| a.as_u64()), Some(42));
+    assert!(dynamic.get_field("b").unwrap().is_none());
+    assert_eq!(dynamic.into_inner().as_actual().a(), 42);
+}
+
+#[test]
+fn get_field() {
+    let schema = Arc::new(monster_schema());
+    let data = schema.json_to_flatbuffer(MONSTER_JSON).unwrap();
+    let monster = DynamicFlatBuffer::new(data, schema.clone()).unwrap();
+
+    assert_eq!(monster.get_field("name").unwrap().unwrap().as_str(), Some("Orc"));
+    // Absent scalars return their default.
+    assert!(matches!(monster.get_field("hp").unwrap(), Some(Value::Short(100))));
+    assert!(matches!(monster.get_field("color").unwrap(), Some(Value::UByte(0))));
+    assert_eq!(monster.get_field("pos.y").unwrap().unwrap().as_f64(), Some(2.5));
+    assert_eq!(monster.get_field("inventory[2]").unwrap().unwrap().as_i64(), Some(3));
+    assert_eq!(monster.get_field("weapons[0].name").unwrap().unwrap().as_str(), Some("Axe"));
+    assert_eq!(monster.get_field("weapons[1].damage").unwrap().unwrap().as_i64(), Some(0));
+    assert_eq!(monster.get_field("path[1].z").unwrap().unwrap().as_f64(), Some(1.0));
+    assert!(monster.get_field("weapons[2].name").unwrap().is_none());
+    assert!(monster.get_field("weapons[1].name").unwrap().is_some());
+
+    let equipped = monster.get_field("equipped").unwrap().unwrap().as_table().unwrap();
+    assert_eq!(equipped.object().name, "game.Weapon");
+    assert_eq!(monster.get_field("equipped.damage").unwrap().unwrap().as_i64(), Some(9));
+}
+
+#[test]
+fn field_errors() {
+    let schema = monster_schema();
+    let data = schema.json_to_flatbuffer(MONSTER_JSON).unwrap();
+    let monster = DynamicFlatBuffer::new(&data[..], &schema).unwrap();
+
+    assert_eq!(monster.get_field("mana").unwrap_err(), FieldError::UnknownField("mana".to_string()));
+    assert_eq!(monster.get_field("weapons[0].mana").unwrap_err(), FieldError::UnknownField("weapons[0].mana".to_string()));
+    assert_eq!(monster.get_field("name.first").unwrap_err(), FieldError::NotATable("name.first".to_string()));
+    assert_eq!(monster.get_field("hp[0]").unwrap_err(), FieldError::NotAVector("hp[0]".to_string()));
+    assert_eq!(monster.get_field("weapons[x]").unwrap_err(), FieldError::InvalidPath("weapons[x]".to_string()));
+}
+
+#[test]
+fn iterate_fields() {
+    let schema = monster_schema();
+    let data = schema.json_to_flatbuffer(r#"{ "name": "Orc", "hp": 80, "inventory": [4, 5] }"#).unwrap();
+    let monster = DynamicFlatBuffer::new(data, &schema).unwrap();
+
+    let names = monster.root().fields().map(|(field, _)| field.name.as_str()).collect::<Vec<_>>();
+    assert_eq!(names, ["name", "hp", "inventory"]);
+
+    let inventory = monster.get_field("inventory").unwrap().unwrap().as_vector().unwrap();
+    assert_eq!(inventory.len(), 2);
+    assert_eq!(inventory.iter().filter_map(|value| value.as_u64()).collect::<Vec<_>>(), [4, 5]);
+    assert!(inventory.get(2).is_none());
+
+    let pos = schema.json_to_flatbuffer(MONSTER_JSON).unwrap();
+    let pos = DynamicFlatBuffer::new(pos, &schema).unwrap();
+    let pos = pos.get_field("pos").unwrap().unwrap().as_struct().unwrap();
+    assert_eq!(pos.fields().filter_map(|(_, value)| value.as_f64()).collect::<Vec<_>>(), [1.0, 2.5, -3.0]);
+}
+
+#[test]
+fn new_as() {
+    let schema = monster_schema();
+    let data = schema.json_to_flatbuffer_as("game.Weapon", r#"{ "name": "Axe", "damage": 5 }"#).unwrap();
+
+    let weapon = DynamicFlatBuffer::new_as(&data[..], &schema, "game.Weapon").unwrap();
+    assert_eq!(weapon.get_field("name").unwrap().unwrap().as_str(), Some("Axe"));
+
+    assert!(matches!(DynamicFlatBuffer::new_as(&data[..], &schema, "game.Vec3"), Err(DynamicError::UnknownTable(_))));
+}
+
+#[test]
+fn invalid_flatbuffer() {
+    let schema = monster_schema();
+    let data = schema.json_to_flatbuffer(MONSTER_JSON).unwrap();
+
+    assert!(matches!(DynamicFlatBuffer::new(&data[..data.len() / 2], &schema), Err(DynamicError::InvalidFlatbuffer { .. })));
+    assert!(matches!(DynamicFlatBuffer::new(&[][..], &schema), Err(DynamicError::InvalidFlatbuffer { .. })));
+
+    // An empty Foo lacks the required name of a monster.
+    let foo_data = foo_schema().json_to_flatbuffer("{}").unwrap();
+    assert_eq!(
+        DynamicFlatBuffer::new(&foo_data[..], &schema).unwrap_err(),
+        DynamicError::MissingField { path: "game.Monster.name".to_string() }
+    );
+}
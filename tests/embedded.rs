@@ -0,0 +1,42 @@
+mod common;
+
+use flatbuffers::FlatBufferBuilder;
+use common::generated_fbs::foo::{Foo, FooArgs};
+use flatbuffers_owned::{flatbuffers_owned, EmbeddedFlatBuffer};
+
+flatbuffers_owned!(Foo);
+
+const HEADER: [u8; 8] = *b"RECORD01";
+
+fn get_record_bytes() -> Box<[u8]> {
+    let mut builder = FlatBufferBuilder::new();
+    let b = builder.create_string("Hello, world!");
+
+    let offset = Foo::create(&mut builder, &FooArgs {
+        a: 42,
+        b: Some(b),
+    });
+
+    builder.finish(offset, None);
+
+    [&HEADER[..], builder.finished_data()].concat().into()
+}
+
+#[test]
+fn new_at() {
+    let record_bytes = get_record_bytes();
+    let embedded_foo = EmbeddedFlatBuffer::<Foo<'static>, _>::new_at(&record_bytes[..], HEADER.len()).unwrap();
+
+    assert_eq!(embedded_foo.loc(), HEADER.len());
+    assert_eq!(embedded_foo.as_actual_at().a(), 42);
+    assert_eq!(embedded_foo.as_actual_at().b(), Some("Hello, world!"));
+}
+
+#[test]
+fn verify_at() {
+    let record_bytes = get_record_bytes();
+
+    assert!(EmbeddedFlatBuffer::<Foo<'static>, &[u8]>::verify_at(&record_bytes, HEADER.len()).is_ok());
+    assert!(EmbeddedFlatBuffer::<Foo<'static>, &[u8]>::verify_at(&record_bytes, 0).is_err());
+    assert!(EmbeddedFlatBuffer::<Foo<'static>, &[u8]>::verify_at(&record_bytes, record_bytes.len()).is_err());
+}
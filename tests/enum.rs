@@ -0,0 +1,59 @@
+mod common;
+
+use flatbuffers::FlatBufferBuilder;
+use common::generated_fbs::foo::Foo;
+use common::generated_fbs::bar::{finish_bar_buffer, Bar, BarArgs, BAR_IDENTIFIER};
+use common::build_foo_bytes;
+use flatbuffers_owned::{flatbuffers_owned, flatbuffers_owned_enum, DecodeError};
+
+flatbuffers_owned!(Foo, Bar);
+
+flatbuffers_owned_enum!(AnyMessage {
+    Foo: "FOO_",
+    Bar: BAR_IDENTIFIER,
+});
+
+fn get_bar_bytes() -> Box<[u8]> {
+    let mut builder = FlatBufferBuilder::new();
+    let name = builder.create_string("bar");
+    let offset = Bar::create(&mut builder, &BarArgs { id: 7, name: Some(name) });
+    finish_bar_buffer(&mut builder, offset);
+
+    builder.finished_data().into()
+}
+
+#[test]
+fn decode_by_identifier() {
+    match AnyMessage::decode(build_foo_bytes(42, None, Some("FOO_"))).unwrap() {
+        AnyMessage::Foo(foo) => assert_eq!(foo.as_actual().a(), 42),
+        AnyMessage::Bar(_) => panic!("Decoded Foo as Bar"),
+    }
+
+    match AnyMessage::decode(get_bar_bytes()).unwrap() {
+        AnyMessage::Bar(bar) => assert_eq!(bar.as_actual().name().unwrap(), "bar"),
+        AnyMessage::Foo(_) => panic!("Decoded Bar as Foo"),
+    }
+}
+
+#[test]
+fn deref_to_bytes() {
+    let bytes = get_bar_bytes();
+    let message = AnyMessage::decode(bytes.clone()).unwrap();
+
+    assert_eq!(&*message, &*bytes);
+}
+
+#[test]
+fn fail_unknown_identifier() {
+    assert_eq!(AnyMessage::decode(build_foo_bytes(42, None, None)), Err(DecodeError::UnknownIdentifier));
+    assert_eq!(AnyMessage::decode(build_foo_bytes(42, None, Some("BAZ_"))), Err(DecodeError::UnknownIdentifier));
+    assert_eq!(AnyMessage::decode(Box::new([0u8; 2])), Err(DecodeError::UnknownIdentifier));
+}
+
+#[test]
+fn fail_invalid_bytes() {
+    let mut bytes = get_bar_bytes();
+    bytes[0] = 0xff;
+
+    assert!(matches!(AnyMessage::decode(bytes), Err(DecodeError::InvalidFlatbuffer(_))));
+}
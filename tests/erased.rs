@@ -0,0 +1,30 @@
+mod common;
+
+use common::generated_fbs::foo::{Foo, FooArgs};
+use common::generated_fbs::bar::{Bar, BarArgs};
+use flatbuffers_owned::{flatbuffers_owned, ErasedFlatBuffer};
+
+flatbuffers_owned!(Foo { create }, Bar { create });
+
+#[test]
+fn heterogeneous_collection() {
+    let foo_bytes = OwnedFoo::create(&FooArgs { a: 42, b: None }).to_vec();
+
+    let flatbuffers: Vec<Box<dyn ErasedFlatBuffer>> = vec![
+        Box::new(OwnedFoo::new(foo_bytes.clone().into()).unwrap()),
+        Box::new(OwnedBar::create(&BarArgs { id: 7, name: None })),
+        Box::new(RelaxedFoo::new(&foo_bytes[..]).unwrap()),
+    ];
+
+    assert!(flatbuffers[0].root_type_name().contains("foo::Foo"));
+    assert!(flatbuffers[1].root_type_name().contains("bar::Bar"));
+
+    for flatbuffer in &flatbuffers {
+        assert!(flatbuffer.re_verify().is_ok());
+        assert!(!flatbuffer.is_empty());
+    }
+
+    let first: &[u8] = &flatbuffers[0];
+    let third: &[u8] = &flatbuffers[2];
+    assert_eq!(first, third);
+}
@@ -0,0 +1,17 @@
+mod common;
+
+use common::generated_fbs::foo::{Foo, FooArgs};
+use flatbuffers_owned::flatbuffers_owned;
+
+flatbuffers_owned!(Foo { sample: |builder| {
+    let b = builder.create_string("sample");
+    Foo::create(builder, &FooArgs { a: 42, b: Some(b) })
+} });
+
+#[test]
+fn build_sample() {
+    let sample = __examples_foo::sample().unwrap();
+
+    assert_eq!(sample.as_actual().a(), 42);
+    assert_eq!(sample.as_actual().b(), Some("sample"));
+}
@@ -0,0 +1,48 @@
+mod common;
+
+use common::generated_fbs::foo::{Foo, FooArgs, FooT};
+use common::generated_fbs::record::{Record, RecordArgs};
+use flatbuffers_owned::flatbuffers_owned;
+
+flatbuffers_owned!(Foo { object_api }, Record);
+
+fn build_record(with_foo: bool) -> OwnedRecord {
+    OwnedRecord::build(|builder| {
+        let nested = if with_foo {
+            let b = builder.create_string("Hello, world!");
+            Some(Foo::create(builder, &FooArgs { a: 42, b: Some(b) }))
+        } else {
+            None
+        };
+
+        Record::create(builder, &RecordArgs { id: 7, foo: nested })
+    }).unwrap()
+}
+
+#[test]
+fn extract() {
+    let owned_record = build_record(true);
+
+    let owned_foo: OwnedFoo = owned_record.extract(|record| record.foo()).unwrap();
+    drop(owned_record);
+
+    assert!(OwnedFoo::verify(&owned_foo).is_ok());
+    assert_eq!(owned_foo.as_actual().a(), 42);
+    assert_eq!(owned_foo.as_actual().b(), Some("Hello, world!"));
+}
+
+#[test]
+fn extract_missing() {
+    let owned_record = build_record(false);
+
+    assert!(owned_record.extract::<Foo, _>(|record| record.foo()).is_none());
+}
+
+#[test]
+fn extract_borrowed() {
+    let bytes = build_record(true).into_inner();
+    let borrowed_record = RelaxedRecord::new(&bytes[..]).unwrap();
+
+    let owned_foo: OwnedFoo = borrowed_record.extract(|record| record.foo()).unwrap();
+    assert!(owned_foo.len() < bytes.len());
+}
@@ -0,0 +1,99 @@
+mod common;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use common::generated_fbs::foo::Foo;
+use common::build_foo;
+use flatbuffers_owned::{flatbuffers_owned, FileError};
+
+flatbuffers_owned!(Foo);
+
+/// Counts the temporary files left behind next to the target.
+fn leftovers(path: &Path) -> usize {
+    let prefix = format!(".{}", path.file_name().unwrap().to_string_lossy());
+
+    fs::read_dir(path.parent().unwrap()).unwrap()
+        .filter(|entry| entry.as_ref().unwrap().file_name().to_string_lossy().starts_with(&prefix))
+        .count()
+}
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("flatbuffers-owned-file-{}-{}", std::process::id(), name))
+}
+
+#[test]
+fn from_file() {
+    let path = temp_path("foo.bin");
+    fs::write(&path, &*build_foo::<Foo>(42)).unwrap();
+
+    let owned_foo = OwnedFoo::from_file(&path).unwrap();
+    assert_eq!(owned_foo.as_actual().a(), 42);
+    assert_eq!(owned_foo.as_actual().b(), Some("Hello, world!"));
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn from_file_with_limit() {
+    let path = temp_path("limit.bin");
+    let bytes: OwnedFoo = build_foo(42);
+    fs::write(&path, &*bytes).unwrap();
+
+    assert!(OwnedFoo::from_file_with_limit(&path, bytes.len()).is_ok());
+
+    let result = OwnedFoo::from_file_with_limit(&path, 8);
+    assert!(matches!(result, Err(FileError::TooLarge { len, max: 8 }) if len == bytes.len() as u64));
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn from_file_invalid() {
+    let path = temp_path("invalid.bin");
+    fs::write(&path, [0xFF; 16]).unwrap();
+
+    assert!(matches!(OwnedFoo::from_file(&path), Err(FileError::InvalidFlatbuffer(_))));
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn from_file_missing() {
+    assert!(matches!(OwnedFoo::from_file(temp_path("missing.bin")), Err(FileError::Io(_))));
+}
+
+#[test]
+fn write_to_file() {
+    let path = temp_path("write.bin");
+    fs::write(&path, [0xFF; 16]).unwrap();
+
+    build_foo::<Foo>(42).write_to_file(&path).unwrap();
+    assert_eq!(OwnedFoo::from_file(&path).unwrap().as_actual().a(), 42);
+
+    assert_eq!(leftovers(&path), 0);
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn write_to_file_concurrently() {
+    let path = temp_path("concurrent.bin");
+
+    std::thread::scope(|scope| {
+        for _ in 0..8 {
+            scope.spawn(|| build_foo::<Foo>(42).write_to_file(&path).unwrap());
+        }
+    });
+
+    assert_eq!(OwnedFoo::from_file(&path).unwrap().as_actual().a(), 42);
+    assert_eq!(leftovers(&path), 0);
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn write_to_file_missing_directory() {
+    let path = temp_path("missing").join("write.bin");
+
+    assert!(build_foo::<Foo>(42).write_to_file(&path).is_err());
+}
@@ -0,0 +1,117 @@
+mod common;
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use common::generated_fbs::record::{Record, RecordArgs};
+use flatbuffers_owned::flatbuffers_owned;
+use flatbuffers_owned::flatlog::{index_path, FlatLogWriter, SyncPolicy, INDEX_ENTRY_LEN};
+use flatbuffers_owned::size_prefixed::SizePrefixedReader;
+
+flatbuffers_owned!(Record);
+
+fn build_record(id: u64) -> OwnedRecord {
+    OwnedRecord::build(|builder| Record::create(builder, &RecordArgs { id, foo: None })).unwrap()
+}
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("flatbuffers-owned-flatlog-{}-{}", std::process::id(), name))
+}
+
+fn remove_log(path: &PathBuf) {
+    fs::remove_file(path).unwrap();
+    fs::remove_file(index_path(path)).unwrap();
+}
+
+fn read_ids(path: &PathBuf) -> Vec<u64> {
+    SizePrefixedReader::<Record<'static>, _>::new(fs::File::open(path).unwrap())
+        .map(|record| record.unwrap().as_actual_at().id())
+        .collect()
+}
+
+#[test]
+fn append() {
+    let path = temp_path("append.log");
+    let mut log = FlatLogWriter::<Record<'static>>::with_sync_policy(&path, SyncPolicy::EveryMessages(2)).unwrap();
+    assert!(log.is_empty());
+
+    for id in 0..5 {
+        assert_eq!(log.append(&build_record(id)).unwrap(), id);
+    }
+
+    assert_eq!(log.len(), 5);
+    drop(log);
+
+    assert_eq!(read_ids(&path), (0..5).collect::<Vec<_>>());
+    assert_eq!(fs::metadata(index_path(&path)).unwrap().len(), 5 * INDEX_ENTRY_LEN as u64);
+
+    remove_log(&path);
+}
+
+#[test]
+fn reopen() {
+    let path = temp_path("reopen.log");
+
+    let mut log = FlatLogWriter::<Record<'static>>::open(&path).unwrap();
+    log.append(&build_record(1)).unwrap();
+    log.sync().unwrap();
+    drop(log);
+
+    let mut log = FlatLogWriter::<Record<'static>>::open(&path).unwrap();
+    assert_eq!(log.len(), 1);
+    assert_eq!(log.append(&build_record(2)).unwrap(), 1);
+    drop(log);
+
+    assert_eq!(read_ids(&path), vec![1, 2]);
+
+    remove_log(&path);
+}
+
+#[test]
+fn recover_torn_append() {
+    let path = temp_path("torn.log");
+
+    let mut log = FlatLogWriter::<Record<'static>>::open(&path).unwrap();
+    log.append(&build_record(1)).unwrap();
+    log.append(&build_record(2)).unwrap();
+    drop(log);
+
+    // A frame written without its index entry, followed by a torn frame and a torn index entry.
+    let log_len = fs::metadata(&path).unwrap().len();
+    let frame = fs::read(&path).unwrap();
+    OpenOptions::new().append(true).open(&path).unwrap().write_all(&frame[..frame.len() / 2 + 10]).unwrap();
+    OpenOptions::new().append(true).open(index_path(&path)).unwrap().write_all(&[1, 2, 3]).unwrap();
+
+    let mut log = FlatLogWriter::<Record<'static>>::open(&path).unwrap();
+    assert_eq!(log.len(), 2);
+    assert_eq!(fs::metadata(&path).unwrap().len(), log_len);
+
+    log.append(&build_record(3)).unwrap();
+    drop(log);
+
+    assert_eq!(read_ids(&path), vec![1, 2, 3]);
+
+    remove_log(&path);
+}
+
+#[test]
+fn recover_missing_frame() {
+    let path = temp_path("missing.log");
+
+    let mut log = FlatLogWriter::<Record<'static>>::open(&path).unwrap();
+    log.append(&build_record(1)).unwrap();
+    log.append(&build_record(2)).unwrap();
+    drop(log);
+
+    // The index entry of the second record points behind the truncated log.
+    let log_len = fs::metadata(&path).unwrap().len();
+    OpenOptions::new().write(true).open(&path).unwrap().set_len(log_len - 1).unwrap();
+
+    let log = FlatLogWriter::<Record<'static>>::open(&path).unwrap();
+    assert_eq!(log.len(), 1);
+    drop(log);
+
+    assert_eq!(read_ids(&path), vec![1]);
+
+    remove_log(&path);
+}
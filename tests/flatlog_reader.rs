@@ -0,0 +1,116 @@
+mod common;
+
+use std::fs::{self, OpenOptions};
+use std::path::PathBuf;
+use common::generated_fbs::record::{Record, RecordArgs};
+use flatbuffers_owned::flatbuffers_owned;
+use flatbuffers_owned::flatlog::{index_path, FlatLogReader, FlatLogWriter};
+use flatbuffers_owned::size_prefixed::FrameError;
+use flatbuffers_owned::TrustedPolicy;
+
+flatbuffers_owned!(Record);
+
+fn build_record(id: u64) -> OwnedRecord {
+    OwnedRecord::build(|builder| Record::create(builder, &RecordArgs { id, foo: None })).unwrap()
+}
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("flatbuffers-owned-flatlog-reader-{}-{}", std::process::id(), name))
+}
+
+fn write_log(name: &str, ids: impl IntoIterator<Item = u64>) -> PathBuf {
+    let path = temp_path(name);
+    let mut log = FlatLogWriter::<Record<'static>>::open(&path).unwrap();
+
+    for id in ids {
+        log.append(&build_record(id)).unwrap();
+    }
+
+    path
+}
+
+fn remove_log(path: &PathBuf) {
+    fs::remove_file(path).unwrap();
+    fs::remove_file(index_path(path)).unwrap();
+}
+
+#[test]
+fn get() {
+    let path = write_log("get.log", 10..20);
+    let reader = unsafe { FlatLogReader::<Record<'static>>::open(&path) }.unwrap();
+
+    assert_eq!(reader.len(), 10);
+    assert_eq!(reader.get(3).unwrap().unwrap().as_actual_at().id(), 13);
+    assert_eq!(reader.get(3).unwrap().unwrap().as_actual_at().id(), 13);
+    assert!(reader.get(10).is_none());
+
+    // The records keep the mapped log alive on their own.
+    let record = reader.get(9).unwrap().unwrap();
+    drop(reader);
+    assert_eq!(record.as_actual_at().id(), 19);
+
+    remove_log(&path);
+}
+
+#[test]
+fn iter() {
+    let path = write_log("iter.log", 0..100);
+    let reader = unsafe { FlatLogReader::<Record<'static>>::open(&path) }.unwrap();
+
+    assert_eq!(reader.iter().len(), 100);
+
+    let ids = reader.iter().map(|record| record.unwrap().as_actual_at().id()).collect::<Vec<_>>();
+    assert_eq!(ids, (0..100).collect::<Vec<_>>());
+
+    remove_log(&path);
+}
+
+#[test]
+fn empty() {
+    let path = write_log("empty.log", 0..0);
+    let reader = unsafe { FlatLogReader::<Record<'static>>::open(&path) }.unwrap();
+
+    assert!(reader.is_empty());
+    assert!(reader.iter().next().is_none());
+
+    remove_log(&path);
+}
+
+#[test]
+fn invalid_record() {
+    let path = write_log("invalid.log", 0..3);
+
+    // Overwrites the root offset of the second record, which starts behind the first.
+    let mut bytes = fs::read(&path).unwrap();
+    let second = fs::read(index_path(&path)).unwrap()[8] as usize;
+    bytes[second + 4..second + 8].copy_from_slice(&u32::MAX.to_le_bytes());
+    fs::write(&path, &bytes).unwrap();
+
+    let reader = unsafe { FlatLogReader::<Record<'static>>::open(&path) }.unwrap();
+    let results = reader.iter().map(|record| record.map(|record| record.as_actual_at().id())).collect::<Vec<_>>();
+
+    assert_eq!(results[0], Ok(0));
+    assert!(matches!(results[1], Err(FrameError::InvalidFlatbuffer(_))));
+    assert_eq!(results[2], Ok(2));
+    assert!(matches!(reader.get(1), Some(Err(FrameError::InvalidFlatbuffer(_)))));
+
+    // A trusted policy skips the verifier.
+    let trusted = unsafe { FlatLogReader::<Record<'static>, TrustedPolicy>::open(&path) }.unwrap();
+    assert!(trusted.get(0).unwrap().is_ok());
+
+    remove_log(&path);
+}
+
+#[test]
+fn truncated_record() {
+    let path = write_log("truncated.log", 0..2);
+
+    let log_len = fs::metadata(&path).unwrap().len();
+    OpenOptions::new().write(true).open(&path).unwrap().set_len(log_len - 1).unwrap();
+
+    let reader = unsafe { FlatLogReader::<Record<'static>>::open(&path) }.unwrap();
+    assert_eq!(reader.get(0).unwrap().unwrap().as_actual_at().id(), 0);
+    assert!(matches!(reader.get(1), Some(Err(FrameError::Truncated { .. }))));
+
+    remove_log(&path);
+}
@@ -0,0 +1,86 @@
+use std::collections::HashSet;
+use std::convert::TryFrom;
+use std::sync::Arc;
+use flatbuffers_owned::flexbuffers::OwnedFlexBuffer;
+use flexbuffers::{Builder, ReaderError};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Monster<'a> {
+    name: &'a str,
+    hp: u16,
+    inventory: Vec<u8>,
+}
+
+fn monster_bytes() -> Vec<u8> {
+    let mut builder = Builder::default();
+    let mut map = builder.start_map();
+    map.push("name", "Orc");
+    map.push("hp", 80u16);
+    let mut inventory = map.start_vector("inventory");
+    inventory.push(1u8);
+    inventory.push(2u8);
+    inventory.end_vector();
+    map.end_map();
+
+    builder.view().to_vec()
+}
+
+#[test]
+fn as_reader() {
+    let monster = OwnedFlexBuffer::try_from(monster_bytes()).unwrap();
+    let map = monster.as_reader().as_map();
+
+    assert_eq!(map.idx("name").as_str(), "Orc");
+    assert_eq!(map.idx("hp").as_u64(), 80);
+    assert_eq!(map.idx("inventory").as_vector().idx(1).as_u8(), 2);
+}
+
+#[test]
+fn deserialize() {
+    let monster = OwnedFlexBuffer::from_serialize(Monster { name: "Orc", hp: 80, inventory: vec![1, 2] }).unwrap();
+
+    let deserialized = monster.deserialize::<Monster>().unwrap();
+    assert_eq!(deserialized, Monster { name: "Orc", hp: 80, inventory: vec![1, 2] });
+    assert_eq!(OwnedFlexBuffer::new(monster_bytes()).unwrap().deserialize::<Monster>().unwrap(), deserialized);
+}
+
+#[test]
+fn from_builder() {
+    let mut builder = Builder::default();
+    assert!(OwnedFlexBuffer::from_builder(&builder).is_err());
+
+    builder.build_singleton(42u32);
+    let answer = OwnedFlexBuffer::from_builder(&builder).unwrap();
+    assert_eq!(answer.as_reader().as_u32(), 42);
+}
+
+#[test]
+fn invalid_root() {
+    assert_eq!(OwnedFlexBuffer::new(&[][..]).unwrap_err(), ReaderError::FlexbufferOutOfBounds);
+    assert_eq!(OwnedFlexBuffer::new(&[0, 0, 3][..]).unwrap_err(), ReaderError::InvalidRootWidth);
+
+    let mut truncated = monster_bytes();
+    truncated.drain(..truncated.len() - 3);
+    assert!(OwnedFlexBuffer::new(truncated).is_err());
+}
+
+#[test]
+fn buffer_types() {
+    let bytes = monster_bytes();
+
+    let borrowed = OwnedFlexBuffer::new(&bytes[..]).unwrap();
+    let copied = borrowed;
+    let owned = copied.to_owned();
+    let shared = OwnedFlexBuffer::new(Arc::<[u8]>::from(bytes.clone())).unwrap();
+
+    assert_eq!(&*owned, &bytes[..]);
+    assert_eq!(shared.clone().as_reader().as_map().idx("hp").as_u64(), 80);
+    assert_eq!(owned.into_inner().into_vec(), bytes);
+
+    let set = [borrowed].iter().copied().collect::<HashSet<_>>();
+    assert!(set.contains(&bytes[..]));
+
+    let handle = std::thread::spawn(move || shared.as_reader().as_map().idx("name").as_str().to_string());
+    assert_eq!(handle.join().unwrap(), "Orc");
+}
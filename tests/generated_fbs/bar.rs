@@ -0,0 +1,155 @@
+// This file is manually copied here, so we do not need flatc to run our tests.
+// The generated Rust code is based on the following flatbuffer schema:
+// table Bar {
+//     id: uint64;
+//     name: string;
+// }
+//
+// root_type Bar;
+// file_identifier "BAR_";
+//
+// Used flatc version: 23.5.26
+
+use core::mem;
+use core::cmp::Ordering;
+
+extern crate flatbuffers;
+use self::flatbuffers::{EndianScalar, Follow};
+
+pub enum BarOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct Bar<'a> {
+    pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for Bar<'a> {
+    type Inner = Bar<'a>;
+    #[inline]
+    unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+        Self { _tab: flatbuffers::Table::new(buf, loc) }
+    }
+}
+
+impl<'a> Bar<'a> {
+    pub const VT_ID: flatbuffers::VOffsetT = 4;
+    pub const VT_NAME: flatbuffers::VOffsetT = 6;
+
+    #[inline]
+    pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+        Bar { _tab: table }
+    }
+    #[allow(unused_mut)]
+    pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+        _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+        args: &'args BarArgs<'args>
+    ) -> flatbuffers::WIPOffset<Bar<'bldr>> {
+        let mut builder = BarBuilder::new(_fbb);
+        builder.add_id(args.id);
+        if let Some(x) = args.name { builder.add_name(x); }
+        builder.finish()
+    }
+
+
+    #[inline]
+    pub fn id(&self) -> u64 {
+        // Safety:
+        // Created from valid Table for this object
+        // which contains a valid value in this slot
+        unsafe { self._tab.get::<u64>(Bar::VT_ID, Some(0)).unwrap()}
+    }
+    #[inline]
+    pub fn name(&self) -> Option<&'a str> {
+        // Safety:
+        // Created from valid Table for this object
+        // which contains a valid value in this slot
+        unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(Bar::VT_NAME, None)}
+    }
+}
+
+impl flatbuffers::Verifiable for Bar<'_> {
+    #[inline]
+    fn run_verifier(
+        v: &mut flatbuffers::Verifier, pos: usize
+    ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+        use self::flatbuffers::Verifiable;
+        v.visit_table(pos)?
+            .visit_field::<u64>("id", Self::VT_ID, false)?
+            .visit_field::<flatbuffers::ForwardsUOffset<&str>>("name", Self::VT_NAME, false)?
+            .finish();
+        Ok(())
+    }
+}
+pub struct BarArgs<'a> {
+    pub id: u64,
+    pub name: Option<flatbuffers::WIPOffset<&'a str>>,
+}
+impl<'a> Default for BarArgs<'a> {
+    #[inline]
+    fn default() -> Self {
+        BarArgs {
+            id: 0,
+            name: None,
+        }
+    }
+}
+
+pub struct BarBuilder<'a: 'b, 'b> {
+    fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+    start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> BarBuilder<'a, 'b> {
+    #[inline]
+    pub fn add_id(&mut self, id: u64) {
+        self.fbb_.push_slot::<u64>(Bar::VT_ID, id, 0);
+    }
+    #[inline]
+    pub fn add_name(&mut self, name: flatbuffers::WIPOffset<&'b  str>) {
+        self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Bar::VT_NAME, name);
+    }
+    #[inline]
+    pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> BarBuilder<'a, 'b> {
+        let start = _fbb.start_table();
+        BarBuilder {
+            fbb_: _fbb,
+            start_: start,
+        }
+    }
+    #[inline]
+    pub fn finish(self) -> flatbuffers::WIPOffset<Bar<'a>> {
+        let o = self.fbb_.end_table(self.start_);
+        flatbuffers::WIPOffset::new(o.value())
+    }
+}
+
+impl core::fmt::Debug for Bar<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mut ds = f.debug_struct("Bar");
+        ds.field("id", &self.id());
+        ds.field("name", &self.name());
+        ds.finish()
+    }
+}
+#[inline]
+/// Verifies that a buffer of bytes contains a `Bar`
+/// and returns it.
+/// Note that verification is still experimental and may not
+/// catch every error, or be maximally performant. For the
+/// previous, unchecked, behavior use
+/// `root_as_bar_unchecked`.
+pub fn root_as_bar(buf: &[u8]) -> Result<Bar, flatbuffers::InvalidFlatbuffer> {
+    flatbuffers::root::<Bar>(buf)
+}
+pub const BAR_IDENTIFIER: &str = "BAR_";
+
+#[inline]
+pub fn bar_buffer_has_identifier(buf: &[u8]) -> bool {
+    flatbuffers::buffer_has_identifier(buf, BAR_IDENTIFIER, false)
+}
+
+#[inline]
+pub fn finish_bar_buffer<'a, 'b>(
+    fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+    root: flatbuffers::WIPOffset<Bar<'a>>) {
+    fbb.finish(root, Some(BAR_IDENTIFIER));
+}
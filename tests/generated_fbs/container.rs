@@ -0,0 +1,124 @@
+// This file is manually copied here, so we do not need flatc to run our tests.
+// The generated Rust code is based on the following flatbuffer schema:
+// include "foo.fbs";
+//
+// table Container {
+//     foo: [ubyte] (nested_flatbuffer: "Foo");
+// }
+//
+// Used flatc version: 23.5.26
+
+use core::mem;
+use core::cmp::Ordering;
+
+extern crate flatbuffers;
+use self::flatbuffers::{EndianScalar, Follow};
+
+use super::foo::*;
+
+pub enum ContainerOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct Container<'a> {
+    pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for Container<'a> {
+    type Inner = Container<'a>;
+    #[inline]
+    unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+        Self { _tab: flatbuffers::Table::new(buf, loc) }
+    }
+}
+
+impl<'a> Container<'a> {
+    pub const VT_FOO: flatbuffers::VOffsetT = 4;
+
+    #[inline]
+    pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+        Container { _tab: table }
+    }
+    #[allow(unused_mut)]
+    pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+        _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+        args: &'args ContainerArgs<'args>
+    ) -> flatbuffers::WIPOffset<Container<'bldr>> {
+        let mut builder = ContainerBuilder::new(_fbb);
+        if let Some(x) = args.foo { builder.add_foo(x); }
+        builder.finish()
+    }
+
+
+    #[inline]
+    pub fn foo(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+        // Safety:
+        // Created from valid Table for this object
+        // which contains a valid value in this slot
+        unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(Container::VT_FOO, None)}
+    }
+    pub fn foo_nested_flatbuffer(&'a self) -> Option<Foo<'a>> {
+        self.foo().map(|data| {
+            use flatbuffers::Follow;
+            // Safety:
+            // Created from a valid Table for this object
+            // Which contains a valid flatbuffer in this slot
+            unsafe { <flatbuffers::ForwardsUOffset<Foo<'a>>>::follow(data.bytes(), 0) }
+        })
+    }
+}
+
+impl flatbuffers::Verifiable for Container<'_> {
+    #[inline]
+    fn run_verifier(
+        v: &mut flatbuffers::Verifier, pos: usize
+    ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+        use self::flatbuffers::Verifiable;
+        v.visit_table(pos)?
+            .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>("foo", Self::VT_FOO, false)?
+            .finish();
+        Ok(())
+    }
+}
+pub struct ContainerArgs<'a> {
+    pub foo: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+}
+impl<'a> Default for ContainerArgs<'a> {
+    #[inline]
+    fn default() -> Self {
+        ContainerArgs {
+            foo: None,
+        }
+    }
+}
+
+pub struct ContainerBuilder<'a: 'b, 'b> {
+    fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+    start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> ContainerBuilder<'a, 'b> {
+    #[inline]
+    pub fn add_foo(&mut self, foo: flatbuffers::WIPOffset<flatbuffers::Vector<'b , u8>>) {
+        self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Container::VT_FOO, foo);
+    }
+    #[inline]
+    pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> ContainerBuilder<'a, 'b> {
+        let start = _fbb.start_table();
+        ContainerBuilder {
+            fbb_: _fbb,
+            start_: start,
+        }
+    }
+    #[inline]
+    pub fn finish(self) -> flatbuffers::WIPOffset<Container<'a>> {
+        let o = self.fbb_.end_table(self.start_);
+        flatbuffers::WIPOffset::new(o.value())
+    }
+}
+
+impl core::fmt::Debug for Container<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mut ds = f.debug_struct("Container");
+        ds.field("foo", &self.foo());
+        ds.finish()
+    }
+}
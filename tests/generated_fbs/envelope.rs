@@ -0,0 +1,269 @@
+// This file is manually copied here, so we do not need flatc to run our tests.
+// The generated Rust code is based on the following flatbuffer schema:
+// include "foo.fbs";
+// include "bar.fbs";
+//
+// union Payload { Foo, Bar }
+//
+// table Envelope {
+//     payload: Payload;
+// }
+//
+// Used flatc version: 23.5.26
+
+use core::mem;
+use core::cmp::Ordering;
+
+extern crate flatbuffers;
+use self::flatbuffers::{EndianScalar, Follow};
+
+use super::foo::*;
+use super::bar::*;
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[repr(transparent)]
+pub struct Payload(pub u8);
+#[allow(non_upper_case_globals)]
+impl Payload {
+    pub const NONE: Self = Self(0);
+    pub const Foo: Self = Self(1);
+    pub const Bar: Self = Self(2);
+
+    pub const ENUM_MIN: u8 = 0;
+    pub const ENUM_MAX: u8 = 2;
+    pub const ENUM_VALUES: &'static [Self] = &[
+        Self::NONE,
+        Self::Foo,
+        Self::Bar,
+    ];
+    /// Returns the variant's name or "" if unknown.
+    pub fn variant_name(self) -> Option<&'static str> {
+        match self {
+            Self::NONE => Some("NONE"),
+            Self::Foo => Some("Foo"),
+            Self::Bar => Some("Bar"),
+            _ => None,
+        }
+    }
+}
+impl core::fmt::Debug for Payload {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        if let Some(name) = self.variant_name() {
+            f.write_str(name)
+        } else {
+            f.write_fmt(format_args!("<UNKNOWN {:?}>", self.0))
+        }
+    }
+}
+impl<'a> flatbuffers::Follow<'a> for Payload {
+    type Inner = Self;
+    #[inline]
+    unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+        let b = flatbuffers::read_scalar_at::<u8>(buf, loc);
+        Self(b)
+    }
+}
+
+impl flatbuffers::Push for Payload {
+    type Output = Payload;
+    #[inline]
+    unsafe fn push(&self, dst: &mut [u8], _written_len: usize) {
+        flatbuffers::emplace_scalar::<u8>(dst, self.0);
+    }
+}
+
+impl flatbuffers::EndianScalar for Payload {
+    type Scalar = u8;
+    #[inline]
+    fn to_little_endian(self) -> u8 {
+        self.0.to_le()
+    }
+    #[inline]
+    #[allow(clippy::wrong_self_convention)]
+    fn from_little_endian(v: u8) -> Self {
+        let b = u8::from_le(v);
+        Self(b)
+    }
+}
+
+impl<'a> flatbuffers::Verifiable for Payload {
+    #[inline]
+    fn run_verifier(
+        v: &mut flatbuffers::Verifier, pos: usize
+    ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+        use self::flatbuffers::Verifiable;
+        u8::run_verifier(v, pos)
+    }
+}
+
+impl flatbuffers::SimpleToVerifyInSlice for Payload {}
+pub struct PayloadUnionTableOffset {}
+
+pub enum EnvelopeOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct Envelope<'a> {
+    pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for Envelope<'a> {
+    type Inner = Envelope<'a>;
+    #[inline]
+    unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+        Self { _tab: flatbuffers::Table::new(buf, loc) }
+    }
+}
+
+impl<'a> Envelope<'a> {
+    pub const VT_PAYLOAD_TYPE: flatbuffers::VOffsetT = 4;
+    pub const VT_PAYLOAD: flatbuffers::VOffsetT = 6;
+
+    #[inline]
+    pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+        Envelope { _tab: table }
+    }
+    #[allow(unused_mut)]
+    pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+        _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+        args: &'args EnvelopeArgs
+    ) -> flatbuffers::WIPOffset<Envelope<'bldr>> {
+        let mut builder = EnvelopeBuilder::new(_fbb);
+        if let Some(x) = args.payload { builder.add_payload(x); }
+        builder.add_payload_type(args.payload_type);
+        builder.finish()
+    }
+
+
+    #[inline]
+    pub fn payload_type(&self) -> Payload {
+        // Safety:
+        // Created from valid Table for this object
+        // which contains a valid value in this slot
+        unsafe { self._tab.get::<Payload>(Envelope::VT_PAYLOAD_TYPE, Some(Payload::NONE)).unwrap()}
+    }
+    #[inline]
+    pub fn payload(&self) -> Option<flatbuffers::Table<'a>> {
+        // Safety:
+        // Created from valid Table for this object
+        // which contains a valid value in this slot
+        unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Table<'a>>>(Envelope::VT_PAYLOAD, None)}
+    }
+    #[inline]
+    #[allow(non_snake_case)]
+    pub fn payload_as_foo(&self) -> Option<Foo<'a>> {
+        if self.payload_type() == Payload::Foo {
+            self.payload().map(|t| {
+                // Safety:
+                // Created from a valid Table for this object
+                // Which contains a valid union in this slot
+                unsafe { Foo::init_from_table(t) }
+            })
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    #[allow(non_snake_case)]
+    pub fn payload_as_bar(&self) -> Option<Bar<'a>> {
+        if self.payload_type() == Payload::Bar {
+            self.payload().map(|t| {
+                // Safety:
+                // Created from a valid Table for this object
+                // Which contains a valid union in this slot
+                unsafe { Bar::init_from_table(t) }
+            })
+        } else {
+            None
+        }
+    }
+
+}
+
+impl flatbuffers::Verifiable for Envelope<'_> {
+    #[inline]
+    fn run_verifier(
+        v: &mut flatbuffers::Verifier, pos: usize
+    ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+        use self::flatbuffers::Verifiable;
+        v.visit_table(pos)?
+            .visit_union::<Payload, _>("payload_type", Self::VT_PAYLOAD_TYPE, "payload", Self::VT_PAYLOAD, false, |key, v, pos| {
+                match key {
+                    Payload::Foo => v.verify_union_variant::<flatbuffers::ForwardsUOffset<Foo>>("Payload::Foo", pos),
+                    Payload::Bar => v.verify_union_variant::<flatbuffers::ForwardsUOffset<Bar>>("Payload::Bar", pos),
+                    _ => Ok(()),
+                }
+            })?
+            .finish();
+        Ok(())
+    }
+}
+pub struct EnvelopeArgs {
+    pub payload_type: Payload,
+    pub payload: Option<flatbuffers::WIPOffset<flatbuffers::UnionWIPOffset>>,
+}
+impl<'a> Default for EnvelopeArgs {
+    #[inline]
+    fn default() -> Self {
+        EnvelopeArgs {
+            payload_type: Payload::NONE,
+            payload: None,
+        }
+    }
+}
+
+pub struct EnvelopeBuilder<'a: 'b, 'b> {
+    fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+    start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> EnvelopeBuilder<'a, 'b> {
+    #[inline]
+    pub fn add_payload_type(&mut self, payload_type: Payload) {
+        self.fbb_.push_slot::<Payload>(Envelope::VT_PAYLOAD_TYPE, payload_type, Payload::NONE);
+    }
+    #[inline]
+    pub fn add_payload(&mut self, payload: flatbuffers::WIPOffset<flatbuffers::UnionWIPOffset>) {
+        self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Envelope::VT_PAYLOAD, payload);
+    }
+    #[inline]
+    pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> EnvelopeBuilder<'a, 'b> {
+        let start = _fbb.start_table();
+        EnvelopeBuilder {
+            fbb_: _fbb,
+            start_: start,
+        }
+    }
+    #[inline]
+    pub fn finish(self) -> flatbuffers::WIPOffset<Envelope<'a>> {
+        let o = self.fbb_.end_table(self.start_);
+        flatbuffers::WIPOffset::new(o.value())
+    }
+}
+
+impl core::fmt::Debug for Envelope<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mut ds = f.debug_struct("Envelope");
+        ds.field("payload_type", &self.payload_type());
+        match self.payload_type() {
+            Payload::Foo => {
+                if let Some(x) = self.payload_as_foo() {
+                    ds.field("payload", &x)
+                } else {
+                    ds.field("payload", &"InvalidFlatbuffer: Union discriminant does not match value.")
+                }
+            },
+            Payload::Bar => {
+                if let Some(x) = self.payload_as_bar() {
+                    ds.field("payload", &x)
+                } else {
+                    ds.field("payload", &"InvalidFlatbuffer: Union discriminant does not match value.")
+                }
+            },
+            _ => {
+                let x: Option<()> = None;
+                ds.field("payload", &x)
+            },
+        };
+        ds.finish()
+    }
+}
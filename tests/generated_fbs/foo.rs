@@ -5,7 +5,7 @@
 //     b: string;
 // }
 //
-// Used flatc version: 23.5.26
+// Used flatc version: 23.5.26 (with --gen-object-api)
 
 use core::mem;
 use core::cmp::Ordering;
@@ -48,6 +48,17 @@ impl<'a> Foo<'a> {
     }
 
 
+    pub fn unpack(&self) -> FooT {
+        let a = self.a();
+        let b = self.b().map(|x| {
+            x.to_string()
+        });
+        FooT {
+            a,
+            b,
+        }
+    }
+
     #[inline]
     pub fn a(&self) -> u32 {
         // Safety:
@@ -126,4 +137,32 @@ impl core::fmt::Debug for Foo<'_> {
         ds.field("b", &self.b());
         ds.finish()
     }
+}
+#[derive(Debug, Clone, PartialEq)]
+pub struct FooT {
+    pub a: u32,
+    pub b: Option<String>,
+}
+impl Default for FooT {
+    fn default() -> Self {
+        Self {
+            a: 0,
+            b: None,
+        }
+    }
+}
+impl FooT {
+    pub fn pack<'b>(
+        &self,
+        _fbb: &mut flatbuffers::FlatBufferBuilder<'b>
+    ) -> flatbuffers::WIPOffset<Foo<'b>> {
+        let a = self.a;
+        let b = self.b.as_ref().map(|x|{
+            _fbb.create_string(x)
+        });
+        Foo::create(_fbb, &FooArgs{
+            a,
+            b,
+        })
+    }
 }
\ No newline at end of file
@@ -0,0 +1,7 @@
+pub mod foo;
+pub mod bar;
+pub mod container;
+pub mod envelope;
+pub mod record;
+pub mod reflection;
+pub mod vec3;
@@ -0,0 +1,133 @@
+// This file is manually copied here, so we do not need flatc to run our tests.
+// The generated Rust code is based on the following flatbuffer schema:
+// include "foo.fbs";
+//
+// table Record {
+//     id: uint64;
+//     foo: Foo;
+// }
+//
+// Used flatc version: 23.5.26
+
+use core::mem;
+use core::cmp::Ordering;
+
+extern crate flatbuffers;
+use self::flatbuffers::{EndianScalar, Follow};
+
+use super::foo::*;
+
+pub enum RecordOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct Record<'a> {
+    pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for Record<'a> {
+    type Inner = Record<'a>;
+    #[inline]
+    unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+        Self { _tab: flatbuffers::Table::new(buf, loc) }
+    }
+}
+
+impl<'a> Record<'a> {
+    pub const VT_ID: flatbuffers::VOffsetT = 4;
+    pub const VT_FOO: flatbuffers::VOffsetT = 6;
+
+    #[inline]
+    pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+        Record { _tab: table }
+    }
+    #[allow(unused_mut)]
+    pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+        _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+        args: &'args RecordArgs<'args>
+    ) -> flatbuffers::WIPOffset<Record<'bldr>> {
+        let mut builder = RecordBuilder::new(_fbb);
+        builder.add_id(args.id);
+        if let Some(x) = args.foo { builder.add_foo(x); }
+        builder.finish()
+    }
+
+
+    #[inline]
+    pub fn id(&self) -> u64 {
+        // Safety:
+        // Created from valid Table for this object
+        // which contains a valid value in this slot
+        unsafe { self._tab.get::<u64>(Record::VT_ID, Some(0)).unwrap()}
+    }
+    #[inline]
+    pub fn foo(&self) -> Option<Foo<'a>> {
+        // Safety:
+        // Created from valid Table for this object
+        // which contains a valid value in this slot
+        unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<Foo>>(Record::VT_FOO, None)}
+    }
+}
+
+impl flatbuffers::Verifiable for Record<'_> {
+    #[inline]
+    fn run_verifier(
+        v: &mut flatbuffers::Verifier, pos: usize
+    ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+        use self::flatbuffers::Verifiable;
+        v.visit_table(pos)?
+            .visit_field::<u64>("id", Self::VT_ID, false)?
+            .visit_field::<flatbuffers::ForwardsUOffset<Foo>>("foo", Self::VT_FOO, false)?
+            .finish();
+        Ok(())
+    }
+}
+pub struct RecordArgs<'a> {
+    pub id: u64,
+    pub foo: Option<flatbuffers::WIPOffset<Foo<'a>>>,
+}
+impl<'a> Default for RecordArgs<'a> {
+    #[inline]
+    fn default() -> Self {
+        RecordArgs {
+            id: 0,
+            foo: None,
+        }
+    }
+}
+
+pub struct RecordBuilder<'a: 'b, 'b> {
+    fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+    start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> RecordBuilder<'a, 'b> {
+    #[inline]
+    pub fn add_id(&mut self, id: u64) {
+        self.fbb_.push_slot::<u64>(Record::VT_ID, id, 0);
+    }
+    #[inline]
+    pub fn add_foo(&mut self, foo: flatbuffers::WIPOffset<Foo<'b >>) {
+        self.fbb_.push_slot_always::<flatbuffers::WIPOffset<Foo>>(Record::VT_FOO, foo);
+    }
+    #[inline]
+    pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> RecordBuilder<'a, 'b> {
+        let start = _fbb.start_table();
+        RecordBuilder {
+            fbb_: _fbb,
+            start_: start,
+        }
+    }
+    #[inline]
+    pub fn finish(self) -> flatbuffers::WIPOffset<Record<'a>> {
+        let o = self.fbb_.end_table(self.start_);
+        flatbuffers::WIPOffset::new(o.value())
+    }
+}
+
+impl core::fmt::Debug for Record<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mut ds = f.debug_struct("Record");
+        ds.field("id", &self.id());
+        ds.field("foo", &self.foo());
+        ds.finish()
+    }
+}
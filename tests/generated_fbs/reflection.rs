@@ -0,0 +1,207 @@
+// This file is manually written, so we do not need flatc to run our tests.
+// It is the builder side of flatc's reflection.fbs, trimmed to the tables and fields the tests write:
+// table Type { base_type: BaseType; element: BaseType; index: int = -1; fixed_length: uint16; base_size: uint = 4; element_size: uint; }
+// table KeyValue { key: string (required, key); value: string; }
+// table EnumVal { name: string (required); value: long (key); object: Object (deprecated); union_type: Type; documentation: [string]; attributes: [KeyValue]; }
+// table Enum { name: string (required, key); values: [EnumVal] (required); is_union: bool; underlying_type: Type (required); attributes: [KeyValue]; documentation: [string]; }
+// table Field { name: string (required, key); type: Type (required); id: ushort; offset: ushort; default_integer: long; default_real: double;
+//               deprecated: bool; required: bool; key: bool; attributes: [KeyValue]; documentation: [string]; optional: bool; padding: uint16; }
+// table Object { name: string (required, key); fields: [Field] (required); is_struct: bool; minalign: int; bytesize: int; attributes: [KeyValue]; documentation: [string]; }
+// table Schema { objects: [Object] (required); enums: [Enum] (required); file_ident: string; file_ext: string; root_table: Object; }
+// root_type Schema;
+// file_identifier "BFBS";
+
+extern crate flatbuffers;
+use self::flatbuffers::{FlatBufferBuilder, ForwardsUOffset, Vector, WIPOffset};
+
+pub enum TypeOffset {}
+pub enum KeyValueOffset {}
+pub enum EnumValOffset {}
+pub enum EnumOffset {}
+pub enum FieldOffset {}
+pub enum ObjectOffset {}
+pub enum SchemaOffset {}
+
+pub const SCHEMA_IDENTIFIER: &str = "BFBS";
+
+pub mod base_type {
+    pub const NONE: i8 = 0;
+    pub const UTYPE: i8 = 1;
+    pub const BOOL: i8 = 2;
+    pub const BYTE: i8 = 3;
+    pub const UBYTE: i8 = 4;
+    pub const SHORT: i8 = 5;
+    pub const USHORT: i8 = 6;
+    pub const INT: i8 = 7;
+    pub const UINT: i8 = 8;
+    pub const LONG: i8 = 9;
+    pub const ULONG: i8 = 10;
+    pub const FLOAT: i8 = 11;
+    pub const DOUBLE: i8 = 12;
+    pub const STRING: i8 = 13;
+    pub const VECTOR: i8 = 14;
+    pub const OBJ: i8 = 15;
+    pub const UNION: i8 = 16;
+    pub const ARRAY: i8 = 17;
+}
+
+type Offsets<'a, T> = WIPOffset<Vector<'a, ForwardsUOffset<T>>>;
+
+pub struct TypeArgs {
+    pub base_type: i8,
+    pub element: i8,
+    pub index: i32,
+    pub fixed_length: u16,
+    pub base_size: u32,
+    pub element_size: u32,
+}
+
+impl Default for TypeArgs {
+    fn default() -> Self {
+        TypeArgs { base_type: 0, element: 0, index: -1, fixed_length: 0, base_size: 4, element_size: 0 }
+    }
+}
+
+pub fn create_type<'a>(fbb: &mut FlatBufferBuilder<'a>, args: &TypeArgs) -> WIPOffset<TypeOffset> {
+    let start = fbb.start_table();
+    fbb.push_slot::<u32>(14, args.element_size, 0);
+    fbb.push_slot::<u32>(12, args.base_size, 4);
+    fbb.push_slot::<i32>(8, args.index, -1);
+    fbb.push_slot::<u16>(10, args.fixed_length, 0);
+    fbb.push_slot::<i8>(6, args.element, 0);
+    fbb.push_slot::<i8>(4, args.base_type, 0);
+    WIPOffset::new(fbb.end_table(start).value())
+}
+
+pub struct KeyValueArgs<'a> {
+    pub key: Option<WIPOffset<&'a str>>,
+    pub value: Option<WIPOffset<&'a str>>,
+}
+
+pub fn create_key_value<'a>(fbb: &mut FlatBufferBuilder<'a>, args: &KeyValueArgs<'a>) -> WIPOffset<KeyValueOffset> {
+    let start = fbb.start_table();
+    if let Some(x) = args.value { fbb.push_slot_always(6, x); }
+    if let Some(x) = args.key { fbb.push_slot_always(4, x); }
+    WIPOffset::new(fbb.end_table(start).value())
+}
+
+#[derive(Default)]
+pub struct EnumValArgs<'a> {
+    pub name: Option<WIPOffset<&'a str>>,
+    pub value: i64,
+    pub union_type: Option<WIPOffset<TypeOffset>>,
+    pub documentation: Option<Offsets<'a, &'a str>>,
+    pub attributes: Option<Offsets<'a, KeyValueOffset>>,
+}
+
+pub fn create_enum_val<'a>(fbb: &mut FlatBufferBuilder<'a>, args: &EnumValArgs<'a>) -> WIPOffset<EnumValOffset> {
+    let start = fbb.start_table();
+    fbb.push_slot::<i64>(6, args.value, 0);
+    if let Some(x) = args.attributes { fbb.push_slot_always(14, x); }
+    if let Some(x) = args.documentation { fbb.push_slot_always(12, x); }
+    if let Some(x) = args.union_type { fbb.push_slot_always(10, x); }
+    if let Some(x) = args.name { fbb.push_slot_always(4, x); }
+    WIPOffset::new(fbb.end_table(start).value())
+}
+
+#[derive(Default)]
+pub struct EnumArgs<'a> {
+    pub name: Option<WIPOffset<&'a str>>,
+    pub values: Option<Offsets<'a, EnumValOffset>>,
+    pub is_union: bool,
+    pub underlying_type: Option<WIPOffset<TypeOffset>>,
+    pub attributes: Option<Offsets<'a, KeyValueOffset>>,
+    pub documentation: Option<Offsets<'a, &'a str>>,
+}
+
+pub fn create_enum<'a>(fbb: &mut FlatBufferBuilder<'a>, args: &EnumArgs<'a>) -> WIPOffset<EnumOffset> {
+    let start = fbb.start_table();
+    if let Some(x) = args.documentation { fbb.push_slot_always(14, x); }
+    if let Some(x) = args.attributes { fbb.push_slot_always(12, x); }
+    if let Some(x) = args.underlying_type { fbb.push_slot_always(10, x); }
+    if let Some(x) = args.values { fbb.push_slot_always(6, x); }
+    if let Some(x) = args.name { fbb.push_slot_always(4, x); }
+    fbb.push_slot::<bool>(8, args.is_union, false);
+    WIPOffset::new(fbb.end_table(start).value())
+}
+
+#[derive(Default)]
+pub struct FieldArgs<'a> {
+    pub name: Option<WIPOffset<&'a str>>,
+    pub type_: Option<WIPOffset<TypeOffset>>,
+    pub id: u16,
+    pub offset: u16,
+    pub default_integer: i64,
+    pub default_real: f64,
+    pub deprecated: bool,
+    pub required: bool,
+    pub key: bool,
+    pub attributes: Option<Offsets<'a, KeyValueOffset>>,
+    pub documentation: Option<Offsets<'a, &'a str>>,
+    pub optional: bool,
+    pub padding: u16,
+}
+
+pub fn create_field<'a>(fbb: &mut FlatBufferBuilder<'a>, args: &FieldArgs<'a>) -> WIPOffset<FieldOffset> {
+    let start = fbb.start_table();
+    fbb.push_slot::<f64>(14, args.default_real, 0.0);
+    fbb.push_slot::<i64>(12, args.default_integer, 0);
+    if let Some(x) = args.documentation { fbb.push_slot_always(24, x); }
+    if let Some(x) = args.attributes { fbb.push_slot_always(22, x); }
+    if let Some(x) = args.type_ { fbb.push_slot_always(6, x); }
+    if let Some(x) = args.name { fbb.push_slot_always(4, x); }
+    fbb.push_slot::<u16>(28, args.padding, 0);
+    fbb.push_slot::<u16>(10, args.offset, 0);
+    fbb.push_slot::<u16>(8, args.id, 0);
+    fbb.push_slot::<bool>(26, args.optional, false);
+    fbb.push_slot::<bool>(20, args.key, false);
+    fbb.push_slot::<bool>(18, args.required, false);
+    fbb.push_slot::<bool>(16, args.deprecated, false);
+    WIPOffset::new(fbb.end_table(start).value())
+}
+
+#[derive(Default)]
+pub struct ObjectArgs<'a> {
+    pub name: Option<WIPOffset<&'a str>>,
+    pub fields: Option<Offsets<'a, FieldOffset>>,
+    pub is_struct: bool,
+    pub minalign: i32,
+    pub bytesize: i32,
+    pub attributes: Option<Offsets<'a, KeyValueOffset>>,
+    pub documentation: Option<Offsets<'a, &'a str>>,
+}
+
+pub fn create_object<'a>(fbb: &mut FlatBufferBuilder<'a>, args: &ObjectArgs<'a>) -> WIPOffset<ObjectOffset> {
+    let start = fbb.start_table();
+    if let Some(x) = args.documentation { fbb.push_slot_always(16, x); }
+    if let Some(x) = args.attributes { fbb.push_slot_always(14, x); }
+    fbb.push_slot::<i32>(12, args.bytesize, 0);
+    fbb.push_slot::<i32>(10, args.minalign, 0);
+    if let Some(x) = args.fields { fbb.push_slot_always(6, x); }
+    if let Some(x) = args.name { fbb.push_slot_always(4, x); }
+    fbb.push_slot::<bool>(8, args.is_struct, false);
+    WIPOffset::new(fbb.end_table(start).value())
+}
+
+#[derive(Default)]
+pub struct SchemaArgs<'a> {
+    pub objects: Option<Offsets<'a, ObjectOffset>>,
+    pub enums: Option<Offsets<'a, EnumOffset>>,
+    pub file_ident: Option<WIPOffset<&'a str>>,
+    pub file_ext: Option<WIPOffset<&'a str>>,
+    pub root_table: Option<WIPOffset<ObjectOffset>>,
+}
+
+pub fn create_schema<'a>(fbb: &mut FlatBufferBuilder<'a>, args: &SchemaArgs<'a>) -> WIPOffset<SchemaOffset> {
+    let start = fbb.start_table();
+    if let Some(x) = args.root_table { fbb.push_slot_always(12, x); }
+    if let Some(x) = args.file_ext { fbb.push_slot_always(10, x); }
+    if let Some(x) = args.file_ident { fbb.push_slot_always(8, x); }
+    if let Some(x) = args.enums { fbb.push_slot_always(6, x); }
+    if let Some(x) = args.objects { fbb.push_slot_always(4, x); }
+    WIPOffset::new(fbb.end_table(start).value())
+}
+
+pub fn finish_schema_buffer<'a>(fbb: &mut FlatBufferBuilder<'a>, root: WIPOffset<SchemaOffset>) {
+    fbb.finish(root, Some(SCHEMA_IDENTIFIER));
+}
@@ -0,0 +1,59 @@
+mod common;
+
+use std::convert::Infallible;
+use bytes::Bytes;
+use common::generated_fbs::foo::Foo;
+use common::build_foo;
+use http_body_util::{BodyExt, Full, StreamBody};
+use http_body::Frame;
+use flatbuffers_owned::flatbuffers_owned;
+use flatbuffers_owned::http::{from_body, into_body, to_body, BodyError};
+
+flatbuffers_owned!(Foo);
+
+type ChunkedBody = StreamBody<futures::stream::Iter<std::vec::IntoIter<Result<Frame<Bytes>, Infallible>>>>;
+
+fn chunked(bytes: &[u8]) -> ChunkedBody {
+    let frames = bytes.chunks(7).map(|chunk| Ok(Frame::data(Bytes::copy_from_slice(chunk)))).collect::<Vec<_>>();
+
+    StreamBody::new(futures::stream::iter(frames))
+}
+
+#[tokio::test]
+async fn collect() {
+    let owned_foo: OwnedFoo = from_body(Full::new(Bytes::from(build_foo::<Foo>(42).to_vec())), 1024).await.unwrap();
+    assert_eq!(owned_foo.as_actual().a(), 42);
+
+    let shared_foo: RelaxedFoo<Bytes> = from_body(chunked(&build_foo::<Foo>(42)), 1024).await.unwrap();
+    assert_eq!(shared_foo.as_actual().b(), Some("Hello, world!"));
+}
+
+#[tokio::test]
+async fn collect_too_large() {
+    let bytes = build_foo::<Foo>(42).to_vec();
+
+    let result = from_body::<OwnedFoo, _>(Full::new(Bytes::from(bytes.clone())), 8).await;
+    assert!(matches!(result, Err(BodyError::TooLarge { limit: 8 })));
+
+    // Without a size hint, the limit is checked while collecting.
+    let result = from_body::<OwnedFoo, _>(chunked(&bytes), bytes.len() - 1).await;
+    assert!(matches!(result, Err(BodyError::TooLarge { .. })));
+}
+
+#[tokio::test]
+async fn collect_invalid() {
+    let result = from_body::<OwnedFoo, _>(Full::new(Bytes::from_static(&[0xFF; 16])), 1024).await;
+
+    assert!(matches!(result, Err(BodyError::InvalidFlatbuffer(_))));
+}
+
+#[tokio::test]
+async fn body() {
+    let owned_foo: OwnedFoo = build_foo(42);
+
+    let bytes = to_body(&owned_foo).collect().await.unwrap().to_bytes();
+    assert_eq!(&bytes[..], &owned_foo[..]);
+
+    let bytes = into_body(owned_foo.clone()).collect().await.unwrap().to_bytes();
+    assert_eq!(&bytes[..], &owned_foo[..]);
+}
@@ -0,0 +1,48 @@
+mod common;
+
+use common::generated_fbs::foo::Foo;
+use common::build_foo_bytes;
+use flatbuffers_owned::flatbuffers_owned;
+use flatbuffers_owned::buffer::{InlineBuffer, INLINE_CAPACITY};
+
+flatbuffers_owned!(Foo);
+
+#[test]
+fn inline() {
+    let bytes = build_foo_bytes(42, Some("Hello, world!"), None);
+    assert!(bytes.len() <= INLINE_CAPACITY);
+
+    let inline_foo = InlineFoo::new(InlineBuffer::from_slice(&bytes)).unwrap();
+    assert!(!inline_foo.buffer().spilled());
+    assert_eq!(inline_foo.as_actual().a(), 42);
+    assert_eq!(inline_foo.as_actual().b(), Some("Hello, world!"));
+
+    // The bytes move with the wrapper.
+    let moved = Box::new(inline_foo);
+    assert_eq!(moved.as_actual().b(), Some("Hello, world!"));
+}
+
+#[test]
+fn spills_to_heap() {
+    let b = "Hello, world!".repeat(INLINE_CAPACITY);
+    let bytes = build_foo_bytes(42, Some(&b), None);
+
+    let inline_foo = InlineFoo::new(InlineBuffer::from_slice(&bytes)).unwrap();
+    assert!(inline_foo.buffer().spilled());
+    assert_eq!(inline_foo.as_actual().b(), Some(b.as_str()));
+}
+
+#[test]
+fn inline_invalid() {
+    assert!(InlineFoo::new(InlineBuffer::from_slice(&[0xFF; 16])).is_err());
+}
+
+#[test]
+fn to_owned() {
+    let bytes = build_foo_bytes(42, Some("Hello, world!"), None);
+    let inline_foo = InlineFoo::new(InlineBuffer::from_slice(&bytes)).unwrap();
+
+    let owned_foo: OwnedFoo = inline_foo.to_owned();
+    assert_eq!(owned_foo, inline_foo.to_owned());
+    assert_eq!(owned_foo.as_actual().a(), 42);
+}
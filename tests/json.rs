@@ -0,0 +1,229 @@
+mod common;
+
+use flatbuffers::{FlatBufferBuilder, Follow, ForwardsUOffset, Table, Vector, WIPOffset};
+use flatbuffers_owned::flatbuffers_owned;
+use flatbuffers_owned::reflection::json::{from_json, JsonError};
+use flatbuffers_owned::reflection::Schema;
+use std::convert::TryInto;
+use common::generated_fbs::foo::Foo;
+use common::generated_fbs::reflection::*;
+
+flatbuffers_owned!(Foo);
+
+type Builder = FlatBufferBuilder<'static>;
+
+fn field(fbb: &mut Builder, name: &str, id: u16, ty: TypeArgs, args: FieldArgs<'static>) -> WIPOffset<FieldOffset> {
+    let name = fbb.create_string(name);
+    let type_ = create_type(fbb, &ty);
+    create_field(fbb, &FieldArgs { name: Some(name), type_: Some(type_), id, offset: 4 + 2 * id, ..args })
+}
+
+fn object(fbb: &mut Builder, name: &str, fields: &[WIPOffset<FieldOffset>], args: ObjectArgs<'static>) -> WIPOffset<ObjectOffset> {
+    let name = fbb.create_string(name);
+    let fields = fbb.create_vector(fields);
+    create_object(fbb, &ObjectArgs { name: Some(name), fields: Some(fields), ..args })
+}
+
+fn enumeration(fbb: &mut Builder, name: &str, values: &[(&str, Option<TypeArgs>)], underlying_type: i8, is_union: bool) -> WIPOffset<EnumOffset> {
+    let values = values.iter().enumerate()
+        .map(|(value, (name, union_type))| {
+            let name = fbb.create_string(name);
+            let union_type = union_type.as_ref().map(|ty| create_type(fbb, ty));
+            create_enum_val(fbb, &EnumValArgs { name: Some(name), value: value as i64, union_type, ..Default::default() })
+        })
+        .collect::<Vec<_>>();
+    let values = fbb.create_vector(&values);
+    let underlying_type = create_type(fbb, &TypeArgs { base_type: underlying_type, base_size: 1, ..Default::default() });
+    let name = fbb.create_string(name);
+    create_enum(fbb, &EnumArgs { name: Some(name), values: Some(values), is_union, underlying_type: Some(underlying_type), ..Default::default() })
+}
+
+fn finish(mut fbb: Builder, objects: &[WIPOffset<ObjectOffset>], enums: &[WIPOffset<EnumOffset>], root: usize, file_ident: Option<&str>) -> Schema {
+    let objects_vector = fbb.create_vector(objects);
+    let enums = fbb.create_vector(enums);
+    let file_ident = file_ident.map(|ident| fbb.create_string(ident));
+    let schema = create_schema(&mut fbb, &SchemaArgs {
+        objects: Some(objects_vector),
+        enums: Some(enums),
+        file_ident,
+        root_table: Some(objects[root]),
+        ..Default::default()
+    });
+    finish_schema_buffer(&mut fbb, schema);
+
+    Schema::from_bfbs(fbb.finished_data()).unwrap()
+}
+
+// table Foo { a: uint32; b: string; }
+// root_type Foo;
+fn foo_schema() -> Schema {
+    let mut fbb = FlatBufferBuilder::new();
+
+    let a = field(&mut fbb, "a", 0, TypeArgs { base_type: base_type::UINT, ..Default::default() }, Default::default());
+    let b = field(&mut fbb, "b", 1, TypeArgs { base_type: base_type::STRING, ..Default::default() }, Default::default());
+    let foo_object = object(&mut fbb, "Foo", &[a, b], ObjectArgs { minalign: 4, ..Default::default() });
+
+    finish(fbb, &[foo_object], &[], 0, None)
+}
+
+// namespace game;
+// enum Color : ubyte { Red, Green, Blue }
+// union Equipment { Weapon }
+// struct Vec3 { x: float; y: float; z: float; }
+// table Weapon { name: string; damage: short; }
+// table Monster { name: string (required); hp: short = 100; pos: Vec3; color: Color = Blue; inventory: [ubyte]; weapons: [Weapon]; equipped: Equipment; path: [Vec3]; }
+// root_type Monster;
+// file_identifier "MONS";
+fn monster_schema() -> Schema {
+    let mut fbb = FlatBufferBuilder::new();
+    let ty = |base_type, index| TypeArgs { base_type, index, ..Default::default() };
+    let vector = |element, index| TypeArgs { base_type: base_type::VECTOR, element, index, ..Default::default() };
+
+    let monster = {
+        let fields = [
+            field(&mut fbb, "color", 3, ty(base_type::UBYTE, 0), FieldArgs { default_integer: 2, ..Default::default() }),
+            field(&mut fbb, "equipped", 7, ty(base_type::UNION, 1), Default::default()),
+            field(&mut fbb, "equipped_type", 6, ty(base_type::UTYPE, 1), Default::default()),
+            field(&mut fbb, "hp", 1, ty(base_type::SHORT, -1), FieldArgs { default_integer: 100, ..Default::default() }),
+            field(&mut fbb, "inventory", 4, vector(base_type::UBYTE, -1), Default::default()),
+            field(&mut fbb, "name", 0, ty(base_type::STRING, -1), FieldArgs { required: true, ..Default::default() }),
+            field(&mut fbb, "path", 8, vector(base_type::OBJ, 1), Default::default()),
+            field(&mut fbb, "pos", 2, ty(base_type::OBJ, 1), Default::default()),
+            field(&mut fbb, "weapons", 5, vector(base_type::OBJ, 2), Default::default()),
+        ];
+        object(&mut fbb, "game.Monster", &fields, ObjectArgs { minalign: 4, ..Default::default() })
+    };
+
+    let vec3 = {
+        let fields = ["x", "y", "z"].iter().enumerate()
+            .map(|(id, name)| {
+                let name = fbb.create_string(name);
+                let type_ = create_type(&mut fbb, &ty(base_type::FLOAT, -1));
+                create_field(&mut fbb, &FieldArgs { name: Some(name), type_: Some(type_), id: id as u16, offset: 4 * id as u16, ..Default::default() })
+            })
+            .collect::<Vec<_>>();
+        object(&mut fbb, "game.Vec3", &fields, ObjectArgs { is_struct: true, minalign: 4, bytesize: 12, ..Default::default() })
+    };
+
+    let weapon = {
+        let fields = [
+            field(&mut fbb, "damage", 1, ty(base_type::SHORT, -1), Default::default()),
+            field(&mut fbb, "name", 0, ty(base_type::STRING, -1), Default::default()),
+        ];
+        object(&mut fbb, "game.Weapon", &fields, ObjectArgs { minalign: 2, ..Default::default() })
+    };
+
+    let color = enumeration(&mut fbb, "game.Color", &[("Red", None), ("Green", None), ("Blue", None)], base_type::UBYTE, false);
+    let equipment = enumeration(&mut fbb, "game.Equipment", &[("NONE", None), ("Weapon", Some(ty(base_type::OBJ, 2)))], base_type::UTYPE, true);
+
+    finish(fbb, &[monster, vec3, weapon], &[color, equipment], 0, Some("MONS"))
+}
+
+fn root(data: &[u8]) -> Table<'_> {
+    unsafe { <ForwardsUOffset<Table>>::follow(data, 0) }
+}
+
+#[test]
+fn from_json_typed() {
+    let owned_foo: OwnedFoo = from_json(&foo_schema(), r#"{ "a": 42, "b": "Hello, world!" }"#).unwrap();
+
+    assert_eq!(owned_foo.as_actual().a(), 42);
+    assert_eq!(owned_foo.as_actual().b(), Some("Hello, world!"));
+
+    let owned_foo: OwnedFoo = from_json(&foo_schema(), "{}").unwrap();
+    assert_eq!(owned_foo.as_actual().a(), 0);
+    assert_eq!(owned_foo.as_actual().b(), None);
+}
+
+#[test]
+fn json_to_flatbuffer() {
+    let json = r#"{
+        "name": "Orc",
+        "hp": 80,
+        "pos": { "x": 1.0, "y": 2.5, "z": -3.0 },
+        "color": "Red",
+        "inventory": [1, 2, 3],
+        "weapons": [{ "name": "Axe", "damage": 5 }, { "name": "Bow" }],
+        "equipped_type": "Weapon",
+        "equipped": { "name": "Sword", "damage": 9 },
+        "path": [{ "x": 0, "y": 0, "z": 0 }, { "x": 1, "y": 1, "z": 1 }]
+    }"#;
+
+    let data = monster_schema().json_to_flatbuffer(json).unwrap();
+    assert_eq!(&data[4..8], b"MONS");
+
+    let monster = root(&data);
+    unsafe {
+        assert_eq!(monster.get::<ForwardsUOffset<&str>>(4, None), Some("Orc"));
+        assert_eq!(monster.get::<i16>(6, Some(100)), Some(80));
+        assert_eq!(monster.get::<u8>(10, Some(2)), Some(0));
+
+        let pos_loc = monster.vtable().get(8) as usize + monster.loc();
+        assert_eq!(f32::from_le_bytes(data[pos_loc + 4..pos_loc + 8].try_into().unwrap()), 2.5);
+        assert_eq!(f32::from_le_bytes(data[pos_loc + 8..pos_loc + 12].try_into().unwrap()), -3.0);
+
+        let inventory = monster.get::<ForwardsUOffset<Vector<u8>>>(12, None).unwrap();
+        assert_eq!(inventory.bytes(), [1, 2, 3]);
+
+        let weapons = monster.get::<ForwardsUOffset<Vector<ForwardsUOffset<Table>>>>(14, None).unwrap();
+        assert_eq!(weapons.len(), 2);
+        assert_eq!(weapons.get(0).get::<ForwardsUOffset<&str>>(4, None), Some("Axe"));
+        assert_eq!(weapons.get(0).get::<i16>(6, Some(0)), Some(5));
+        assert_eq!(weapons.get(1).get::<i16>(6, Some(0)), Some(0));
+
+        assert_eq!(monster.get::<u8>(16, Some(0)), Some(1));
+        let equipped = monster.get::<ForwardsUOffset<Table>>(18, None).unwrap();
+        assert_eq!(equipped.get::<ForwardsUOffset<&str>>(4, None), Some("Sword"));
+
+        let path_loc = monster.vtable().get(20) as usize + monster.loc();
+        let path_loc = path_loc + u32::from_le_bytes(data[path_loc..path_loc + 4].try_into().unwrap()) as usize;
+        assert_eq!(u32::from_le_bytes(data[path_loc..path_loc + 4].try_into().unwrap()), 2);
+        assert_eq!(f32::from_le_bytes(data[path_loc + 4..path_loc + 8].try_into().unwrap()), 0.0);
+        assert_eq!(f32::from_le_bytes(data[path_loc + 16..path_loc + 20].try_into().unwrap()), 1.0);
+    }
+}
+
+#[test]
+fn defaults_are_omitted() {
+    let data = monster_schema().json_to_flatbuffer(r#"{ "name": "Orc", "hp": 100, "color": "Blue" }"#).unwrap();
+    let monster = root(&data);
+
+    assert_eq!(monster.vtable().get(6), 0);
+    assert_eq!(monster.vtable().get(10), 0);
+}
+
+#[test]
+fn json_to_flatbuffer_as() {
+    let schema = monster_schema();
+    let data = schema.json_to_flatbuffer_as("game.Weapon", r#"{ "name": "Axe", "damage": 5 }"#).unwrap();
+
+    unsafe {
+        assert_eq!(root(&data).get::<i16>(6, Some(0)), Some(5));
+    }
+
+    assert!(matches!(schema.json_to_flatbuffer_as("game.Vec3", "{}"), Err(JsonError::UnknownTable(_))));
+    assert!(matches!(schema.json_to_flatbuffer_as("game.Missing", "{}"), Err(JsonError::UnknownTable(_))));
+}
+
+#[test]
+fn errors() {
+    let schema = monster_schema();
+    let error = |json: &str| schema.json_to_flatbuffer(json).unwrap_err();
+
+    assert!(matches!(error("{"), JsonError::Syntax(_)));
+    assert!(matches!(error("{}"), JsonError::MissingField { path } if path == "name"));
+    assert!(matches!(error(r#"{ "name": "Orc", "mana": 1 }"#), JsonError::UnknownField { path } if path == "mana"));
+    assert!(matches!(error(r#"{ "name": "Orc", "hp": 40000 }"#), JsonError::InvalidValue { path, .. } if path == "hp"));
+    assert!(matches!(error(r#"{ "name": "Orc", "color": "Purple" }"#), JsonError::InvalidValue { path, .. } if path == "color"));
+    assert!(matches!(error(r#"{ "name": 1 }"#), JsonError::InvalidValue { path, .. } if path == "name"));
+    assert!(matches!(error(r#"{ "name": "Orc", "pos": { "x": 1, "y": 2 } }"#), JsonError::MissingField { path } if path == "pos.z"));
+    assert!(matches!(error(r#"{ "name": "Orc", "weapons": [{ "damage": "high" }] }"#), JsonError::InvalidValue { path, .. } if path == "weapons[0].damage"));
+    assert!(matches!(error(r#"{ "name": "Orc", "equipped": {} }"#), JsonError::MissingField { path } if path == "equipped_type"));
+}
+
+#[test]
+fn schema_mismatch() {
+    let result = from_json::<OwnedFoo>(&monster_schema(), r#"{ "name": "Orc", "hp": 30000 }"#);
+
+    assert!(matches!(result, Err(JsonError::InvalidFlatbuffer(_))));
+}
@@ -0,0 +1,46 @@
+mod common;
+
+use std::fs;
+use std::path::PathBuf;
+use common::generated_fbs::foo::Foo;
+use common::get_foo_bytes;
+use flatbuffers_owned::{flatbuffers_owned, FileError};
+
+flatbuffers_owned!(Foo { cached });
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("flatbuffers-owned-{}-{}", std::process::id(), name))
+}
+
+#[test]
+fn from_file_mmap() {
+    let path = temp_path("foo.bin");
+    fs::write(&path, get_foo_bytes()).unwrap();
+
+    let mapped_foo = unsafe { OwnedFoo::from_file_mmap(&path) }.unwrap();
+    assert_eq!(mapped_foo.as_actual().a(), 42);
+
+    let cached_foo = CachedFoo::new(mapped_foo);
+    assert_eq!(cached_foo.get().b(), Some("Hello, world!"));
+
+    drop(cached_foo);
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn from_file_mmap_invalid() {
+    let path = temp_path("invalid.bin");
+    fs::write(&path, [0xFF; 16]).unwrap();
+
+    let result = unsafe { OwnedFoo::from_file_mmap(&path) };
+    assert!(matches!(result, Err(FileError::InvalidFlatbuffer(_))));
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn from_file_mmap_missing() {
+    let result = unsafe { OwnedFoo::from_file_mmap(temp_path("missing.bin")) };
+
+    assert!(matches!(result, Err(FileError::Io(_))));
+}
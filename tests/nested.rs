@@ -0,0 +1,43 @@
+mod common;
+
+use common::generated_fbs::foo::{Foo, FooArgs};
+use common::generated_fbs::container::{Container, ContainerArgs};
+use flatbuffers_owned::flatbuffers_owned;
+
+flatbuffers_owned!(Foo, Container { create, nested: foo -> Foo });
+
+#[test]
+fn extract_nested_flatbuffer() {
+    let owned_foo = OwnedFoo::build(|builder| {
+        let b = builder.create_string("nested");
+        Foo::create(builder, &FooArgs { a: 42, b: Some(b) })
+    }).unwrap();
+
+    let container = OwnedContainer::build(|builder| {
+        let foo = builder.create_vector(&owned_foo);
+        Container::create(builder, &ContainerArgs { foo: Some(foo) })
+    }).unwrap();
+
+    let nested = container.foo_owned().unwrap().unwrap();
+
+    assert_eq!(nested, owned_foo);
+    assert_eq!(nested.as_actual().a(), 42);
+    assert_eq!(nested.as_actual().b(), Some("nested"));
+}
+
+#[test]
+fn extract_missing_nested_flatbuffer() {
+    let container = OwnedContainer::create(&ContainerArgs::default());
+
+    assert!(container.foo_owned().unwrap().is_none());
+}
+
+#[test]
+fn extract_invalid_nested_flatbuffer() {
+    let container = OwnedContainer::build(|builder| {
+        let foo = builder.create_vector(&[1u8, 2, 3]);
+        Container::create(builder, &ContainerArgs { foo: Some(foo) })
+    }).unwrap();
+
+    assert!(container.foo_owned().is_err());
+}
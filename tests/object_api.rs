@@ -0,0 +1,84 @@
+mod common;
+
+use flatbuffers::FlatBufferBuilder;
+use common::generated_fbs::foo::{Foo, FooArgs, FooT};
+use common::get_foo_bytes;
+use flatbuffers_owned::flatbuffers_owned;
+
+flatbuffers_owned!(Foo { object_api });
+
+#[test]
+fn unpack_owned_foo() {
+    let owned_foo = OwnedFoo::new(get_foo_bytes()).unwrap();
+    let native = owned_foo.unpack();
+
+    assert_eq!(native.a, 42);
+    assert_eq!(native.b.as_deref(), Some("Hello, world!"));
+}
+
+#[test]
+fn pack_owned_foo() {
+    let native = FooT {
+        a: 7,
+        b: Some("packed".to_string()),
+    };
+
+    let owned_foo = OwnedFoo::pack(&native);
+    assert!(OwnedFoo::verify(&owned_foo).is_ok());
+
+    let foo = owned_foo.as_actual();
+    assert_eq!(foo.a(), 7);
+    assert_eq!(foo.b().unwrap(), "packed");
+}
+
+#[test]
+fn unpack_borrowed_foo() {
+    let foo_bytes = get_foo_bytes();
+    let borrowed_foo = RelaxedFoo::new(&foo_bytes[..]).unwrap();
+
+    assert_eq!(borrowed_foo.unpack(), OwnedFoo::new(get_foo_bytes()).unwrap().unpack());
+}
+
+#[test]
+fn update_owned_foo() {
+    let owned_foo = OwnedFoo::new(get_foo_bytes()).unwrap();
+
+    let updated_foo = owned_foo.update(|native| {
+        native.a += 1;
+        native.b = Some("updated".to_string());
+    }).unwrap();
+
+    assert_eq!(updated_foo.as_actual().a(), 43);
+    assert_eq!(updated_foo.as_actual().b(), Some("updated"));
+    assert_eq!(owned_foo.as_actual().b(), Some("Hello, world!"));
+}
+
+#[test]
+fn update_borrowed_foo() {
+    let foo_bytes = get_foo_bytes();
+    let borrowed_foo = RelaxedFoo::new(&foo_bytes[..]).unwrap();
+
+    let updated_foo: OwnedFoo = borrowed_foo.update(|native| native.b = None).unwrap();
+    assert_eq!(updated_foo.as_actual().a(), 42);
+    assert_eq!(updated_foo.as_actual().b(), None);
+}
+
+#[test]
+fn compact_foo() {
+    let mut builder = FlatBufferBuilder::new();
+    // Unreferenced data left behind, e.g. by an aborted write.
+    builder.create_string(&"unused".repeat(16));
+    let b = builder.create_string("Hello, world!");
+    let offset = Foo::create(&mut builder, &FooArgs { a: 42, b: Some(b) });
+    builder.finish(offset, None);
+
+    let bloated_foo = OwnedFoo::new(builder.finished_data().into()).unwrap();
+    let (compacted_foo, saved) = bloated_foo.compact();
+
+    assert!(saved >= 96);
+    assert_eq!(compacted_foo.len() + saved, bloated_foo.len());
+    assert_eq!(compacted_foo.unpack(), bloated_foo.unpack());
+
+    let (_, saved) = compacted_foo.compact();
+    assert_eq!(saved, 0);
+}
@@ -0,0 +1,50 @@
+mod common;
+
+use flatbuffers::VerifierOptions;
+use common::generated_fbs::foo::Foo;
+use common::get_foo_bytes;
+use flatbuffers_owned::{flatbuffers_owned, RelaxedFlatBufferTrait, RelaxedFollowTrait, TrustedPolicy, VerifierPolicy};
+
+flatbuffers_owned!(Foo);
+
+struct NoTablesPolicy;
+
+unsafe impl VerifierPolicy for NoTablesPolicy {
+    fn options<T: RelaxedFollowTrait>() -> VerifierOptions {
+        VerifierOptions { max_tables: 0, ..Default::default() }
+    }
+}
+
+type StrictFoo = RelaxedFoo<Box<[u8]>, NoTablesPolicy>;
+type TrustedFoo<'a> = RelaxedFoo<&'a [u8], TrustedPolicy>;
+
+#[test]
+fn policy_options() {
+    let foo_bytes = get_foo_bytes();
+
+    assert!(OwnedFoo::new(foo_bytes.clone()).is_ok());
+    assert!(StrictFoo::new(foo_bytes.clone()).is_err());
+    assert!(StrictFoo::verify(&foo_bytes).is_err());
+}
+
+#[test]
+fn trusted_policy() {
+    let foo_bytes = get_foo_bytes();
+    let trusted_foo = TrustedFoo::new(&foo_bytes).unwrap();
+
+    assert_eq!(trusted_foo.as_actual().a(), 42);
+    assert_eq!(trusted_foo.as_actual().b(), Some("Hello, world!"));
+}
+
+#[test]
+fn convert_with_custom_options() {
+    let strict_foo = StrictFoo::new_with_opts(get_foo_bytes(), &VerifierOptions::default()).unwrap();
+
+    let shared_foo = strict_foo.into_shared();
+    assert_eq!(shared_foo.as_actual().a(), 42);
+}
+
+#[test]
+fn trusted_policy_still_verifies_explicitly() {
+    assert!(TrustedFoo::verify(&[0, 1, 2]).is_err());
+}
@@ -0,0 +1,155 @@
+mod common;
+
+use std::thread;
+use common::generated_fbs::foo::{Foo, FooArgs};
+use common::get_foo_bytes;
+use flatbuffers_owned::flatbuffers_owned;
+use flatbuffers_owned::pool::{BufferPool, BuilderPool};
+
+flatbuffers_owned!(Foo { cached });
+
+#[test]
+fn new_pooled() {
+    let pool = BufferPool::new(4);
+    let bytes = get_foo_bytes();
+
+    let foo = OwnedFoo::new_pooled(&pool, &bytes).unwrap();
+    assert_eq!(foo.as_actual().a(), 42);
+    assert!(pool.is_empty());
+
+    drop(foo);
+    assert_eq!(pool.len(), 1);
+}
+
+#[test]
+fn recycles_allocation() {
+    let pool = BufferPool::new(4);
+    let bytes = get_foo_bytes();
+
+    let first = pool.copy_from_slice(&bytes);
+    let address = first.as_ptr();
+    drop(first);
+
+    let second = OwnedFoo::new_pooled(&pool, &bytes).unwrap();
+    assert_eq!(second.as_ptr(), address);
+    assert_eq!(second.as_actual().b(), Some("Hello, world!"));
+}
+
+#[test]
+fn new_pooled_invalid() {
+    let pool = BufferPool::new(4);
+
+    assert!(OwnedFoo::new_pooled(&pool, &[0xFF; 16]).is_err());
+    assert_eq!(pool.len(), 1);
+}
+
+#[test]
+fn acquire() {
+    let pool = BufferPool::new(4);
+    let bytes = get_foo_bytes();
+
+    let mut buffer = pool.acquire(bytes.len());
+    assert!(buffer.iter().all(|&byte| byte == 0));
+
+    buffer.as_mut().copy_from_slice(&bytes);
+    let foo = RelaxedFoo::new(buffer).unwrap();
+    assert_eq!(foo.as_actual().a(), 42);
+
+    let cached_foo = CachedFoo::new(foo);
+    assert_eq!(cached_foo.get().b(), Some("Hello, world!"));
+}
+
+#[test]
+fn capacity() {
+    let pool = BufferPool::new(1);
+
+    let first = pool.acquire(8);
+    let second = pool.acquire(8);
+    drop(first);
+    drop(second);
+    assert_eq!(pool.len(), 1);
+
+    pool.clear();
+    assert!(pool.is_empty());
+}
+
+#[test]
+fn across_threads() {
+    let pool = BufferPool::new(4);
+    let foo = OwnedFoo::new_pooled(&pool, &get_foo_bytes()).unwrap();
+
+    thread::spawn(move || assert_eq!(foo.as_actual().a(), 42)).join().unwrap();
+    assert_eq!(pool.len(), 1);
+}
+
+#[test]
+fn outlives_pool() {
+    let pool = BufferPool::new(4);
+    let foo = OwnedFoo::new_pooled(&pool, &get_foo_bytes()).unwrap();
+
+    drop(pool);
+    assert_eq!(foo.as_actual().a(), 42);
+}
+
+fn build_foo(pool: &BuilderPool, a: u32) -> OwnedFoo {
+    OwnedFoo::build_pooled(pool, |builder| {
+        let b = builder.create_string("Hello, world!");
+        Foo::create(builder, &FooArgs { a, b: Some(b) })
+    }).unwrap()
+}
+
+#[test]
+fn build_pooled() {
+    let pool = BuilderPool::default();
+
+    let first = build_foo(&pool, 1);
+    assert_eq!(pool.len(), 1);
+
+    let second = build_foo(&pool, 2);
+    assert_eq!(pool.len(), 1);
+
+    assert_eq!(first.as_actual().a(), 1);
+    assert_eq!(second.as_actual().a(), 2);
+    assert_eq!(second.as_actual().b(), Some("Hello, world!"));
+}
+
+#[test]
+fn with_builder_nested() {
+    let pool = BuilderPool::new(4, 1024 * 1024);
+
+    let (outer, inner) = pool.with_builder(|outer| {
+        let inner = build_foo(&pool, 7);
+
+        let b = outer.create_string("outer");
+        let offset = Foo::create(outer, &FooArgs { a: 1, b: Some(b) });
+        outer.finish(offset, None);
+
+        (OwnedFoo::from_builder(outer).unwrap(), inner)
+    });
+
+    assert_eq!(outer.as_actual().b(), Some("outer"));
+    assert_eq!(inner.as_actual().a(), 7);
+    assert_eq!(pool.len(), 2);
+}
+
+#[test]
+fn builder_pool_limits() {
+    let pool = BuilderPool::new(1, 1024 * 1024);
+    pool.with_builder(|_| pool.with_builder(|_| ()));
+    assert_eq!(pool.len(), 1);
+
+    let pool = BuilderPool::new(4, 16);
+    build_foo(&pool, 42);
+    assert!(pool.is_empty());
+}
+
+#[test]
+fn build_local() {
+    let foo = OwnedFoo::build_local(|builder| {
+        let b = builder.create_string("Hello, world!");
+        Foo::create(builder, &FooArgs { a: 42, b: Some(b) })
+    }).unwrap();
+
+    assert_eq!(foo.as_actual().a(), 42);
+    assert_eq!(BuilderPool::with_local(|pool| pool.len()), 1);
+}
@@ -0,0 +1,53 @@
+mod common;
+
+use common::generated_fbs::foo::Foo;
+use common::build_foo;
+use flatbuffers_owned::flatbuffers_owned;
+use redis::{cmd, Commands, ErrorKind, FromRedisValue, Value};
+use redis_test::{MockCmd, MockRedisConnection};
+
+flatbuffers_owned!(Foo);
+
+#[test]
+fn set_and_get() {
+    let owned_foo: OwnedFoo = build_foo(42);
+    let borrowed_foo = RelaxedFoo::new(&owned_foo[..]).unwrap();
+    let mut connection = MockRedisConnection::new(vec![
+        MockCmd::new(cmd("SET").arg("foo").arg(&owned_foo[..]), Ok("OK")),
+        MockCmd::new(cmd("SET").arg("bar").arg(&owned_foo[..]), Ok("OK")),
+        MockCmd::new(cmd("GET").arg("foo"), Ok(owned_foo.to_vec())),
+    ]);
+
+    let _: () = connection.set("foo", &owned_foo).unwrap();
+    let _: () = connection.set("bar", borrowed_foo).unwrap();
+    let foo: OwnedFoo = connection.get("foo").unwrap();
+
+    assert_eq!(foo, owned_foo);
+    assert_eq!(foo.as_actual().b(), Some("Hello, world!"));
+}
+
+#[test]
+fn verify_from_redis_value() {
+    let error = OwnedFoo::from_redis_value(&Value::BulkString(vec![0xff, 0xff, 0xff, 0xff])).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::TypeError);
+
+    let error = OwnedFoo::from_owned_redis_value(Value::Int(7)).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::TypeError);
+
+    let foo = OwnedFoo::from_owned_redis_value(Value::BulkString(build_foo::<Foo>(7).to_vec())).unwrap();
+    assert_eq!(foo.as_actual().a(), 7);
+}
+
+#[test]
+fn nil_reply() {
+    let error = OwnedFoo::from_redis_value(&Value::Nil).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::TypeError);
+    assert!(error.to_string().contains("Nil reply"));
+
+    let mut connection = MockRedisConnection::new(vec![
+        MockCmd::new(cmd("GET").arg("missing"), Ok(Value::Nil)),
+    ]);
+
+    let foo: Option<OwnedFoo> = connection.get("missing").unwrap();
+    assert!(foo.is_none());
+}
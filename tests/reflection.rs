@@ -0,0 +1,184 @@
+mod common;
+
+use flatbuffers::FlatBufferBuilder;
+use flatbuffers_owned::reflection::{BaseType, Schema};
+use flatbuffers_owned::{Constructor, DecodeError};
+use common::generated_fbs::reflection::*;
+
+// namespace game;
+// enum Color : ubyte { Red, Green, Blue }
+// struct Vec3 { x: float; y: float; z: float; }
+// /// A monster.
+// table Monster { name: string (required, key); hp: short = 100; pos: Vec3; color: Color = Blue; inventory: [ubyte]; friends: [Monster]; }
+// root_type Monster;
+// file_identifier "MONS";
+// file_extension "mon";
+fn build_schema(with_enums: bool) -> Vec<u8> {
+    let mut fbb = FlatBufferBuilder::new();
+
+    let field = |fbb: &mut FlatBufferBuilder<'static>, name: &str, id: u16, ty: TypeArgs, default_integer: i64| {
+        let name = fbb.create_string(name);
+        let type_ = create_type(fbb, &ty);
+        create_field(fbb, &FieldArgs { name: Some(name), type_: Some(type_), id, offset: 4 + 2 * id, default_integer, ..Default::default() })
+    };
+
+    let color = field(&mut fbb, "color", 3, TypeArgs { base_type: base_type::UBYTE, index: 0, base_size: 1, ..Default::default() }, 2);
+    let friends = field(&mut fbb, "friends", 5, TypeArgs { base_type: base_type::VECTOR, element: base_type::OBJ, index: 0, element_size: 4, ..Default::default() }, 0);
+    let hp = field(&mut fbb, "hp", 1, TypeArgs { base_type: base_type::SHORT, base_size: 2, ..Default::default() }, 100);
+    let inventory = field(&mut fbb, "inventory", 4, TypeArgs { base_type: base_type::VECTOR, element: base_type::UBYTE, element_size: 1, ..Default::default() }, 0);
+    let pos = field(&mut fbb, "pos", 2, TypeArgs { base_type: base_type::OBJ, index: 1, base_size: 12, ..Default::default() }, 0);
+
+    let name_field = {
+        let key = fbb.create_string("key");
+        let attribute = create_key_value(&mut fbb, &KeyValueArgs { key: Some(key), value: None });
+        let attributes = fbb.create_vector(&[attribute]);
+        let name = fbb.create_string("name");
+        let type_ = create_type(&mut fbb, &TypeArgs { base_type: base_type::STRING, ..Default::default() });
+        create_field(&mut fbb, &FieldArgs { name: Some(name), type_: Some(type_), id: 0, offset: 4, required: true, key: true, attributes: Some(attributes), ..Default::default() })
+    };
+
+    let monster = {
+        let documentation = fbb.create_string(" A monster.");
+        let documentation = fbb.create_vector(&[documentation]);
+        let name = fbb.create_string("game.Monster");
+        let fields = fbb.create_vector(&[color, friends, hp, inventory, name_field, pos]);
+        create_object(&mut fbb, &ObjectArgs { name: Some(name), fields: Some(fields), minalign: 1, documentation: Some(documentation), ..Default::default() })
+    };
+
+    let vec3 = {
+        let fields = ["x", "y", "z"].iter().enumerate()
+            .map(|(id, name)| field(&mut fbb, name, id as u16, TypeArgs { base_type: base_type::FLOAT, ..Default::default() }, 0))
+            .collect::<Vec<_>>();
+        let fields = fbb.create_vector(&fields);
+        let name = fbb.create_string("game.Vec3");
+        create_object(&mut fbb, &ObjectArgs { name: Some(name), fields: Some(fields), is_struct: true, minalign: 4, bytesize: 12, ..Default::default() })
+    };
+
+    let enums = if with_enums {
+        let values = ["Red", "Green", "Blue"].iter().enumerate()
+            .map(|(value, name)| {
+                let name = fbb.create_string(name);
+                create_enum_val(&mut fbb, &EnumValArgs { name: Some(name), value: value as i64, ..Default::default() })
+            })
+            .collect::<Vec<_>>();
+        let values = fbb.create_vector(&values);
+        let underlying_type = create_type(&mut fbb, &TypeArgs { base_type: base_type::UBYTE, index: 0, base_size: 1, ..Default::default() });
+        let name = fbb.create_string("game.Color");
+        let color = create_enum(&mut fbb, &EnumArgs { name: Some(name), values: Some(values), underlying_type: Some(underlying_type), ..Default::default() });
+
+        Some(fbb.create_vector(&[color]))
+    } else {
+        None
+    };
+
+    let objects = fbb.create_vector(&[monster, vec3]);
+    let file_ident = fbb.create_string("MONS");
+    let file_ext = fbb.create_string("mon");
+    let schema = create_schema(&mut fbb, &SchemaArgs {
+        objects: Some(objects),
+        enums,
+        file_ident: Some(file_ident),
+        file_ext: Some(file_ext),
+        root_table: Some(monster),
+    });
+    finish_schema_buffer(&mut fbb, schema);
+
+    fbb.finished_data().to_vec()
+}
+
+#[test]
+fn from_bfbs() {
+    let schema = Schema::from_bfbs(&build_schema(true)).unwrap();
+
+    assert_eq!(schema.objects.len(), 2);
+    assert_eq!(schema.enums.len(), 1);
+    assert_eq!(schema.file_ident.as_deref(), Some("MONS"));
+    assert_eq!(schema.file_ext.as_deref(), Some("mon"));
+
+    let monster = schema.root_table().unwrap();
+    assert_eq!(monster.name, "game.Monster");
+    assert!(!monster.is_struct);
+    assert_eq!(monster.documentation, [" A monster."]);
+
+    let vec3 = schema.object("game.Vec3").unwrap();
+    assert!(vec3.is_struct);
+    assert_eq!(vec3.bytesize, 12);
+    assert_eq!(vec3.fields.len(), 3);
+}
+
+#[test]
+fn fields() {
+    let schema = Schema::from_bfbs(&build_schema(true)).unwrap();
+    let monster = schema.root_table().unwrap();
+
+    let names = monster.fields_by_id().iter().map(|field| field.name.as_str()).collect::<Vec<_>>();
+    assert_eq!(names, ["name", "hp", "pos", "color", "inventory", "friends"]);
+
+    let name = monster.field("name").unwrap();
+    assert_eq!(name.ty.base_type, BaseType::String);
+    assert_eq!(name.ty.index, None);
+    assert!(name.required && name.key);
+    assert_eq!(name.attributes[0].key, "key");
+    assert_eq!(name.attributes[0].value, None);
+
+    let hp = monster.field("hp").unwrap();
+    assert_eq!(hp.ty.base_type, BaseType::Short);
+    assert_eq!(hp.ty.base_type.scalar_size(), Some(2));
+    assert_eq!(hp.default_integer, 100);
+    assert_eq!(hp.offset, 6);
+
+    let inventory = monster.field("inventory").unwrap();
+    assert_eq!((inventory.ty.base_type, inventory.ty.element), (BaseType::Vector, BaseType::UByte));
+
+    assert!(monster.field("missing").is_none());
+}
+
+#[test]
+fn resolve_types() {
+    let schema = Schema::from_bfbs(&build_schema(true)).unwrap();
+    let monster = schema.root_table().unwrap();
+
+    let pos = &monster.field("pos").unwrap().ty;
+    assert_eq!(schema.object_of(pos).unwrap().name, "game.Vec3");
+    assert!(schema.enum_of(pos).is_none());
+
+    let friends = &monster.field("friends").unwrap().ty;
+    assert_eq!(schema.object_of(friends).unwrap().name, "game.Monster");
+
+    let color_field = monster.field("color").unwrap();
+    let color = schema.enum_of(&color_field.ty).unwrap();
+    assert_eq!(color.name, "game.Color");
+    assert!(!color.is_union);
+    assert_eq!(color.underlying_type.base_type, BaseType::UByte);
+    assert_eq!(color.value_of(color_field.default_integer).unwrap().name, "Blue");
+    assert_eq!(color.value_by_name("Green").unwrap().value, 1);
+    assert!(schema.object_of(&color_field.ty).is_none());
+
+    let hp = &monster.field("hp").unwrap().ty;
+    assert!(schema.object_of(hp).is_none());
+    assert!(schema.enum_of(hp).is_none());
+}
+
+#[test]
+fn unknown_identifier() {
+    let mut data = build_schema(true);
+    data[4..8].copy_from_slice(b"MONS");
+
+    assert_eq!(Schema::from_bfbs(&data), Err(DecodeError::UnknownIdentifier));
+    assert_eq!(Schema::from_bfbs(&[]), Err(DecodeError::UnknownIdentifier));
+}
+
+#[test]
+fn invalid_bfbs() {
+    let data = build_schema(true);
+
+    match Schema::from_bfbs(&data[..data.len() - 8]) {
+        Err(DecodeError::InvalidFlatbuffer(e)) => assert_eq!(e.constructor, Constructor::FromBfbs),
+        result => panic!("Expected an invalid FlatBuffer, got {:?}", result),
+    }
+}
+
+#[test]
+fn missing_required_field() {
+    assert!(matches!(Schema::from_bfbs(&build_schema(false)), Err(DecodeError::InvalidFlatbuffer(_))));
+}
@@ -0,0 +1,65 @@
+mod common;
+
+use common::generated_fbs::foo::{Foo, FooArgs};
+use common::generated_fbs::bar::{Bar, BarArgs, BAR_IDENTIFIER, finish_bar_buffer};
+use flatbuffers_owned::{flatbuffers_owned, registry, DecodeError};
+use flatbuffers_owned::registry::{DynamicResolver, StaticResolver};
+
+flatbuffers_owned!(Foo { create }, Bar { register: BAR_IDENTIFIER });
+
+fn bar_bytes() -> Box<[u8]> {
+    let mut builder = flatbuffers::FlatBufferBuilder::new();
+    let name = builder.create_string("bar");
+    let bar = Bar::create(&mut builder, &BarArgs { id: 7, name: Some(name) });
+    finish_bar_buffer(&mut builder, bar);
+
+    builder.finished_data().into()
+}
+
+#[test]
+fn lookup_registration() {
+    let registration = registry::lookup(BAR_IDENTIFIER).unwrap();
+
+    assert_eq!(registration.name, "Bar");
+    assert!(registry::lookup("FOO_").is_none());
+    assert_eq!(registry::registrations().count(), 1);
+}
+
+#[test]
+fn decode_registered() {
+    let decoded = registry::decode(bar_bytes()).unwrap();
+    let bar = decoded.downcast_ref::<OwnedBar>().unwrap();
+
+    assert_eq!(bar.as_actual().id(), 7);
+    assert_eq!(bar.as_actual().name(), Some("bar"));
+}
+
+#[test]
+fn decode_unregistered() {
+    let foo = OwnedFoo::create(&FooArgs { a: 1, b: None });
+
+    assert_eq!(registry::decode(foo.to_vec().into()).unwrap_err(), DecodeError::UnknownIdentifier);
+}
+
+#[test]
+fn decode_dynamic() {
+    let mut builder = flatbuffers::FlatBufferBuilder::new();
+    let offset = Foo::create(&mut builder, &FooArgs { a: 42, b: None });
+    builder.finish(offset, Some("FOO_"));
+    let foo_bytes: Box<[u8]> = builder.finished_data().into();
+
+    let plugins = DynamicResolver::new();
+    assert_eq!(registry::decode_with(&plugins, foo_bytes.clone()).unwrap_err(), DecodeError::UnknownIdentifier);
+
+    assert!(plugins.register("FOO_", registry::decoder::<OwnedFoo>()).is_none());
+
+    let resolver = (StaticResolver, &plugins);
+    let decoded = registry::decode_with(&resolver, foo_bytes.clone()).unwrap();
+    assert_eq!(decoded.downcast_ref::<OwnedFoo>().unwrap().as_actual().a(), 42);
+
+    let decoded = registry::decode_with(&resolver, bar_bytes()).unwrap();
+    assert!(decoded.downcast_ref::<OwnedBar>().is_some());
+
+    assert!(plugins.unregister("FOO_").is_some());
+    assert!(registry::decode_with(&resolver, foo_bytes).is_err());
+}
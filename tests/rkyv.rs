@@ -0,0 +1,62 @@
+mod common;
+
+use common::generated_fbs::foo::Foo;
+use common::build_foo;
+use flatbuffers_owned::flatbuffers_owned;
+use rkyv::rancor::Error;
+use rkyv::{Archive, Deserialize, Serialize};
+
+flatbuffers_owned!(Foo);
+
+#[derive(Archive, Serialize, Deserialize)]
+struct Record {
+    id: u64,
+    foo: OwnedFoo,
+    history: Vec<OwnedFoo>,
+}
+
+fn record() -> Record {
+    Record { id: 7, foo: build_foo(42), history: vec![build_foo(1), build_foo(2)] }
+}
+
+#[test]
+fn access_archived() {
+    let bytes = rkyv::to_bytes::<Error>(&record()).unwrap();
+    let archived = rkyv::access::<ArchivedRecord, Error>(&bytes).unwrap();
+
+    assert_eq!(archived.id, 7);
+    assert_eq!(archived.foo.as_bytes(), &*build_foo::<Foo>(42));
+
+    let foo_flatbuffer = archived.foo.as_flatbuffer().unwrap();
+    assert_eq!(foo_flatbuffer.as_actual().a(), 42);
+    assert_eq!(foo_flatbuffer.as_actual().b(), Some("Hello, world!"));
+
+    let history = archived.history.iter().map(|foo| foo.as_flatbuffer().unwrap().as_actual().a()).collect::<Vec<_>>();
+    assert_eq!(history, [1, 2]);
+}
+
+#[test]
+fn deserialize() {
+    let bytes = rkyv::to_bytes::<Error>(&record()).unwrap();
+    let deserialized = rkyv::from_bytes::<Record, Error>(&bytes).unwrap();
+
+    assert_eq!(deserialized.id, 7);
+    assert_eq!(deserialized.foo, build_foo(42));
+    assert_eq!(deserialized.history[1].as_actual().a(), 2);
+}
+
+#[test]
+fn reverify_on_access() {
+    let mut record = record();
+    let mut bytes = record.foo.into_vec();
+    bytes[0] = 0xff;
+    // Bypass the verification of the wrapper, the archive only holds raw bytes.
+    record.foo = unsafe { OwnedFoo::new_unchecked(bytes.into_boxed_slice()) };
+
+    let bytes = rkyv::to_bytes::<Error>(&record).unwrap();
+    let archived = rkyv::access::<ArchivedRecord, Error>(&bytes).unwrap();
+
+    assert!(archived.foo.as_flatbuffer().is_err());
+    assert!(archived.history[0].as_flatbuffer().is_ok());
+    assert!(rkyv::from_bytes::<Record, Error>(&bytes).is_err());
+}
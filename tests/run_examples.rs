@@ -0,0 +1,26 @@
+// Runs the examples as part of the test suite, so they keep compiling and working.
+
+#[allow(dead_code)]
+#[path = "../examples/echo.rs"]
+mod echo;
+#[allow(dead_code)]
+#[path = "../examples/replay.rs"]
+mod replay;
+#[allow(dead_code)]
+#[path = "../examples/router.rs"]
+mod router;
+
+#[test]
+fn echo() {
+    echo::main().unwrap();
+}
+
+#[test]
+fn replay() {
+    replay::main().unwrap();
+}
+
+#[test]
+fn router() {
+    router::main();
+}
@@ -0,0 +1,48 @@
+mod common;
+
+use common::generated_fbs::foo::Foo;
+use common::build_foo;
+use flatbuffers_owned::{flatbuffers_owned, VerifyError};
+use rusqlite::types::Type;
+use rusqlite::Connection;
+
+flatbuffers_owned!(Foo);
+
+fn connect() -> Connection {
+    let connection = Connection::open_in_memory().unwrap();
+    connection.execute("CREATE TABLE foos (id INTEGER PRIMARY KEY, foo)", ()).unwrap();
+
+    connection
+}
+
+#[test]
+fn to_and_from_sql() {
+    let connection = connect();
+    let owned_foo: OwnedFoo = build_foo(42);
+    let borrowed_foo = RelaxedFoo::new(&owned_foo[..]).unwrap();
+
+    connection.execute("INSERT INTO foos (id, foo) VALUES (?1, ?2), (?3, ?4)", (1, &owned_foo, 2, borrowed_foo)).unwrap();
+
+    let foos = connection.prepare("SELECT foo FROM foos ORDER BY id").unwrap()
+        .query_map((), |row| row.get::<_, OwnedFoo>(0)).unwrap()
+        .collect::<Result<Vec<_>, _>>().unwrap();
+
+    assert_eq!(foos, [build_foo(42), build_foo(42)]);
+    assert_eq!(foos[1].as_actual().b(), Some("Hello, world!"));
+}
+
+#[test]
+fn verify_from_sql() {
+    let connection = connect();
+    connection.execute("INSERT INTO foos (id, foo) VALUES (1, ?1), (2, 'text')", [&[0xffu8, 0xff, 0xff, 0xff][..]]).unwrap();
+
+    match connection.query_row("SELECT foo FROM foos WHERE id = 1", (), |row| row.get::<_, OwnedFoo>(0)) {
+        Err(rusqlite::Error::FromSqlConversionFailure(0, Type::Blob, e)) => assert!(e.downcast_ref::<VerifyError>().is_some()),
+        result => panic!("Expected a conversion failure, got {:?}", result),
+    }
+
+    assert!(matches!(
+        connection.query_row("SELECT foo FROM foos WHERE id = 2", (), |row| row.get::<_, OwnedFoo>(0)),
+        Err(rusqlite::Error::InvalidColumnType(0, _, Type::Text))
+    ));
+}
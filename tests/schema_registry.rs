@@ -0,0 +1,132 @@
+mod common;
+
+use std::sync::Arc;
+use flatbuffers::FlatBufferBuilder;
+use flatbuffers_owned::{flatbuffers_owned, registry, DecodeError};
+use flatbuffers_owned::reflection::dynamic::DynamicError;
+use flatbuffers_owned::registry::{SchemaRegistry, StaticResolver};
+use common::generated_fbs::bar::{Bar, BarArgs, BAR_IDENTIFIER, finish_bar_buffer};
+use common::generated_fbs::reflection::*;
+
+flatbuffers_owned!(Bar);
+
+// table Bar { id: uint64; name: string; }
+// table Baz { id: uint64; }
+// root_type Bar;
+fn bar_bfbs(file_ident: Option<&str>) -> Vec<u8> {
+    let mut fbb = FlatBufferBuilder::new();
+
+    let mut field = |name: &str, id: u16, base_type: i8| {
+        let name = fbb.create_string(name);
+        let type_ = create_type(&mut fbb, &TypeArgs { base_type, ..Default::default() });
+        create_field(&mut fbb, &FieldArgs { name: Some(name), type_: Some(type_), id, offset: 4 + 2 * id, ..Default::default() })
+    };
+    let bar_fields = [field("id", 0, base_type::ULONG), field("name", 1, base_type::STRING)];
+    let baz_fields = [field("id", 0, base_type::ULONG)];
+
+    let mut object = |name: &str, fields: &[_]| {
+        let name = fbb.create_string(name);
+        let fields = fbb.create_vector(fields);
+        create_object(&mut fbb, &ObjectArgs { name: Some(name), fields: Some(fields), minalign: 8, ..Default::default() })
+    };
+    let bar_object = object("Bar", &bar_fields);
+    let baz_object = object("Baz", &baz_fields);
+
+    let objects = fbb.create_vector(&[bar_object, baz_object]);
+    let enums = fbb.create_vector::<flatbuffers::WIPOffset<EnumOffset>>(&[]);
+    let file_ident = file_ident.map(|ident| fbb.create_string(ident));
+    let schema = create_schema(&mut fbb, &SchemaArgs {
+        objects: Some(objects),
+        enums: Some(enums),
+        file_ident,
+        root_table: Some(bar_object),
+        ..Default::default()
+    });
+    finish_schema_buffer(&mut fbb, schema);
+
+    fbb.finished_data().to_vec()
+}
+
+fn bar_bytes() -> Box<[u8]> {
+    let mut builder = FlatBufferBuilder::new();
+    let name = builder.create_string("bar");
+    let bar = Bar::create(&mut builder, &BarArgs { id: 7, name: Some(name) });
+    finish_bar_buffer(&mut builder, bar);
+
+    builder.finished_data().into()
+}
+
+#[test]
+fn lookup_schema() {
+    let schemas = SchemaRegistry::new();
+    let schema = schemas.load_bfbs(&bar_bfbs(Some(BAR_IDENTIFIER))).unwrap();
+
+    assert!(Arc::ptr_eq(&schemas.schema(BAR_IDENTIFIER).unwrap(), &schema));
+    assert!(Arc::ptr_eq(&schemas.schema_by_name("Baz").unwrap(), &schema));
+    assert!(Arc::ptr_eq(&schemas.identify(&bar_bytes()).unwrap(), &schema));
+    assert!(schemas.schema("FOO_").is_none());
+    assert!(schemas.schema_by_name("Foo").is_none());
+    assert!(schemas.identify(&[]).is_none());
+
+    assert_eq!(schemas.load_bfbs(&[]).unwrap_err(), DecodeError::UnknownIdentifier);
+}
+
+#[test]
+fn register_as() {
+    let schemas = SchemaRegistry::new();
+    let schema = schemas.load_bfbs(&bar_bfbs(None)).unwrap();
+
+    assert!(schemas.identify(&bar_bytes()).is_none());
+    assert!(schemas.schema_by_name("Bar").is_some());
+
+    schemas.register_as(BAR_IDENTIFIER, schema.clone());
+    assert!(Arc::ptr_eq(&schemas.identify(&bar_bytes()).unwrap(), &schema));
+
+    assert!(Arc::ptr_eq(&schemas.unregister(BAR_IDENTIFIER).unwrap(), &schema));
+    assert!(schemas.schema(BAR_IDENTIFIER).is_none());
+    assert!(schemas.schema_by_name("Bar").is_none());
+    assert!(schemas.unregister(BAR_IDENTIFIER).is_none());
+}
+
+#[test]
+fn decode_dynamic() {
+    let schemas = SchemaRegistry::new();
+    schemas.load_bfbs(&bar_bfbs(Some(BAR_IDENTIFIER))).unwrap();
+
+    let bar = schemas.decode_dynamic(bar_bytes()).unwrap();
+    assert_eq!(bar.object().name, "Bar");
+    assert_eq!(bar.get_field("id").unwrap().and_then(|id| id.as_u64()), Some(7));
+    assert_eq!(bar.get_field("name").unwrap().and_then(|name| name.as_str()), Some("bar"));
+
+    let mut unknown = bar_bytes();
+    unknown[4..8].copy_from_slice(b"FOO_");
+    assert_eq!(schemas.decode_dynamic(unknown).unwrap_err(), DynamicError::UnknownIdentifier);
+
+    let truncated = bar_bytes();
+    assert!(matches!(schemas.decode_dynamic(&truncated[..truncated.len() - 4]), Err(DynamicError::InvalidFlatbuffer { .. })));
+}
+
+#[test]
+fn decode_registered() {
+    let schemas = SchemaRegistry::new();
+    assert!(schemas.register_decoder(BAR_IDENTIFIER, registry::decoder::<OwnedBar>()).is_none());
+
+    let decoded = registry::decode_with(&(StaticResolver, &schemas), bar_bytes()).unwrap();
+    assert_eq!(decoded.downcast_ref::<OwnedBar>().unwrap().as_actual().id(), 7);
+
+    schemas.unregister(BAR_IDENTIFIER);
+    assert!(matches!(registry::decode_with(&schemas, bar_bytes()), Err(DecodeError::UnknownIdentifier)));
+}
+
+#[test]
+fn shared_between_threads() {
+    let schemas = Arc::new(SchemaRegistry::new());
+
+    let registering = {
+        let schemas = schemas.clone();
+        std::thread::spawn(move || schemas.load_bfbs(&bar_bfbs(Some(BAR_IDENTIFIER))).unwrap())
+    };
+    let schema = registering.join().unwrap();
+
+    assert!(Arc::ptr_eq(&schemas.schema(BAR_IDENTIFIER).unwrap(), &schema));
+}
@@ -0,0 +1,35 @@
+mod common;
+
+use common::generated_fbs::foo::Foo;
+use common::build_foo_bytes;
+use flatbuffers_owned::flatbuffers_owned;
+use flatbuffers_owned::buffer::SecretBuffer;
+use zeroize::ZeroizeOnDrop;
+
+flatbuffers_owned!(Foo);
+
+fn assert_zeroize_on_drop<Z: ZeroizeOnDrop>(_: &Z) {}
+
+#[test]
+fn secret() {
+    let secret_foo = SecretFoo::new(SecretBuffer::new(build_foo_bytes(42, Some("hunter2"), None))).unwrap();
+
+    assert_zeroize_on_drop(&secret_foo);
+    assert_eq!(secret_foo.as_actual().a(), 42);
+    assert_eq!(secret_foo.as_actual().b(), Some("hunter2"));
+}
+
+#[test]
+fn into_secret() {
+    let owned_foo = OwnedFoo::new(build_foo_bytes(42, Some("hunter2"), None)).unwrap();
+    let address = owned_foo.as_ptr();
+
+    let secret_foo: SecretFoo = owned_foo.into_secret();
+    assert_eq!(secret_foo.as_ptr(), address);
+    assert_eq!(secret_foo.as_actual().b(), Some("hunter2"));
+}
+
+#[test]
+fn secret_invalid() {
+    assert!(SecretFoo::new(SecretBuffer::new(Box::new([0xFF; 16]))).is_err());
+}
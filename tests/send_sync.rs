@@ -0,0 +1,53 @@
+mod common;
+
+use std::sync::Arc;
+use std::thread;
+use common::generated_fbs::foo::Foo;
+use common::get_foo_bytes;
+use flatbuffers_owned::flatbuffers_owned;
+
+flatbuffers_owned!(Foo);
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn send_sync() {
+    assert_send_sync::<OwnedFoo>();
+    assert_send_sync::<RelaxedFoo<Arc<[u8]>>>();
+    assert_send_sync::<RelaxedFoo<&'static [u8]>>();
+}
+
+#[test]
+fn move_into_thread() {
+    let owned_foo = OwnedFoo::new(get_foo_bytes()).unwrap();
+    let handle = thread::spawn(move || owned_foo.as_actual().a());
+
+    assert_eq!(handle.join().unwrap(), 42);
+}
+
+#[test]
+fn share_between_threads() {
+    let shared_foo = RelaxedFoo::new(Arc::<[u8]>::from(get_foo_bytes())).unwrap();
+
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let shared_foo = shared_foo.clone();
+            thread::spawn(move || shared_foo.as_actual().b().map(str::len))
+        })
+        .collect();
+
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), Some(13));
+    }
+}
+
+#[test]
+fn borrow_across_scoped_threads() {
+    let foo_bytes = get_foo_bytes();
+    let borrowed_foo = RelaxedFoo::new(&foo_bytes[..]).unwrap();
+
+    thread::scope(|scope| {
+        scope.spawn(|| assert_eq!(borrowed_foo.as_actual().a(), 42));
+        scope.spawn(|| assert_eq!(borrowed_foo.as_actual().b(), Some("Hello, world!")));
+    });
+}
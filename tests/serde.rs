@@ -0,0 +1,67 @@
+mod common;
+
+use flatbuffers::{FlatBufferBuilder, WIPOffset};
+use common::generated_fbs::foo::{Foo, FooArgs};
+use common::build_foo;
+use serde::{Deserialize, Serialize};
+use flatbuffers_owned::{flatbuffers_owned, ObjectApiTrait};
+use flatbuffers_owned::serde::Structured;
+
+flatbuffers_owned!(Foo);
+
+// The flatc generated `FooT` does not derive serde, so the object API is implemented by hand with a native struct which does.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct FooNative {
+    pub a: u32,
+    pub b: Option<String>,
+}
+
+impl ObjectApiTrait for Foo<'static> {
+    type Native = FooNative;
+
+    fn unpack<'a>(actual: Foo<'a>) -> FooNative {
+        FooNative { a: actual.a(), b: actual.b().map(str::to_string) }
+    }
+
+    fn pack(builder: &mut FlatBufferBuilder<'static>, native: &FooNative) -> WIPOffset<Self> {
+        let b = native.b.as_deref().map(|b| builder.create_string(b));
+        Foo::create(builder, &FooArgs { a: native.a, b })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct AdminView {
+    id: u32,
+    #[serde(with = "flatbuffers_owned::serde::structured")]
+    foo: OwnedFoo,
+}
+
+#[test]
+fn serialize() {
+    let owned_foo: OwnedFoo = build_foo(42);
+
+    assert_eq!(serde_json::to_string(&Structured(&owned_foo)).unwrap(), r#"{"a":42,"b":"Hello, world!"}"#);
+    assert_eq!(serde_json::to_string(&Structured(owned_foo)).unwrap(), r#"{"a":42,"b":"Hello, world!"}"#);
+}
+
+#[test]
+fn deserialize() {
+    let Structured(owned_foo): Structured<OwnedFoo> = serde_json::from_str(r#"{"a":7,"b":"packed"}"#).unwrap();
+
+    assert_eq!(owned_foo.unpack(), FooNative { a: 7, b: Some("packed".to_string()) });
+}
+
+#[test]
+fn with_field() {
+    let json = serde_json::to_string(&AdminView { id: 1, foo: build_foo(42) }).unwrap();
+    assert_eq!(json, r#"{"id":1,"foo":{"a":42,"b":"Hello, world!"}}"#);
+
+    let view: AdminView = serde_json::from_str(&json).unwrap();
+    assert_eq!(view.foo.as_actual().a(), 42);
+    assert_eq!(view.foo.as_actual().b(), Some("Hello, world!"));
+}
+
+#[test]
+fn deserialize_invalid() {
+    assert!(serde_json::from_str::<Structured<OwnedFoo>>(r#"{"a":"not a number"}"#).is_err());
+}
@@ -0,0 +1,64 @@
+#![cfg(unix)]
+
+mod common;
+
+use std::io;
+use common::generated_fbs::foo::Foo;
+use common::get_foo_bytes;
+use flatbuffers_owned::flatbuffers_owned;
+use flatbuffers_owned::shm::SharedSegment;
+
+flatbuffers_owned!(Foo { cached });
+
+fn segment_name(name: &str) -> String {
+    format!("/flatbuffers-owned-{}-{}", std::process::id(), name)
+}
+
+#[test]
+fn publish_and_subscribe() {
+    let name = segment_name("foo");
+    let published = unsafe { SharedSegment::create(&name, &get_foo_bytes()) }.unwrap();
+
+    let subscribed = unsafe { SharedSegment::open(&name) }.unwrap();
+    assert_eq!(&*subscribed, &*published);
+
+    let foo = RelaxedFoo::new(subscribed.clone()).unwrap();
+    assert_eq!(foo.as_actual().a(), 42);
+
+    let cached_foo = CachedFoo::new(RelaxedFoo::new(subscribed).unwrap());
+    assert_eq!(cached_foo.get().b(), Some("Hello, world!"));
+
+    // The wrappers keep the segment mapped after it is removed.
+    SharedSegment::unlink(&name).unwrap();
+    drop(published);
+    assert_eq!(foo.as_actual().b(), Some("Hello, world!"));
+}
+
+#[test]
+fn create_existing() {
+    let name = segment_name("existing");
+    let _segment = unsafe { SharedSegment::create(&name, &get_foo_bytes()) }.unwrap();
+
+    let result = unsafe { SharedSegment::create(&name, &get_foo_bytes()) };
+    assert_eq!(result.unwrap_err().kind(), io::ErrorKind::AlreadyExists);
+
+    SharedSegment::unlink(&name).unwrap();
+}
+
+#[test]
+fn open_invalid() {
+    let name = segment_name("invalid");
+    unsafe { SharedSegment::create(&name, &[0xFF; 16]) }.unwrap();
+
+    let segment = unsafe { SharedSegment::open(&name) }.unwrap();
+    assert!(RelaxedFoo::new(segment).is_err());
+
+    SharedSegment::unlink(&name).unwrap();
+}
+
+#[test]
+fn open_missing() {
+    let result = unsafe { SharedSegment::open(&segment_name("missing")) };
+
+    assert_eq!(result.unwrap_err().kind(), io::ErrorKind::NotFound);
+}
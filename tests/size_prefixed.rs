@@ -0,0 +1,283 @@
+mod common;
+
+use std::io::{self, Read, Write};
+use std::sync::Arc;
+use flatbuffers::FlatBufferBuilder;
+use common::generated_fbs::foo::{Foo, FooArgs};
+use common::generated_fbs::record::{Record, RecordArgs};
+use flatbuffers_owned::buffer::AlignedBuffer;
+use flatbuffers_owned::flatbuffers_owned;
+use flatbuffers_owned::size_prefixed::{iter_size_prefixed, iter_size_prefixed_shared, FlushPolicy, FrameError, SizePrefixedReader, SizePrefixedWriter, StreamError, PREFIX_LEN};
+
+flatbuffers_owned!(Record);
+
+fn build_frame(id: u64) -> Vec<u8> {
+    let mut builder = FlatBufferBuilder::new();
+    let b = builder.create_string("Hello, world!");
+    let nested = Foo::create(&mut builder, &FooArgs { a: 42, b: Some(b) });
+
+    let offset = Record::create(&mut builder, &RecordArgs { id, foo: Some(nested) });
+    builder.finish_size_prefixed(offset, None);
+
+    builder.finished_data().to_vec()
+}
+
+fn build_stream(ids: &[u64]) -> AlignedBuffer {
+    let frames = ids.iter().flat_map(|&id| build_frame(id)).collect::<Vec<_>>();
+
+    AlignedBuffer::copy_from_slice(&frames)
+}
+
+/// Returns at most one byte per read, like a slow socket.
+struct ByteReader<'a>(&'a [u8]);
+
+impl Read for ByteReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match (self.0.split_first(), buf.first_mut()) {
+            (Some((&byte, rest)), Some(first)) => {
+                *first = byte;
+                self.0 = rest;
+                Ok(1)
+            },
+            _ => Ok(0),
+        }
+    }
+}
+
+/// Counts the flushes of the written bytes.
+#[derive(Default)]
+struct FlushCounter {
+    bytes: Vec<u8>,
+    flushes: usize,
+}
+
+impl Write for FlushCounter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.bytes.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.flushes += 1;
+        Ok(())
+    }
+}
+
+fn build_record(id: u64) -> OwnedRecord {
+    OwnedRecord::build(|builder| Record::create(builder, &RecordArgs { id, foo: None })).unwrap()
+}
+
+#[test]
+fn iter() {
+    let stream = build_stream(&[1, 2, 3]);
+
+    let ids = iter_size_prefixed::<Record<'static>>(&stream)
+        .map(|record| record.unwrap().as_actual_at().id())
+        .collect::<Vec<_>>();
+
+    assert_eq!(ids, [1, 2, 3]);
+}
+
+#[test]
+fn iter_empty() {
+    assert!(iter_size_prefixed::<Record<'static>>(&[]).next().is_none());
+}
+
+#[test]
+fn iter_loc() {
+    let stream = build_stream(&[5]);
+    let record = iter_size_prefixed::<Record<'static>>(&stream).next().unwrap().unwrap();
+
+    assert_eq!(record.loc(), PREFIX_LEN);
+    assert_eq!(record.buffer().len(), stream.len());
+    assert_eq!(record.as_actual_at().foo().unwrap().b(), Some("Hello, world!"));
+}
+
+#[test]
+fn iter_truncated() {
+    let stream = build_stream(&[1, 2]);
+    let frame_len = build_frame(1).len();
+    let truncated = &stream[..stream.len() - 3];
+
+    let mut iter = iter_size_prefixed::<Record<'static>>(truncated);
+
+    assert_eq!(iter.next().unwrap().unwrap().as_actual_at().id(), 1);
+    assert_eq!(iter.next().unwrap().unwrap_err(), FrameError::Truncated {
+        offset: frame_len,
+        len: frame_len,
+        available: frame_len - 3,
+    });
+    assert!(iter.next().is_none());
+    assert_eq!(iter.offset(), truncated.len());
+}
+
+#[test]
+fn iter_truncated_prefix() {
+    let stream = build_stream(&[1]);
+    let truncated = [&stream[..], &[0u8; 2]].concat();
+
+    let results = iter_size_prefixed::<Record<'static>>(&truncated).collect::<Vec<_>>();
+
+    assert_eq!(results.len(), 2);
+    assert!(matches!(results[1], Err(FrameError::Truncated { len: PREFIX_LEN, available: 2, .. })));
+}
+
+#[test]
+fn iter_invalid_frame() {
+    let mut frames = build_frame(1);
+    let mut invalid = build_frame(2);
+    // Points the root offset out of the frame.
+    invalid[PREFIX_LEN..PREFIX_LEN + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+    frames.extend_from_slice(&invalid);
+    frames.extend_from_slice(&build_frame(3));
+    let stream = AlignedBuffer::copy_from_slice(&frames);
+
+    let results = iter_size_prefixed::<Record<'static>>(&stream).collect::<Vec<_>>();
+
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0].as_ref().unwrap().as_actual_at().id(), 1);
+    assert!(matches!(results[1], Err(FrameError::InvalidFlatbuffer(_))));
+    assert_eq!(results[2].as_ref().unwrap().as_actual_at().id(), 3);
+}
+
+#[test]
+fn iter_shared() {
+    let stream: Arc<[u8]> = Arc::from(&build_stream(&[1, 2, 3])[..]);
+
+    let records = iter_size_prefixed_shared::<Record<'static>, _>(stream.clone())
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    drop(stream);
+
+    let ids = records.iter().map(|record| record.as_actual_at().id()).collect::<Vec<_>>();
+    assert_eq!(ids, [1, 2, 3]);
+
+    let frame_len = build_frame(1).len();
+    assert_eq!(records[1].buffer().range(), frame_len..frame_len * 2);
+}
+
+#[test]
+fn reader() {
+    let stream = build_stream(&[1, 2, 3]);
+
+    let ids = SizePrefixedReader::<Record<'static>, _>::new(ByteReader(&stream))
+        .map(|record| record.unwrap().as_actual_at().id())
+        .collect::<Vec<_>>();
+
+    assert_eq!(ids, [1, 2, 3]);
+}
+
+#[test]
+fn reader_read_message() {
+    let stream = build_stream(&[1, 2]);
+    let mut reader = SizePrefixedReader::<Record<'static>, _>::new(&stream[..]);
+
+    assert_eq!(reader.read_message().unwrap().unwrap().as_actual_at().id(), 1);
+    assert_eq!(reader.read_message().unwrap().unwrap().as_actual_at().id(), 2);
+    assert!(reader.read_message().unwrap().is_none());
+}
+
+#[test]
+fn reader_too_large() {
+    let stream = build_stream(&[1, 2]);
+    let frame_len = build_frame(1).len();
+    let mut reader = SizePrefixedReader::<Record<'static>, _>::with_max_message_size(&stream[..], frame_len - PREFIX_LEN - 1);
+
+    assert!(matches!(reader.next(), Some(Err(StreamError::TooLarge { max, .. })) if max == frame_len - PREFIX_LEN - 1));
+    assert!(reader.next().is_none());
+}
+
+#[test]
+fn reader_unexpected_eof() {
+    let stream = build_stream(&[1, 2]);
+    let mut reader = SizePrefixedReader::<Record<'static>, _>::new(&stream[..stream.len() - 3]);
+
+    assert_eq!(reader.next().unwrap().unwrap().as_actual_at().id(), 1);
+    assert!(matches!(reader.next(), Some(Err(StreamError::Io(e))) if e.kind() == io::ErrorKind::UnexpectedEof));
+    assert!(reader.next().is_none());
+}
+
+#[test]
+fn reader_invalid_frame() {
+    let mut frames = build_frame(1);
+    let mut invalid = build_frame(2);
+    invalid[PREFIX_LEN..PREFIX_LEN + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+    frames.extend_from_slice(&invalid);
+    frames.extend_from_slice(&build_frame(3));
+
+    let results = SizePrefixedReader::<Record<'static>, _>::new(&frames[..]).collect::<Vec<_>>();
+
+    assert_eq!(results.len(), 3);
+    assert!(matches!(results[1], Err(StreamError::InvalidFlatbuffer(_))));
+    assert_eq!(results[2].as_ref().unwrap().as_actual_at().id(), 3);
+}
+
+#[test]
+fn writer_write_message() {
+    let mut writer = SizePrefixedWriter::new(Vec::new());
+    for id in 1..=3 {
+        writer.write_message(&build_record(id)).unwrap();
+    }
+    let stream = AlignedBuffer::copy_from_slice(&writer.into_inner());
+
+    let ids = iter_size_prefixed::<Record<'static>>(&stream)
+        .map(|record| record.unwrap().as_actual_at().id())
+        .collect::<Vec<_>>();
+    assert_eq!(ids, [1, 2, 3]);
+
+    let ids = SizePrefixedReader::<Record<'static>, _>::new(&stream[..])
+        .map(|record| record.unwrap().as_actual_at().id())
+        .collect::<Vec<_>>();
+    assert_eq!(ids, [1, 2, 3]);
+}
+
+#[test]
+fn writer_write_message_identifier() {
+    let mut builder = FlatBufferBuilder::new();
+    let offset = Record::create(&mut builder, &RecordArgs { id: 7, foo: None });
+    builder.finish(offset, Some("RCRD"));
+    let owned_record = OwnedRecord::new(builder.finished_data().into()).unwrap();
+
+    let mut writer = SizePrefixedWriter::new(Vec::new());
+    writer.write_message(&owned_record).unwrap();
+    let stream = AlignedBuffer::copy_from_slice(&writer.into_inner());
+
+    let record = iter_size_prefixed::<Record<'static>>(&stream).next().unwrap().unwrap();
+    assert_eq!(record.as_actual_at().id(), 7);
+    assert!(flatbuffers::buffer_has_identifier(&stream[PREFIX_LEN..], "RCRD", false));
+}
+
+#[test]
+fn writer_write_frame() {
+    let mut writer = SizePrefixedWriter::new(Vec::new());
+    writer.write_frame(&build_frame(1)).unwrap();
+    writer.write_frame(&build_frame(2)).unwrap();
+
+    assert_eq!(writer.get_ref().len(), build_frame(1).len() * 2);
+
+    let frame = build_frame(3);
+    let error = writer.write_frame(&frame[..frame.len() - 1]).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn writer_flush_policy() {
+    let record = build_record(1);
+
+    let mut writer = SizePrefixedWriter::new(FlushCounter::default());
+    writer.write_message(&record).unwrap();
+    writer.write_message(&record).unwrap();
+    assert_eq!(writer.get_ref().flushes, 0);
+    writer.flush().unwrap();
+    assert_eq!(writer.get_ref().flushes, 1);
+
+    let mut writer = SizePrefixedWriter::with_flush_policy(FlushCounter::default(), FlushPolicy::EveryMessage);
+    writer.write_message(&record).unwrap();
+    writer.write_message(&record).unwrap();
+    assert_eq!(writer.get_ref().flushes, 2);
+
+    let mut writer = SizePrefixedWriter::with_flush_policy(FlushCounter::default(), FlushPolicy::EveryMessages(2));
+    for _ in 0..5 {
+        writer.write_message(&record).unwrap();
+    }
+    assert_eq!(writer.get_ref().flushes, 2);
+}
@@ -0,0 +1,30 @@
+mod common;
+
+use std::sync::Arc;
+use common::generated_fbs::foo::Foo;
+use common::build_foo_bytes;
+use flatbuffers_owned::flatbuffers_owned;
+use flatbuffers_owned::buffer::SlicedBuffer;
+
+flatbuffers_owned!(Foo);
+
+#[test]
+fn sliced_batch() {
+    let first = build_foo_bytes(1, Some("Hello, world!"), None).into_vec();
+    let second = build_foo_bytes(2, Some("Hello, world!"), None).into_vec();
+    let batch: Arc<[u8]> = [&first[..], &second[..]].concat().into();
+
+    let first_foo = RelaxedFoo::new(SlicedBuffer::new(batch.clone(), 0..first.len()).unwrap()).unwrap();
+    let second_foo = RelaxedFoo::new(SlicedBuffer::new(batch.clone(), first.len()..batch.len()).unwrap()).unwrap();
+
+    assert_eq!(first_foo.as_actual().a(), 1);
+    assert_eq!(second_foo.as_actual().a(), 2);
+    assert!(Arc::ptr_eq(first_foo.buffer().buffer(), second_foo.buffer().buffer()));
+}
+
+#[test]
+fn sliced_out_of_bounds() {
+    let foo_bytes = build_foo_bytes(1, Some("Hello, world!"), None).into_vec();
+
+    assert!(SlicedBuffer::new(&foo_bytes[..], 0..foo_bytes.len() + 1).is_none());
+}
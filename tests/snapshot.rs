@@ -0,0 +1,180 @@
+mod common;
+
+use common::generated_fbs::foo::{Foo, FooArgs};
+use flatbuffers_owned::flatbuffers_owned;
+use flatbuffers_owned::snapshot::{HighestSequence, LatestWins, Merge, Merged, Sequenced, SequenceGap, SnapshotStream};
+
+flatbuffers_owned!(Foo { create });
+
+// The field `a` carries the sequence number in these tests.
+impl Sequenced for Foo<'_> {
+    fn sequence(&self) -> u64 {
+        self.a() as u64
+    }
+}
+
+fn foo(sequence: u32) -> OwnedFoo {
+    OwnedFoo::create(&FooArgs { a: sequence, b: None })
+}
+
+fn sequences(updates: &[OwnedFoo]) -> Vec<u64> {
+    updates.iter().map(Sequenced::sequence).collect()
+}
+
+#[test]
+fn buffers_updates_until_snapshot() {
+    let mut stream = SnapshotStream::<OwnedFoo, OwnedFoo>::new();
+
+    for sequence in 3..=6 {
+        assert!(stream.push_update(foo(sequence)).unwrap().is_none());
+    }
+    assert!(stream.sequence().is_none());
+
+    let ready = stream.push_snapshot(foo(4));
+    assert_eq!(sequences(&ready), vec![5, 6]);
+    assert_eq!(stream.sequence(), Some(6));
+}
+
+#[test]
+fn applies_and_drops_updates_after_snapshot() {
+    let mut stream = SnapshotStream::<OwnedFoo, OwnedFoo>::new();
+    assert!(stream.push_snapshot(foo(10)).is_empty());
+
+    assert!(stream.push_update(foo(9)).unwrap().is_none());
+    assert!(stream.push_update(foo(10)).unwrap().is_none());
+    assert_eq!(stream.push_update(foo(11)).unwrap().unwrap().sequence(), 11);
+    assert_eq!(stream.sequence(), Some(11));
+}
+
+#[test]
+fn detects_sequence_gap() {
+    let mut stream = SnapshotStream::<OwnedFoo, OwnedFoo>::new();
+    stream.push_snapshot(foo(1));
+
+    let gap = stream.push_update(foo(3)).unwrap_err();
+    assert_eq!(gap, SequenceGap { expected: 2, received: 3 });
+    assert!(stream.snapshot().is_none());
+
+    let ready = stream.push_snapshot(foo(2));
+    assert_eq!(sequences(&ready), vec![3]);
+}
+
+#[test]
+fn highest_sequence_snapshot_wins_by_default() {
+    let mut stream = SnapshotStream::<OwnedFoo, OwnedFoo>::new();
+    stream.push_snapshot(foo(5));
+    stream.push_snapshot(foo(3));
+
+    assert_eq!(stream.sequence(), Some(5));
+}
+
+#[test]
+fn latest_snapshot_wins_ties() {
+    let mut stream = SnapshotStream::<OwnedFoo, OwnedFoo, _>::with_merge(LatestWins);
+    stream.push_snapshot(foo(5));
+
+    let incoming = OwnedFoo::build(|builder| {
+        let b = builder.create_string("incoming");
+        Foo::create(builder, &FooArgs { a: 5, b: Some(b) })
+    }).unwrap();
+    stream.push_snapshot(incoming);
+
+    assert_eq!(stream.snapshot().unwrap().as_actual().b(), Some("incoming"));
+    assert_eq!(stream.sequence(), Some(5));
+}
+
+fn drops_stale_snapshot_after_updates<M: Merge<OwnedFoo, OwnedFoo>>(merge: M) {
+    let mut stream = SnapshotStream::<OwnedFoo, OwnedFoo, _>::with_merge(merge);
+    stream.push_snapshot(foo(5));
+    for sequence in 6..=9 {
+        stream.push_update(foo(sequence)).unwrap().unwrap();
+    }
+
+    // Both snapshots lack updates that were already applied on top of the current one.
+    assert!(stream.push_snapshot(foo(7)).is_empty());
+    assert!(stream.push_snapshot(foo(9)).is_empty());
+
+    assert_eq!(stream.snapshot().unwrap().sequence(), 5);
+    assert_eq!(stream.sequence(), Some(9));
+    assert_eq!(stream.push_update(foo(10)).unwrap().unwrap().sequence(), 10);
+}
+
+#[test]
+fn drops_stale_snapshot() {
+    drops_stale_snapshot_after_updates(HighestSequence);
+    drops_stale_snapshot_after_updates(LatestWins);
+}
+
+#[test]
+fn highest_sequence_snapshot_wins() {
+    let mut stream = SnapshotStream::<OwnedFoo, OwnedFoo, _>::with_merge(HighestSequence);
+    stream.push_snapshot(foo(5));
+    stream.push_update(foo(6)).unwrap();
+    stream.push_snapshot(foo(4));
+
+    assert_eq!(stream.snapshot().unwrap().sequence(), 5);
+    assert_eq!(stream.sequence(), Some(6));
+
+    stream.push_snapshot(foo(8));
+    assert_eq!(stream.sequence(), Some(8));
+}
+
+#[test]
+fn merge_snapshots_with_closure() {
+    let mut stream = SnapshotStream::<OwnedFoo, OwnedFoo, _>::with_merge(|current: &OwnedFoo, incoming: &OwnedFoo| {
+        Merged::Replaced(foo(current.as_actual().a() + incoming.as_actual().a()))
+    });
+    stream.push_snapshot(foo(1));
+    stream.push_snapshot(foo(2));
+
+    assert_eq!(stream.sequence(), Some(3));
+}
+
+#[test]
+fn merge_duplicate_updates() {
+    struct ReapplyDuplicates;
+
+    impl Merge<OwnedFoo, OwnedFoo> for ReapplyDuplicates {
+        fn merge_snapshots(&mut self, _current: &OwnedFoo, _incoming: &OwnedFoo) -> Merged<OwnedFoo> {
+            Merged::Current
+        }
+
+        fn merge_updates(&mut self, snapshot: &OwnedFoo, incoming: OwnedFoo) -> Option<OwnedFoo> {
+            assert!(incoming.sequence() > snapshot.sequence());
+            Some(incoming)
+        }
+    }
+
+    let mut stream = SnapshotStream::<OwnedFoo, OwnedFoo, _>::with_merge(ReapplyDuplicates);
+    stream.push_snapshot(foo(1));
+
+    // The snapshot already includes the update, so the hook is not called.
+    assert!(stream.push_update(foo(1)).unwrap().is_none());
+
+    assert!(stream.push_update(foo(2)).unwrap().is_some());
+    assert!(stream.push_update(foo(2)).unwrap().is_some());
+    assert!(stream.push_update(foo(1)).unwrap().is_none());
+}
+
+#[test]
+fn merge_buffered_duplicate_updates() {
+    struct ReapplyDuplicates;
+
+    impl Merge<OwnedFoo, OwnedFoo> for ReapplyDuplicates {
+        fn merge_snapshots(&mut self, _current: &OwnedFoo, _incoming: &OwnedFoo) -> Merged<OwnedFoo> {
+            Merged::Current
+        }
+
+        fn merge_updates(&mut self, _snapshot: &OwnedFoo, incoming: OwnedFoo) -> Option<OwnedFoo> {
+            Some(incoming)
+        }
+    }
+
+    let mut stream = SnapshotStream::<OwnedFoo, OwnedFoo, _>::with_merge(ReapplyDuplicates);
+    for sequence in [2, 3, 3, 4] {
+        stream.push_update(foo(sequence)).unwrap();
+    }
+
+    let ready = stream.push_snapshot(foo(2));
+    assert_eq!(sequences(&ready), vec![3, 3, 4]);
+}
@@ -0,0 +1,71 @@
+mod common;
+
+use common::generated_fbs::foo::Foo;
+use common::build_foo;
+use flatbuffers_owned::{flatbuffers_owned, VerifyError};
+use sqlx::{Connection, Row, SqliteConnection};
+
+flatbuffers_owned!(Foo);
+
+async fn connect() -> SqliteConnection {
+    let mut connection = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+    sqlx::query("CREATE TABLE foos (id INTEGER PRIMARY KEY, foo BLOB NOT NULL)")
+        .execute(&mut connection)
+        .await
+        .unwrap();
+
+    connection
+}
+
+#[tokio::test]
+async fn bind_and_decode() {
+    let mut connection = connect().await;
+    let foo_seven: OwnedFoo = build_foo(7);
+
+    sqlx::query("INSERT INTO foos (id, foo) VALUES (?, ?), (?, ?)")
+        .bind(1)
+        .bind(build_foo::<Foo>(42))
+        .bind(2)
+        .bind(&foo_seven)
+        .execute(&mut connection)
+        .await
+        .unwrap();
+
+    let foo_owned: OwnedFoo = sqlx::query_scalar("SELECT foo FROM foos WHERE id = 1")
+        .fetch_one(&mut connection)
+        .await
+        .unwrap();
+    assert_eq!(foo_owned.as_actual().a(), 42);
+    assert_eq!(foo_owned, build_foo(42));
+
+    let row = sqlx::query("SELECT foo FROM foos WHERE id = 2")
+        .fetch_one(&mut connection)
+        .await
+        .unwrap();
+    let foo_borrowed = row.try_get::<RelaxedFoo<&[u8]>, _>("foo").unwrap();
+    assert_eq!(foo_borrowed.as_actual().a(), 7);
+    assert_eq!(foo_borrowed.as_actual().b(), Some("Hello, world!"));
+    assert_eq!(&*foo_borrowed, &*foo_seven);
+}
+
+#[tokio::test]
+async fn verify_on_decode() {
+    let mut connection = connect().await;
+
+    sqlx::query("INSERT INTO foos (id, foo) VALUES (1, ?)")
+        .bind(&[0xffu8, 0xff, 0xff, 0xff][..])
+        .execute(&mut connection)
+        .await
+        .unwrap();
+
+    let row = sqlx::query("SELECT foo FROM foos WHERE id = 1")
+        .fetch_one(&mut connection)
+        .await
+        .unwrap();
+
+    match row.try_get::<OwnedFoo, _>("foo") {
+        Err(sqlx::Error::ColumnDecode { source, .. }) => assert!(source.downcast_ref::<VerifyError>().is_some()),
+        result => panic!("Expected a column decode error, got {:?}", result),
+    }
+    assert!(row.try_get::<RelaxedFoo<&[u8]>, _>("foo").is_err());
+}
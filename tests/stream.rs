@@ -0,0 +1,51 @@
+mod common;
+
+use futures::executor::block_on;
+use futures::stream::{self, StreamExt};
+use common::generated_fbs::record::{Record, RecordArgs};
+use flatbuffers_owned::flatbuffers_owned;
+use flatbuffers_owned::stream::{VerifiedStream, VerifyStreamTrait};
+use flatbuffers_owned::TrustedPolicy;
+
+flatbuffers_owned!(Record);
+
+fn build_record_bytes(id: u64) -> Vec<u8> {
+    OwnedRecord::build(|builder| Record::create(builder, &RecordArgs { id, foo: None })).unwrap().into_vec()
+}
+
+fn messages() -> Vec<Vec<u8>> {
+    vec![build_record_bytes(1), vec![0xff; 3], build_record_bytes(2)]
+}
+
+#[test]
+fn verify_flatbuffers() {
+    let results = block_on(stream::iter(messages()).verify_flatbuffers::<Record<'static>>().collect::<Vec<_>>());
+
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0].as_ref().unwrap().as_actual().id(), 1);
+    assert!(results[1].is_err());
+    assert_eq!(results[2].as_ref().unwrap().as_actual().id(), 2);
+}
+
+#[test]
+fn skip_invalid() {
+    let mut errors = Vec::new();
+
+    let ids = block_on(stream::iter(messages())
+        .verify_flatbuffers::<Record<'static>>()
+        .skip_invalid(|e| errors.push(e))
+        .map(|record| record.as_actual().id())
+        .collect::<Vec<_>>());
+
+    assert_eq!(ids, [1, 2]);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].len, 3);
+}
+
+#[test]
+fn policy() {
+    let stream = VerifiedStream::<Record<'static>, _, TrustedPolicy>::new(stream::iter(vec![build_record_bytes(1)]));
+    let results = block_on(stream.collect::<Vec<_>>());
+
+    assert_eq!(results[0].as_ref().unwrap().as_actual().id(), 1);
+}
@@ -0,0 +1,39 @@
+mod common;
+
+use common::generated_fbs::foo::{Foo, FooArgs};
+use common::generated_fbs::vec3::Vec3;
+use flatbuffers_owned::flatbuffers_owned;
+
+flatbuffers_owned!(struct Vec3, Foo { create });
+
+#[test]
+fn create_owned_vec3() {
+    let owned_vec3 = OwnedVec3::create(&Vec3::new(1.0, 2.0, 3.0));
+    let vec3: &Vec3 = owned_vec3.as_actual();
+
+    assert_eq!(vec3.x(), 1.0);
+    assert_eq!(vec3.y(), 2.0);
+    assert_eq!(vec3.z(), 3.0);
+}
+
+#[test]
+fn verify_owned_vec3() {
+    let bytes: Box<[u8]> = OwnedVec3::create(&Vec3::new(1.0, 2.0, 3.0)).to_vec().into();
+    let owned_vec3 = OwnedVec3::new(bytes).expect("Failed to parse Vec3");
+
+    assert_eq!(*owned_vec3.as_actual(), Vec3::new(1.0, 2.0, 3.0));
+}
+
+#[test]
+fn fail_truncated_vec3() {
+    let bytes = OwnedVec3::create(&Vec3::new(1.0, 2.0, 3.0));
+
+    assert!(RelaxedVec3::new(&bytes[..bytes.len() - 4]).is_err());
+}
+
+#[test]
+fn mixed_with_tables() {
+    let owned_foo = OwnedFoo::create(&FooArgs { a: 1, b: None });
+
+    assert_eq!(owned_foo.as_actual().a(), 1);
+}
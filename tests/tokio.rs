@@ -0,0 +1,100 @@
+mod common;
+
+use std::io;
+use flatbuffers::FlatBufferBuilder;
+use common::generated_fbs::record::{Record, RecordArgs};
+use flatbuffers_owned::flatbuffers_owned;
+use flatbuffers_owned::size_prefixed::{AsyncSizePrefixedReader, AsyncSizePrefixedWriter, FlushPolicy, StreamError, PREFIX_LEN};
+use tokio::io::AsyncWriteExt;
+
+flatbuffers_owned!(Record);
+
+fn build_record(id: u64) -> OwnedRecord {
+    OwnedRecord::build(|builder| Record::create(builder, &RecordArgs { id, foo: None })).unwrap()
+}
+
+fn build_frame(id: u64) -> Vec<u8> {
+    let mut builder = FlatBufferBuilder::new();
+    let offset = Record::create(&mut builder, &RecordArgs { id, foo: None });
+    builder.finish_size_prefixed(offset, None);
+
+    builder.finished_data().to_vec()
+}
+
+#[tokio::test]
+async fn round_trip() {
+    let (client, server) = tokio::io::duplex(64);
+
+    let writer = tokio::spawn(async move {
+        let mut writer = AsyncSizePrefixedWriter::with_flush_policy(client, FlushPolicy::EveryMessage);
+
+        for id in 1..=100 {
+            writer.write_message(&build_record(id)).await.unwrap();
+        }
+    });
+
+    let mut reader = AsyncSizePrefixedReader::<Record<'static>, _>::new(server);
+    let mut ids = Vec::new();
+    while let Some(record) = reader.next_message().await.unwrap() {
+        ids.push(record.as_actual_at().id());
+    }
+
+    writer.await.unwrap();
+    assert_eq!(ids, (1..=100).collect::<Vec<_>>());
+}
+
+#[tokio::test]
+async fn write_frame() {
+    let mut writer = AsyncSizePrefixedWriter::new(Vec::new());
+    writer.write_frame(&build_frame(1)).await.unwrap();
+    writer.write_message(&build_record(2)).await.unwrap();
+
+    let frame = build_frame(3);
+    let error = writer.write_frame(&frame[..frame.len() - 1]).await.unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+
+    let bytes = writer.into_inner();
+    let mut reader = AsyncSizePrefixedReader::<Record<'static>, _>::new(&bytes[..]);
+
+    assert_eq!(reader.next_message().await.unwrap().unwrap().as_actual_at().id(), 1);
+    assert_eq!(reader.next_message().await.unwrap().unwrap().as_actual_at().id(), 2);
+    assert!(reader.next_message().await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn cancelled_read() {
+    let (mut client, server) = tokio::io::duplex(1024);
+    let frame = build_frame(7);
+    let mut reader = AsyncSizePrefixedReader::<Record<'static>, _>::new(server);
+
+    client.write_all(&frame[..PREFIX_LEN + 3]).await.unwrap();
+
+    // Reads the available bytes, then cancels the read waiting for the rest.
+    tokio::select! {
+        biased;
+        _ = reader.next_message() => panic!("read a partial frame"),
+        _ = async {} => {},
+    }
+
+    client.write_all(&frame[PREFIX_LEN + 3..]).await.unwrap();
+    drop(client);
+
+    assert_eq!(reader.next_message().await.unwrap().unwrap().as_actual_at().id(), 7);
+    assert!(reader.next_message().await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn too_large() {
+    let frame = build_frame(1);
+    let mut reader = AsyncSizePrefixedReader::<Record<'static>, _>::with_max_message_size(&frame[..], 4);
+
+    assert!(matches!(reader.next_message().await, Err(StreamError::TooLarge { max: 4, .. })));
+}
+
+#[tokio::test]
+async fn unexpected_eof() {
+    let frame = build_frame(1);
+    let mut reader = AsyncSizePrefixedReader::<Record<'static>, _>::new(&frame[..frame.len() - 1]);
+
+    assert!(matches!(reader.next_message().await, Err(StreamError::Io(e)) if e.kind() == io::ErrorKind::UnexpectedEof));
+}
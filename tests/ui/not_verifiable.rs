@@ -0,0 +1,18 @@
+use flatbuffers::Follow;
+use flatbuffers_owned::flatbuffers_owned;
+
+pub struct Plain<'a> {
+    buf: &'a [u8],
+}
+
+impl<'a> Follow<'a> for Plain<'a> {
+    type Inner = Plain<'a>;
+
+    unsafe fn follow(buf: &'a [u8], _loc: usize) -> Self::Inner {
+        Plain { buf }
+    }
+}
+
+flatbuffers_owned!(Plain);
+
+fn main() {}
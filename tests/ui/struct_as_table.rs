@@ -0,0 +1,10 @@
+#[allow(dead_code, unused_imports, clippy::all)]
+#[path = "../generated_fbs/vec3.rs"]
+mod vec3;
+
+use vec3::Vec3;
+use flatbuffers_owned::flatbuffers_owned;
+
+flatbuffers_owned!(Vec3);
+
+fn main() {}
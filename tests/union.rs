@@ -0,0 +1,47 @@
+mod common;
+
+use common::generated_fbs::foo::{Foo, FooArgs};
+use common::generated_fbs::bar::{Bar, BarArgs};
+use common::generated_fbs::envelope::{Envelope, EnvelopeArgs, Payload};
+use flatbuffers_owned::flatbuffers_owned;
+
+flatbuffers_owned!(Envelope { create, union: payload { Foo, Bar } });
+
+#[test]
+fn resolve_union_variants() {
+    let foo_envelope = OwnedEnvelope::build(|builder| {
+        let foo = Foo::create(builder, &FooArgs { a: 42, b: None });
+
+        Envelope::create(builder, &EnvelopeArgs {
+            payload_type: Payload::Foo,
+            payload: Some(foo.as_union_value()),
+        })
+    }).unwrap();
+
+    match foo_envelope.payload() {
+        Some(EnvelopePayload::Foo(foo)) => assert_eq!(foo.a(), 42),
+        other => panic!("Expected Foo, got {:?}", other),
+    }
+
+    let bar_envelope = OwnedEnvelope::build(|builder| {
+        let name = builder.create_string("bar");
+        let bar = Bar::create(builder, &BarArgs { id: 7, name: Some(name) });
+
+        Envelope::create(builder, &EnvelopeArgs {
+            payload_type: Payload::Bar,
+            payload: Some(bar.as_union_value()),
+        })
+    }).unwrap();
+
+    match bar_envelope.payload() {
+        Some(EnvelopePayload::Bar(bar)) => assert_eq!(bar.name().unwrap(), "bar"),
+        other => panic!("Expected Bar, got {:?}", other),
+    }
+}
+
+#[test]
+fn resolve_missing_union() {
+    let envelope = OwnedEnvelope::create(&EnvelopeArgs::default());
+
+    assert!(envelope.payload().is_none());
+}
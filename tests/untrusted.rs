@@ -0,0 +1,63 @@
+mod common;
+
+use flatbuffers::FlatBufferBuilder;
+use common::generated_fbs::foo::{Foo, FooArgs};
+use common::generated_fbs::record::{Record, RecordArgs};
+use flatbuffers_owned::flatbuffers_owned;
+use flatbuffers_owned::size_prefixed::iter_size_prefixed;
+
+flatbuffers_owned!(Foo, Record);
+
+// The constructors taking raw bytes must return an error for every malformed input instead of panicking.
+// A link-time check like `no-panic` is not feasible, as the verifier of the `flatbuffers` crate keeps panicking branches the optimizer cannot prove unreachable.
+
+fn record_bytes() -> Vec<u8> {
+    let mut builder = FlatBufferBuilder::new();
+    let b = builder.create_string("Hello, world!");
+    let nested = Foo::create(&mut builder, &FooArgs { a: 42, b: Some(b) });
+    let offset = Record::create(&mut builder, &RecordArgs { id: 7, foo: Some(nested) });
+    builder.finish(offset, None);
+
+    builder.finished_data().to_vec()
+}
+
+fn construct(data: &[u8]) {
+    let _ = OwnedRecord::new(data.into());
+    let _ = RelaxedRecord::new(data);
+    let _ = RelaxedRecord::<&[u8]>::new_realigned(data);
+    let _ = iter_size_prefixed::<Record<'static>>(data).count();
+}
+
+#[test]
+fn truncated_input() {
+    let bytes = record_bytes();
+
+    for len in 0..bytes.len() {
+        construct(&bytes[..len]);
+    }
+
+    assert!(OwnedRecord::new(bytes[..4].into()).is_err());
+}
+
+#[test]
+fn corrupted_input() {
+    let bytes = record_bytes();
+
+    for index in 0..bytes.len() {
+        for value in [0x00, 0x7F, 0x80, 0xFF] {
+            let mut corrupted = bytes.clone();
+            corrupted[index] = value;
+
+            construct(&corrupted);
+        }
+    }
+}
+
+#[test]
+fn huge_offsets_and_length_prefixes() {
+    construct(&[0xFF; 64]);
+    construct(&[0xFF, 0xFF, 0xFF, 0x7F, 0x04, 0x00, 0x00, 0x00]);
+    construct(&u32::MAX.to_le_bytes());
+
+    assert!(iter_size_prefixed::<Record<'static>>(&[0xFF; 8]).all(|frame| frame.is_err()));
+}
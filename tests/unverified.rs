@@ -0,0 +1,42 @@
+mod common;
+
+use std::sync::mpsc;
+use std::thread;
+use common::generated_fbs::foo::{Foo, FooArgs};
+use flatbuffers_owned::{flatbuffers_owned, Unverified};
+
+flatbuffers_owned!(Foo { create });
+
+fn foo_bytes() -> Box<[u8]> {
+    OwnedFoo::create(&FooArgs { a: 42, b: None }).to_vec().into()
+}
+
+#[test]
+fn verify_on_other_thread() {
+    let (sender, receiver) = mpsc::channel::<Unverified<OwnedFoo>>();
+
+    let worker = thread::spawn(move || {
+        receiver.recv().unwrap().verify().unwrap().as_actual().a()
+    });
+
+    sender.send(Unverified::new(foo_bytes())).unwrap();
+
+    assert_eq!(worker.join().unwrap(), 42);
+}
+
+#[test]
+fn reject_invalid() {
+    let unverified = Unverified::<OwnedFoo>::new(Box::from(&[1u8, 2, 3][..]));
+
+    assert_eq!(unverified.as_bytes(), &[1, 2, 3]);
+    assert!(unverified.clone().verify().is_err());
+    assert_eq!(&*unverified.into_inner(), &[1, 2, 3]);
+}
+
+#[test]
+fn borrowed_buffer() {
+    let bytes = foo_bytes();
+    let unverified = Unverified::<RelaxedFoo<&[u8]>>::new(&bytes);
+
+    assert_eq!(unverified.verify().unwrap().as_actual().a(), 42);
+}
@@ -0,0 +1,53 @@
+mod common;
+
+use flatbuffers::FlatBufferBuilder;
+use common::generated_fbs::foo::{Foo, FooArgs};
+use flatbuffers_owned::flatbuffers_owned;
+
+flatbuffers_owned!(Foo, Vector<Foo> as FooBatch);
+
+fn get_foo_batch_bytes() -> Box<[u8]> {
+    let mut builder = FlatBufferBuilder::new();
+
+    let items: Vec<_> = (0..3)
+        .map(|a| {
+            let b = builder.create_string(&format!("Foo #{}", a));
+            Foo::create(&mut builder, &FooArgs { a, b: Some(b) })
+        })
+        .collect();
+
+    let offset = builder.create_vector(&items);
+    builder.finish(offset, None);
+
+    builder.finished_data().into()
+}
+
+#[test]
+fn create_owned_foo_batch() {
+    let owned_batch = OwnedFooBatch::new(get_foo_batch_bytes()).expect("Failed to parse FooBatch");
+    let batch = owned_batch.as_actual();
+
+    assert_eq!(batch.len(), 3);
+    for (i, foo) in batch.iter().enumerate() {
+        assert_eq!(foo.a(), i as u32);
+        assert_eq!(foo.b().unwrap(), format!("Foo #{}", i));
+    }
+}
+
+#[test]
+fn build_owned_foo_batch() {
+    let owned_batch = OwnedFooBatch::build(|builder| {
+        let foo = Foo::create(builder, &FooArgs { a: 42, b: None });
+        builder.create_vector(&[foo])
+    }).expect("Failed to build FooBatch");
+
+    assert_eq!(owned_batch.as_actual().get(0).a(), 42);
+}
+
+#[test]
+fn fail_invalid_foo_batch() {
+    let mut bytes = get_foo_batch_bytes();
+    bytes[0] = 0xff;
+
+    assert!(OwnedFooBatch::new(bytes).is_err());
+}
@@ -0,0 +1,41 @@
+mod common;
+
+use flatbuffers::FlatBufferBuilder;
+use common::generated_fbs::foo::Foo;
+use common::generated_fbs::container::{Container, ContainerArgs};
+use common::get_foo_bytes;
+use flatbuffers_owned::{flatbuffers_owned, RelaxedFollowTrait};
+
+flatbuffers_owned!(
+    Foo { verifier: { max_tables: 0 } },
+    Container { cached, verifier: { max_depth: 1, max_apparent_size: 1 << 20 } }
+);
+
+#[test]
+fn verifier_option() {
+    let opts = <Container as RelaxedFollowTrait>::default_options();
+
+    assert_eq!(opts.max_depth, 1);
+    assert_eq!(opts.max_apparent_size, 1 << 20);
+    assert_eq!(opts.max_tables, flatbuffers::VerifierOptions::default().max_tables);
+}
+
+#[test]
+fn verifier_option_applies_to_new() {
+    let foo_bytes = get_foo_bytes();
+
+    assert!(OwnedFoo::new(foo_bytes.clone()).is_err());
+    assert!(OwnedFoo::verify(&foo_bytes).is_err());
+    assert!(OwnedFoo::new_with_opts(foo_bytes, &Default::default()).is_ok());
+}
+
+#[test]
+fn verifier_option_with_other_options() {
+    let mut builder = FlatBufferBuilder::new();
+    let offset = Container::create(&mut builder, &ContainerArgs { foo: None });
+    builder.finish(offset, None);
+
+    let container = CachedContainer::new(OwnedContainer::new(builder.finished_data().into()).unwrap());
+
+    assert!(container.get().foo().is_none());
+}
@@ -0,0 +1,45 @@
+mod common;
+
+use std::convert::TryFrom;
+use bytes::Bytes;
+use common::generated_fbs::foo::Foo;
+use common::build_foo;
+use tungstenite::Message;
+use flatbuffers_owned::flatbuffers_owned;
+use flatbuffers_owned::websocket::MessageError;
+
+flatbuffers_owned!(Foo);
+
+#[test]
+fn try_from_message() {
+    let message = Message::from(&build_foo::<Foo>(42));
+
+    let owned_foo = OwnedFoo::try_from(message.clone()).unwrap();
+    assert_eq!(owned_foo.as_actual().a(), 42);
+
+    let shared_foo = RelaxedFoo::<Bytes>::try_from(message).unwrap();
+    assert_eq!(shared_foo.as_actual().b(), Some("Hello, world!"));
+}
+
+#[test]
+fn into_message() {
+    let owned_foo: OwnedFoo = build_foo(42);
+
+    assert_eq!(Message::from(&owned_foo), Message::Binary(Bytes::copy_from_slice(&owned_foo)));
+    assert_eq!(Message::from(owned_foo.clone()), Message::Binary(Bytes::copy_from_slice(&owned_foo)));
+}
+
+#[test]
+fn not_binary() {
+    let message = Message::text("Hello, world!");
+
+    assert_eq!(OwnedFoo::try_from(message.clone()).unwrap_err(), MessageError::NotBinary(message));
+    assert!(matches!(OwnedFoo::try_from(Message::Close(None)), Err(MessageError::NotBinary(Message::Close(None)))));
+}
+
+#[test]
+fn invalid() {
+    let result = OwnedFoo::try_from(Message::binary(vec![0xFF; 16]));
+
+    assert!(matches!(result, Err(MessageError::InvalidFlatbuffer(_))));
+}